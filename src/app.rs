@@ -1,22 +1,139 @@
 use dirs::home_dir;
 use log::LevelFilter;
+use ratatui::layout::Position;
+use ratatui::style::Color;
 use toml::Value;
 
 use crate::{
-    constants::{DisplayMode, Pages, Popups},
-    game_logic::game::Game,
-    pieces::PieceColor,
+    clipboard::{read_clipboard, write_clipboard},
+    constants::{
+        config_path, DisplayMode, EndGameChoice, FlipPolicy, Focus, MoveNotation, Pages, Popups,
+        TICK_RATE_MS,
+    },
+    diagram::{encode_png, position_svg},
+    engine::{
+        analysis::{Analysis, AnalysisLine, DEFAULT_ANALYSIS_LINES},
+        game_analysis::{evaluate_game, GAME_ANALYSIS_DEPTH},
+        profile::{parse_engine_profiles, EngineProfile},
+        random_bot::RandomBot,
+        uci::{EngineInfo, Score, SearchReport, UciEngine, DEFAULT_MOVETIME},
+        Difficulty, Engine, Ponder, SearchConstraints, SearchHandle, DEFAULT_ELO, ELO_STEP,
+        MAX_ELO, MIN_ELO,
+    },
+    error::ChessError,
+    event::Event,
+    game_logic::{
+        clock::Clock,
+        coord::Coord,
+        game::{Game, GameState},
+        game_setup::{GameSetup, TimeControl},
+        puzzle::PuzzleSet,
+        ui::BoardRenderSettings,
+    },
+    gif::{palette, render_position, white_view_positions, DEFAULT_FRAME_DELAY_MS},
+    library::{scan_games, GameSource, LibraryGame},
+    logging::tail_lines,
+    network::{
+        discovery::{Discovery, LanGame},
+        handshake::{Handshake, HandshakeState, DEFAULT_CONNECTION_TIMEOUT},
+        lobby::decode_game_code,
+        opponent::{Opponent, OpponentEvent},
+        protocol::Message,
+        spectate::{GameSnapshot, Spectators},
+    },
+    pgn::{
+        load_games, sideline_game, to_annotated_pgn, PgnGame, PgnTags, PgnVariation, PlayerNames,
+    },
+    pieces::{PieceColor, PieceMove, PieceType},
+    rng::Rng,
+    save::SavedGame,
+    sound::{SoundEvent, SoundMap},
+    ui::{
+        idle_animation::IdleTracker,
+        prompt::Prompt,
+        theme::{theme_index, BoardTheme, THEMES},
+        toast::Toast,
+    },
+    utils::flip_coord,
 };
 
 use std::{
     error,
     fs::{self, File},
     io::Write,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+    thread,
+    time::{Duration, Instant},
 };
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
+/// The analysis panel has room for this many lines
+pub const MAX_ANALYSIS_LINES: i64 = 10;
+
+/// Under this remaining time the clocks show tenths of a second
+pub const DEFAULT_CLOCK_TENTHS_THRESHOLD: Duration = Duration::from_secs(20);
+
+/// Number of ticks before a forced recapture is automatically played
+pub const AUTO_RECAPTURE_DELAY_TICKS: u16 = 8;
+
+/// Number of lines of the log file kept in the log viewer
+pub const LOG_VIEWER_LINES: usize = 200;
+
+/// Number of ticks the board border flashes when the opponent checks the local player
+pub const CHECK_ALERT_TICKS: u16 = 8;
+
+/// Number of ticks the move suggested with `H` stays lit
+pub const HINT_TICKS: u16 = 12;
+
+/// Moves ahead the engine looks for a hint, enough for a beginner and quick to answer
+pub const HINT_DEPTH: u32 = 8;
+
+/// Silence of the engine past its thinking time after which it is considered stuck
+pub const ENGINE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A forced recapture waiting to be played automatically
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingRecapture {
+    /// The only legal move
+    pub piece_move: PieceMove,
+    /// Number of ticks before the move is played
+    pub remaining_ticks: u16,
+}
+
+/// A flip of the board waiting for the player who just moved to see the result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingFlip {
+    /// The color kept at the bottom until the board flips
+    pub bottom_color: PieceColor,
+    /// Number of ticks before the board flips
+    pub remaining_ticks: u16,
+}
+
+/// A move held back until the player confirms it, because it leaves a piece for the taking
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingBlunder {
+    pub from: Coord,
+    pub to: Coord,
+    /// The most valuable piece left hanging
+    pub hanging_piece: PieceType,
+    /// The promotion cursor of the piece a typed promotion gives
+    pub promotion: Option<i8>,
+}
+
+/// A replayed line left to look at one of its sidelines, brought back when the sideline is left
+#[derive(Clone)]
+pub struct ParentLine {
+    pub game: Game,
+    pub comments: Vec<Option<String>>,
+    pub variations: Vec<PgnVariation>,
+    /// Index in `variations` of the sideline being looked at
+    pub sideline: usize,
+}
+
 /// Application.
 pub struct App {
     /// Is the application running?
@@ -32,80 +149,2901 @@ pub struct App {
     /// menu current cursor
     pub menu_cursor: u8,
     pub log_level: LevelFilter,
+    /// Notification currently displayed
+    pub toast: Option<Toast>,
+    /// Idle detection for the home page animation
+    pub idle: IdleTracker,
+    /// Automatically play a recapture when it is the only legal move
+    pub auto_recapture: bool,
+    /// Material advantage in pawns up to which the bot accepts a draw offer
+    pub bot_draw_threshold: i32,
+    /// The recapture about to be played
+    pub pending_recapture: Option<PendingRecapture>,
+    /// The panel receiving the arrow keys
+    pub focus: Focus,
+    /// The settings of the current game, reused for a rematch
+    pub setup: GameSetup,
+    /// Under this remaining time the clocks show tenths of a second
+    pub clock_tenths_threshold: Duration,
+    /// Make the clock blink under the tenths threshold
+    pub clock_flash: bool,
+    /// Remaining ticks of the flashing border after a move received from the opponent checked us
+    pub check_alert: Option<u16>,
+    /// Remaining ticks of the move suggested by the engine, lit on the board
+    pub hint: Option<u16>,
+    /// The FEN of the position the engine looks for a hint in
+    pub pending_hint: Option<String>,
+    /// Best line found so far while the engine is thinking
+    pub engine_info: Option<EngineInfo>,
+    /// The evaluation and depth the engine reported for its last move
+    pub engine_report: Option<SearchReport>,
+    /// Posts the progress of the engine to the main loop, the bot answers on the tick without it
+    pub event_sender: Option<Sender<Event>>,
+    /// The search of the engine running in the background, `s` stops it
+    pub engine_search: Option<SearchHandle>,
+    /// The move of a search abandoned by leaving the game is not played
+    pub discard_engine_move: bool,
+    /// Let the engine think on the time of the player about the answer it expects
+    pub enable_pondering: bool,
+    /// The answer the engine expects to the move it is about to play
+    pub ponder_move: Option<String>,
+    /// The search running on the time of the player, resumed when they play the expected move
+    pub ponder: Option<Ponder>,
+    /// Ticks since the engine last sent something during its search
+    pub engine_silent_ticks: u32,
+    /// What happened to the engine, shown until it is restarted or replaced
+    pub engine_failure: Option<String>,
+    /// Keep the piece selected when it is sent to a cell it cannot reach
+    pub keep_selection_on_illegal_target: bool,
+    /// Ask for a confirmation before resigning
+    pub confirm_resign: bool,
+    /// Ask for a confirmation before a move leaving a piece for the taking
+    pub blunder_warning: bool,
+    /// The move waiting for its confirmation
+    pub pending_blunder: Option<PendingBlunder>,
+    /// The file waiting for a confirmation before the game is saved over it
+    pub pending_save: Option<PathBuf>,
+    /// The file the game in progress is written to after every move, `None` disables it
+    pub autosave_path: Option<PathBuf>,
+    /// When the board turns around between the moves
+    pub flip_policy: FlipPolicy,
+    /// How the moves are written in the move list and the logs
+    pub move_notation: MoveNotation,
+    /// Number of ticks the board waits before flipping when both players share the keyboard
+    pub flip_delay_ticks: u16,
+    /// The flip about to happen
+    pub pending_flip: Option<PendingFlip>,
+    /// Seed of the random choices of the bots, taken from the entropy when missing
+    pub seed: Option<u64>,
+    /// The opponent of the player on the bot page
+    pub bot: Option<Box<dyn Engine>>,
+    /// The UCI engine playing on the bot page, the random bot plays without it
+    pub engine_path: Option<String>,
+    /// The engines of the `[[engines]]` tables of the config, chosen before a bot game
+    pub engine_profiles: Vec<EngineProfile>,
+    /// Index in `engine_profiles` of the engine of the bot, `engine_path` plays without one
+    pub engine_profile: Option<usize>,
+    /// Depth and thinking time set in the config, every search of the engine stays within them
+    pub engine_limits: SearchConstraints,
+    /// The rating of the Elo difficulty, picked in the difficulty popup
+    pub engine_elo: u32,
+    /// Index in [`THEMES`] of the colors of the board
+    pub theme_index: usize,
+    /// The puzzles being solved, every move is compared to the solution
+    pub puzzles: Option<PuzzleSet>,
+    /// The games of the PGN file listed in the game list
+    pub pgn_games: Vec<PgnGame>,
+    /// Index in `pgn_games` of the game selected in the game list
+    pub game_list_cursor: usize,
+    /// The comments of the replayed PGN game, by position
+    pub pgn_comments: Vec<Option<String>>,
+    /// The sidelines of the replayed line, counted from the start of the game
+    pub pgn_variations: Vec<PgnVariation>,
+    /// The lines left to look at a sideline, the main line first
+    pub parent_lines: Vec<ParentLine>,
+    /// The games of the games folder listed on the My games page
+    pub my_games: Vec<LibraryGame>,
+    /// Index in `my_games` of the selected game
+    pub my_games_cursor: usize,
+    /// The search for games hosted on the local network, while their page is open
+    pub lan_discovery: Option<Discovery>,
+    /// Index in the games found on the local network of the selected one
+    pub lan_games_cursor: usize,
+    /// Mark every king in check, not only the one of the player to move
+    pub highlight_all_checks: bool,
+    /// Draw the board with cells twice as wide as tall so that it looks square
+    pub compact_cells: bool,
+    /// The menu cursor goes from the last entry to the first one and back
+    pub menu_wrap: bool,
+    /// The cell the cursor starts on, seen from white, for a player with white
+    pub initial_cursor: Option<Coord>,
+    /// Index in [`EndGameChoice::ALL`] of the option selected once the game is over
+    pub end_popup_cursor: usize,
+    /// Take the mouse from the terminal to play with it, mouse events are ignored otherwise
+    pub mouse_capture: bool,
+    /// The connection to the host of an online game being opened
+    pub handshake: Option<Handshake>,
+    /// Time given to the host of an online game to start it
+    pub connection_timeout: Duration,
+    /// The connection to the opponent of the online game
+    pub opponent: Option<Opponent>,
+    /// The name the opponent of the online game gave when it started
+    pub opponent_name: Option<String>,
+    /// The names of the players set in the configuration or on the command line
+    pub player_names: PlayerNames,
+    /// The name of the local player, given to their color in the exported games
+    pub player_name: Option<String>,
+    /// The headers of the game being exported, edited before it is written
+    pub pgn_tags: PgnTags,
+    /// Index in [`PgnTags::EDITABLE`] of the header being edited
+    pub pgn_tag_cursor: usize,
+    /// Index in the board history of the position under review, the live game is shown without it
+    pub review_index: Option<usize>,
+    /// The move number typed during the review
+    pub review_input: String,
+    /// The file the logs of this session are written to, `None` when logging is off
+    pub log_file: Option<PathBuf>,
+    /// The configuration file the settings are read from and saved to, the default one when `None`
+    pub config_path: Option<PathBuf>,
+    /// The latest lines of the log file shown in the log viewer
+    pub log_lines: Vec<String>,
+    /// Number of lines the log viewer is scrolled up from the latest entry
+    pub log_scroll: usize,
+    /// The UCI lines exchanged with the engine shown in the engine console
+    pub engine_console_lines: Vec<String>,
+    /// Number of lines the engine console is scrolled up from the latest one
+    pub engine_console_scroll: usize,
+    /// The sound of each event of the game
+    pub sounds: SoundMap,
+    /// Milliseconds each position stays on screen in the GIFs of the games
+    pub gif_frame_delay_ms: u32,
+    /// The color of the selected piece's cell, the one of the theme when `None`
+    pub selected_cell_color: Option<Color>,
+    /// The browsers watching the game, when it is served with `--spectate`
+    pub spectators: Option<Spectators>,
+    /// The engine analysing the position shown, on the Analyze page or in the analysis panel
+    pub analysis: Option<Analysis>,
+    /// Number of best lines shown on the Analyze page
+    pub analysis_line_count: usize,
+    /// The FEN of the position being analysed, the analysis starts over when another one is shown
+    pub analysed_position: Option<String>,
+    /// The reviewed game and position the Analyze page goes back to, when opened from a review
+    pub analysis_return: Option<(Game, usize)>,
+    /// The FENs of the positions of the finished game the engine still has to evaluate, the next one first
+    pub pending_evaluations: Vec<String>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            running: true,
+            game: Game::default(),
+            current_page: Pages::Home,
+            current_popup: None,
+            selected_color: None,
+            menu_cursor: 0,
+            log_level: LevelFilter::Off,
+            toast: None,
+            idle: IdleTracker::default(),
+            auto_recapture: false,
+            bot_draw_threshold: 0,
+            pending_recapture: None,
+            focus: Focus::Board,
+            setup: GameSetup::default(),
+            clock_tenths_threshold: DEFAULT_CLOCK_TENTHS_THRESHOLD,
+            clock_flash: false,
+            check_alert: None,
+            hint: None,
+            pending_hint: None,
+            engine_info: None,
+            engine_report: None,
+            event_sender: None,
+            engine_search: None,
+            discard_engine_move: false,
+            enable_pondering: false,
+            ponder_move: None,
+            ponder: None,
+            engine_silent_ticks: 0,
+            engine_failure: None,
+            keep_selection_on_illegal_target: false,
+            confirm_resign: true,
+            blunder_warning: false,
+            pending_blunder: None,
+            pending_save: None,
+            autosave_path: None,
+            flip_policy: FlipPolicy::default(),
+            move_notation: MoveNotation::default(),
+            flip_delay_ticks: 0,
+            pending_flip: None,
+            seed: None,
+            bot: None,
+            engine_path: None,
+            engine_profiles: vec![],
+            engine_profile: None,
+            engine_limits: SearchConstraints::default(),
+            engine_elo: DEFAULT_ELO,
+            theme_index: 0,
+            puzzles: None,
+            pgn_games: vec![],
+            game_list_cursor: 0,
+            pgn_comments: vec![],
+            pgn_variations: vec![],
+            parent_lines: vec![],
+            my_games: vec![],
+            my_games_cursor: 0,
+            lan_discovery: None,
+            lan_games_cursor: 0,
+            highlight_all_checks: false,
+            compact_cells: false,
+            menu_wrap: true,
+            initial_cursor: None,
+            end_popup_cursor: 0,
+            mouse_capture: true,
+            handshake: None,
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            opponent: None,
+            opponent_name: None,
+            player_names: PlayerNames::default(),
+            player_name: None,
+            pgn_tags: PgnTags::new(&PlayerNames::default(), ""),
+            pgn_tag_cursor: 0,
+            review_index: None,
+            review_input: String::new(),
+            log_file: None,
+            config_path: None,
+            log_lines: vec![],
+            log_scroll: 0,
+            engine_console_lines: vec![],
+            engine_console_scroll: 0,
+            sounds: SoundMap::default(),
+            gif_frame_delay_ms: DEFAULT_FRAME_DELAY_MS,
+            selected_cell_color: None,
+            spectators: None,
+            analysis: None,
+            analysis_line_count: DEFAULT_ANALYSIS_LINES,
+            analysed_position: None,
+            analysis_return: None,
+            pending_evaluations: vec![],
+        }
+    }
 }
 
-impl Default for App {
-    fn default() -> Self {
-        Self {
-            running: true,
-            game: Game::default(),
-            current_page: Pages::Home,
-            current_popup: None,
-            selected_color: None,
-            menu_cursor: 0,
-            log_level: LevelFilter::Off,
+impl App {
+    pub fn toggle_help_popup(&mut self) {
+        if self.current_popup == Some(Popups::Help) {
+            self.current_popup = None;
+        } else {
+            self.current_popup = Some(Popups::Help);
+        }
+    }
+    pub fn toggle_credit_popup(&mut self) {
+        if self.current_page == Pages::Home {
+            self.current_page = Pages::Credit;
+        } else {
+            self.current_page = Pages::Home;
+        }
+    }
+
+    pub fn go_to_home(&mut self) {
+        self.current_page = Pages::Home;
+        self.focus = Focus::Board;
+        self.bot = None;
+        self.puzzles = None;
+        self.restart();
+    }
+
+    /// Handles the tick event of the terminal.
+    pub fn tick(&mut self) {
+        // The idle animation only plays on the home page
+        if self.current_page == Pages::Home && self.current_popup.is_none() {
+            self.idle.tick();
+        } else {
+            self.idle.reset();
+        }
+
+        if let Some(pending_recapture) = &mut self.pending_recapture {
+            pending_recapture.remaining_ticks = pending_recapture.remaining_ticks.saturating_sub(1);
+            if pending_recapture.remaining_ticks == 0 {
+                self.confirm_auto_recapture();
+            }
+        }
+
+        if let Some(clock) = &mut self.game.clock {
+            if !self.game.game_state.is_over() && clock.flagged(Instant::now()).is_some() {
+                clock.stop(Instant::now());
+                self.game.game_state = GameState::Timeout;
+                self.pending_recapture = None;
+                self.autosave();
+            }
+        }
+
+        if self.engine_search.is_some() {
+            self.engine_silent_ticks += 1;
+            if self.engine_silent_ticks > self.engine_timeout_ticks() {
+                self.abandon_engine_search();
+                self.report_engine_failure(ChessError::EngineFailure(
+                    "the engine stopped answering".to_string(),
+                ));
+            }
+        }
+
+        // The bot answers on the tick following the move of the player
+        if self.is_bot_turn() {
+            self.play_bot_move();
+        }
+
+        self.poll_handshake(Instant::now());
+        self.poll_opponent(Instant::now());
+
+        if let Some(discovery) = &mut self.lan_discovery {
+            discovery.poll();
+        }
+
+        if self.analysis.is_some() {
+            self.follow_analysis();
+        }
+
+        // The log viewer follows the entries written while it is open
+        if self.current_popup == Some(Popups::LogViewer) {
+            self.refresh_log_viewer();
+        }
+        if self.current_popup == Some(Popups::EngineConsole) {
+            self.refresh_engine_console();
+        }
+
+        if let Some(pending_flip) = &mut self.pending_flip {
+            pending_flip.remaining_ticks = pending_flip.remaining_ticks.saturating_sub(1);
+            if pending_flip.remaining_ticks == 0 {
+                self.pending_flip = None;
+            }
+        }
+
+        if let Some(remaining_ticks) = &mut self.check_alert {
+            *remaining_ticks = remaining_ticks.saturating_sub(1);
+            if *remaining_ticks == 0 {
+                self.check_alert = None;
+            }
+        }
+
+        if let Some(remaining_ticks) = &mut self.hint {
+            *remaining_ticks = remaining_ticks.saturating_sub(1);
+            if *remaining_ticks == 0 {
+                self.clear_hint();
+            }
+        }
+
+        if let Some(toast) = &mut self.toast {
+            toast.remaining_ticks = toast.remaining_ticks.saturating_sub(1);
+            if toast.remaining_ticks == 0 {
+                self.toast = None;
+            }
+        }
+    }
+
+    /// Show or hide the latest entries of the log file
+    pub fn toggle_log_viewer(&mut self) {
+        if self.current_popup == Some(Popups::LogViewer) {
+            self.current_popup = None;
+        } else if self.current_popup.is_none() {
+            self.log_scroll = 0;
+            self.refresh_log_viewer();
+            self.current_popup = Some(Popups::LogViewer);
+        }
+    }
+
+    /// Read the end of the log file again, a missing or empty file is explained instead
+    pub fn refresh_log_viewer(&mut self) {
+        self.log_lines = match &self.log_file {
+            None => vec![
+                "Logging is off, set `log_level` in the config file to record the logs".to_string(),
+            ],
+            Some(path) => match tail_lines(path, LOG_VIEWER_LINES) {
+                Ok(lines) if lines.is_empty() => vec!["The log file is empty".to_string()],
+                Ok(lines) => lines,
+                Err(error) => vec![format!("Can't read {}: {error}", path.display())],
+            },
+        };
+        self.log_scroll = self.log_scroll.min(self.log_lines.len().saturating_sub(1));
+    }
+
+    /// Show older log entries
+    pub fn log_scroll_up(&mut self) {
+        self.log_scroll = (self.log_scroll + 1).min(self.log_lines.len().saturating_sub(1));
+    }
+
+    /// Show newer log entries
+    pub fn log_scroll_down(&mut self) {
+        self.log_scroll = self.log_scroll.saturating_sub(1);
+    }
+
+    /// Show or hide the UCI lines exchanged with the engine
+    pub fn toggle_engine_console(&mut self) {
+        if self.current_popup == Some(Popups::EngineConsole) {
+            self.current_popup = None;
+        } else if self.current_popup.is_none() {
+            self.engine_console_scroll = 0;
+            self.refresh_engine_console();
+            self.current_popup = Some(Popups::EngineConsole);
+        }
+    }
+
+    /// Read the lines of the engine analysing the position, or else of the bot
+    pub fn refresh_engine_console(&mut self) {
+        let console = self
+            .analysis
+            .as_ref()
+            .and_then(Analysis::console)
+            .or_else(|| self.bot.as_ref().and_then(|bot| bot.console()));
+        self.engine_console_lines = match console {
+            None => vec![
+                "No engine is running, play against one or analyse a position to see its lines"
+                    .to_string(),
+            ],
+            Some(console) => match console.lines() {
+                lines if lines.is_empty() => {
+                    vec!["Nothing was exchanged with the engine yet".to_string()]
+                }
+                lines => lines,
+            },
+        };
+        self.engine_console_scroll = self
+            .engine_console_scroll
+            .min(self.engine_console_lines.len().saturating_sub(1));
+    }
+
+    /// Show older lines of the engine console
+    pub fn engine_console_scroll_up(&mut self) {
+        self.engine_console_scroll =
+            (self.engine_console_scroll + 1).min(self.engine_console_lines.len().saturating_sub(1));
+    }
+
+    /// Show newer lines of the engine console
+    pub fn engine_console_scroll_down(&mut self) {
+        self.engine_console_scroll = self.engine_console_scroll.saturating_sub(1);
+    }
+
+    /// Connect to the host of an online game, the connection is checked on every tick
+    pub fn join_game(&mut self, address: SocketAddr) {
+        self.handshake = Some(Handshake::connect(
+            address,
+            self.connection_timeout,
+            self.player_names.clone(),
+        ));
+    }
+
+    /// The names of the players of the current game: the online opponent gives its own,
+    /// the bot defaults to the name of the engine and the configured names come next
+    pub fn players(&self) -> PlayerNames {
+        let mut names = self.player_names.clone();
+        let Some(opponent_color) = self.selected_color.map(PieceColor::opposite) else {
+            return names;
+        };
+        let opponent_name = match opponent_color {
+            PieceColor::White => &mut names.white,
+            PieceColor::Black => &mut names.black,
+        };
+        match (&self.opponent_name, &self.bot) {
+            (Some(name), _) => *opponent_name = Some(name.clone()),
+            (None, Some(bot)) if self.current_page == Pages::Bot => {
+                opponent_name.get_or_insert_with(|| bot.name());
+            }
+            _ => {}
+        }
+        names
+    }
+
+    /// Open the editor of the headers of the game before it is saved in PGN, the local player
+    /// is named after `player_name` unless their color already has a name
+    pub fn open_pgn_tags(&mut self) {
+        if !self.current_page.is_game() || self.current_popup.is_some() {
+            return;
+        }
+        let mut names = self.players();
+        let local_name = match self.selected_color {
+            Some(PieceColor::White) => Some(&mut names.white),
+            Some(PieceColor::Black) => Some(&mut names.black),
+            None => None,
+        };
+        if let (Some(local_name @ None), Some(player_name)) = (local_name, &self.player_name) {
+            *local_name = Some(player_name.clone());
+        }
+        let date = chrono::Local::now().format("%Y.%m.%d").to_string();
+        self.pgn_tags = PgnTags::new(&names, &date);
+        self.pgn_tag_cursor = 0;
+        self.current_popup = Some(Popups::PgnTags);
+    }
+
+    pub fn pgn_tag_cursor_up(&mut self) {
+        let len = PgnTags::EDITABLE.len();
+        self.pgn_tag_cursor = (self.pgn_tag_cursor + len - 1) % len;
+    }
+
+    pub fn pgn_tag_cursor_down(&mut self) {
+        self.pgn_tag_cursor = (self.pgn_tag_cursor + 1) % PgnTags::EDITABLE.len();
+    }
+
+    /// Add a character to the header being edited, up to the length of a prompt
+    pub fn type_pgn_tag(&mut self, c: char) {
+        let value = self.pgn_tags.value_mut(self.pgn_tag_cursor);
+        if value.chars().count() < 40 {
+            value.push(c);
+        }
+    }
+
+    pub fn delete_pgn_tag_char(&mut self) {
+        self.pgn_tags.value_mut(self.pgn_tag_cursor).pop();
+    }
+
+    /// Save the game with the edited headers
+    pub fn submit_pgn_tags(&mut self) {
+        if self.current_popup == Some(Popups::PgnTags) {
+            self.current_popup = None;
+            self.export_pgn();
+        }
+    }
+
+    /// Write the game in PGN under the edited headers to the `games` folder of the configuration,
+    /// the file is named in a toast
+    pub fn export_pgn(&mut self) {
+        let now = chrono::Local::now();
+        // A replayed game is written with its comments and sidelines
+        let (game, comments, variations) = match self.parent_lines.first() {
+            Some(main_line) => (&main_line.game, &main_line.comments, &main_line.variations),
+            None => (&self.game, &self.pgn_comments, &self.pgn_variations),
+        };
+        let pgn = to_annotated_pgn(game, &self.pgn_tags, comments, variations);
+        let folder = match home_dir() {
+            Some(home_dir) => home_dir.join(".config/chess-tui/games"),
+            None => {
+                self.show_toast(Toast::error("Could not get home directory"));
+                return;
+            }
+        };
+        let path = folder.join(format!("{}.pgn", now.format("%Y-%m-%d_%H-%M-%S")));
+        match fs::create_dir_all(&folder).and_then(|()| fs::write(&path, pgn)) {
+            Ok(()) => self.show_toast(Toast::info(format!("Game saved to {}", path.display()))),
+            Err(e) => self.show_toast(Toast::error(format!("Could not save the game: {e}"))),
+        }
+    }
+
+    /// Open the prompt to name the file the game is saved to
+    pub fn open_save_input(&mut self) {
+        if self.current_page.is_game() && self.current_popup.is_none() {
+            self.game.ui.prompt = Prompt::new();
+            self.current_popup = Some(Popups::SaveInput);
+        }
+    }
+
+    /// Save the game to the file named in the prompt, in the `games` folder unless the path is absolute
+    /// A name without an extension is given `.toml`
+    pub fn submit_save_input(&mut self) {
+        let name = self.game.ui.prompt.input.trim().to_string();
+        if name.is_empty() {
+            self.show_toast(Toast::error("Type the name of the file"));
+            return;
+        }
+        let mut path = PathBuf::from(name);
+        if path.extension().is_none() {
+            path.set_extension("toml");
+        }
+        if path.is_relative() {
+            match home_dir() {
+                Some(home_dir) => path = home_dir.join(".config/chess-tui/games").join(path),
+                None => {
+                    self.show_toast(Toast::error("Could not get home directory"));
+                    return;
+                }
+            }
+        }
+        self.game.ui.prompt.submit_message();
+        self.current_popup = None;
+        self.save_game_to(path);
+    }
+
+    /// Save the game to a file, the player confirms before an existing file is replaced
+    pub fn save_game_to(&mut self, path: PathBuf) {
+        if path.exists() {
+            self.pending_save = Some(path);
+            self.current_popup = Some(Popups::OverwriteConfirmation);
+        } else {
+            self.write_save(&path, false);
+        }
+    }
+
+    /// The player agrees to replace the existing file
+    pub fn confirm_overwrite(&mut self) {
+        if self.current_popup == Some(Popups::OverwriteConfirmation) {
+            self.current_popup = None;
+            if let Some(path) = self.pending_save.take() {
+                self.write_save(&path, true);
+            }
+        }
+    }
+
+    /// The existing file is kept, the game is not saved
+    pub fn cancel_overwrite(&mut self) {
+        if self.current_popup == Some(Popups::OverwriteConfirmation) {
+            self.current_popup = None;
+            self.pending_save = None;
+        }
+    }
+
+    /// Write the local game or the game against the bot to the autosave file after every move,
+    /// the file goes away once the game is over
+    fn autosave(&self) {
+        let Some(path) = &self.autosave_path else {
+            return;
+        };
+        let is_local_game = matches!(self.current_page, Pages::Solo | Pages::Bot)
+            && self.opponent.is_none()
+            && self.puzzles.is_none();
+        if !is_local_game || self.game.game_state == GameState::Promotion {
+            return;
+        }
+        if self.game.game_state.is_over() {
+            if path.exists() {
+                if let Err(e) = fs::remove_file(path) {
+                    log::warn!("Could not remove {}: {e}", path.display());
+                }
+            }
+            return;
+        }
+        let saved = SavedGame {
+            player_color: self
+                .selected_color
+                .filter(|_| self.current_page == Pages::Bot),
+            ..SavedGame::from_game(&self.game)
+        };
+        if let Err(e) = saved.write_to(path, true) {
+            log::warn!("Could not autosave the game: {e}");
+        }
+    }
+
+    /// Go back to the game written to the autosave file, against the bot when it was a bot game
+    pub fn resume_last_game(&mut self) {
+        let saved = match &self.autosave_path {
+            Some(path) if path.exists() => fs::read_to_string(path)
+                .map_err(|e| ChessError::InvalidSave(e.to_string()))
+                .and_then(|content| SavedGame::from_toml(&content)),
+            _ => {
+                self.show_toast(Toast::error("No game to resume"));
+                return;
+            }
+        };
+        if let Err(e) = saved.and_then(|saved| self.resume_saved_game(&saved)) {
+            self.show_toast(Toast::error(format!("Could not resume the game: {e}")));
+        }
+    }
+
+    // Play on from the saved game, against the bot when it was a bot game
+    fn resume_saved_game(&mut self, saved: &SavedGame) -> Result<(), ChessError> {
+        let mut game = saved.to_game()?;
+        let player_color = saved.player_color;
+        self.restart();
+        game.ui.display_mode = self.game.ui.display_mode;
+        self.game = game;
+        self.selected_color = player_color;
+        self.setup.player_color = player_color;
+        match player_color {
+            Some(_) => {
+                self.current_page = Pages::Bot;
+                self.launch_bot();
+            }
+            None => self.current_page = Pages::Solo,
+        }
+        if !self.game.game_board.move_history.is_empty() {
+            self.update_clock();
+        }
+        self.place_initial_cursor();
+        Ok(())
+    }
+
+    /// List the games saved in the `games` folder of the configuration on the My games page
+    pub fn open_my_games(&mut self) {
+        match home_dir() {
+            Some(home_dir) => self.open_my_games_in(&home_dir.join(".config/chess-tui/games")),
+            None => self.show_toast(Toast::error("Could not get home directory")),
+        }
+    }
+
+    /// List the PGN files and saved games of `folder` on the My games page
+    pub fn open_my_games_in(&mut self, folder: &Path) {
+        self.my_games = scan_games(folder, self.player_name.as_deref());
+        self.my_games_cursor = 0;
+        self.current_popup = None;
+        self.current_page = Pages::MyGames;
+    }
+
+    pub fn my_games_up(&mut self) {
+        self.my_games_cursor = self.my_games_cursor.saturating_sub(1);
+    }
+
+    pub fn my_games_down(&mut self) {
+        if self.my_games_cursor + 1 < self.my_games.len() {
+            self.my_games_cursor += 1;
+        }
+    }
+
+    /// Look for the games hosted on the local network, they are listed as they answer
+    pub fn open_lan_games(&mut self) {
+        match Discovery::start() {
+            Ok(discovery) => {
+                self.lan_discovery = Some(discovery);
+                self.lan_games_cursor = 0;
+                self.current_popup = None;
+                self.current_page = Pages::LanGames;
+            }
+            Err(e) => self.show_toast(Toast::error(format!(
+                "Could not look for games on the network: {e}"
+            ))),
+        }
+    }
+
+    /// Stop looking for games on the local network and go back to the home menu
+    pub fn close_lan_games(&mut self) {
+        self.lan_discovery = None;
+        self.current_page = Pages::Home;
+    }
+
+    /// The games found on the local network so far
+    pub fn lan_games(&self) -> &[LanGame] {
+        self.lan_discovery
+            .as_ref()
+            .map_or(&[], |discovery| discovery.games.as_slice())
+    }
+
+    pub fn lan_games_up(&mut self) {
+        self.lan_games_cursor = self.lan_games_cursor.saturating_sub(1);
+    }
+
+    pub fn lan_games_down(&mut self) {
+        if self.lan_games_cursor + 1 < self.lan_games().len() {
+            self.lan_games_cursor += 1;
+        }
+    }
+
+    /// Join the game selected among the ones found on the local network, the search stops
+    pub fn join_lan_game(&mut self) {
+        let Some(game) = self.lan_games().get(self.lan_games_cursor).cloned() else {
+            return;
+        };
+        self.lan_discovery = None;
+        self.restart();
+        self.current_page = Pages::Solo;
+        self.join_game(game.address);
+    }
+
+    /// Open the prompt to type the code of the online game to join
+    pub fn open_game_code_input(&mut self) {
+        self.game.ui.prompt = Prompt::new();
+        self.current_popup = Some(Popups::GameCodeInput);
+    }
+
+    /// Join the online game of the code typed in the prompt, a malformed code is reported
+    pub fn submit_game_code_input(&mut self) {
+        match decode_game_code(&self.game.ui.prompt.input) {
+            Ok(address) => {
+                self.game.ui.prompt.submit_message();
+                self.current_popup = None;
+                self.lan_discovery = None;
+                self.restart();
+                self.current_page = Pages::Solo;
+                self.join_game(address);
+            }
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    /// Replay the game selected on the My games page from its first move, or play on
+    /// from its last position when it is not over
+    pub fn open_my_game(&mut self) {
+        let Some(entry) = self.my_games.get(self.my_games_cursor).cloned() else {
+            return;
+        };
+        if let Err(e) = self.load_library_game(&entry) {
+            self.show_toast(Toast::error(format!("Could not open the game: {e}")));
+        }
+    }
+
+    fn load_library_game(&mut self, entry: &LibraryGame) -> Result<(), ChessError> {
+        match entry.source {
+            GameSource::Pgn(index) => {
+                let games = load_games(&entry.path)?;
+                if index >= games.len() {
+                    return Err(ChessError::InvalidPgn(format!(
+                        "{} changed since it was listed",
+                        entry.path.display()
+                    )));
+                }
+                self.restart();
+                self.pgn_games = games;
+                self.load_pgn_game(index)?;
+            }
+            GameSource::Save => {
+                let saved = fs::read_to_string(&entry.path)
+                    .map_err(|e| ChessError::InvalidSave(e.to_string()))
+                    .and_then(|content| SavedGame::from_toml(&content))?;
+                if entry.is_unfinished() {
+                    return self.resume_saved_game(&saved);
+                }
+                let mut game = saved.to_game()?;
+                self.restart();
+                game.ui.display_mode = self.game.ui.display_mode;
+                self.game = game;
+                self.current_page = Pages::Solo;
+                self.place_initial_cursor();
+                self.review_index = Some(0);
+            }
+        }
+        if entry.is_unfinished() {
+            self.review_index = None;
+        }
+        Ok(())
+    }
+
+    fn write_save(&mut self, path: &Path, overwrite: bool) {
+        match SavedGame::from_game(&self.game).write_to(path, overwrite) {
+            Ok(()) => self.show_toast(Toast::info(format!("Game saved to {}", path.display()))),
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    /// Write the positions of the game as an animated GIF next to the saved games
+    #[cfg(feature = "gif")]
+    pub fn export_gif(&mut self) {
+        use crate::gif::{board_frames, encode_gif};
+
+        let gif = encode_gif(
+            &board_frames(&self.game),
+            &palette(self.theme()),
+            self.gif_frame_delay_ms,
+        );
+        let folder = match home_dir() {
+            Some(home_dir) => home_dir.join(".config/chess-tui/games"),
+            None => {
+                self.show_toast(Toast::error("Could not get home directory"));
+                return;
+            }
+        };
+        let now = chrono::Local::now();
+        let path = folder.join(format!("{}.gif", now.format("%Y-%m-%d_%H-%M-%S")));
+        match fs::create_dir_all(&folder).and_then(|()| fs::write(&path, gif)) {
+            Ok(()) => self.show_toast(Toast::info(format!("GIF saved to {}", path.display()))),
+            Err(e) => self.show_toast(Toast::error(format!("Could not save the GIF: {e}"))),
+        }
+    }
+
+    /// Write the displayed position as SVG and PNG diagrams seen from white next to the saved games
+    pub fn export_diagram(&mut self) {
+        let Some((board, last_move)) = white_view_positions(&self.displayed_game()).pop() else {
+            return;
+        };
+        let palette = palette(self.theme());
+        let folder = match home_dir() {
+            Some(home_dir) => home_dir.join(".config/chess-tui/games"),
+            None => {
+                self.show_toast(Toast::error("Could not get home directory"));
+                return;
+            }
+        };
+        let name = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+        let svg_path = folder.join(format!("{name}.svg"));
+        let png = encode_png(&render_position(&board, last_move), &palette);
+        let written = fs::create_dir_all(&folder)
+            .and_then(|()| fs::write(&svg_path, position_svg(&board, last_move, &palette)))
+            .and_then(|()| fs::write(folder.join(format!("{name}.png")), png));
+        match written {
+            Ok(()) => self.show_toast(Toast::info(format!(
+                "Diagram saved to {} and .png",
+                svg_path.display()
+            ))),
+            Err(e) => self.show_toast(Toast::error(format!("Could not save the diagram: {e}"))),
+        }
+    }
+
+    /// Without the `gif` feature there is no GIF encoder
+    #[cfg(not(feature = "gif"))]
+    pub fn export_gif(&mut self) {
+        self.show_toast(Toast::error(
+            "chess-tui was built without the `gif` feature",
+        ));
+    }
+
+    /// Stop waiting for the host, the background connection is dropped once it ends
+    pub fn cancel_handshake(&mut self) {
+        self.handshake = None;
+    }
+
+    /// Move on once the handshake is over: the game starts or the failure is reported
+    pub fn poll_handshake(&mut self, now: Instant) {
+        let Some(handshake) = &mut self.handshake else {
+            return;
+        };
+        match handshake.poll(now).clone() {
+            HandshakeState::Connecting => return,
+            HandshakeState::Connected(color, host_name) => {
+                let session_token = handshake.session_token.clone();
+                let stream = handshake.take_stream();
+                // The online game starts from the initial position on the game page
+                self.restart();
+                self.current_page = Pages::Solo;
+                self.opponent = stream.and_then(|stream| Opponent::new(stream, session_token).ok());
+                self.selected_color = Some(color);
+                self.opponent_name = Some(host_name);
+                let color = match color {
+                    PieceColor::White => "white",
+                    PieceColor::Black => "black",
+                };
+                self.show_toast(Toast::info(format!("Connected, you play {color}")));
+            }
+            HandshakeState::Failed(error) => self.show_toast(Toast::error(error.to_string())),
+        }
+        self.handshake = None;
+    }
+
+    /// Apply what the opponent of the online game sent since the last tick
+    /// The game is kept when the connection drops, it goes on once the host takes the player back
+    pub fn poll_opponent(&mut self, now: Instant) {
+        let Some(opponent) = &mut self.opponent else {
+            return;
+        };
+        for event in opponent.poll(now) {
+            match event {
+                OpponentEvent::Message(Message::Move(uci)) => {
+                    if let Err(e) = self.apply_received_move(&uci) {
+                        log::warn!("Ignored move from the opponent: {e}");
+                    }
+                }
+                OpponentEvent::Message(Message::Chat(text)) => self.show_toast(Toast::info(text)),
+                OpponentEvent::Message(Message::Error(reason)) => {
+                    self.show_toast(Toast::error(format!("The opponent refused: {reason}")));
+                }
+                OpponentEvent::Message(Message::Resign) if !self.game.game_state.is_over() => {
+                    if let Some(color) = self.selected_color {
+                        self.end_by_resignation(color.opposite());
+                        self.show_toast(Toast::info("The opponent resigned"));
+                    }
+                }
+                OpponentEvent::Message(_) => {}
+                // Nothing is left to play for once the game is over
+                OpponentEvent::Disconnected if self.game.game_state.is_over() => {
+                    self.opponent = None;
+                    return;
+                }
+                OpponentEvent::Disconnected => {
+                    log::info!("Lost the connection to the opponent, reconnecting");
+                }
+                OpponentEvent::Reconnected(moves) => {
+                    self.show_toast(Toast::info("Reconnected, the game goes on"));
+                    self.catch_up(&moves);
+                }
+                OpponentEvent::Lost(error) => {
+                    self.opponent = None;
+                    self.show_toast(Toast::error(format!(
+                        "The opponent is gone, the game is kept: {error}"
+                    )));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Stop waiting for the connection to come back, the game stays on the board
+    pub fn give_up_reconnecting(&mut self) {
+        self.opponent = None;
+        self.show_toast(Toast::info("Disconnected, the game is kept"));
+    }
+
+    // After reconnecting, the moves missed by the host are sent again
+    // and the moves missed by the local player are played
+    fn catch_up(&mut self, host_moves: &[String]) {
+        let local_moves = self.game.game_board.uci_moves();
+        if let Some(missed) = local_moves.strip_prefix(host_moves) {
+            if let Some(opponent) = &mut self.opponent {
+                for uci in missed {
+                    opponent.send(&Message::Move(uci.clone()));
+                }
+            }
+        } else if let Some(missed) = host_moves.strip_prefix(local_moves.as_slice()) {
+            for uci in missed {
+                if let Err(e) = self.apply_received_move(uci) {
+                    log::warn!("Ignored move from the opponent: {e}");
+                    return;
+                }
+            }
+        } else {
+            self.show_toast(Toast::error(
+                "The game of the opponent is not this one anymore",
+            ));
+        }
+    }
+
+    /// The panels that can get the focus on the current page, the board always comes first
+    pub fn available_focuses(&self) -> Vec<Focus> {
+        match self.current_page {
+            Pages::Solo | Pages::Bot | Pages::Analyze => {
+                vec![
+                    Focus::Board,
+                    Focus::MoveHistory,
+                    Focus::BlackMaterial,
+                    Focus::WhiteMaterial,
+                ]
+            }
+            _ => vec![Focus::Board],
+        }
+    }
+
+    /// Give the focus to the next panel
+    pub fn cycle_focus(&mut self) {
+        self.focus = self.focus.next(&self.available_focuses());
+    }
+
+    /// Give the focus to the previous panel
+    pub fn cycle_focus_back(&mut self) {
+        self.focus = self.focus.previous(&self.available_focuses());
+    }
+
+    /// Scroll the focused side panel up
+    pub fn scroll_focused_panel_up(&mut self) {
+        self.scroll_panel_up(self.focus);
+    }
+
+    /// Scroll the focused side panel down, without going past its content
+    pub fn scroll_focused_panel_down(&mut self) {
+        self.scroll_panel_down(self.focus);
+    }
+
+    /// Scroll a side panel up
+    pub fn scroll_panel_up(&mut self, panel: Focus) {
+        match panel {
+            Focus::MoveHistory => {
+                self.game.ui.history_scroll = self.game.ui.history_scroll.saturating_sub(1);
+            }
+            Focus::BlackMaterial => {
+                self.game.ui.black_material_scroll =
+                    self.game.ui.black_material_scroll.saturating_sub(1);
+            }
+            Focus::WhiteMaterial => {
+                self.game.ui.white_material_scroll =
+                    self.game.ui.white_material_scroll.saturating_sub(1);
+            }
+            Focus::Board => {}
+        }
+    }
+
+    /// Scroll a side panel down, without going past its content
+    pub fn scroll_panel_down(&mut self, panel: Focus) {
+        let game_board = &self.game.game_board;
+        match panel {
+            Focus::MoveHistory => {
+                // One line for each move of white
+                let last_line = game_board.move_history.len().div_ceil(2).saturating_sub(1);
+                if (self.game.ui.history_scroll as usize) < last_line {
+                    self.game.ui.history_scroll += 1;
+                }
+            }
+            Focus::BlackMaterial => {
+                let last_line = game_board.black_taken_pieces.len().saturating_sub(1);
+                if (self.game.ui.black_material_scroll as usize) < last_line {
+                    self.game.ui.black_material_scroll += 1;
+                }
+            }
+            Focus::WhiteMaterial => {
+                let last_line = game_board.white_taken_pieces.len().saturating_sub(1);
+                if (self.game.ui.white_material_scroll as usize) < last_line {
+                    self.game.ui.white_material_scroll += 1;
+                }
+            }
+            Focus::Board => {}
+        }
+    }
+
+    /// The side panel drawn at this cell of the terminal, if any
+    pub fn panel_at(&self, column: u16, row: u16) -> Option<Focus> {
+        self.game
+            .ui
+            .panel_areas
+            .iter()
+            .find(|(_, area)| area.contains(Position::new(column, row)))
+            .map(|(panel, _)| *panel)
+    }
+
+    /// Select a cell or play a move on the board, then react to the new position
+    pub fn handle_cell_click(&mut self) {
+        if self.is_bot_turn() || self.is_opponent_turn() {
+            return;
+        }
+        let ui = &self.game.ui;
+        if ui.is_cell_selected()
+            && self.hold_back_blunder(ui.selected_coordinates, ui.cursor_coordinates, None)
+        {
+            return;
+        }
+        let position = (
+            self.game.game_board.move_history.len(),
+            self.game.game_state,
+        );
+        self.game.handle_cell_click();
+        if position
+            != (
+                self.game.game_board.move_history.len(),
+                self.game.game_state,
+            )
+        {
+            self.on_position_changed();
+        }
+    }
+
+    // A move leaving a piece for the taking waits for a confirmation when the warning is enabled
+    fn hold_back_blunder(&mut self, from: Coord, to: Coord, promotion: Option<i8>) -> bool {
+        if !self.blunder_warning || self.game.game_state != GameState::Playing {
+            return false;
+        }
+        let is_legal = self
+            .game
+            .game_board
+            .get_authorized_positions(self.game.player_turn, from)
+            .contains(&to);
+        let Some(hanging_piece) = is_legal
+            .then(|| self.game.piece_left_hanging(from, to))
+            .flatten()
+        else {
+            return false;
+        };
+        self.pending_blunder = Some(PendingBlunder {
+            from,
+            to,
+            hanging_piece,
+            promotion,
+        });
+        self.current_popup = Some(Popups::BlunderWarning);
+        true
+    }
+
+    /// Play the move held back by the blunder warning
+    pub fn confirm_blunder(&mut self) {
+        if self.current_popup != Some(Popups::BlunderWarning) {
+            return;
+        }
+        self.current_popup = None;
+        if let Some(PendingBlunder {
+            from,
+            to,
+            promotion,
+            ..
+        }) = self.pending_blunder.take()
+        {
+            if self.game.play_move(from, to) {
+                if let (GameState::Promotion, Some(promotion_cursor)) =
+                    (self.game.game_state, promotion)
+                {
+                    self.game.ui.promotion_cursor = promotion_cursor;
+                    self.game.promote_piece();
+                }
+                self.on_position_changed();
+            }
+        }
+    }
+
+    /// Give up the move held back by the blunder warning, the piece stays selected
+    pub fn cancel_blunder(&mut self) {
+        if self.current_popup == Some(Popups::BlunderWarning) {
+            self.current_popup = None;
+            self.pending_blunder = None;
+        }
+    }
+
+    /// Open the prompt to type a move in algebraic notation
+    pub fn open_move_input(&mut self) {
+        if self.current_page.is_game()
+            && self.current_popup.is_none()
+            && self.game.game_state == GameState::Playing
+            && !self.is_bot_turn()
+            && !self.is_opponent_turn()
+        {
+            self.game.ui.unselect_cell();
+            self.game.ui.prompt = Prompt::new();
+            self.current_popup = Some(Popups::MoveInput);
+        }
+    }
+
+    /// Play the move typed in the prompt, it stays open to fix a move that can't be played
+    pub fn submit_move_input(&mut self) {
+        let input = self.game.ui.prompt.input.clone();
+        match self.play_algebraic_move(&input) {
+            Ok(()) => {
+                self.game.ui.prompt.submit_message();
+                // A move held back by the blunder warning leaves its popup open
+                if self.current_popup == Some(Popups::MoveInput) {
+                    self.current_popup = None;
+                }
+            }
+            Err(error) => self.show_toast(Toast::error(error.to_string())),
+        }
+    }
+
+    /// Play a move written in algebraic notation for the player to move
+    /// A move leaving a piece for the taking waits for a confirmation like a clicked one
+    pub fn play_algebraic_move(&mut self, san: &str) -> Result<(), ChessError> {
+        if self.is_bot_turn() {
+            return Err(ChessError::IllegalMove(format!(
+                "{san}, it is the turn of the bot"
+            )));
+        }
+        if self.is_opponent_turn() {
+            return Err(ChessError::IllegalMove(format!(
+                "{san}, it is the turn of the opponent"
+            )));
+        }
+        let (piece_move, promotion) = self.game.resolve_algebraic_move(san)?;
+        if self.hold_back_blunder(piece_move.from, piece_move.to, promotion) {
+            return Ok(());
+        }
+        self.game.play_algebraic_move(san)?;
+        self.on_position_changed();
+        Ok(())
+    }
+
+    /// Play a move received from the opponent of an online game, in UCI notation
+    /// The board border flashes when the move checks the local player
+    pub fn apply_received_move(&mut self, uci: &str) -> Result<(), ChessError> {
+        self.game.play_uci_move(uci)?;
+        self.on_position_changed();
+        self.update_check_alert();
+        Ok(())
+    }
+
+    /// Play a move received from the opponent of an online game
+    /// Returns false when the move is not legal
+    pub fn apply_network_move(&mut self, piece_move: PieceMove) -> bool {
+        let played = self.play_piece_move(piece_move);
+        if played {
+            self.update_check_alert();
+        }
+        played
+    }
+
+    /// Play a move computed by the engine
+    /// Returns false when the move is not legal
+    pub fn apply_engine_move(&mut self, piece_move: PieceMove) -> bool {
+        self.finish_engine_search();
+        self.play_piece_move(piece_move)
+    }
+
+    /// Play the move the engine settled on at the end of its search, in UCI notation
+    /// The move of a search abandoned meanwhile is dropped, the search running now goes on
+    pub fn apply_engine_best_move(&mut self, uci: &str) -> Result<(), ChessError> {
+        if std::mem::take(&mut self.discard_engine_move) {
+            self.engine_info = None;
+            self.ponder_move = None;
+            return Ok(());
+        }
+        self.engine_search = None;
+        self.finish_engine_search();
+        self.game.play_uci_move(uci)?;
+        self.on_position_changed();
+        self.start_pondering();
+        Ok(())
+    }
+
+    /// Show the best line of the running search
+    /// The lines of a search on the time of the player or of an abandoned one are not shown
+    pub fn update_engine_info(&mut self, engine_info: EngineInfo) {
+        self.engine_silent_ticks = 0;
+        if self.ponder.is_none() && !self.discard_engine_move {
+            self.engine_info = Some(engine_info);
+        }
+    }
+
+    // Once the bot moved, it thinks about the answer it expects while the player thinks
+    fn start_pondering(&mut self) {
+        let Some(ponder_move) = self.ponder_move.take() else {
+            return;
+        };
+        if !self.enable_pondering
+            || self.current_page != Pages::Bot
+            || self.game.game_state != GameState::Playing
+        {
+            return;
+        }
+        // An answer that is not legal here is not worth thinking about
+        let mut expected_game = self.game.clone();
+        if expected_game.play_uci_move(&ponder_move).is_err() {
+            return;
+        }
+        let fen = self.game.to_fen();
+        let constraints = self.engine_constraints();
+        let (Some(bot), Some(sender)) = (&mut self.bot, &self.event_sender) else {
+            return;
+        };
+        match bot.start_ponder(&fen, &ponder_move, constraints, sender.clone()) {
+            Some(Ok(search)) => {
+                self.ponder = Some(Ponder {
+                    fen: expected_game.to_fen(),
+                    search,
+                });
+            }
+            Some(Err(e)) => log::warn!("The engine could not ponder: {e}"),
+            None => {}
+        }
+    }
+
+    // The engine already thinks about the position when the player made the expected move
+    // Returns false when the search on their time was about another position and is dropped
+    fn resume_ponder(&mut self) -> bool {
+        let Some(ponder) = self.ponder.take() else {
+            return false;
+        };
+        if ponder.fen == self.game.to_fen() {
+            match ponder.search.ponderhit() {
+                Ok(()) => {
+                    self.engine_search = Some(ponder.search);
+                    self.engine_silent_ticks = 0;
+                    return true;
+                }
+                Err(e) => log::warn!("Could not resume the search of the engine: {e}"),
+            }
+        }
+        let _ = ponder.search.stop();
+        self.discard_engine_move = true;
+        false
+    }
+
+    // The move found on the time of the player is for another position, it is not played
+    fn abandon_ponder(&mut self) {
+        if let Some(ponder) = self.ponder.take() {
+            let _ = ponder.search.stop();
+            self.discard_engine_move = true;
+        }
+    }
+
+    // The last line of the search is what the engine reports about its move
+    fn finish_engine_search(&mut self) {
+        if let Some(engine_info) = self.engine_info.take() {
+            self.engine_report = SearchReport::from_info(&engine_info);
+            self.record_engine_report();
+        }
+    }
+
+    // The score of the engine is the evaluation of the position it played its move in
+    fn record_engine_report(&mut self) {
+        if let Some(report) = self.engine_report {
+            let index = self.game.game_board.board_history.len().saturating_sub(1);
+            self.game.set_evaluation(index, report.score);
+        }
+    }
+
+    // Moves coming from outside of the board are promoted to a queen
+    fn play_piece_move(&mut self, piece_move: PieceMove) -> bool {
+        if piece_move.piece_color != self.game.player_turn
+            || !self.game.play_move(piece_move.from, piece_move.to)
+        {
+            return false;
+        }
+        if self.game.game_state == GameState::Promotion {
+            self.game.ui.promotion_cursor = 0;
+            self.game.promote_piece();
+        }
+        self.on_position_changed();
+        true
+    }
+
+    fn update_check_alert(&mut self) {
+        self.check_alert = if !self.game.game_state.is_over()
+            && self.game.game_board.is_check(self.game.player_turn)
+        {
+            Some(CHECK_ALERT_TICKS)
+        } else {
+            None
+        };
+    }
+
+    /// Should the board border be drawn in the alert color on this tick
+    pub fn is_check_alert_visible(&self) -> bool {
+        self.check_alert
+            .is_some_and(|remaining_ticks| remaining_ticks % 2 == 0)
+    }
+
+    /// Called every time a move or a promotion changed the position
+    fn on_position_changed(&mut self) {
+        if log::log_enabled!(log::Level::Debug) {
+            // Logged from the point of view of white so every diagram reads the same way
+            let mut game_board = self.game.game_board.clone();
+            if self.game.board_orientation() == PieceColor::Black {
+                game_board.flip_the_board();
+            }
+            let title = match self.game.game_board.move_history.len().checked_sub(1) {
+                Some(index) => format!(
+                    "Position after {}",
+                    self.game.notated_move(index, self.move_notation)
+                ),
+                None => "Initial position".to_string(),
+            };
+            log::debug!("{title}:\n{}", game_board.to_ascii_art());
+        }
+        if let Some(event) = self.latest_move_sound() {
+            self.sounds.play(event);
+        }
+        // A promotion is only shown once the piece is chosen
+        if let Some(spectators) = &self.spectators {
+            if self.game.game_state != GameState::Promotion {
+                spectators.broadcast(&GameSnapshot::from_game(&self.game));
+            }
+        }
+        // The moves of the local player go to the opponent of the online game
+        let last_color = self
+            .game
+            .game_board
+            .move_history
+            .last()
+            .map(|piece_move| piece_move.piece_color);
+        if let Some(opponent) = &mut self.opponent {
+            if last_color.is_some()
+                && last_color == self.selected_color
+                && self.game.game_state != GameState::Promotion
+            {
+                if let Some(uci) = self.game.game_board.uci_moves().pop() {
+                    opponent.send(&Message::Move(uci));
+                }
+            }
+        }
+        self.clear_hint();
+        // The analysis of a finished game is about the moves before this one
+        self.pending_evaluations.clear();
+        if self.game.game_state.is_over() {
+            self.abandon_ponder();
+        }
+        self.check_puzzle_move();
+        self.update_clock();
+        self.autosave();
+        self.update_pending_flip();
+        self.pending_recapture = None;
+        if self.auto_recapture {
+            self.pending_recapture =
+                self.game
+                    .forced_recapture()
+                    .map(|piece_move| PendingRecapture {
+                        piece_move,
+                        remaining_ticks: AUTO_RECAPTURE_DELAY_TICKS,
+                    });
+        }
+    }
+
+    /// The event the latest move makes a sound for
+    pub fn latest_move_sound(&self) -> Option<SoundEvent> {
+        let san_move = self.game.latest_san_move()?;
+        Some(if self.game.game_state.is_over() {
+            SoundEvent::GameEnd
+        } else if san_move.ends_with('+') {
+            SoundEvent::Check
+        } else if san_move.contains('x') {
+            SoundEvent::Capture
+        } else {
+            SoundEvent::Move
+        })
+    }
+
+    // Keep the board turned to the player who just moved for a moment when both players share the keyboard
+    fn update_pending_flip(&mut self) {
+        self.pending_flip = None;
+        let Some(last_move) = self.game.game_board.move_history.last() else {
+            return;
+        };
+        if self.flip_delay_ticks == 0
+            || self.current_page != Pages::Solo
+            || self.game.game_state == GameState::Promotion
+        {
+            return;
+        }
+
+        let own_color = self.setup.player_color.unwrap_or(PieceColor::White);
+        let bottom_before_move = match self.flip_policy {
+            // The player who just moved was playing from the bottom
+            FlipPolicy::AlwaysFlip => last_move.piece_color,
+            flip_policy => {
+                let moves = self.game.game_board.move_history.len();
+                self.game
+                    .snapshot(moves - 1)
+                    .bottom_color(flip_policy, own_color)
+            }
+        };
+        if bottom_before_move != self.game.bottom_color(self.flip_policy, own_color) {
+            self.pending_flip = Some(PendingFlip {
+                bottom_color: bottom_before_move,
+                remaining_ticks: self.flip_delay_ticks,
+            });
+        }
+    }
+
+    /// Is the board displayed upside down compared to the way it is stored
+    /// The local player is White when both players share the keyboard
+    /// The player always plays from the bottom against the bot
+    pub fn is_board_flipped(&self) -> bool {
+        let bottom_color = match (&self.current_page, self.selected_color, self.pending_flip) {
+            (Pages::Bot, Some(player_color), _) => player_color,
+            // The board stays still while lines are tried on it
+            (Pages::Analyze, _, _) => PieceColor::White,
+            (_, _, Some(pending_flip)) => pending_flip.bottom_color,
+            _ => {
+                let own_color = self.setup.player_color.unwrap_or(PieceColor::White);
+                self.game.bottom_color(self.flip_policy, own_color)
+            }
+        };
+        let orientation = match self.review_index {
+            Some(index) => self.game.snapshot(index).board_orientation(),
+            None => self.game.board_orientation(),
+        };
+        bottom_color != orientation
+    }
+
+    /// The game drawn on the board: the live game or the position under review
+    pub fn displayed_game(&self) -> Game {
+        match self.review_index {
+            Some(index) => self.game.snapshot(index),
+            None => self.game.clone(),
+        }
+    }
+
+    /// Let either side move at any time and take moves back, in local games only
+    pub fn toggle_free_play(&mut self) {
+        if self.current_page != Pages::Solo || self.opponent.is_some() || self.puzzles.is_some() {
+            return;
+        }
+        self.game.free_play = !self.game.free_play;
+    }
+
+    /// Write the moves in the other notation, the choice is saved to the configuration
+    pub fn toggle_move_notation(&mut self) {
+        self.move_notation = self.move_notation.toggled();
+        self.update_config();
+        self.show_toast(Toast::info(format!(
+            "Moves written in {}",
+            self.move_notation
+        )));
+    }
+
+    /// Take back the latest move of a game in free play
+    pub fn take_back(&mut self) {
+        if !self.game.free_play || self.review_index.is_some() {
+            return;
+        }
+        if self.game.take_back() {
+            self.pending_blunder = None;
+            self.on_position_changed();
+        }
+    }
+
+    /// Start looking back at the game from the current position, or go back to the live game
+    pub fn toggle_review(&mut self) {
+        self.review_input.clear();
+        self.review_index = match self.review_index {
+            // The game goes on from the main line
+            Some(_) => {
+                while !self.parent_lines.is_empty() {
+                    self.leave_sideline();
+                }
+                None
+            }
+            None if self.current_page.is_game() => {
+                self.game.cancel_drag();
+                self.game.ui.unselect_cell();
+                Some(self.game.game_board.board_history.len().saturating_sub(1))
+            }
+            None => None,
+        };
+    }
+
+    /// Show the position one halfmove before the reviewed one
+    pub fn review_step_back(&mut self) {
+        if let Some(index) = &mut self.review_index {
+            *index = index.saturating_sub(1);
+        }
+    }
+
+    /// Show the position one halfmove after the reviewed one
+    pub fn review_step_forward(&mut self) {
+        let last_index = self.game.game_board.board_history.len().saturating_sub(1);
+        if let Some(index) = &mut self.review_index {
+            *index = (*index + 1).min(last_index);
+        }
+    }
+
+    /// Add a digit to the move number to jump to, a few digits are enough for any game
+    pub fn review_type_digit(&mut self, digit: char) {
+        if digit.is_ascii_digit() && self.review_input.len() < 4 {
+            self.review_input.push(digit);
+        }
+    }
+
+    pub fn review_delete_digit(&mut self) {
+        self.review_input.pop();
+    }
+
+    /// Show the position after white's move typed during the review
+    pub fn review_jump(&mut self) {
+        if let Ok(move_number) = self.review_input.parse::<u32>() {
+            if self.review_index.is_some() {
+                self.review_index = Some(self.game.history_index_of_move(move_number));
+            }
+        }
+        self.review_input.clear();
+    }
+
+    /// Is the bot expected to play the next move
+    pub fn is_bot_turn(&self) -> bool {
+        self.current_page == Pages::Bot
+            && self.bot.is_some()
+            && self.current_popup.is_none()
+            && self.game.game_state == GameState::Playing
+            && self.selected_color != Some(self.game.player_turn)
+    }
+
+    /// Is the opponent of the online game expected to play the next move
+    pub fn is_opponent_turn(&self) -> bool {
+        self.opponent.is_some()
+            && self.game.game_state == GameState::Playing
+            && self.selected_color != Some(self.game.player_turn)
+    }
+
+    /// Let the bot play its move, engines searching in the background play it once their search ends
+    pub fn play_bot_move(&mut self) {
+        if self.engine_search.is_some() || self.resume_ponder() {
+            return;
+        }
+        let constraints = self.engine_constraints();
+        let Some(bot) = &mut self.bot else {
+            return;
+        };
+        let fen = self.game.to_fen();
+        if let Some(sender) = &self.event_sender {
+            match bot.start_search(&fen, constraints, sender.clone()) {
+                Some(Ok(search)) => {
+                    self.engine_search = Some(search);
+                    self.engine_silent_ticks = 0;
+                    return;
+                }
+                Some(Err(e)) => return self.report_engine_failure(e),
+                None => {}
+            }
+        }
+        match bot.best_move(&fen, constraints) {
+            Ok(piece_move) => {
+                self.engine_report = bot.last_report();
+                self.record_engine_report();
+                if !self.play_piece_move(piece_move) {
+                    log::warn!("Ignored illegal bot move {piece_move:?}");
+                }
+            }
+            Err(e) => self.report_engine_failure(e),
+        }
+    }
+
+    // The difficulty of the game, within the limits of the config
+    fn engine_constraints(&self) -> SearchConstraints {
+        self.game
+            .difficulty
+            .search_constraints()
+            .within(self.engine_limits)
+    }
+
+    // Ticks of silence of a searching engine before it is considered stuck
+    fn engine_timeout_ticks(&self) -> u32 {
+        let movetime = self
+            .engine_constraints()
+            .movetime
+            .unwrap_or(DEFAULT_MOVETIME);
+        ((movetime + ENGINE_TIMEOUT).as_millis() / u128::from(TICK_RATE_MS)) as u32
+    }
+
+    /// Handle the engine stopping in the middle of its search
+    /// The failure of an abandoned search is dropped, like its move
+    pub fn apply_engine_failure(&mut self, error: ChessError) {
+        if std::mem::take(&mut self.discard_engine_move) {
+            return;
+        }
+        self.report_engine_failure(error);
+    }
+
+    // The game waits for the player to restart the engine or to go on against the random bot
+    fn report_engine_failure(&mut self, error: ChessError) {
+        log::error!("The bot could not play: {error}");
+        self.engine_search = None;
+        self.engine_info = None;
+        self.ponder = None;
+        self.ponder_move = None;
+        self.engine_failure = Some(error.to_string());
+        self.current_popup = Some(Popups::EngineFailure);
+    }
+
+    /// Start the engine of `engine_path` again, the game goes on from the position on the board
+    /// The popup stays open with the new error when it can't be started
+    pub fn restart_engine(&mut self) {
+        let Some(engine) = self.start_engine() else {
+            self.engine_failure = Some("Set engine_path in the config to restart it".to_string());
+            return;
+        };
+        match engine {
+            Ok(engine) => {
+                self.bot = Some(engine);
+                self.engine_failure = None;
+                self.current_popup = None;
+                self.start_bot_game();
+                if self.current_popup.is_none() {
+                    self.show_toast(Toast::info("Engine restarted"));
+                }
+            }
+            Err(e) => {
+                log::error!("Could not restart the engine: {e}");
+                self.engine_failure = Some(e.to_string());
+            }
+        }
+    }
+
+    /// The game goes on against the random bot instead of the failed engine
+    pub fn replace_engine_with_random_bot(&mut self) {
+        self.bot = Some(Box::new(self.random_bot()));
+        self.engine_failure = None;
+        self.current_popup = None;
+        self.show_toast(Toast::info("The random bot takes over"));
+    }
+
+    /// Light up the move the engine suggests to the player to move, for a few seconds
+    /// The engine looks for it in the background, the move arrives as an [`Event::EngineHint`]
+    pub fn show_hint(&mut self) {
+        if !matches!(self.current_page, Pages::Solo | Pages::Bot)
+            || self.opponent.is_some()
+            || self.review_index.is_some()
+            || self.game.game_state != GameState::Playing
+            || self.is_bot_turn()
+            || self.pending_hint.is_some()
+        {
+            return;
+        }
+        let Some(engine_path) = self.engine_path.clone() else {
+            self.show_toast(Toast::error("Set engine_path in the config to get hints"));
+            return;
+        };
+        let constraints = SearchConstraints {
+            depth: Some(HINT_DEPTH),
+            movetime: None,
+        }
+        .within(self.engine_limits);
+        let fen = self.game.to_fen();
+        self.pending_hint = Some(fen.clone());
+        let search_hint = {
+            let fen = fen.clone();
+            move || {
+                UciEngine::new(&engine_path)
+                    .and_then(|mut engine| engine.best_move(&fen, constraints))
+            }
+        };
+        // Without the main loop the hint is found right away
+        match &self.event_sender {
+            Some(sender) => {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    let _ = sender.send(Event::EngineHint(fen, search_hint()));
+                });
+            }
+            None => {
+                let hint = search_hint();
+                self.apply_hint(&fen, hint);
+            }
+        }
+    }
+
+    /// Light up the move found for the position `fen`, unless the game moved on since it was asked
+    pub fn apply_hint(&mut self, fen: &str, hint: Result<PieceMove, ChessError>) {
+        if self.pending_hint.as_deref() != Some(fen) {
+            return;
+        }
+        self.pending_hint = None;
+        if self.game.to_fen() != fen {
+            return;
+        }
+        match hint {
+            // The move is given from the side of the player to move, like the board
+            Ok(piece_move) => {
+                self.game.ui.hint_highlight = Some((piece_move.from, piece_move.to));
+                self.hint = Some(HINT_TICKS);
+            }
+            Err(e) => {
+                log::warn!("No hint from the engine: {e}");
+                self.show_toast(Toast::error(e.to_string()));
+            }
+        }
+    }
+
+    /// Let the engine evaluate every position of the finished game to mark its bad moves,
+    /// the review opens meanwhile and the marks show up in the move list
+    /// The evaluations are made in the background, they arrive as [`Event::PositionEvaluation`]
+    pub fn analyze_game(&mut self) {
+        if !self.game.game_state.is_over() || !self.pending_evaluations.is_empty() {
+            return;
+        }
+        let Some(engine_path) = self.engine_path.clone() else {
+            self.show_toast(Toast::error(
+                "Set engine_path in the config to analyse the game",
+            ));
+            return;
+        };
+        let constraints = SearchConstraints {
+            depth: Some(GAME_ANALYSIS_DEPTH),
+            movetime: None,
+        }
+        .within(self.engine_limits);
+        // The engine has no move to look for once the game ended on the board
+        let final_score = match self.game.game_state {
+            GameState::Checkmate => Some(Score::Mate(0)),
+            GameState::Draw if self.game.legal_move_count() == 0 => Some(Score::Centipawns(0)),
+            _ => None,
+        };
+        let fens = self.game.fen_history();
+        self.game.evaluations.clear();
+        self.pending_evaluations = fens.clone();
+        if self.review_index.is_none() {
+            self.toggle_review();
+        }
+        // Without the main loop the game is analysed right away
+        match &self.event_sender {
+            Some(sender) => {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    evaluate_game(
+                        &engine_path,
+                        &fens,
+                        final_score,
+                        constraints,
+                        |fen, evaluation| {
+                            sender
+                                .send(Event::PositionEvaluation(fen.to_string(), evaluation))
+                                .is_ok()
+                        },
+                    );
+                });
+            }
+            None => evaluate_game(
+                &engine_path,
+                &fens,
+                final_score,
+                constraints,
+                |fen, evaluation| {
+                    self.apply_evaluation(fen, evaluation);
+                    !self.pending_evaluations.is_empty()
+                },
+            ),
+        }
+    }
+
+    /// Keep the evaluation of the position `fen` of the game being analysed,
+    /// evaluations of a game that is not shown anymore are dropped
+    pub fn apply_evaluation(&mut self, fen: &str, evaluation: Result<Score, ChessError>) {
+        if self.pending_evaluations.first().map(String::as_str) != Some(fen) {
+            return;
+        }
+        self.pending_evaluations.remove(0);
+        match evaluation {
+            Ok(score) => {
+                let index =
+                    self.game.game_board.board_history.len() - self.pending_evaluations.len() - 1;
+                self.game.set_evaluation(index, score);
+                if self.pending_evaluations.is_empty() {
+                    self.show_toast(Toast::info("Game analysed, see the marks in the move list"));
+                }
+            }
+            Err(e) => {
+                log::warn!("The analysis of the game stopped: {e}");
+                self.pending_evaluations.clear();
+                self.show_toast(Toast::error(e.to_string()));
+            }
+        }
+    }
+
+    fn clear_hint(&mut self) {
+        self.hint = None;
+        self.game.ui.hint_highlight = None;
+    }
+
+    /// Hand the side of the player to the bot and take over the other one, the game goes on
+    /// from the position on the board and the board turns to the new side of the player
+    pub fn switch_sides(&mut self) {
+        if self.current_page != Pages::Bot
+            || self.bot.is_none()
+            || self.current_popup.is_some()
+            || self.review_index.is_some()
+            || self.game.game_state != GameState::Playing
+        {
+            return;
+        }
+        let Some(color) = self.selected_color.map(PieceColor::opposite) else {
+            return;
+        };
+        // The bot was thinking for the side the player takes
+        self.abandon_engine_search();
+        self.engine_info = None;
+        self.pending_blunder = None;
+        self.selected_color = Some(color);
+        self.setup.player_color = Some(color);
+        self.game.cancel_drag();
+        self.game.ui.unselect_cell();
+        self.place_initial_cursor();
+        let side = match color {
+            PieceColor::White => "White",
+            PieceColor::Black => "Black",
+        };
+        self.show_toast(Toast::info(format!("You now play {side}")));
+    }
+
+    /// Make the engine play the best move it found so far instead of thinking until the end
+    pub fn stop_engine_search(&mut self) {
+        if let Some(search) = &self.engine_search {
+            if let Err(e) = search.stop() {
+                log::warn!("Could not stop the engine: {e}");
+            }
+        }
+    }
+
+    // Leaving the game stops the engine, its move arrives after the game it was meant for
+    fn abandon_engine_search(&mut self) {
+        if let Some(search) = self.engine_search.take() {
+            let _ = search.stop();
+            self.discard_engine_move = true;
+        }
+        self.abandon_ponder();
+    }
+
+    fn random_bot(&self) -> RandomBot {
+        RandomBot::new(self.seed.map_or_else(Rng::from_entropy, Rng::from_seed))
+    }
+
+    /// A click on a cell of the board: the selected piece goes there when it can,
+    /// otherwise the piece on the cell gets picked up
+    pub fn click_board_cell(&mut self, coords: Coord) {
+        if self.is_bot_turn() || self.is_opponent_turn() {
+            return;
+        }
+        if self.game.ui.is_cell_selected() && self.game.selected_piece_positions().contains(&coords)
+        {
+            self.game.ui.cursor_coordinates = coords;
+            self.handle_cell_click();
+        } else if !self.game.start_drag(coords) {
+            self.on_illegal_target();
+        }
+    }
+
+    // The selection is dropped unless the config asks to keep it
+    fn on_illegal_target(&mut self) {
+        if !self.keep_selection_on_illegal_target {
+            self.game.ui.unselect_cell();
+        }
+    }
+
+    /// Release the dragged piece, the move is played if the cell is one of its targets
+    /// Releasing it on its own cell keeps it selected like a click
+    pub fn drop_dragged_piece(&mut self, target: Option<Coord>) {
+        let Some(dragged_from) = self.game.ui.dragged_from else {
+            return;
+        };
+        let drag_targets = self.game.drag_targets();
+        self.game.cancel_drag();
+
+        match target {
+            Some(target) if target == dragged_from => {}
+            Some(target) if drag_targets.contains(&target) => {
+                self.game.ui.cursor_coordinates = target;
+                self.handle_cell_click();
+            }
+            _ => self.on_illegal_target(),
+        }
+    }
+
+    /// Run the clock of the player to move, or stop the clocks once the game is over
+    fn update_clock(&mut self) {
+        let now = Instant::now();
+        if let Some(clock) = &mut self.game.clock {
+            if self.game.game_state.is_over() {
+                clock.stop(now);
+            } else {
+                clock.switch_to(self.game.player_turn, now);
+            }
+        }
+    }
+
+    /// Play the pending recapture right away
+    pub fn confirm_auto_recapture(&mut self) {
+        if let Some(pending_recapture) = self.pending_recapture.take() {
+            let PieceMove { from, to, .. } = pending_recapture.piece_move;
+            if self.game.play_move(from, to) {
+                self.on_position_changed();
+            }
+        }
+    }
+
+    /// Let the player play the recapture on their own
+    pub fn cancel_auto_recapture(&mut self) {
+        self.pending_recapture = None;
+    }
+
+    /// The sentence displayed in the status bar below the board
+    pub fn status_message(&self) -> String {
+        match &self.pending_recapture {
+            Some(pending_recapture) => {
+                let mut capture_cell = pending_recapture.piece_move.to;
+                // The board is flipped when black is playing
+                if self.game.player_turn == PieceColor::Black {
+                    capture_cell = flip_coord(capture_cell);
+                }
+                format!(
+                    "Recapturing on {}: `Space` to play now, `Esc` to cancel",
+                    capture_cell.to_algebraic()
+                )
+            }
+            None => match &self.engine_info {
+                Some(engine_info) if !self.game.game_state.is_over() => match self.engine_search {
+                    Some(_) => format!("Engine thinking: {engine_info} - `s` to play now"),
+                    None => format!("Engine thinking: {engine_info}"),
+                },
+                None if self.engine_search.is_some() => {
+                    "Engine thinking - `s` to play now".to_string()
+                }
+                None if self.pending_hint.is_some() => "Engine looking for a hint...".to_string(),
+                _ => match &self.puzzles {
+                    Some(puzzles) => format!(
+                        "Puzzle {}/{}: {}",
+                        puzzles.current + 1,
+                        puzzles.puzzles.len(),
+                        self.game.status_message()
+                    ),
+                    None => self.game.status_message(),
+                },
+            },
+        }
+    }
+
+    /// The player to move offers a draw to their opponent in a local game, the bot answers right away
+    pub fn offer_draw(&mut self) {
+        if self.current_popup.is_some() || self.game.game_state != GameState::Playing {
+            return;
+        }
+        match self.current_page {
+            Pages::Solo => self.current_popup = Some(Popups::DrawOffer),
+            Pages::Bot if self.bot.is_some() => {
+                if self.bot_accepts_draw() {
+                    self.end_in_draw();
+                    self.show_toast(Toast::info("The bot accepts the draw"));
+                } else {
+                    self.show_toast(Toast::info("The bot declines the draw"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The bot takes a draw unless it is ahead by more than `bot_draw_threshold` pawns of material
+    pub fn bot_accepts_draw(&self) -> bool {
+        let Some(bot_color) = self.selected_color.map(PieceColor::opposite) else {
+            return false;
+        };
+        self.game.game_board.evaluate(bot_color) <= self.bot_draw_threshold
+    }
+
+    /// The opponent accepts the draw offer, the game ends as a draw
+    pub fn accept_draw(&mut self) {
+        if self.current_popup == Some(Popups::DrawOffer) {
+            self.current_popup = None;
+            self.end_in_draw();
+        }
+    }
+
+    fn end_in_draw(&mut self) {
+        self.game.game_state = GameState::Draw;
+        self.update_clock();
+        self.autosave();
+    }
+
+    /// The opponent declines the draw offer, the game goes on
+    pub fn decline_draw(&mut self) {
+        if self.current_popup == Some(Popups::DrawOffer) {
+            self.current_popup = None;
+        }
+    }
+
+    /// The local player wants to resign, the game ends once they confirm it
+    pub fn request_resign(&mut self) {
+        if !self.current_page.is_game()
+            || self.current_popup.is_some()
+            || self.game.game_state != GameState::Playing
+        {
+            return;
+        }
+        if self.confirm_resign {
+            self.current_popup = Some(Popups::ResignConfirmation);
+        } else {
+            self.resign();
+        }
+    }
+
+    /// The player confirms the resignation
+    pub fn confirm_resign(&mut self) {
+        if self.current_popup == Some(Popups::ResignConfirmation) {
+            self.current_popup = None;
+            self.resign();
+        }
+    }
+
+    /// The player changed their mind, the game goes on
+    pub fn cancel_resign(&mut self) {
+        if self.current_popup == Some(Popups::ResignConfirmation) {
+            self.current_popup = None;
+        }
+    }
+
+    /// The player at the keyboard: the one to move when both players share it
+    pub fn local_player(&self) -> PieceColor {
+        match self.selected_color {
+            Some(color) if self.current_page == Pages::Bot || self.opponent.is_some() => color,
+            _ => self.game.player_turn,
+        }
+    }
+
+    fn resign(&mut self) {
+        let resigned = self.local_player();
+        if let Some(opponent) = &mut self.opponent {
+            opponent.send(&Message::Resign);
+        }
+        self.end_by_resignation(resigned);
+    }
+
+    fn end_by_resignation(&mut self, resigned: PieceColor) {
+        self.game.game_state = GameState::Resignation(resigned);
+        self.pending_recapture = None;
+        self.update_clock();
+        self.autosave();
+    }
+
+    /// Apply the values read from the configuration file
+    pub fn apply_config(&mut self, config: &Value) {
+        let Some(table) = config.as_table() else {
+            return;
+        };
+
+        if let Some(display_mode) = table.get("display_mode").and_then(Value::as_str) {
+            self.game.ui.display_mode = match display_mode {
+                "ASCII" => DisplayMode::ASCII,
+                _ => DisplayMode::DEFAULT,
+            };
+        }
+        if let Some(log_level) = table
+            .get("log_level")
+            .and_then(Value::as_str)
+            .and_then(|log_level| log_level.parse().ok())
+        {
+            self.log_level = log_level;
+        }
+        // The timeout is configured in seconds, 0 disables the animation
+        if let Some(timeout) = table
+            .get("idle_animation_timeout")
+            .and_then(Value::as_integer)
+        {
+            let timeout_ticks =
+                (timeout > 0).then(|| (timeout as u64 * 1000 / TICK_RATE_MS) as u32);
+            self.idle = IdleTracker::new(timeout_ticks);
+        }
+        if let Some(auto_recapture) = table.get("auto_recapture").and_then(Value::as_bool) {
+            self.auto_recapture = auto_recapture;
+        }
+        if let Some(time_control) = table.get("time_control").and_then(Value::as_str) {
+            match time_control.parse::<TimeControl>() {
+                Ok(time_control) => {
+                    self.setup.time_control = Some(time_control);
+                    self.game.clock = Some(Clock::new(time_control));
+                }
+                Err(error) => self.show_toast(Toast::error(error.to_string())),
+            }
+        }
+        if let Some(swap_colors) = table.get("swap_colors_on_rematch").and_then(Value::as_bool) {
+            self.setup.swap_colors_on_rematch = swap_colors;
+        }
+        if let Some(threshold) = table
+            .get("clock_tenths_threshold")
+            .and_then(Value::as_integer)
+        {
+            self.clock_tenths_threshold = Duration::from_secs(threshold.max(0) as u64);
+        }
+        if let Some(clock_flash) = table.get("clock_flash").and_then(Value::as_bool) {
+            self.clock_flash = clock_flash;
+        }
+        if table.get("autosave").and_then(Value::as_bool) == Some(false) {
+            self.autosave_path = None;
+        }
+        if let Some(flip_policy) = table.get("flip_policy").and_then(Value::as_str) {
+            self.flip_policy = match flip_policy {
+                "NeverFlip" => FlipPolicy::NeverFlip,
+                "FlipOnOwnMove" => FlipPolicy::FlipOnOwnMove,
+                _ => FlipPolicy::AlwaysFlip,
+            };
+        }
+        if let Some(move_notation) = table.get("move_notation").and_then(Value::as_str) {
+            self.move_notation = match move_notation {
+                "UCI" => MoveNotation::Uci,
+                _ => MoveNotation::San,
+            };
+        }
+        // The delay is configured in milliseconds and counted in ticks
+        if let Some(delay) = table.get("flip_delay_ms").and_then(Value::as_integer) {
+            let ticks = (delay.max(0) as u64).div_ceil(TICK_RATE_MS);
+            self.flip_delay_ticks = ticks.min(u64::from(u16::MAX)) as u16;
+        }
+        if let Some(confirm_resign) = table.get("confirm_resign").and_then(Value::as_bool) {
+            self.confirm_resign = confirm_resign;
+        }
+        if let Some(blunder_warning) = table.get("blunder_warning").and_then(Value::as_bool) {
+            self.blunder_warning = blunder_warning;
+        }
+        if let Some(keep_selection) = table
+            .get("keep_selection_on_illegal_target")
+            .and_then(Value::as_bool)
+        {
+            self.keep_selection_on_illegal_target = keep_selection;
+        }
+        if let Some(engine_path) = table.get("engine_path").and_then(Value::as_str) {
+            self.engine_path = (!engine_path.is_empty()).then(|| engine_path.to_string());
+        }
+        if let Some(engines) = table.get("engines").and_then(Value::as_array) {
+            self.engine_profiles = parse_engine_profiles(engines);
+        }
+        // 0 leaves the limit to the difficulty
+        if let Some(movetime) = table.get("engine_movetime_ms").and_then(Value::as_integer) {
+            self.engine_limits.movetime =
+                (movetime > 0).then(|| Duration::from_millis(movetime as u64));
+        }
+        if let Some(depth) = table.get("engine_depth").and_then(Value::as_integer) {
+            self.engine_limits.depth = (depth > 0).then(|| depth.min(i64::from(u32::MAX)) as u32);
+        }
+        if let Some(elo) = table.get("engine_elo").and_then(Value::as_integer) {
+            self.engine_elo = elo.clamp(i64::from(MIN_ELO), i64::from(MAX_ELO)) as u32;
+        }
+        // The rating of the config applies to the Elo difficulty whichever key comes first
+        if let Difficulty::Elo(_) = self.game.difficulty {
+            self.game.difficulty = Difficulty::Elo(self.engine_elo);
+        }
+        if let Some(limit_strength) = table.get("engine_limit_strength").and_then(Value::as_bool) {
+            self.game.difficulty = if limit_strength {
+                Difficulty::Elo(self.engine_elo)
+            } else {
+                Difficulty::default()
+            };
+        }
+        if let Some(enable_pondering) = table.get("enable_pondering").and_then(Value::as_bool) {
+            self.enable_pondering = enable_pondering;
+        }
+        if let Some(lines) = table.get("analysis_lines").and_then(Value::as_integer) {
+            self.analysis_line_count = lines.clamp(1, MAX_ANALYSIS_LINES) as usize;
+        }
+        if let Some(delay) = table.get("gif_frame_delay_ms").and_then(Value::as_integer) {
+            self.gif_frame_delay_ms = delay.clamp(0, i64::from(u32::MAX)) as u32;
+        }
+        if let Some(sounds) = table.get("sounds").and_then(Value::as_table) {
+            self.sounds.apply_config(sounds);
+        }
+        if let Some(mouse_capture) = table.get("mouse_capture").and_then(Value::as_bool) {
+            self.mouse_capture = mouse_capture;
+        }
+        if let Some(time_odds) = table.get("bot_time_odds").and_then(Value::as_integer) {
+            self.setup.handicap.time_odds = Duration::from_secs(time_odds.max(0) as u64);
+        }
+        if let Some(threshold) = table.get("bot_draw_threshold").and_then(Value::as_integer) {
+            self.bot_draw_threshold = threshold as i32;
+        }
+        if let Some(material_odds) = table.get("bot_material_odds").and_then(Value::as_str) {
+            match material_odds.parse() {
+                Ok(material_odds) => self.setup.handicap.material_odds = Some(material_odds),
+                Err(error) => self.show_toast(Toast::error(error.to_string())),
+            }
+        }
+        if let Some(highlight_all_checks) =
+            table.get("highlight_all_checks").and_then(Value::as_bool)
+        {
+            self.highlight_all_checks = highlight_all_checks;
+        }
+        if let Some(compact_cells) = table.get("compact_cells").and_then(Value::as_bool) {
+            self.compact_cells = compact_cells;
+        }
+        if let Some(menu_wrap) = table.get("menu_wrap").and_then(Value::as_bool) {
+            self.menu_wrap = menu_wrap;
+        }
+        // An empty cell keeps the cursor in the middle of the board
+        if let Some(square) = table.get("initial_cursor").and_then(Value::as_str) {
+            match (square, Coord::from_algebraic(square)) {
+                ("", _) => self.initial_cursor = None,
+                (_, Some(cell)) => {
+                    self.initial_cursor = Some(cell);
+                    self.place_initial_cursor();
+                }
+                (_, None) => {
+                    let error = ChessError::InvalidSquare(square.to_string());
+                    self.show_toast(Toast::error(format!("initial_cursor: {error}")));
+                }
+            }
+        }
+        if let Some(theme) = table.get("theme").and_then(Value::as_str) {
+            self.theme_index = theme_index(theme);
+        }
+        // An empty color keeps the one of the theme
+        if let Some(color) = table.get("selected_cell_color").and_then(Value::as_str) {
+            match color {
+                "" => self.selected_cell_color = None,
+                _ => match color.parse::<Color>() {
+                    Ok(color) => self.selected_cell_color = Some(color),
+                    Err(_) => self.show_toast(Toast::error(format!(
+                        "Unknown selected_cell_color `{color}`"
+                    ))),
+                },
+            }
+        }
+        // An empty name is an unknown player
+        for (key, name) in [
+            ("white_player", &mut self.player_names.white),
+            ("black_player", &mut self.player_names.black),
+            ("player_name", &mut self.player_name),
+        ] {
+            if let Some(value) = table.get(key).and_then(Value::as_str) {
+                *name = (!value.trim().is_empty()).then(|| value.trim().to_string());
+            }
+        }
+        if let Some(timeout) = table.get("connection_timeout").and_then(Value::as_integer) {
+            self.connection_timeout = Duration::from_secs(timeout.max(1) as u64);
+        }
+    }
+
+    /// The colors the board is drawn with
+    pub fn theme(&self) -> &'static BoardTheme {
+        &THEMES[self.theme_index % THEMES.len()]
+    }
+
+    /// How the board of the game is drawn
+    pub fn board_render_settings(&self) -> BoardRenderSettings {
+        BoardRenderSettings {
+            flipped: self.is_board_flipped(),
+            theme: self.board_theme(),
+            highlight_all_checks: self.highlight_all_checks,
+            compact: self.compact_cells,
         }
     }
-}
 
-impl App {
-    pub fn toggle_help_popup(&mut self) {
-        if self.current_popup == Some(Popups::Help) {
-            self.current_popup = None;
-        } else {
-            self.current_popup = Some(Popups::Help);
+    /// The colors of the theme with the selected cell color of the configuration
+    pub fn board_theme(&self) -> BoardTheme {
+        BoardTheme {
+            selected_cell: self
+                .selected_cell_color
+                .unwrap_or(self.theme().selected_cell),
+            ..*self.theme()
         }
     }
-    pub fn toggle_credit_popup(&mut self) {
-        if self.current_page == Pages::Home {
-            self.current_page = Pages::Credit;
+
+    /// Switch to the next board theme, going back to the first one after the last
+    pub fn cycle_theme(&mut self) {
+        self.theme_index = (self.theme_index + 1) % THEMES.len();
+    }
+
+    /// Display a notification for a few ticks
+    pub fn show_toast(&mut self, toast: Toast) {
+        self.toast = Some(toast);
+    }
+
+    /// Replace the current game by the position described by a FEN string
+    pub fn load_fen(&mut self, fen: &str) -> Result<(), ChessError> {
+        let mut game = Game::from_fen(fen)?;
+        game.ui.display_mode = self.game.ui.display_mode;
+        game.clock = self.setup.time_control.map(Clock::new);
+        self.game = game;
+        self.pgn_comments.clear();
+        self.pgn_variations.clear();
+        self.parent_lines.clear();
+        self.current_popup = None;
+        self.current_page = Pages::Solo;
+        self.place_initial_cursor();
+        Ok(())
+    }
+
+    /// Open the prompt to type or paste the FEN of the position to set up on the board
+    pub fn open_position_setup(&mut self) {
+        if self.opponent.is_some() {
+            self.show_toast(Toast::error(
+                "The position can't be changed during an online game",
+            ));
+            return;
+        }
+        self.game.ui.prompt = Prompt::for_fen();
+        self.current_popup = Some(Popups::PositionSetup);
+    }
+
+    /// Replace the game by the position typed in the prompt, which stays open on a wrong FEN
+    pub fn submit_position_setup(&mut self) {
+        let fen = self.game.ui.prompt.input.trim().to_string();
+        if fen.is_empty() {
+            self.show_toast(Toast::error("Type or paste the FEN of the position"));
+            return;
+        }
+        match self.load_fen(&fen) {
+            Ok(()) => self.show_toast(Toast::info("Position set up")),
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    /// Type the text pasted in the terminal in the open prompt
+    /// Out of any popup, the text is taken for a FEN to set up
+    pub fn paste_text(&mut self, text: &str) {
+        match self.current_popup {
+            Some(
+                Popups::PositionSetup
+                | Popups::FenInput
+                | Popups::PgnInput
+                | Popups::SaveInput
+                | Popups::MoveInput,
+            ) => {}
+            None => self.open_position_setup(),
+            Some(_) => return,
+        }
+        self.game.ui.prompt.enter_str(text);
+    }
+
+    /// Open the prompt to type the FEN of the position to analyse
+    pub fn open_analysis_input(&mut self) {
+        self.game.ui.prompt = Prompt::for_fen();
+        self.current_popup = Some(Popups::FenInput);
+    }
+
+    /// Analyse the position typed in the prompt, the starting position when it is left empty
+    pub fn submit_analysis_input(&mut self) {
+        let fen = self.game.ui.prompt.input.trim().to_string();
+        let game = match fen.as_str() {
+            "" => Ok(Game::default()),
+            fen => Game::from_fen(fen),
+        };
+        match game {
+            Ok(game) => {
+                self.game.ui.prompt.submit_message();
+                self.current_popup = None;
+                self.analysis_return = None;
+                self.open_analysis(game);
+            }
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    /// Analyse the position under review, leaving the Analyze page comes back to the review
+    pub fn analyze_reviewed_position(&mut self) {
+        let Some(index) = self.review_index else {
+            return;
+        };
+        if self.current_page != Pages::Solo {
+            return;
+        }
+        let Some(fen) = self.game.fen_history().get(index).cloned() else {
+            return;
+        };
+        match Game::from_fen(&fen) {
+            Ok(game) => {
+                let reviewed_game = self.game.clone();
+                if self.open_analysis(game) {
+                    self.analysis_return = Some((reviewed_game, index));
+                }
+            }
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    // Show `game` on the Analyze page once the engine is running, returns false when it can't start
+    fn open_analysis(&mut self, mut game: Game) -> bool {
+        let Some(analysis) = self.start_analysis_engine() else {
+            return false;
+        };
+        game.ui.display_mode = self.game.ui.display_mode;
+        // Both sides move to try the lines of the engine
+        game.free_play = true;
+        self.game = game;
+        self.review_index = None;
+        self.review_input.clear();
+        self.current_page = Pages::Analyze;
+        self.focus = Focus::Board;
+        self.analysis = Some(analysis);
+        self.analysed_position = None;
+        self.place_initial_cursor();
+        self.follow_analysis();
+        true
+    }
+
+    // The engine of the analysis, failures to start it are reported with a toast
+    fn start_analysis_engine(&mut self) -> Option<Analysis> {
+        match self.engine_path.as_deref().map(UciEngine::new) {
+            Some(Ok(engine)) => Some(Analysis::new(engine)),
+            Some(Err(e)) => {
+                self.show_toast(Toast::error(e.to_string()));
+                None
+            }
+            None => {
+                self.show_toast(Toast::error(
+                    "Set engine_path in the config to analyse positions",
+                ));
+                None
+            }
+        }
+    }
+
+    /// Show or hide the best lines of the engine next to the board, in local games and against the bot
+    /// The Analyze page always shows them
+    pub fn toggle_analysis_panel(&mut self) {
+        if !matches!(self.current_page, Pages::Solo | Pages::Bot) || self.opponent.is_some() {
+            return;
+        }
+        if self.analysis.take().is_some() {
+            self.analysed_position = None;
+            self.game.engine_lines.clear();
+            return;
+        }
+        self.analysis = self.start_analysis_engine();
+        self.follow_analysis();
+    }
+
+    /// Start the analysis over when the position shown on the board changed
+    pub fn follow_analysis(&mut self) {
+        if self.analysis.is_none() || self.game.game_state == GameState::Promotion {
+            return;
+        }
+        let fen = match self.review_index {
+            Some(index) => match self.game.fen_history().get(index) {
+                Some(fen) => fen.clone(),
+                None => return,
+            },
+            None => self.game.to_fen(),
+        };
+        if self.analysed_position.as_ref() == Some(&fen) {
+            return;
+        }
+        self.game.engine_lines.clear();
+        // The lines come back as events of the main loop
+        let Some(sender) = self.event_sender.clone() else {
+            return;
+        };
+        let line_count = self.analysis_line_count;
+        if let Some(Err(e)) = self
+            .analysis
+            .as_mut()
+            .map(|analysis| analysis.start(&fen, line_count, sender))
+        {
+            log::error!("The analysis stopped: {e}");
+            self.show_toast(Toast::error(e.to_string()));
+            self.analysis = None;
+        }
+        self.analysed_position = Some(fen);
+    }
+
+    /// Show a line sent by the engine, lines about a previous position are dropped
+    /// The score of the best line is the evaluation of the position shown
+    pub fn update_analysis(&mut self, search: u64, line: AnalysisLine) {
+        if !self
+            .analysis
+            .as_ref()
+            .is_some_and(|analysis| analysis.is_current(search))
+        {
+            return;
+        }
+        if let (1, Some(score)) = (line.rank, line.score) {
+            let index = self
+                .review_index
+                .unwrap_or_else(|| self.game.game_board.board_history.len().saturating_sub(1));
+            self.game.set_evaluation(index, score);
+        }
+        self.game.update_engine_line(line);
+    }
+
+    /// Stop the engine and go back to the review the Analyze page was opened from, or to the home page
+    pub fn leave_analysis(&mut self) {
+        self.analysis = None;
+        self.analysed_position = None;
+        match self.analysis_return.take() {
+            Some((mut game, index)) => {
+                game.engine_lines.clear();
+                self.game = game;
+                self.current_page = Pages::Solo;
+                self.focus = Focus::Board;
+                self.review_index = Some(index);
+            }
+            None => self.return_to_menu(),
+        }
+    }
+
+    /// Replay a game of a PGN file, the player chooses it from a list when the file holds several
+    pub fn open_pgn_games(&mut self, games: Vec<PgnGame>) -> Result<(), ChessError> {
+        match games.len() {
+            0 => Err(ChessError::InvalidPgn("no game could be read".to_string())),
+            1 => {
+                self.pgn_games = games;
+                self.load_pgn_game(0)
+            }
+            _ => {
+                self.pgn_games = games;
+                self.game_list_cursor = 0;
+                self.current_popup = Some(Popups::GameList);
+                Ok(())
+            }
+        }
+    }
+
+    /// Show the game of the PGN file from its first move, the arrows then step through it
+    pub fn load_pgn_game(&mut self, index: usize) -> Result<(), ChessError> {
+        let Some(pgn_game) = self.pgn_games.get(index) else {
+            return Ok(());
+        };
+        let mut game = pgn_game.to_game()?;
+        game.ui.display_mode = self.game.ui.display_mode;
+        self.player_names = PlayerNames {
+            white: pgn_game.header("White").map(str::to_string),
+            black: pgn_game.header("Black").map(str::to_string),
+        };
+        self.pgn_comments = pgn_game.comments.clone();
+        self.pgn_variations = pgn_game.variations.clone();
+        self.parent_lines.clear();
+        self.game = game;
+        self.current_popup = None;
+        self.current_page = Pages::Solo;
+        self.place_initial_cursor();
+        self.review_input.clear();
+        self.review_index = Some(0);
+        Ok(())
+    }
+
+    /// The comment of the PGN file on the reviewed position of the replayed game
+    pub fn reviewed_comment(&self) -> Option<&str> {
+        self.pgn_comments.get(self.review_index?)?.as_deref()
+    }
+
+    /// The indexes in `pgn_variations` of the sidelines played instead of the move leading to
+    /// the reviewed position
+    pub fn reviewed_sidelines(&self) -> Vec<usize> {
+        let Some(index) = self.review_index else {
+            return vec![];
+        };
+        (0..self.pgn_variations.len())
+            .filter(|&sideline| self.pgn_variations[sideline].branch + 1 == index)
+            .collect()
+    }
+
+    /// Look at a sideline of the move leading to the reviewed position
+    /// Pressed again on the first move of a sideline, it goes to the next sideline of that move
+    pub fn enter_sideline(&mut self) {
+        let Some(index) = self.review_index else {
+            return;
+        };
+        if let Some(parent) = self.parent_lines.last() {
+            let branch = parent.variations[parent.sideline].branch;
+            let next_sideline = (parent.sideline + 1..parent.variations.len())
+                .find(|&sideline| parent.variations[sideline].branch == branch);
+            if let (true, Some(next_sideline)) = (index == branch + 1, next_sideline) {
+                self.leave_sideline();
+                self.open_sideline(next_sideline);
+                return;
+            }
+        }
+        match self.reviewed_sidelines().first() {
+            Some(&sideline) => self.open_sideline(sideline),
+            None => self.show_toast(Toast::info("No sideline for this move")),
+        }
+    }
+
+    // Review the sideline at `sideline` in `pgn_variations` from its first move
+    fn open_sideline(&mut self, sideline: usize) {
+        let variation = self.pgn_variations[sideline].clone();
+        let mut game = match sideline_game(&self.game, variation.branch, &variation.line) {
+            Ok(game) => game,
+            Err(e) => {
+                self.show_toast(Toast::error(e.to_string()));
+                return;
+            }
+        };
+        game.ui.display_mode = self.game.ui.display_mode;
+        // The comment opening the sideline goes with its first move
+        let mut comments = self.pgn_comments.clone();
+        comments.resize(variation.branch + 1, None);
+        let line_comments = &variation.line.comments;
+        let first_move_comment = [line_comments.first(), line_comments.get(1)]
+            .into_iter()
+            .filter_map(|comment| comment.cloned().flatten())
+            .reduce(|opening, comment| format!("{opening} {comment}"));
+        comments.push(first_move_comment);
+        comments.extend(line_comments.iter().skip(2).cloned());
+        let variations = variation
+            .line
+            .variations
+            .iter()
+            .map(|nested| nested.shifted(variation.branch))
+            .collect();
+
+        self.parent_lines.push(ParentLine {
+            game: std::mem::replace(&mut self.game, game),
+            comments: std::mem::replace(&mut self.pgn_comments, comments),
+            variations: std::mem::replace(&mut self.pgn_variations, variations),
+            sideline,
+        });
+        self.review_index = Some(variation.branch + 1);
+    }
+
+    /// Go back to the line the reviewed sideline branches off, on the move it replaces
+    pub fn leave_sideline(&mut self) {
+        let Some(parent) = self.parent_lines.pop() else {
+            return;
+        };
+        self.review_index = Some(parent.variations[parent.sideline].branch + 1);
+        self.game = parent.game;
+        self.pgn_comments = parent.comments;
+        self.pgn_variations = parent.variations;
+    }
+
+    /// Open the prompt to type the path of the PGN file to replay
+    pub fn open_pgn_input(&mut self) {
+        self.game.ui.prompt = Prompt::new();
+        self.current_popup = Some(Popups::PgnInput);
+    }
+
+    /// Replay the PGN file named in the prompt, looked for in the `games` folder unless the path is absolute
+    pub fn submit_pgn_input(&mut self) {
+        let name = self.game.ui.prompt.input.trim().to_string();
+        if name.is_empty() {
+            self.show_toast(Toast::error("Type the path of the PGN file"));
+            return;
+        }
+        let mut path = PathBuf::from(name);
+        if path.is_relative() {
+            match home_dir() {
+                Some(home_dir) => path = home_dir.join(".config/chess-tui/games").join(path),
+                None => {
+                    self.show_toast(Toast::error("Could not get home directory"));
+                    return;
+                }
+            }
+        }
+        self.game.ui.prompt.submit_message();
+        self.current_popup = None;
+        if let Err(e) = load_games(&path).and_then(|games| self.open_pgn_games(games)) {
+            self.show_toast(Toast::error(e.to_string()));
+        }
+    }
+
+    pub fn game_list_up(&mut self) {
+        self.game_list_cursor = self.game_list_cursor.saturating_sub(1);
+    }
+
+    pub fn game_list_down(&mut self) {
+        if self.game_list_cursor + 1 < self.pgn_games.len() {
+            self.game_list_cursor += 1;
+        }
+    }
+
+    /// Replay the game selected in the game list
+    pub fn select_pgn_game(&mut self) {
+        if let Err(e) = self.load_pgn_game(self.game_list_cursor) {
+            self.show_toast(Toast::error(e.to_string()));
+        }
+    }
+
+    /// Start solving the puzzles from the first one
+    pub fn start_puzzles(&mut self, puzzles: PuzzleSet) -> Result<(), ChessError> {
+        self.load_fen(&puzzles.current().fen)?;
+        self.puzzles = Some(puzzles);
+        Ok(())
+    }
+
+    // A right answer moves on to the next puzzle, a wrong one puts the position back
+    fn check_puzzle_move(&mut self) {
+        let Some(puzzles) = &mut self.puzzles else {
+            return;
+        };
+        // The answer is only known once the promoted piece is chosen
+        if self.game.game_state == GameState::Promotion {
+            return;
+        }
+        let Some(uci) = self.game.game_board.uci_moves().pop() else {
+            return;
+        };
+        let toast = if !puzzles.current().is_solution(&uci) {
+            Toast::error(format!("{uci} is not the best move, try again"))
+        } else if puzzles.advance() {
+            Toast::info("Solved! Here is the next puzzle")
         } else {
-            self.current_page = Pages::Home;
+            self.puzzles = None;
+            self.show_toast(Toast::info("Solved! That was the last puzzle"));
+            return;
+        };
+        let fen = puzzles.current().fen.clone();
+        // The positions were checked when the puzzles were loaded
+        if let Err(e) = self.load_fen(&fen) {
+            log::error!("Could not load the puzzle: {e}");
         }
+        self.show_toast(toast);
     }
 
-    pub fn go_to_home(&mut self) {
-        self.current_page = Pages::Home;
-        self.restart();
+    /// Load the FEN held by the system clipboard, failures are reported with a toast
+    pub fn paste_fen(&mut self) {
+        match read_clipboard().and_then(|fen| self.load_fen(&fen)) {
+            Ok(()) => self.show_toast(Toast::info("Position loaded from the clipboard")),
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
     }
 
-    /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    /// Copy the moves of the game in UCI notation, separated by spaces, to the system clipboard
+    pub fn copy_uci_moves(&mut self) {
+        let uci_moves = self.game.game_board.uci_moves().join(" ");
+        match write_clipboard(&uci_moves) {
+            Ok(()) => self.show_toast(Toast::info("Moves copied to the clipboard")),
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
+
+    /// Copy the FEN of the position on the board to the system clipboard, the reviewed one during a review
+    pub fn copy_fen(&mut self) {
+        match write_clipboard(&self.displayed_game().to_fen()) {
+            Ok(()) => self.show_toast(Toast::info("FEN copied to the clipboard")),
+            Err(e) => self.show_toast(Toast::error(e.to_string())),
+        }
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
         self.running = false;
     }
 
+    // Without wrapping the cursor stays on the first and last entries
     pub fn menu_cursor_up(&mut self, l: u8) {
         if self.menu_cursor > 0 {
             self.menu_cursor -= 1;
-        } else {
+        } else if self.menu_wrap {
             self.menu_cursor = l - 1;
         }
     }
     pub fn menu_cursor_right(&mut self, l: u8) {
         if self.menu_cursor < l - 1 {
             self.menu_cursor += 1;
-        } else {
+        } else if self.menu_wrap {
             self.menu_cursor = 0;
         }
     }
     pub fn menu_cursor_left(&mut self, l: u8) {
         if self.menu_cursor > 0 {
             self.menu_cursor -= 1;
-        } else {
+        } else if self.menu_wrap {
             self.menu_cursor = l - 1;
         }
     }
     pub fn menu_cursor_down(&mut self, l: u8) {
         if self.menu_cursor < l - 1 {
             self.menu_cursor += 1;
-        } else {
+        } else if self.menu_wrap {
             self.menu_cursor = 0;
         }
     }
 
+    /// Put the cursor on the configured cell of a game that starts
+    /// A player with black finds it on their side of the board, `e2` becomes `e7`
+    pub fn place_initial_cursor(&mut self) {
+        let Some(square) = self.initial_cursor else {
+            return;
+        };
+        let square = match self.selected_color.unwrap_or(self.game.player_turn) {
+            PieceColor::White => square,
+            PieceColor::Black => Coord::new(7 - square.row, square.col),
+        };
+        self.game.place_cursor(square);
+    }
+
+    /// Go to the bot page and ask for the color of the player, the game on the board is kept
+    /// so a position loaded beforehand is played against the bot
+    pub fn open_bot_setup(&mut self) {
+        self.current_page = Pages::Bot;
+        self.current_popup = Some(Popups::ColorSelection);
+        self.menu_cursor = 0;
+    }
+
     pub fn color_selection(&mut self) {
         self.current_popup = None;
         let color = match self.menu_cursor {
@@ -114,32 +3052,291 @@ impl App {
             _ => unreachable!("Invalid color selection"),
         };
         self.selected_color = Some(color);
+        self.setup.player_color = Some(color);
+        if self.current_page == Pages::Bot {
+            self.menu_cursor = Difficulty::ALL
+                .iter()
+                .position(|difficulty| difficulty.is_same_choice(self.game.difficulty))
+                .unwrap_or_default() as u8;
+            self.current_popup = Some(Popups::DifficultySelection);
+            return;
+        }
+        self.place_initial_cursor();
+    }
+
+    /// Change the rating of the Elo difficulty by `steps` of [`ELO_STEP`] while the cursor is on it
+    pub fn change_engine_elo(&mut self, steps: i32) {
+        if !matches!(
+            Difficulty::ALL.get(self.menu_cursor as usize),
+            Some(Difficulty::Elo(_))
+        ) {
+            return;
+        }
+        let elo = i64::from(self.engine_elo) + i64::from(steps) * i64::from(ELO_STEP);
+        self.engine_elo = elo.clamp(i64::from(MIN_ELO), i64::from(MAX_ELO)) as u32;
+    }
+
+    /// Pick the difficulty under the menu cursor, then the engine when the config lists several
+    pub fn difficulty_selection(&mut self) {
+        self.current_popup = None;
+        self.game.difficulty = match Difficulty::ALL.get(self.menu_cursor as usize) {
+            Some(Difficulty::Elo(_)) => Difficulty::Elo(self.engine_elo),
+            Some(&difficulty) => difficulty,
+            None => Difficulty::default(),
+        };
+        if !self.engine_profiles.is_empty() {
+            self.menu_cursor = self
+                .engine_profile
+                .filter(|&index| index < self.engine_profiles.len())
+                .unwrap_or_default() as u8;
+            self.current_popup = Some(Popups::EngineSelection);
+            return;
+        }
+        self.start_bot();
+    }
+
+    /// Start the game against the engine of the profile under the menu cursor
+    pub fn engine_selection(&mut self) {
+        self.current_popup = None;
+        self.engine_profile =
+            Some((self.menu_cursor as usize).min(self.engine_profiles.len().saturating_sub(1)));
+        self.start_bot();
+    }
+
+    // The engine of the chosen profile, or of `engine_path` without profiles
+    fn start_engine(&self) -> Option<Result<Box<dyn Engine>, ChessError>> {
+        match self
+            .engine_profile
+            .and_then(|index| self.engine_profiles.get(index))
+        {
+            Some(profile) => Some(profile.start()),
+            None => self
+                .engine_path
+                .as_deref()
+                .map(|path| UciEngine::new(path).map(|engine| Box::new(engine) as Box<dyn Engine>)),
+        }
+    }
+
+    /// The two players of an engine match: the first two engines of the config, completed by
+    /// the engine of `engine_path` then by the random bot
+    pub fn match_players(&self) -> Result<[Box<dyn Engine>; 2], ChessError> {
+        let mut players = vec![];
+        for profile in self.engine_profiles.iter().take(2) {
+            players.push(profile.start()?);
+        }
+        if players.len() < 2 {
+            if let Some(path) = &self.engine_path {
+                players.push(Box::new(UciEngine::new(path)?));
+            }
+        }
+        if players.is_empty() {
+            return Err(ChessError::EngineFailure(
+                "set engine_path or [[engines]] in the config to play a match".to_string(),
+            ));
+        }
+        if players.len() < 2 {
+            players.push(Box::new(self.random_bot()));
+        }
+        players
+            .try_into()
+            .map_err(|_| ChessError::EngineFailure("a match needs two players".to_string()))
+    }
+
+    // The random bot plays when no engine is set or when it can't be started
+    fn start_bot(&mut self) {
+        self.launch_bot();
+        self.apply_handicap();
+        self.place_initial_cursor();
+    }
+
+    // The engine of the game against the bot, the random bot when it can't start
+    fn launch_bot(&mut self) {
+        self.bot = Some(match self.start_engine() {
+            Some(Ok(engine)) => engine,
+            Some(Err(e)) => {
+                self.show_toast(Toast::error(format!("{e}, playing the random bot")));
+                Box::new(self.random_bot())
+            }
+            None => Box::new(self.random_bot()),
+        });
+        self.start_bot_game();
+    }
+
+    // The engine forgets the previous game, the player is told when it can't
+    fn start_bot_game(&mut self) {
+        let difficulty = self.game.difficulty;
+        let started = self
+            .bot
+            .as_mut()
+            .map(|bot| bot.new_game().and_then(|()| bot.set_difficulty(difficulty)));
+        if let Some(Err(e)) = started {
+            self.report_engine_failure(e);
+        }
+    }
+
+    /// Give the handicap of the setup to the bot, on a game that did not start yet
+    pub fn apply_handicap(&mut self) {
+        let Some(player_color) = self.selected_color else {
+            return;
+        };
+        let bot_color = player_color.opposite();
+        let handicap = self.setup.handicap;
+        if let Some(clock) = &mut self.game.clock {
+            clock.take_time_odds(bot_color, handicap.time_odds);
+        }
+        if let Some(material_odds) = handicap.material_odds {
+            let game_board = &mut self.game.game_board;
+            // The starting board is stored from the point of view of white
+            match material_odds.remove_from(&mut game_board.board, bot_color) {
+                Ok(()) => game_board.board_history = vec![game_board.board],
+                Err(e) => self.show_toast(Toast::error(e.to_string())),
+            }
+        }
     }
 
     pub fn restart(&mut self) {
         self.game = Game::default();
+        self.game.clock = self.setup.time_control.map(Clock::new);
+        self.check_alert = None;
+        self.engine_info = None;
+        self.engine_report = None;
+        self.abandon_engine_search();
         self.current_popup = None;
+        self.pending_recapture = None;
+        self.end_popup_cursor = 0;
+        self.review_index = None;
+        self.review_input.clear();
+        self.pgn_comments.clear();
+        self.pgn_variations.clear();
+        self.parent_lines.clear();
+        self.pending_flip = None;
+        self.pending_blunder = None;
+        self.pending_evaluations.clear();
+        self.place_initial_cursor();
+    }
+
+    /// The option selected in the popup shown once the game is over
+    pub fn end_popup_choice(&self) -> EndGameChoice {
+        EndGameChoice::ALL[self.end_popup_cursor % EndGameChoice::ALL.len()]
+    }
+
+    pub fn end_popup_cursor_up(&mut self) {
+        let len = EndGameChoice::ALL.len();
+        self.end_popup_cursor = (self.end_popup_cursor + len - 1) % len;
+    }
+
+    pub fn end_popup_cursor_down(&mut self) {
+        self.end_popup_cursor = (self.end_popup_cursor + 1) % EndGameChoice::ALL.len();
+    }
+
+    /// Play the option selected in the popup shown once the game is over
+    pub fn select_end_popup_choice(&mut self) {
+        match self.end_popup_choice() {
+            EndGameChoice::Rematch => self.rematch(),
+            EndGameChoice::NewGame => self.new_game(),
+            EndGameChoice::Review => self.toggle_review(),
+            EndGameChoice::Analyze => self.analyze_game(),
+            EndGameChoice::SavePgn => self.open_pgn_tags(),
+            EndGameChoice::MainMenu => self.return_to_menu(),
+        }
+    }
+
+    /// Start another game on the same page, the bot page asks for the color again
+    pub fn new_game(&mut self) {
+        let display_mode = self.game.ui.display_mode;
+        let show_threats = self.game.ui.show_threats;
+        let show_legal_move_count = self.game.ui.show_legal_move_count;
+        let free_play = self.game.free_play;
+        self.restart();
+        self.game.ui.display_mode = display_mode;
+        self.game.ui.show_threats = show_threats;
+        self.game.ui.show_legal_move_count = show_legal_move_count;
+        self.game.free_play = free_play;
+        self.focus = Focus::Board;
+
+        if self.current_page == Pages::Bot {
+            self.bot = None;
+            self.selected_color = None;
+            self.menu_cursor = 0;
+            self.current_popup = Some(Popups::ColorSelection);
+        }
+    }
+
+    /// Leave the game for the home page
+    pub fn return_to_menu(&mut self) {
+        let display_mode = self.game.ui.display_mode;
+        self.selected_color = None;
+        self.analysis = None;
+        self.analysed_position = None;
+        self.analysis_return = None;
+
+        self.go_to_home();
+        self.game.game_board.reset();
+        self.game.ui.reset();
+        self.game.ui.display_mode = display_mode;
+        self.place_initial_cursor();
+    }
+
+    /// Start a new game right away with the settings of the current one
+    pub fn rematch(&mut self) {
+        let display_mode = self.game.ui.display_mode;
+        let show_threats = self.game.ui.show_threats;
+        let show_legal_move_count = self.game.ui.show_legal_move_count;
+        let free_play = self.game.free_play;
+        let difficulty = self.game.difficulty;
+        self.restart();
+        self.game.ui.display_mode = display_mode;
+        self.game.ui.show_threats = show_threats;
+        self.game.ui.show_legal_move_count = show_legal_move_count;
+        self.game.free_play = free_play;
+        self.game.difficulty = difficulty;
+
+        self.setup = self.setup.rematch();
+        self.selected_color = self.setup.player_color;
+        if self.current_page == Pages::Bot {
+            self.start_bot_game();
+            self.apply_handicap();
+        }
     }
 
     pub fn menu_select(&mut self) {
         match self.menu_cursor {
             0 => self.current_page = Pages::Solo,
-            1 => {
+            1 => self.open_bot_setup(),
+            2 => self.open_analysis_input(),
+            3 => self.open_pgn_input(),
+            4 => self.resume_last_game(),
+            5 => self.open_my_games(),
+            6 => self.open_lan_games(),
+            7 => {
                 self.game.ui.display_mode = match self.game.ui.display_mode {
                     DisplayMode::ASCII => DisplayMode::DEFAULT,
                     DisplayMode::DEFAULT => DisplayMode::ASCII,
                 };
                 self.update_config();
             }
-            2 => self.toggle_help_popup(),
-            3 => self.current_page = Pages::Credit,
+            8 => self.toggle_help_popup(),
+            9 => self.current_page = Pages::Credit,
             _ => {}
         }
     }
 
+    /// Apply the configuration file at `path`, the settings changed in the game are saved back to it
+    /// A missing or invalid file leaves the settings as they are
+    pub fn load_config(&mut self, path: PathBuf) {
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(config) = content.parse::<Value>() {
+                self.apply_config(&config);
+            }
+        }
+        self.config_path = Some(path);
+    }
+
     pub fn update_config(&self) {
-        let home_dir = home_dir().expect("Could not get home directory");
-        let config_path = home_dir.join(".config/chess-tui/config.toml");
+        let config_path = match &self.config_path {
+            Some(path) => path.clone(),
+            None => config_path(None).expect("Could not get home directory"),
+        };
         let mut config = match fs::read_to_string(config_path.clone()) {
             Ok(content) => content
                 .parse::<Value>()
@@ -152,10 +3349,18 @@ impl App {
                 "display_mode".to_string(),
                 Value::String(self.game.ui.display_mode.to_string()),
             );
+            table.insert(
+                "move_notation".to_string(),
+                Value::String(self.move_notation.to_string()),
+            );
             table.insert(
                 "log_level".to_string(),
                 Value::String(self.log_level.to_string().to_string()),
             );
+            table.insert(
+                "theme".to_string(),
+                Value::String(self.theme().name.to_string()),
+            );
         }
 
         let mut file = File::create(config_path.clone()).unwrap();
@@ -165,7 +3370,14 @@ impl App {
     pub fn reset(&mut self) {
         self.game = Game::default();
         self.current_popup = None;
+        self.pending_recapture = None;
+        self.check_alert = None;
+        self.engine_info = None;
+        self.engine_report = None;
+        self.abandon_engine_search();
         self.selected_color = None;
         self.menu_cursor = 0;
+        self.focus = Focus::Board;
+        self.place_initial_cursor();
     }
 }
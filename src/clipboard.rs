@@ -0,0 +1,39 @@
+use crate::error::ChessError;
+
+/// Read the current text content of the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn read_clipboard() -> Result<String, ChessError> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| ChessError::ClipboardUnavailable(e.to_string()))?;
+    clipboard
+        .get_text()
+        .map_err(|e| ChessError::ClipboardUnavailable(e.to_string()))
+}
+
+/// Replace the content of the system clipboard with some text
+#[cfg(feature = "clipboard")]
+pub fn write_clipboard(text: &str) -> Result<(), ChessError> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| ChessError::ClipboardUnavailable(e.to_string()))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| ChessError::ClipboardUnavailable(e.to_string()))
+}
+
+/// Without the `clipboard` feature there is no way to reach the system clipboard
+#[cfg(not(feature = "clipboard"))]
+pub fn read_clipboard() -> Result<String, ChessError> {
+    Err(feature_missing())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn write_clipboard(_text: &str) -> Result<(), ChessError> {
+    Err(feature_missing())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn feature_missing() -> ChessError {
+    ChessError::ClipboardUnavailable(
+        "chess-tui was built without the `clipboard` feature".to_string(),
+    )
+}
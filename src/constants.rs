@@ -1,11 +1,16 @@
 use core::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use ratatui::style::Color;
 
 pub const UNDEFINED_POSITION: u8 = u8::MAX;
+/// Delay between two tick events in milliseconds
+pub const TICK_RATE_MS: u64 = 250;
 pub const WHITE: Color = Color::Rgb(160, 160, 160);
 pub const BLACK: Color = Color::Rgb(128, 95, 69);
+/// Cells controlled by the opponent when the threats overlay is shown
+pub const THREAT_WHITE: Color = Color::Rgb(190, 120, 120);
+pub const THREAT_BLACK: Color = Color::Rgb(150, 75, 65);
 
 pub const TITLE: &str = r"
  ██████╗██╗  ██╗███████╗███████╗███████╗   ████████╗██╗   ██╗██╗
@@ -31,6 +36,56 @@ impl fmt::Display for DisplayMode {
     }
 }
 
+/// When the board turns around in a game played on a single keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlipPolicy {
+    /// The player to move always plays from the bottom
+    #[default]
+    AlwaysFlip,
+    /// The board keeps the orientation of the first player
+    NeverFlip,
+    /// The board only turns around after a move of the local player
+    FlipOnOwnMove,
+}
+
+impl fmt::Display for FlipPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FlipPolicy::AlwaysFlip => write!(f, "AlwaysFlip"),
+            FlipPolicy::NeverFlip => write!(f, "NeverFlip"),
+            FlipPolicy::FlipOnOwnMove => write!(f, "FlipOnOwnMove"),
+        }
+    }
+}
+
+/// How the moves are written in the move list and the logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveNotation {
+    /// Standard algebraic notation, like `Nf3`
+    #[default]
+    San,
+    /// The coordinates of the cells the piece moves between, like `g1f3`, as engines write them
+    Uci,
+}
+
+impl MoveNotation {
+    pub fn toggled(self) -> MoveNotation {
+        match self {
+            MoveNotation::San => MoveNotation::Uci,
+            MoveNotation::Uci => MoveNotation::San,
+        }
+    }
+}
+
+impl fmt::Display for MoveNotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MoveNotation::San => write!(f, "SAN"),
+            MoveNotation::Uci => write!(f, "UCI"),
+        }
+    }
+}
+
 pub fn home_dir() -> Result<PathBuf, &'static str> {
     match dirs::home_dir() {
         Some(dir) => Ok(dir),
@@ -38,20 +93,148 @@ pub fn home_dir() -> Result<PathBuf, &'static str> {
     }
 }
 
+/// Environment variable naming the configuration file to use instead of the default one
+pub const CONFIG_ENV_VAR: &str = "CHESS_TUI_CONFIG";
+
+/// The configuration file given with `--config`, else in `CHESS_TUI_CONFIG`,
+/// else `~/.config/chess-tui/config.toml`
+pub fn config_path(flag: Option<&Path>) -> Result<PathBuf, &'static str> {
+    if let Some(path) = flag {
+        return Ok(path.to_path_buf());
+    }
+    match std::env::var_os(CONFIG_ENV_VAR) {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Ok(home_dir()?.join(".config/chess-tui/config.toml")),
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Pages {
     Home,
     Solo,
+    /// A game against the bot
+    Bot,
+    /// A position analysed by the engine, both sides move freely
+    Analyze,
+    /// The games saved in the games folder
+    MyGames,
+    /// The games hosted on the local network
+    LanGames,
     Credit,
 }
 impl Pages {
+    /// Number of entries of the home menu
     pub fn variant_count() -> usize {
-        4
+        10
+    }
+
+    /// The pages showing a board
+    pub fn is_game(&self) -> bool {
+        matches!(self, Pages::Solo | Pages::Bot | Pages::Analyze)
+    }
+}
+
+/// The part of the game screen receiving the arrow keys
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Focus {
+    Board,
+    MoveHistory,
+    /// The pieces taken by black, drawn above the move list
+    BlackMaterial,
+    /// The pieces taken by white, drawn below the move list
+    WhiteMaterial,
+}
+impl Focus {
+    /// The panel focused after this one, going back to the first one after the last
+    /// Any panel that is not available gives the focus back to the first one
+    pub fn next(self, available: &[Focus]) -> Focus {
+        match available.iter().position(|focus| *focus == self) {
+            Some(i) => available[(i + 1) % available.len()],
+            None => available.first().copied().unwrap_or(Focus::Board),
+        }
+    }
+
+    /// The panel focused before this one, going to the last one before the first
+    pub fn previous(self, available: &[Focus]) -> Focus {
+        match available.iter().position(|focus| *focus == self) {
+            Some(i) => available[(i + available.len() - 1) % available.len()],
+            None => available.first().copied().unwrap_or(Focus::Board),
+        }
+    }
+}
+
+/// The options of the popup shown once the game is over
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EndGameChoice {
+    /// Play again with the same settings
+    Rematch,
+    /// Choose the settings of the next game again
+    NewGame,
+    /// Step through the positions of the finished game
+    Review,
+    /// Let the engine mark the inaccuracies, mistakes and blunders of the finished game
+    Analyze,
+    /// Write the finished game in PGN to the `games` folder
+    SavePgn,
+    MainMenu,
+}
+impl EndGameChoice {
+    /// The options in the order they are listed
+    pub const ALL: [EndGameChoice; 6] = [
+        EndGameChoice::Rematch,
+        EndGameChoice::NewGame,
+        EndGameChoice::Review,
+        EndGameChoice::Analyze,
+        EndGameChoice::SavePgn,
+        EndGameChoice::MainMenu,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EndGameChoice::Rematch => "Rematch",
+            EndGameChoice::NewGame => "New game",
+            EndGameChoice::Review => "Review the game",
+            EndGameChoice::Analyze => "Analyze the game",
+            EndGameChoice::SavePgn => "Save as PGN",
+            EndGameChoice::MainMenu => "Main menu",
+        }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Popups {
     ColorSelection,
+    /// The strength of the bot, chosen after the color
+    DifficultySelection,
+    /// The engine of the bot among the profiles of the config, chosen last
+    EngineSelection,
+    /// The FEN of the position to analyse
+    FenInput,
+    /// The FEN of the position to set up on the board
+    PositionSetup,
     Help,
+    DrawOffer,
+    ResignConfirmation,
+    /// A move typed in algebraic notation
+    MoveInput,
+    /// The latest entries of the log file
+    LogViewer,
+    /// The UCI lines exchanged with the engine
+    EngineConsole,
+    /// A move leaving a piece for the taking waits for a confirmation
+    BlunderWarning,
+    /// The games of a PGN file to choose from
+    GameList,
+    /// The path of the PGN file to replay
+    PgnInput,
+    /// The headers of the game saved in PGN
+    PgnTags,
+    /// The name of the file the game is saved to
+    SaveInput,
+    /// Saving the game would replace an existing file
+    OverwriteConfirmation,
+    /// The engine crashed or stopped answering
+    EngineFailure,
+    /// The code of the online game to join
+    GameCodeInput,
 }
@@ -0,0 +1,178 @@
+use crate::{
+    game_logic::{board::Board, coord::Coord},
+    gif::{cell_color, sprite, Frame, BLACK_PIECE, OUTLINE, SPRITE_SIZE, WHITE_PIECE},
+    pieces::{PieceColor, PieceType},
+};
+
+/// Pixels on each side of a cell of the SVG diagrams, before they are scaled by the viewer
+pub const SVG_CELL_PIXELS: usize = 48;
+
+/// The pieces drawn once at the top of an SVG diagram
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Rook,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+/// The position as an SVG diagram seen from white, with the piece drawings of the GIFs and
+/// the colors of `palette`, the cells of the latest move stand out
+/// A unit of the diagram is a pixel of the piece drawings
+pub fn position_svg(
+    board: &Board,
+    last_move: Option<(Coord, Coord)>,
+    palette: &[[u8; 3]; 8],
+) -> String {
+    let hex = |index: u8| {
+        let [red, green, blue] = palette[index as usize];
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    };
+    let size = 8 * SPRITE_SIZE;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{pixels}\" height=\"{pixels}\" \
+         viewBox=\"0 0 {size} {size}\" shape-rendering=\"crispEdges\">\n",
+        pixels = 8 * SVG_CELL_PIXELS
+    );
+    // The body of a piece takes the color of its player
+    svg.push_str("<defs>\n");
+    for piece_type in PIECE_TYPES {
+        svg.push_str(&format!(
+            "<g id=\"{}\"><path fill=\"{}\" d=\"{}\"/><path fill=\"currentColor\" d=\"{}\"/></g>\n",
+            piece_id(piece_type),
+            hex(OUTLINE),
+            sprite_path(piece_type, b'#'),
+            sprite_path(piece_type, b'o')
+        ));
+    }
+    svg.push_str("</defs>\n");
+
+    for row in 0..8u8 {
+        for col in 0..8u8 {
+            let (x, y) = (col as usize * SPRITE_SIZE, row as usize * SPRITE_SIZE);
+            svg.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{SPRITE_SIZE}\" height=\"{SPRITE_SIZE}\" fill=\"{}\"/>\n",
+                hex(cell_color(Coord::new(row, col), last_move))
+            ));
+            if let Some((piece_type, color)) = board[row as usize][col as usize] {
+                let body = match color {
+                    PieceColor::White => WHITE_PIECE,
+                    PieceColor::Black => BLACK_PIECE,
+                };
+                svg.push_str(&format!(
+                    "<use href=\"#{}\" x=\"{x}\" y=\"{y}\" color=\"{}\"/>\n",
+                    piece_id(piece_type),
+                    hex(body)
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn piece_id(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "pawn",
+        PieceType::Rook => "rook",
+        PieceType::Knight => "knight",
+        PieceType::Bishop => "bishop",
+        PieceType::Queen => "queen",
+        PieceType::King => "king",
+    }
+}
+
+// The pixels of the drawing of a piece marked with `pixel`, as a path of one rectangle per run
+fn sprite_path(piece_type: PieceType, pixel: u8) -> String {
+    let mut path = String::new();
+    for (y, line) in sprite(piece_type).iter().enumerate() {
+        let line = line.as_bytes();
+        let mut x = 0;
+        while x < line.len() {
+            let run = line[x..].iter().take_while(|&&byte| byte == pixel).count();
+            if run > 0 {
+                path.push_str(&format!("M{x} {y}h{run}v1h-{run}z"));
+            }
+            x += run.max(1);
+        }
+    }
+    path
+}
+
+/// The frame as a PNG image with the colors of `palette`, its pixels are stored uncompressed
+pub fn encode_png(frame: &Frame, palette: &[[u8; 3]; 8]) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    let mut header = vec![];
+    header.extend((frame.width as u32).to_be_bytes());
+    header.extend((frame.height as u32).to_be_bytes());
+    // A byte per pixel giving its index in the palette, no interlacing
+    header.extend([8, 3, 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"PLTE", &palette.concat());
+
+    // Every line of pixels starts with the filter it went through, none here
+    let mut lines = Vec::with_capacity((frame.width + 1) * frame.height);
+    for line in frame.pixels.chunks(frame.width.max(1)) {
+        lines.push(0);
+        lines.extend(line);
+    }
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&lines));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+// A chunk of the PNG format: its length, its type, its data and the CRC of the type and data
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// The data in a zlib stream made of deflate blocks stored as they are
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        // The first bit marks the last block
+        stream.push(u8::from(blocks.peek().is_none()));
+        let length = block.len() as u16;
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+/// The CRC-32 of the PNG chunks
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+// The checksum closing a zlib stream
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
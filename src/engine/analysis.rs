@@ -0,0 +1,176 @@
+use std::fmt;
+use std::io::BufRead;
+use std::sync::mpsc::Sender;
+
+use super::{
+    console::EngineConsole,
+    uci::{Score, UciEngine},
+    Engine, SearchHandle,
+};
+use crate::{error::ChessError, event::Event};
+
+/// Number of lines the engine looks for when analysing a position
+pub const DEFAULT_ANALYSIS_LINES: usize = 3;
+
+/// Moves of a line shown in the analysis panel, the rest of the variation is cut
+const SHOWN_MOVES: usize = 8;
+
+/// One of the best lines found by the engine analysing a position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisLine {
+    /// 1 for the best line, 2 for the second best...
+    pub rank: usize,
+    /// From the point of view of the player to move
+    pub score: Option<Score>,
+    pub depth: Option<u32>,
+    /// The variation, in UCI notation
+    pub moves: Vec<String>,
+}
+
+impl fmt::Display for AnalysisLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.", self.rank)?;
+        if let Some(score) = self.score {
+            write!(f, " {score}")?;
+        }
+        if let Some(depth) = self.depth {
+            write!(f, " d{depth}")?;
+        }
+        for uci in self.moves.iter().take(SHOWN_MOVES) {
+            write!(f, " {uci}")?;
+        }
+        if self.moves.len() > SHOWN_MOVES {
+            write!(f, " ...")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse an `info` line of an analysis, lines without a principal variation are skipped
+/// Engines analysing a single line may leave out `multipv`, it is then the best line
+pub fn parse_analysis_line(line: &str) -> Option<AnalysisLine> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("info") {
+        return None;
+    }
+
+    let mut rank = 1;
+    let mut score = None;
+    let mut depth = None;
+    let mut moves = vec![];
+    while let Some(token) = tokens.next() {
+        match token {
+            "multipv" => rank = tokens.next().and_then(|v| v.parse().ok()).unwrap_or(1),
+            "depth" => depth = tokens.next().and_then(|value| value.parse().ok()),
+            "score" => {
+                score = match (tokens.next(), tokens.next().and_then(|v| v.parse().ok())) {
+                    (Some("cp"), Some(value)) => Some(Score::Centipawns(value)),
+                    (Some("mate"), Some(value)) => Some(Score::Mate(value)),
+                    _ => None,
+                }
+            }
+            // The rest of the line is the variation
+            "pv" => {
+                moves = tokens.map(str::to_string).collect();
+                break;
+            }
+            // Free text up to the end of the line
+            "string" => break,
+            _ => {}
+        }
+    }
+    if moves.is_empty() {
+        return None;
+    }
+    Some(AnalysisLine {
+        rank,
+        score,
+        depth,
+        moves,
+    })
+}
+
+/// Forward the lines of the analysis `search` to the main loop until the engine is stopped
+/// Meant to run on a background thread reading the stdout of the engine
+pub fn stream_analysis_output(reader: impl BufRead, search: u64, sender: &Sender<Event>) {
+    for line in reader.lines().map_while(Result::ok) {
+        // The engine answers `stop` with the move it would play
+        if line.starts_with("bestmove") {
+            return;
+        }
+        if let Some(analysis_line) = parse_analysis_line(&line) {
+            if sender
+                .send(Event::AnalysisLine(search, analysis_line))
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// An engine thinking about the displayed position until it changes
+/// Its lines are kept by the game, in [`Game::engine_lines`](crate::game_logic::game::Game::engine_lines)
+pub struct Analysis {
+    engine: UciEngine,
+    /// The running search, stopped before the next position is analysed
+    search: Option<SearchHandle>,
+    /// Counts the searches, the lines of the previous positions still arriving are ignored
+    search_id: u64,
+}
+
+impl Analysis {
+    pub fn new(engine: UciEngine) -> Self {
+        Analysis {
+            engine,
+            search: None,
+            search_id: 0,
+        }
+    }
+
+    /// The name of the engine, shown above its lines
+    pub fn engine_name(&self) -> String {
+        self.engine.name()
+    }
+
+    /// The lines exchanged with the engine of the analysis
+    pub fn console(&self) -> Option<EngineConsole> {
+        self.engine.console()
+    }
+
+    /// Analyse the position described by `fen`, looking for its `line_count` best lines
+    pub fn start(
+        &mut self,
+        fen: &str,
+        line_count: usize,
+        sender: Sender<Event>,
+    ) -> Result<(), ChessError> {
+        self.stop();
+        self.search_id += 1;
+        self.search = Some(
+            self.engine
+                .start_analysis(fen, line_count, self.search_id, sender)?,
+        );
+        Ok(())
+    }
+
+    /// Interrupt the search, the engine waits for the next position
+    pub fn stop(&mut self) {
+        if let Some(search) = self.search.take() {
+            if let Err(e) = search.stop() {
+                log::warn!("Could not stop the analysis: {e}");
+            }
+        }
+    }
+
+    /// Is `search_id` the search of the position shown, the lines of the previous ones are dropped
+    pub fn is_current(&self, search_id: u64) -> bool {
+        search_id == self.search_id
+    }
+}
+
+impl Drop for Analysis {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,337 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{mpsc::Sender, Arc, Mutex};
+use std::thread;
+
+use super::{
+    console::{EngineConsole, Recorded, RECEIVED_PREFIX, SENT_PREFIX},
+    uci::{engine_io_failure, lock, EngineInfo, Score, SearchReport, DEFAULT_MOVETIME},
+    Engine, SearchConstraints, SearchHandle,
+};
+use crate::{error::ChessError, event::Event, game_logic::game::Game, pieces::PieceMove};
+
+/// Scores from this many centipawns on announce a mate, in as many moves as they go over it
+pub const MATE_SCORE: i32 = 100_000;
+
+/// The command making an engine thinking play its move right away
+pub const MOVE_NOW_COMMAND: &str = "?";
+
+/// The `feature` pairs announced by the engine on a line like
+/// `feature myname="GNU Chess 6.2.9" setboard=1 done=1`, quotes removed
+pub fn parse_features(line: &str) -> Vec<(String, String)> {
+    let Some(mut rest) = line.strip_prefix("feature ") else {
+        return vec![];
+    };
+    let mut features = vec![];
+    while let Some((name, value)) = rest.trim_start().split_once('=') {
+        let (value, remainder) = match value.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => value.split_once(' ').unwrap_or((value, "")),
+        };
+        features.push((name.to_string(), value.to_string()));
+        rest = remainder;
+    }
+    features
+}
+
+/// The commands setting the limits of the next search, before `go`
+/// Engines only take whole seconds, a shorter thinking time gets a second
+pub fn limit_commands(constraints: SearchConstraints) -> Vec<String> {
+    let mut commands = vec![];
+    if let Some(depth) = constraints.depth {
+        commands.push(format!("sd {depth}"));
+    }
+    let movetime = match constraints {
+        SearchConstraints {
+            depth: Some(_),
+            movetime: None,
+        } => None,
+        SearchConstraints { movetime, .. } => Some(movetime.unwrap_or(DEFAULT_MOVETIME)),
+    };
+    if let Some(movetime) = movetime {
+        commands.push(format!("st {}", movetime.as_millis().div_ceil(1000).max(1)));
+    }
+    commands
+}
+
+/// Parse the `move` line of the engine playing, in coordinate notation like UCI
+pub fn parse_move(line: &str) -> Option<String> {
+    let mut tokens = line.split_whitespace();
+    match (tokens.next(), tokens.next()) {
+        (Some("move"), Some(best_move)) => Some(best_move.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a line of the thinking output enabled with `post`: the depth, the score in
+/// centipawns, the time, the nodes and the variation, whose first move is often in SAN
+pub fn parse_thinking(line: &str) -> Option<EngineInfo> {
+    let mut tokens = line.split_whitespace();
+    let depth = tokens.next()?.trim_end_matches(['.', '&']).parse().ok()?;
+    let score: i32 = tokens.next()?.parse().ok()?;
+    // Time and nodes
+    tokens.next()?.parse::<u64>().ok()?;
+    tokens.next()?.parse::<u64>().ok()?;
+    let score = match score {
+        score if score >= MATE_SCORE => Score::Mate(score - MATE_SCORE),
+        score if score <= -MATE_SCORE => Score::Mate(score + MATE_SCORE),
+        score => Score::Centipawns(score),
+    };
+    Some(EngineInfo {
+        best_move: tokens.next()?.to_string(),
+        score: Some(score),
+        depth: Some(depth),
+    })
+}
+
+// The engine gave up on the position instead of moving
+fn parse_refusal(line: &str) -> Option<ChessError> {
+    let refusal = if line == "resign" || line.starts_with("tellics resign") {
+        "the engine resigned".to_string()
+    } else if line.starts_with("Illegal move") || line.starts_with("Error") {
+        format!("the engine refused the position: {line}")
+    } else if ["1-0", "0-1", "1/2-1/2"]
+        .iter()
+        .any(|result| line.starts_with(result))
+    {
+        format!("the engine ended the game: {line}")
+    } else {
+        return None;
+    };
+    Some(ChessError::EngineFailure(refusal))
+}
+
+/// Forward the thinking of the engine to the main loop until it plays its move
+/// Meant to run on a background thread reading the stdout of the engine
+pub fn stream_cecp_output(reader: impl BufRead, sender: &Sender<Event>) {
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.trim();
+        let event = if let Some(best_move) = parse_move(line) {
+            Event::EngineBestMove(best_move)
+        } else if let Some(error) = parse_refusal(line) {
+            Event::EngineFailure(error)
+        } else if let Some(info) = parse_thinking(line) {
+            Event::EngineInfo(info)
+        } else {
+            continue;
+        };
+        let is_done = !matches!(event, Event::EngineInfo(_));
+        if sender.send(event).is_err() || is_done {
+            return;
+        }
+    }
+    // The output ends without a move when the engine process died
+    let _ = sender.send(Event::EngineFailure(ChessError::EngineFailure(
+        "the engine stopped before sending its move".to_string(),
+    )));
+}
+
+/// A chess engine running in its own process, driven with the CECP protocol of xboard
+/// The engine must announce `setboard=1`, every search sends it the position from scratch
+pub struct CecpEngine {
+    process: Child,
+    /// Shared with the running search to make it move now
+    stdin: Arc<Mutex<Recorded<ChildStdin>>>,
+    /// Read from a background thread while a search started with `start_search` runs
+    stdout: Arc<Mutex<BufReader<Recorded<ChildStdout>>>>,
+    /// Everything going through `stdin` and `stdout`
+    console: EngineConsole,
+    /// The name the engine gave with `myname`, or the name of its file
+    name: String,
+    /// The evaluation of the last search
+    last_report: Option<SearchReport>,
+    /// Whether the engine answers `ping` with `pong`, to wait for it
+    ping: bool,
+    /// The number of the last `ping`
+    ping_count: u32,
+}
+
+impl CecpEngine {
+    /// Start the engine at `path` and wait until it announced its features
+    pub fn new(path: &str) -> Result<Self, ChessError> {
+        let mut process = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ChessError::EngineFailure(format!("{path}: {e}")))?;
+        let (Some(stdin), Some(stdout)) = (process.stdin.take(), process.stdout.take()) else {
+            let _ = process.kill();
+            return Err(ChessError::EngineFailure(format!(
+                "{path}: could not talk to the engine"
+            )));
+        };
+        let console = EngineConsole::default();
+        let mut engine = CecpEngine {
+            process,
+            stdin: Arc::new(Mutex::new(Recorded::new(
+                stdin,
+                console.clone(),
+                SENT_PREFIX,
+            ))),
+            stdout: Arc::new(Mutex::new(BufReader::new(Recorded::new(
+                stdout,
+                console.clone(),
+                RECEIVED_PREFIX,
+            )))),
+            console,
+            name: Path::new(path)
+                .file_name()
+                .map_or(path.to_string(), |name| name.to_string_lossy().into_owned()),
+            last_report: None,
+            ping: false,
+            ping_count: 0,
+        };
+        engine.send("xboard")?;
+        engine.send("protover 2")?;
+        let mut setboard = false;
+        // The engine lists its features until `done=1`
+        'features: loop {
+            let line = engine.read_line("done=1")?;
+            for (name, value) in parse_features(&line) {
+                match name.as_str() {
+                    "done" if value == "1" => break 'features,
+                    "done" => continue,
+                    "myname" => engine.name = value,
+                    "setboard" => setboard = value == "1",
+                    "ping" => engine.ping = value == "1",
+                    _ => {}
+                }
+                engine.send(&format!("accepted {name}"))?;
+            }
+        }
+        if !setboard {
+            return Err(ChessError::EngineFailure(format!(
+                "{}: the engine can't be given a position with setboard",
+                engine.name
+            )));
+        }
+        engine.new_game()?;
+        Ok(engine)
+    }
+
+    /// Set the option `name` announced by the engine, before a search
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), ChessError> {
+        self.send(&format!("option {name}={value}"))
+    }
+
+    // Give the position to the engine without letting it move, then set the limits of the search
+    fn prepare_search(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+    ) -> Result<(), ChessError> {
+        self.send("force")?;
+        self.send(&format!("setboard {fen}"))?;
+        for command in limit_commands(constraints) {
+            self.send(&command)?;
+        }
+        self.send("go")
+    }
+
+    // Wait for the engine to be done with the previous commands, when it can tell
+    fn sync(&mut self) -> Result<(), ChessError> {
+        if !self.ping {
+            return Ok(());
+        }
+        self.ping_count += 1;
+        let pong = format!("pong {}", self.ping_count);
+        self.send(&format!("ping {}", self.ping_count))?;
+        loop {
+            if self.read_line(&pong)? == pong {
+                return Ok(());
+            }
+        }
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), ChessError> {
+        let mut stdin = lock(&self.stdin);
+        writeln!(stdin, "{command}")
+            .and_then(|()| stdin.flush())
+            .map_err(engine_io_failure)
+    }
+
+    // The next line of the engine output, trimmed, while waiting for `expected`
+    fn read_line(&mut self, expected: &str) -> Result<String, ChessError> {
+        let mut line = String::new();
+        if lock(&self.stdout)
+            .read_line(&mut line)
+            .map_err(engine_io_failure)?
+            == 0
+        {
+            return Err(ChessError::EngineFailure(format!(
+                "the engine stopped before sending {expected}"
+            )));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+impl Engine for CecpEngine {
+    fn best_move(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+    ) -> Result<PieceMove, ChessError> {
+        let game = Game::from_fen(fen)?;
+        self.prepare_search(fen, constraints)?;
+        self.last_report = None;
+        loop {
+            let line = self.read_line("move")?;
+            if let Some(best_move) = parse_move(&line) {
+                return game.piece_move_from_uci(&best_move);
+            }
+            if let Some(error) = parse_refusal(&line) {
+                return Err(error);
+            }
+            if let Some(info) = parse_thinking(&line) {
+                self.last_report = SearchReport::from_info(&info);
+            }
+        }
+    }
+
+    /// `new` clears the previous game and its depth limit, `easy` keeps the engine from
+    /// thinking on the time of the player and `post` makes it show its thinking
+    fn new_game(&mut self) -> Result<(), ChessError> {
+        for command in ["new", "force", "easy", "post"] {
+            self.send(command)?;
+        }
+        self.sync()
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn last_report(&self) -> Option<SearchReport> {
+        self.last_report
+    }
+
+    fn console(&self) -> Option<EngineConsole> {
+        Some(self.console.clone())
+    }
+
+    fn start_search(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+        sender: Sender<Event>,
+    ) -> Option<Result<SearchHandle, ChessError>> {
+        let search = self.prepare_search(fen, constraints).map(|()| {
+            // The output is read until `move`, the next search waits for it
+            let stdout = Arc::clone(&self.stdout);
+            thread::spawn(move || stream_cecp_output(&mut *lock(&stdout), &sender));
+            SearchHandle::with_stop_command(self.stdin.clone(), MOVE_NOW_COMMAND)
+        });
+        Some(search)
+    }
+}
+
+impl Drop for CecpEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
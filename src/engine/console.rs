@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Number of lines of UCI traffic kept for the engine console
+pub const ENGINE_CONSOLE_LINES: usize = 500;
+
+/// Start of the lines sent to the engine in the console
+pub const SENT_PREFIX: &str = "> ";
+
+/// Start of the lines received from the engine in the console
+pub const RECEIVED_PREFIX: &str = "< ";
+
+/// The latest UCI lines exchanged with an engine, shared by the threads talking to it
+#[derive(Debug, Clone, Default)]
+pub struct EngineConsole {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl EngineConsole {
+    /// Keep `line`, the oldest line is dropped once the console is full
+    pub fn record(&self, line: String) {
+        let mut lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        if lines.len() == ENGINE_CONSOLE_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// The lines kept, the oldest first
+    pub fn lines(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        lines.iter().cloned().collect()
+    }
+}
+
+/// A pipe to or from the engine writing every line going through it to the console
+pub struct Recorded<T> {
+    inner: T,
+    console: EngineConsole,
+    prefix: &'static str,
+    /// The start of a line whose end did not go through yet
+    partial_line: Vec<u8>,
+}
+
+impl<T> Recorded<T> {
+    pub fn new(inner: T, console: EngineConsole, prefix: &'static str) -> Self {
+        Recorded {
+            inner,
+            console,
+            prefix,
+            partial_line: vec![],
+        }
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if byte == b'\n' {
+                let line = String::from_utf8_lossy(&self.partial_line);
+                self.console
+                    .record(format!("{}{}", self.prefix, line.trim_end_matches('\r')));
+                self.partial_line.clear();
+            } else {
+                self.partial_line.push(byte);
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for Recorded<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.record(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl<W: Write> Write for Recorded<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.record(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
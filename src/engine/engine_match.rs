@@ -0,0 +1,158 @@
+use super::{Engine, SearchConstraints};
+use crate::{
+    game_logic::game::{Game, GameState},
+    pieces::{PieceColor, PieceMove},
+};
+
+/// Plies after which a game of a match that is still going on is called a draw
+pub const MATCH_MAX_PLIES: usize = 500;
+
+/// How a game of a match ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchGame {
+    /// Counted from 1
+    pub number: u32,
+    /// Index in the players of the match of the one playing white, the other one plays black
+    pub white: usize,
+    /// In PGN notation, like `1-0`
+    pub result: &'static str,
+    /// The way it ended, like `Checkmate 1-0` or why a player forfeited
+    pub reason: String,
+    /// The moves of the game in UCI notation
+    pub uci_moves: Vec<String>,
+}
+
+/// The running score of the two players of a match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchScore {
+    /// Games won by each player
+    pub wins: [u32; 2],
+    pub draws: u32,
+}
+
+impl MatchScore {
+    /// Count a finished game
+    pub fn record(&mut self, game: &MatchGame) {
+        match game.result {
+            "1-0" => self.wins[game.white] += 1,
+            "0-1" => self.wins[1 - game.white] += 1,
+            _ => self.draws += 1,
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins[0] + self.wins[1] + self.draws
+    }
+
+    /// A point per win and half a point per draw
+    pub fn points(&self, player: usize) -> f32 {
+        self.wins[player] as f32 + self.draws as f32 / 2.0
+    }
+
+    /// The table of the wins, draws, losses and points of the players named `names`
+    pub fn table(&self, names: &[String; 2]) -> String {
+        let width = names
+            .iter()
+            .map(String::len)
+            .max()
+            .unwrap_or_default()
+            .max(6);
+        let mut table = format!(
+            "{:<width$}  {:>5}  {:>5}  {:>6}  {:>5}\n",
+            "Engine", "Wins", "Draws", "Losses", "Score"
+        );
+        for (player, name) in names.iter().enumerate() {
+            table.push_str(&format!(
+                "{name:<width$}  {:>5}  {:>5}  {:>6}  {:>5.1}\n",
+                self.wins[player],
+                self.draws,
+                self.wins[1 - player],
+                self.points(player)
+            ));
+        }
+        table
+    }
+}
+
+/// Play one game between `players`, the one at index `white` moving first from the
+/// initial position, each move being searched within `constraints`
+/// A player failing to move, or playing an illegal move, forfeits the game
+pub fn play_match_game(
+    players: &mut [Box<dyn Engine>; 2],
+    white: usize,
+    constraints: SearchConstraints,
+) -> Game {
+    let mut game = Game::default();
+    for (player, engine) in players.iter_mut().enumerate() {
+        if let Err(e) = engine.new_game() {
+            log::error!("{} could not start the game: {e}", engine.name());
+            game.game_state = GameState::Resignation(if player == white {
+                PieceColor::White
+            } else {
+                PieceColor::Black
+            });
+            return game;
+        }
+    }
+    while !game.game_state.is_over() {
+        if game.game_board.move_history.len() >= MATCH_MAX_PLIES {
+            game.game_state = GameState::Draw;
+            break;
+        }
+        let mover = match game.player_turn {
+            PieceColor::White => white,
+            PieceColor::Black => 1 - white,
+        };
+        let engine = &mut players[mover];
+        match engine.best_move(&game.to_fen(), constraints) {
+            Ok(piece_move) if play_engine_move(&mut game, piece_move) => {}
+            Ok(piece_move) => {
+                log::error!("{} played the illegal move {piece_move:?}", engine.name());
+                game.game_state = GameState::Resignation(game.player_turn);
+            }
+            Err(e) => {
+                log::error!("{} failed to move: {e}", engine.name());
+                game.game_state = GameState::Resignation(game.player_turn);
+            }
+        }
+    }
+    game
+}
+
+// Play the move of an engine like the moves of the bot, promoting to a queen
+fn play_engine_move(game: &mut Game, piece_move: PieceMove) -> bool {
+    if piece_move.piece_color != game.player_turn || !game.play_move(piece_move.from, piece_move.to)
+    {
+        return false;
+    }
+    if game.game_state == GameState::Promotion {
+        game.ui.promotion_cursor = 0;
+        game.promote_piece();
+    }
+    true
+}
+
+/// Play `games` games back to back between `players`, who take turns playing white,
+/// each finished game goes to `report` with the score so far
+pub fn run_match(
+    mut players: [Box<dyn Engine>; 2],
+    games: u32,
+    constraints: SearchConstraints,
+    mut report: impl FnMut(&MatchGame, &MatchScore),
+) -> MatchScore {
+    let mut score = MatchScore::default();
+    for number in 1..=games {
+        let white = (number as usize - 1) % 2;
+        let game = play_match_game(&mut players, white, constraints);
+        let match_game = MatchGame {
+            number,
+            white,
+            result: game.result().unwrap_or("1/2-1/2"),
+            reason: game.status_message(),
+            uci_moves: game.game_board.uci_moves(),
+        };
+        score.record(&match_game);
+        report(&match_game, &score);
+    }
+    score
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+
+use super::{
+    uci::{Score, UciEngine},
+    Engine, SearchConstraints,
+};
+use crate::error::ChessError;
+
+/// Moves ahead the engine looks in every position of a finished game
+pub const GAME_ANALYSIS_DEPTH: u32 = 12;
+
+/// Evaluations are capped to ten pawns, swings in a decided game are not worth a mark
+pub const EVALUATION_CAP: i32 = 1000;
+
+/// Centipawns a move must lose to be marked as an inaccuracy
+pub const INACCURACY_LOSS: i32 = 50;
+
+/// Centipawns a move must lose to be marked as a mistake
+pub const MISTAKE_LOSS: i32 = 100;
+
+/// Centipawns a move must lose to be marked as a blunder
+pub const BLUNDER_LOSS: i32 = 300;
+
+/// How bad a move turned out once the engine went over the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveQuality {
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    /// The mark of a move losing `centipawn_loss`, `None` for a good enough move
+    pub fn classify(centipawn_loss: i32) -> Option<MoveQuality> {
+        match centipawn_loss {
+            loss if loss >= BLUNDER_LOSS => Some(MoveQuality::Blunder),
+            loss if loss >= MISTAKE_LOSS => Some(MoveQuality::Mistake),
+            loss if loss >= INACCURACY_LOSS => Some(MoveQuality::Inaccuracy),
+            _ => None,
+        }
+    }
+
+    /// The annotation written after the move, like in a PGN
+    pub fn symbol(self) -> &'static str {
+        match self {
+            MoveQuality::Inaccuracy => "?!",
+            MoveQuality::Mistake => "?",
+            MoveQuality::Blunder => "??",
+        }
+    }
+}
+
+impl fmt::Display for MoveQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveQuality::Inaccuracy => write!(f, "Inaccuracy"),
+            MoveQuality::Mistake => write!(f, "Mistake"),
+            MoveQuality::Blunder => write!(f, "Blunder"),
+        }
+    }
+}
+
+/// The score in centipawns within [`EVALUATION_CAP`], a mate counts as the cap
+pub fn capped_centipawns(score: Score) -> i32 {
+    match score {
+        Score::Centipawns(centipawns) => centipawns.clamp(-EVALUATION_CAP, EVALUATION_CAP),
+        Score::Mate(moves) if moves > 0 => EVALUATION_CAP,
+        Score::Mate(_) => -EVALUATION_CAP,
+    }
+}
+
+/// The mark of every move between the positions of `evaluations`, each from the point of
+/// view of the player to move: a move loses what its player had before it minus what
+/// they have after it, which is the opposite of the score of the opponent
+/// Moves from or to a position without evaluation are not marked
+pub fn move_qualities(evaluations: &[Option<Score>]) -> Vec<Option<MoveQuality>> {
+    evaluations
+        .windows(2)
+        .map(|pair| match (pair[0], pair[1]) {
+            (Some(before), Some(after)) => {
+                MoveQuality::classify(capped_centipawns(before) + capped_centipawns(after))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The evaluation of the position described by `fen` from the point of view of the player to move
+pub fn evaluate_position(
+    engine: &mut dyn Engine,
+    fen: &str,
+    constraints: SearchConstraints,
+) -> Result<Score, ChessError> {
+    engine.best_move(fen, constraints)?;
+    engine
+        .last_report()
+        .map(|report| report.score)
+        .ok_or_else(|| ChessError::EngineFailure("the engine gave no evaluation".to_string()))
+}
+
+/// Evaluate the positions of `fens` one after the other with the engine at `engine_path`,
+/// each evaluation goes to `report` with the FEN of its position until `report` returns false
+/// The last position gets `final_score` without the engine when the game ended on it
+/// The analysis stops at the first failure of the engine, after reporting it
+pub fn evaluate_game(
+    engine_path: &str,
+    fens: &[String],
+    final_score: Option<Score>,
+    constraints: SearchConstraints,
+    mut report: impl FnMut(&str, Result<Score, ChessError>) -> bool,
+) {
+    let Some(first_fen) = fens.first() else {
+        return;
+    };
+    let mut engine = match UciEngine::new(engine_path) {
+        Ok(engine) => engine,
+        Err(e) => {
+            report(first_fen, Err(e));
+            return;
+        }
+    };
+    for (index, fen) in fens.iter().enumerate() {
+        let evaluation = match final_score {
+            Some(score) if index == fens.len() - 1 => Ok(score),
+            _ => evaluate_position(&mut engine, fen, constraints),
+        };
+        let failed = evaluation.is_err();
+        if !report(fen, evaluation) || failed {
+            return;
+        }
+    }
+}
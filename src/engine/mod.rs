@@ -0,0 +1,243 @@
+use std::io::Write;
+use std::sync::{mpsc::Sender, Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::{error::ChessError, event::Event, pieces::PieceMove};
+use console::EngineConsole;
+use uci::SearchReport;
+
+pub mod analysis;
+pub mod cecp;
+pub mod console;
+pub mod engine_match;
+pub mod game_analysis;
+pub mod profile;
+pub mod random_bot;
+pub mod uci;
+
+/// How long an engine may think about its move, it plays as soon as one limit is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchConstraints {
+    /// Number of moves ahead, in plies
+    pub depth: Option<u32>,
+    pub movetime: Option<Duration>,
+}
+
+impl SearchConstraints {
+    /// Stay within `limits` as well, the tighter of two limits wins
+    pub fn within(self, limits: SearchConstraints) -> SearchConstraints {
+        fn tighter<T: Ord>(limit: Option<T>, other: Option<T>) -> Option<T> {
+            match (limit, other) {
+                (Some(limit), Some(other)) => Some(limit.min(other)),
+                (limit, other) => limit.or(other),
+            }
+        }
+        SearchConstraints {
+            depth: tighter(self.depth, limits.depth),
+            movetime: tighter(self.movetime, limits.movetime),
+        }
+    }
+}
+
+/// The protocol spoken by an engine of the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Protocol {
+    #[default]
+    Uci,
+    /// CECP, the protocol of xboard and WinBoard, spoken by GNU Chess or Crafty
+    Xboard,
+}
+
+impl Protocol {
+    /// The protocol of the `protocol` key of an engine, `"uci"` or `"xboard"`
+    pub fn from_name(name: &str) -> Option<Protocol> {
+        match name.to_lowercase().as_str() {
+            "uci" => Some(Protocol::Uci),
+            "xboard" | "cecp" => Some(Protocol::Xboard),
+            _ => None,
+        }
+    }
+}
+
+/// The rating the bot plays at when it is limited to an Elo and none is configured
+pub const DEFAULT_ELO: u32 = 1200;
+
+/// The lowest rating the Elo picker goes down to
+pub const MIN_ELO: u32 = 600;
+
+/// The highest rating the Elo picker goes up to
+pub const MAX_ELO: u32 = 3200;
+
+/// The arrows of the Elo picker change the rating by this much
+pub const ELO_STEP: u32 = 100;
+
+/// How strong the bot plays, chosen before a game against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    #[default]
+    Full,
+    /// The engine plays at this rating with `UCI_LimitStrength`, thinking for its usual time
+    Elo(u32),
+}
+
+impl Difficulty {
+    /// The difficulties in the order of the selection popup, the rating of the Elo one is picked in it
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Full,
+        Difficulty::Elo(DEFAULT_ELO),
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            Difficulty::Beginner => "Beginner".to_string(),
+            Difficulty::Intermediate => "Intermediate".to_string(),
+            Difficulty::Full => "Full strength".to_string(),
+            Difficulty::Elo(elo) => format!("Elo {elo}"),
+        }
+    }
+
+    /// Is it the same choice of the selection popup, whatever the rating of an Elo difficulty
+    pub fn is_same_choice(self, other: Difficulty) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
+    /// How far the engine looks ahead, at full strength it thinks for its usual time
+    pub fn search_constraints(self) -> SearchConstraints {
+        let depth = match self {
+            Difficulty::Beginner => Some(2),
+            Difficulty::Intermediate => Some(8),
+            Difficulty::Full | Difficulty::Elo(_) => None,
+        };
+        SearchConstraints {
+            depth,
+            movetime: None,
+        }
+    }
+
+    /// The rating engines supporting `UCI_LimitStrength` play at, `None` at full strength
+    pub fn elo(self) -> Option<u32> {
+        match self {
+            Difficulty::Beginner => Some(1400),
+            Difficulty::Intermediate => Some(1900),
+            Difficulty::Full => None,
+            Difficulty::Elo(elo) => Some(elo),
+        }
+    }
+}
+
+/// A search running away from the main loop, its progress and its move come back as events
+pub struct SearchHandle {
+    /// The input of the engine, to interrupt the search
+    input: Arc<Mutex<dyn Write + Send>>,
+    /// The command making the engine play right away
+    stop_command: &'static str,
+}
+
+impl SearchHandle {
+    pub fn new(input: Arc<Mutex<dyn Write + Send>>) -> Self {
+        SearchHandle::with_stop_command(input, "stop")
+    }
+
+    /// A search of an engine speaking another protocol than UCI, stopped with `stop_command`
+    pub fn with_stop_command(
+        input: Arc<Mutex<dyn Write + Send>>,
+        stop_command: &'static str,
+    ) -> Self {
+        SearchHandle {
+            input,
+            stop_command,
+        }
+    }
+
+    /// Ask the engine to play the best move it found so far right away
+    pub fn stop(&self) -> Result<(), ChessError> {
+        self.send(self.stop_command)
+    }
+
+    /// Tell an engine pondering that the opponent played the expected move,
+    /// the search goes on as a normal one and ends with its move
+    pub fn ponderhit(&self) -> Result<(), ChessError> {
+        self.send("ponderhit")
+    }
+
+    fn send(&self, command: &str) -> Result<(), ChessError> {
+        let mut input = self.input.lock().unwrap_or_else(PoisonError::into_inner);
+        writeln!(input, "{command}")
+            .and_then(|()| input.flush())
+            .map_err(|e| ChessError::EngineFailure(e.to_string()))
+    }
+}
+
+/// The engine thinking on the time of the opponent, betting on the move it expects from them
+pub struct Ponder {
+    /// The position the engine thinks about, reached once the opponent played the expected move
+    pub fen: String,
+    pub search: SearchHandle,
+}
+
+/// A backend choosing the moves of the computer: a UCI engine, the built-in bot...
+/// Engines are `Send` so the search can run away from the main loop
+pub trait Engine: Send {
+    /// The move of the player to move in the position described by `fen`
+    /// The move is given in the orientation of the board, where the player to move is at the bottom
+    fn best_move(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+    ) -> Result<PieceMove, ChessError>;
+
+    /// Get ready for a game unrelated to the previous one
+    fn new_game(&mut self) -> Result<(), ChessError> {
+        Ok(())
+    }
+
+    /// Play at the strength of `difficulty` for the rest of the game
+    fn set_difficulty(&mut self, _difficulty: Difficulty) -> Result<(), ChessError> {
+        Ok(())
+    }
+
+    /// The name of the engine, written in the PGN headers of its games
+    fn name(&self) -> String {
+        "Computer".to_string()
+    }
+
+    /// The evaluation and depth of the search behind the last move, for engines reporting them
+    fn last_report(&self) -> Option<SearchReport> {
+        None
+    }
+
+    /// The lines exchanged with the engine, for engines running in their own process
+    fn console(&self) -> Option<EngineConsole> {
+        None
+    }
+
+    /// Start looking for the move of the player to move without waiting for it,
+    /// the move is posted to `sender` as an [`Event::EngineBestMove`]
+    /// Engines answering right away return `None` and play through `best_move`
+    fn start_search(
+        &mut self,
+        _fen: &str,
+        _constraints: SearchConstraints,
+        _sender: Sender<Event>,
+    ) -> Option<Result<SearchHandle, ChessError>> {
+        None
+    }
+
+    /// Think on the time of the opponent about the position after their `ponder_move`
+    /// in the position described by `fen`, until [`SearchHandle::ponderhit`] or `stop`
+    /// After `ponderhit` the move is posted to `sender` like the one of `start_search`
+    /// Engines unable to ponder return `None`
+    fn start_ponder(
+        &mut self,
+        _fen: &str,
+        _ponder_move: &str,
+        _constraints: SearchConstraints,
+        _sender: Sender<Event>,
+    ) -> Option<Result<SearchHandle, ChessError>> {
+        None
+    }
+}
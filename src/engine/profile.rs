@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use toml::Value;
+
+use super::{cecp::CecpEngine, uci::UciEngine, Engine, Protocol};
+use crate::error::ChessError;
+
+/// An engine declared in an `[[engines]]` table of the config, chosen before a bot game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineProfile {
+    /// Shown in the engine selection, the name of the file of the engine when left out
+    pub name: String,
+    pub path: String,
+    /// UCI by default, `protocol = "xboard"` for engines speaking CECP
+    pub protocol: Protocol,
+    /// Options set once the engine is started, like `Threads` or `Hash`
+    pub options: Vec<(String, String)>,
+}
+
+impl EngineProfile {
+    /// Read an `[[engines]]` table, one without a path or with an unknown protocol is not an engine
+    pub fn from_config(table: &Value) -> Option<EngineProfile> {
+        let path = table.get("path").and_then(Value::as_str)?;
+        if path.is_empty() {
+            return None;
+        }
+        let protocol = match table.get("protocol").and_then(Value::as_str) {
+            Some(name) => Protocol::from_name(name)?,
+            None => Protocol::default(),
+        };
+        let name = table
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.is_empty())
+            .map_or_else(
+                || {
+                    Path::new(path)
+                        .file_name()
+                        .map_or(path.to_string(), |name| name.to_string_lossy().into_owned())
+                },
+                str::to_string,
+            );
+        let options = table
+            .get("options")
+            .and_then(Value::as_table)
+            .map(|options| {
+                options
+                    .iter()
+                    .map(|(name, value)| {
+                        // Strings are sent without their quotes
+                        let value = match value {
+                            Value::String(value) => value.clone(),
+                            value => value.to_string(),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(EngineProfile {
+            name,
+            path: path.to_string(),
+            protocol,
+            options,
+        })
+    }
+
+    /// Start the engine with the options of the profile
+    pub fn start(&self) -> Result<Box<dyn Engine>, ChessError> {
+        match self.protocol {
+            Protocol::Uci => {
+                let mut engine = UciEngine::new(&self.path)?;
+                for (name, value) in &self.options {
+                    engine.set_option(name, value)?;
+                }
+                Ok(Box::new(engine))
+            }
+            Protocol::Xboard => {
+                let mut engine = CecpEngine::new(&self.path)?;
+                for (name, value) in &self.options {
+                    engine.set_option(name, value)?;
+                }
+                Ok(Box::new(engine))
+            }
+        }
+    }
+}
+
+/// The profiles of the `engines` array of the config, in their order
+/// Entries that are not engines are skipped and logged
+pub fn parse_engine_profiles(engines: &[Value]) -> Vec<EngineProfile> {
+    engines
+        .iter()
+        .filter_map(|table| {
+            let profile = EngineProfile::from_config(table);
+            if profile.is_none() {
+                log::warn!("Skipped an entry of `engines` without a path or with an unknown protocol: {table}");
+            }
+            profile
+        })
+        .collect()
+}
@@ -0,0 +1,40 @@
+use super::{Engine, SearchConstraints};
+use crate::{error::ChessError, game_logic::game::Game, pieces::PieceMove, rng::Rng};
+
+/// A bot playing any of its legal moves at random
+#[derive(Debug, Clone)]
+pub struct RandomBot {
+    rng: Rng,
+}
+
+impl RandomBot {
+    pub fn new(rng: Rng) -> Self {
+        RandomBot { rng }
+    }
+
+    /// Pick a move for the player to move, `None` when there is no legal move
+    pub fn choose_move(&mut self, game: &Game) -> Option<PieceMove> {
+        let legal_moves = game.game_board.legal_moves(game.player_turn);
+        if legal_moves.is_empty() {
+            return None;
+        }
+        Some(legal_moves[self.rng.below(legal_moves.len())])
+    }
+}
+
+impl Engine for RandomBot {
+    // The bot answers right away whatever the constraints
+    fn best_move(
+        &mut self,
+        fen: &str,
+        _constraints: SearchConstraints,
+    ) -> Result<PieceMove, ChessError> {
+        let game = Game::from_fen(fen)?;
+        self.choose_move(&game)
+            .ok_or_else(|| ChessError::EngineFailure("no legal move to play".to_string()))
+    }
+
+    fn name(&self) -> String {
+        "Random bot".to_string()
+    }
+}
@@ -0,0 +1,504 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{mpsc::Sender, Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use super::{
+    analysis::stream_analysis_output,
+    console::{EngineConsole, Recorded, RECEIVED_PREFIX, SENT_PREFIX},
+    Difficulty, Engine, SearchConstraints, SearchHandle,
+};
+use crate::{error::ChessError, event::Event, game_logic::game::Game, pieces::PieceMove};
+
+/// Thinking time of the engine when the search is not constrained
+pub const DEFAULT_MOVETIME: Duration = Duration::from_secs(1);
+
+/// Evaluation of the position reported by the engine, from its own point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    /// Advantage in hundredths of a pawn
+    Centipawns(i32),
+    /// Number of moves before mate, negative when the engine gets mated
+    Mate(i32),
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Centipawns(centipawns) => write!(f, "{:+.2}", *centipawns as f32 / 100.0),
+            Score::Mate(moves) => write!(f, "#{moves}"),
+        }
+    }
+}
+
+/// The best line found so far by an engine still thinking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineInfo {
+    /// First move of the principal variation, in UCI notation
+    pub best_move: String,
+    pub score: Option<Score>,
+    pub depth: Option<u32>,
+}
+
+impl fmt::Display for EngineInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.best_move)?;
+        if let Some(score) = self.score {
+            write!(f, " ({score})")?;
+        }
+        if let Some(depth) = self.depth {
+            write!(f, " depth {depth}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The evaluation the engine settled on for the move it played, shown in the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchReport {
+    pub score: Score,
+    pub depth: Option<u32>,
+}
+
+impl SearchReport {
+    /// The report of the best line found so far, if the engine scored it
+    pub fn from_info(info: &EngineInfo) -> Option<Self> {
+        Some(SearchReport {
+            score: info.score?,
+            depth: info.depth,
+        })
+    }
+}
+
+impl fmt::Display for SearchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "eval {}", self.score)?;
+        if let Some(depth) = self.depth {
+            write!(f, " d{depth}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The `position` command sending a game to the engine
+/// Games without a starting FEN started from the standard position
+pub fn position_command(starting_fen: Option<&str>, uci_moves: &[String]) -> String {
+    let mut command = match starting_fen {
+        Some(fen) => format!("position fen {fen}"),
+        None => "position startpos".to_string(),
+    };
+    if !uci_moves.is_empty() {
+        command.push_str(" moves ");
+        command.push_str(&uci_moves.join(" "));
+    }
+    command
+}
+
+/// The `go` command starting the search within the constraints
+pub fn go_command(constraints: SearchConstraints) -> String {
+    let mut command = "go".to_string();
+    if let Some(depth) = constraints.depth {
+        command.push_str(&format!(" depth {depth}"));
+    }
+    let movetime = match constraints {
+        SearchConstraints {
+            depth: Some(_),
+            movetime: None,
+        } => None,
+        SearchConstraints { movetime, .. } => Some(movetime.unwrap_or(DEFAULT_MOVETIME)),
+    };
+    if let Some(movetime) = movetime {
+        command.push_str(&format!(" movetime {}", movetime.as_millis()));
+    }
+    command
+}
+
+/// The `go ponder` command thinking about the expected position within the constraints,
+/// they apply from the `ponderhit` on
+pub fn ponder_command(constraints: SearchConstraints) -> String {
+    go_command(constraints).replacen("go", "go ponder", 1)
+}
+
+/// Parse an `info` line of the engine, lines without a principal variation are skipped
+pub fn parse_info(line: &str) -> Option<EngineInfo> {
+    let (best_move, score, depth) = info_fields(line)?;
+    Some(EngineInfo {
+        best_move: best_move?,
+        score,
+        depth,
+    })
+}
+
+/// The score and depth of the last `info` line scoring the position before `bestmove`
+pub fn parse_search_report<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<SearchReport> {
+    lines
+        .into_iter()
+        .take_while(|line| !line.starts_with("bestmove"))
+        .filter_map(info_fields)
+        .filter_map(|(_, score, depth)| {
+            Some(SearchReport {
+                score: score?,
+                depth,
+            })
+        })
+        .last()
+}
+
+// The first move of the variation, the score and the depth of an `info` line
+fn info_fields(line: &str) -> Option<(Option<String>, Option<Score>, Option<u32>)> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("info") {
+        return None;
+    }
+
+    let mut best_move = None;
+    let mut score = None;
+    let mut depth = None;
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => depth = tokens.next().and_then(|value| value.parse().ok()),
+            "score" => {
+                score = match (tokens.next(), tokens.next().and_then(|v| v.parse().ok())) {
+                    (Some("cp"), Some(value)) => Some(Score::Centipawns(value)),
+                    (Some("mate"), Some(value)) => Some(Score::Mate(value)),
+                    _ => None,
+                }
+            }
+            "pv" => {
+                best_move = tokens.next().map(str::to_string);
+                // The rest of the line is the variation
+                break;
+            }
+            // Free text up to the end of the line
+            "string" => break,
+            _ => {}
+        }
+    }
+    Some((best_move, score, depth))
+}
+
+/// Parse the `bestmove` line ending the search
+pub fn parse_bestmove(line: &str) -> Option<String> {
+    let mut tokens = line.split_whitespace();
+    match (tokens.next(), tokens.next()) {
+        (Some("bestmove"), Some(best_move)) if best_move != "(none)" => Some(best_move.to_string()),
+        _ => None,
+    }
+}
+
+/// The lowest and highest values of the spin option `name` described by an `option` line,
+/// like `option name UCI_Elo type spin default 1320 min 1320 max 3190`
+pub fn parse_spin_range(line: &str, name: &str) -> Option<(u32, u32)> {
+    let description = line.strip_prefix("option name ")?.strip_prefix(name)?;
+    let mut tokens = description.split_whitespace();
+    if tokens.next() != Some("type") || tokens.next() != Some("spin") {
+        return None;
+    }
+    let (mut min, mut max) = (None, None);
+    while let Some(token) = tokens.next() {
+        match token {
+            "min" => min = tokens.next().and_then(|value| value.parse().ok()),
+            "max" => max = tokens.next().and_then(|value| value.parse().ok()),
+            _ => {}
+        }
+    }
+    Some((min?, max?))
+}
+
+/// The answer the engine expects to its move, given after `ponder` on the `bestmove` line
+pub fn parse_ponder(line: &str) -> Option<String> {
+    let mut tokens = line.split_whitespace();
+    match (tokens.next(), tokens.nth(1), tokens.next()) {
+        (Some("bestmove"), Some("ponder"), Some(ponder_move)) => Some(ponder_move.to_string()),
+        _ => None,
+    }
+}
+
+/// The latest best line of a sequence of engine output lines
+pub fn best_line_so_far<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<EngineInfo> {
+    lines.into_iter().filter_map(parse_info).last()
+}
+
+/// Forward the progress of the engine to the main loop until it plays its move
+/// Meant to run on a background thread reading the stdout of the engine
+pub fn stream_engine_output(reader: impl BufRead, sender: &Sender<Event>) {
+    for line in reader.lines().map_while(Result::ok) {
+        let event = if let Some(best_move) = parse_bestmove(&line) {
+            if let Some(ponder_move) = parse_ponder(&line) {
+                if sender.send(Event::EnginePonder(ponder_move)).is_err() {
+                    return;
+                }
+            }
+            Event::EngineBestMove(best_move)
+        } else if let Some(info) = parse_info(&line) {
+            Event::EngineInfo(info)
+        } else {
+            continue;
+        };
+        let is_done = matches!(event, Event::EngineBestMove(_));
+        if sender.send(event).is_err() || is_done {
+            return;
+        }
+    }
+    // The output ends without a move when the engine process died
+    let _ = sender.send(Event::EngineFailure(ChessError::EngineFailure(
+        "the engine stopped before sending bestmove".to_string(),
+    )));
+}
+
+/// A chess engine running in its own process, driven with the UCI protocol
+pub struct UciEngine {
+    process: Child,
+    /// Shared with the running search to stop it
+    stdin: Arc<Mutex<Recorded<ChildStdin>>>,
+    /// Read from a background thread while a search started with `start_search` runs
+    stdout: Arc<Mutex<BufReader<Recorded<ChildStdout>>>>,
+    /// Everything going through `stdin` and `stdout`
+    console: EngineConsole,
+    /// The name the engine gave with `id name`, or the name of its file
+    name: String,
+    /// The evaluation of the last search
+    last_report: Option<SearchReport>,
+    /// The `Ponder` option is set before the first search on the time of the opponent
+    ponder_enabled: bool,
+    /// The lowest and highest `UCI_Elo` the engine accepts, when it has the option
+    elo_range: Option<(u32, u32)>,
+}
+
+impl UciEngine {
+    /// Start the engine at `path` and wait until it is ready
+    pub fn new(path: &str) -> Result<Self, ChessError> {
+        let mut process = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ChessError::EngineFailure(format!("{path}: {e}")))?;
+        let (Some(stdin), Some(stdout)) = (process.stdin.take(), process.stdout.take()) else {
+            let _ = process.kill();
+            return Err(ChessError::EngineFailure(format!(
+                "{path}: could not talk to the engine"
+            )));
+        };
+        let console = EngineConsole::default();
+        let mut engine = UciEngine {
+            process,
+            stdin: Arc::new(Mutex::new(Recorded::new(
+                stdin,
+                console.clone(),
+                SENT_PREFIX,
+            ))),
+            stdout: Arc::new(Mutex::new(BufReader::new(Recorded::new(
+                stdout,
+                console.clone(),
+                RECEIVED_PREFIX,
+            )))),
+            console,
+            name: Path::new(path)
+                .file_name()
+                .map_or(path.to_string(), |name| name.to_string_lossy().into_owned()),
+            last_report: None,
+            ponder_enabled: false,
+            elo_range: None,
+        };
+        engine.send("uci")?;
+        // The engine introduces itself before `uciok`
+        loop {
+            let line = engine.read_line("uciok")?;
+            if line == "uciok" {
+                break;
+            }
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = name.trim().to_string();
+            }
+            if let Some(range) = parse_spin_range(&line, "UCI_Elo") {
+                engine.elo_range = Some(range);
+            }
+        }
+        engine.send("isready")?;
+        engine.read_until("readyok")?;
+        Ok(engine)
+    }
+
+    /// Think about the position described by `fen` until stopped, its `line_count` best lines
+    /// are posted to `sender` as [`Event::AnalysisLine`] tagged with `search`
+    pub fn start_analysis(
+        &mut self,
+        fen: &str,
+        line_count: usize,
+        search: u64,
+        sender: Sender<Event>,
+    ) -> Result<SearchHandle, ChessError> {
+        self.send(&format!("setoption name MultiPV value {line_count}"))?;
+        self.send(&position_command(Some(fen), &[]))?;
+        self.send("go infinite")?;
+        // The output is read until `bestmove`, the next search waits for it
+        let stdout = Arc::clone(&self.stdout);
+        thread::spawn(move || stream_analysis_output(&mut *lock(&stdout), search, &sender));
+        Ok(SearchHandle::new(self.stdin.clone()))
+    }
+
+    /// Set the UCI option `name` of the engine, before a search
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), ChessError> {
+        self.send(&format!("setoption name {name} value {value}"))
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), ChessError> {
+        let mut stdin = lock(&self.stdin);
+        writeln!(stdin, "{command}")
+            .and_then(|()| stdin.flush())
+            .map_err(engine_io_failure)
+    }
+
+    // Skip the output of the engine up to the first line starting with `prefix`
+    fn read_until(&mut self, prefix: &str) -> Result<String, ChessError> {
+        loop {
+            let line = self.read_line(prefix)?;
+            if line.starts_with(prefix) {
+                return Ok(line);
+            }
+        }
+    }
+
+    // The next line of the engine output, trimmed, while waiting for `expected`
+    fn read_line(&mut self, expected: &str) -> Result<String, ChessError> {
+        let mut line = String::new();
+        if lock(&self.stdout)
+            .read_line(&mut line)
+            .map_err(engine_io_failure)?
+            == 0
+        {
+            return Err(ChessError::EngineFailure(format!(
+                "the engine stopped before sending {expected}"
+            )));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+// A thread stopped in the middle of a search leaves the engine usable
+pub(super) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(super) fn engine_io_failure(error: io::Error) -> ChessError {
+    ChessError::EngineFailure(error.to_string())
+}
+
+impl Engine for UciEngine {
+    fn best_move(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+    ) -> Result<PieceMove, ChessError> {
+        let game = Game::from_fen(fen)?;
+        self.send(&position_command(Some(fen), &[]))?;
+        self.send(&go_command(constraints))?;
+        let mut lines = vec![];
+        loop {
+            let line = self.read_line("bestmove")?;
+            let is_done = line.starts_with("bestmove");
+            lines.push(line);
+            if is_done {
+                break;
+            }
+        }
+        self.last_report = parse_search_report(lines.iter().map(String::as_str));
+        let best_move = lines
+            .last()
+            .and_then(|line| parse_bestmove(line))
+            .ok_or_else(|| ChessError::EngineFailure("the engine found no move".to_string()))?;
+        game.piece_move_from_uci(&best_move)
+    }
+
+    /// `ucinewgame` clears what the engine learned during the previous game
+    fn new_game(&mut self) -> Result<(), ChessError> {
+        self.send("ucinewgame")?;
+        self.send("isready")?;
+        self.read_until("readyok").map(|_| ())
+    }
+
+    /// Engines without the `UCI_LimitStrength` option ignore it and only the depth weakens them
+    /// The rating is brought within the range of `UCI_Elo` given by the engine
+    fn set_difficulty(&mut self, difficulty: Difficulty) -> Result<(), ChessError> {
+        let Some(mut elo) = difficulty.elo() else {
+            return Ok(());
+        };
+        if let Some((min, max)) = self.elo_range {
+            if !(min..=max).contains(&elo) {
+                log::info!("{} plays between {min} and {max} Elo, not {elo}", self.name);
+                elo = elo.clamp(min, max);
+            }
+        }
+        self.send("setoption name UCI_LimitStrength value true")?;
+        self.send(&format!("setoption name UCI_Elo value {elo}"))?;
+        self.send("isready")?;
+        self.read_until("readyok").map(|_| ())
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn last_report(&self) -> Option<SearchReport> {
+        self.last_report
+    }
+
+    fn console(&self) -> Option<EngineConsole> {
+        Some(self.console.clone())
+    }
+
+    fn start_search(
+        &mut self,
+        fen: &str,
+        constraints: SearchConstraints,
+        sender: Sender<Event>,
+    ) -> Option<Result<SearchHandle, ChessError>> {
+        let search = self
+            .send(&position_command(Some(fen), &[]))
+            .and_then(|()| self.send(&go_command(constraints)))
+            .map(|()| {
+                // The output is read until `bestmove`, the next search waits for it
+                let stdout = Arc::clone(&self.stdout);
+                thread::spawn(move || stream_engine_output(&mut *lock(&stdout), &sender));
+                SearchHandle::new(self.stdin.clone())
+            });
+        Some(search)
+    }
+
+    fn start_ponder(
+        &mut self,
+        fen: &str,
+        ponder_move: &str,
+        constraints: SearchConstraints,
+        sender: Sender<Event>,
+    ) -> Option<Result<SearchHandle, ChessError>> {
+        if !self.ponder_enabled {
+            if let Err(e) = self.send("setoption name Ponder value true") {
+                return Some(Err(e));
+            }
+            self.ponder_enabled = true;
+        }
+        let search = self
+            .send(&position_command(Some(fen), &[ponder_move.to_string()]))
+            .and_then(|()| self.send(&ponder_command(constraints)))
+            .map(|()| {
+                // Read like a normal search, the engine gives its move after `ponderhit` or `stop`
+                let stdout = Arc::clone(&self.stdout);
+                thread::spawn(move || stream_engine_output(&mut *lock(&stdout), &sender));
+                SearchHandle::new(self.stdin.clone())
+            });
+        Some(search)
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
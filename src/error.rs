@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Errors raised by the game logic and its integrations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChessError {
+    /// The FEN string could not be parsed or describes an impossible position
+    InvalidFen(String),
+    /// The system clipboard could not be accessed
+    ClipboardUnavailable(String),
+    /// The game code of an online game could not be decoded
+    InvalidGameCode(String),
+    /// The time control is not written `minutes+increment`
+    InvalidTimeControl(String),
+    /// The move can't be played in the current position
+    IllegalMove(String),
+    /// The cell name is not a file followed by a rank (`e4`)
+    InvalidSquare(String),
+    /// The saved game could not be read
+    InvalidSave(String),
+    /// The game is not saved over a file that already exists unless asked to
+    SaveFileExists(String),
+    /// The saved game could not be written
+    SaveFailed(String),
+    /// The saved game was written by a newer version of chess-tui
+    UnsupportedSaveVersion(u32),
+    /// The puzzle file could not be read
+    InvalidPuzzle(String),
+    /// The game in Portable Game Notation could not be read
+    InvalidPgn(String),
+    /// The handicap given against the bot can't be applied
+    InvalidHandicap(String),
+    /// The engine could not be started or did not give a move
+    EngineFailure(String),
+    /// The host of the online game could not be reached
+    ConnectionFailed(String),
+}
+
+impl fmt::Display for ChessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChessError::InvalidFen(reason) => write!(f, "Invalid FEN: {reason}"),
+            ChessError::ClipboardUnavailable(reason) => {
+                write!(f, "Clipboard unavailable: {reason}")
+            }
+            ChessError::InvalidGameCode(reason) => write!(f, "Invalid game code: {reason}"),
+            ChessError::IllegalMove(piece_move) => write!(f, "Illegal move: {piece_move}"),
+            ChessError::InvalidSquare(square) => write!(f, "Invalid square: {square}"),
+            ChessError::InvalidSave(reason) => write!(f, "Invalid saved game: {reason}"),
+            ChessError::SaveFileExists(path) => write!(f, "{path} already exists"),
+            ChessError::SaveFailed(reason) => write!(f, "Could not save the game: {reason}"),
+            ChessError::UnsupportedSaveVersion(version) => write!(
+                f,
+                "Saved game version {version} is not supported, please update chess-tui"
+            ),
+            ChessError::InvalidPuzzle(reason) => write!(f, "Invalid puzzle: {reason}"),
+            ChessError::InvalidPgn(reason) => write!(f, "Invalid PGN: {reason}"),
+            ChessError::InvalidHandicap(reason) => write!(f, "Invalid handicap: {reason}"),
+            ChessError::EngineFailure(reason) => write!(f, "Engine failure: {reason}"),
+            ChessError::ConnectionFailed(reason) => write!(f, "Connection failed: {reason}"),
+            ChessError::InvalidTimeControl(time_control) => {
+                write!(
+                    f,
+                    "Invalid time control: {time_control}, expected minutes+increment"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChessError {}
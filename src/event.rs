@@ -1,11 +1,17 @@
 use crate::app::AppResult;
+use crate::engine::{
+    analysis::AnalysisLine,
+    uci::{EngineInfo, Score},
+};
+use crate::error::ChessError;
+use crate::pieces::PieceMove;
 use ratatui::crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-/// Terminal events.
-#[derive(Clone, Copy, Debug)]
+/// Terminal events, and results of the work done in background threads.
+#[derive(Clone, Debug)]
 pub enum Event {
     /// Terminal tick.
     Tick,
@@ -15,6 +21,28 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
+    /// Text pasted in the terminal.
+    Paste(String),
+    /// Move computed by the engine, in the orientation of the player to move.
+    EngineMove(PieceMove),
+    /// Best line found so far by the engine.
+    EngineInfo(EngineInfo),
+    /// Move played by the engine at the end of its search, in UCI notation.
+    EngineBestMove(String),
+    /// The engine stopped in the middle of its search.
+    EngineFailure(ChessError),
+    /// Answer the engine expects to its move, in UCI notation, sent just before the move.
+    EnginePonder(String),
+    /// Move suggested by the engine for the position of the FEN, in the orientation of the player to move.
+    EngineHint(String, Result<PieceMove, ChessError>),
+    /// Line found by the engine analysing a position, with the number of its search.
+    AnalysisLine(u64, AnalysisLine),
+    /// Evaluation of the position of the FEN by the analysis of a finished game, from the point of view of the player to move.
+    PositionEvaluation(String, Result<Score, ChessError>),
+    /// Move received from the opponent, in the orientation of the player to move.
+    NetworkMove(PieceMove),
+    /// Chat message received from the opponent.
+    NetworkChat(String),
 }
 
 /// Terminal event handler.
@@ -48,6 +76,7 @@ impl EventHandler {
                             CrosstermEvent::Key(e) => sender.send(Event::Key(e)),
                             CrosstermEvent::Mouse(e) => sender.send(Event::Mouse(e)),
                             CrosstermEvent::Resize(w, h) => sender.send(Event::Resize(w, h)),
+                            CrosstermEvent::Paste(text) => sender.send(Event::Paste(text)),
                             _ => unimplemented!(),
                         }
                         .expect("failed to send terminal event");
@@ -67,6 +96,11 @@ impl EventHandler {
         }
     }
 
+    /// A sender for background threads to post their results into the event loop.
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
+
     /// Receive the next event from the handler thread.
     ///
     /// This function will always block the current thread if
@@ -0,0 +1,44 @@
+use crate::{
+    pieces::{PieceColor, PieceType},
+    rng::Rng,
+};
+
+/// The back rank of a random Chess960 starting position, from the a-file to the h-file
+/// The bishops stand on cells of different colors and the king between the rooks
+pub fn chess960_back_rank(rng: &mut Rng) -> [PieceType; 8] {
+    let mut back_rank: [Option<PieceType>; 8] = [None; 8];
+
+    // a1 is a dark cell, the dark cells have an even index
+    back_rank[2 * rng.below(4)] = Some(PieceType::Bishop);
+    back_rank[2 * rng.below(4) + 1] = Some(PieceType::Bishop);
+
+    for piece_type in [PieceType::Queen, PieceType::Knight, PieceType::Knight] {
+        let empty_cells: Vec<usize> = (0..8).filter(|&i| back_rank[i].is_none()).collect();
+        back_rank[empty_cells[rng.below(empty_cells.len())]] = Some(piece_type);
+    }
+
+    // The three cells left get a rook, the king and the other rook
+    let mut remaining_pieces = [PieceType::Rook, PieceType::King, PieceType::Rook].into_iter();
+    back_rank.map(|piece_type| {
+        piece_type
+            .or_else(|| remaining_pieces.next())
+            .expect("three cells are left for the rooks and the king")
+    })
+}
+
+/// The FEN of a random Chess960 starting position
+/// Both players can castle with either rook, written `KQkq` as in X-FEN
+pub fn chess960_fen(rng: &mut Rng) -> String {
+    let back_rank = chess960_back_rank(rng);
+    let rank = |piece_color: PieceColor| -> String {
+        back_rank
+            .iter()
+            .map(|&piece_type| PieceType::piece_to_fen_enum(Some(piece_type), Some(piece_color)))
+            .collect()
+    };
+    format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1",
+        rank(PieceColor::Black),
+        rank(PieceColor::White)
+    )
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use super::game_setup::TimeControl;
+use crate::pieces::PieceColor;
+
+/// The clocks of both players
+/// The clock only starts running once the first move is played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    /// Remaining time of white and black when the active clock last started
+    remaining: [Duration; 2],
+    /// Time added to a player once they played
+    increment: Duration,
+    /// The player whose time is running
+    active: PieceColor,
+    /// When the active clock started, `None` while the clocks are stopped
+    running_since: Option<Instant>,
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl) -> Self {
+        Clock {
+            remaining: [time_control.initial; 2],
+            increment: time_control.increment,
+            active: PieceColor::White,
+            running_since: None,
+        }
+    }
+
+    /// The clocks of a game resumed with this time left, stopped until the next move
+    pub fn resumed(remaining: [Duration; 2], increment: Duration) -> Self {
+        Clock {
+            remaining,
+            increment,
+            active: PieceColor::White,
+            running_since: None,
+        }
+    }
+
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// Take time off the clock of a player before the game starts, a second is always left
+    pub fn take_time_odds(&mut self, color: PieceColor, odds: Duration) {
+        let remaining = &mut self.remaining[color as usize];
+        *remaining = remaining.saturating_sub(odds).max(Duration::from_secs(1));
+    }
+
+    /// Remaining time of a player at a given instant
+    pub fn remaining(&self, color: PieceColor, now: Instant) -> Duration {
+        let remaining = self.remaining[color as usize];
+        match self.running_since {
+            Some(running_since) if color == self.active => {
+                remaining.saturating_sub(now.saturating_duration_since(running_since))
+            }
+            _ => remaining,
+        }
+    }
+
+    /// Give the move to a player: the previous player gets the increment and the new one starts thinking
+    /// Nothing happens if it is already the turn of that player and the clock runs
+    pub fn switch_to(&mut self, color: PieceColor, now: Instant) {
+        if color == self.active && self.running_since.is_some() {
+            return;
+        }
+        if self.running_since.is_some() {
+            let previous = self.active as usize;
+            self.remaining[previous] = self.remaining(self.active, now) + self.increment;
+        }
+        self.active = color;
+        self.running_since = Some(now);
+    }
+
+    /// Freeze both clocks, at the end of the game
+    pub fn stop(&mut self, now: Instant) {
+        self.remaining[self.active as usize] = self.remaining(self.active, now);
+        self.running_since = None;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    /// The player who ran out of time
+    pub fn flagged(&self, now: Instant) -> Option<PieceColor> {
+        (self.is_running() && self.remaining(self.active, now).is_zero()).then_some(self.active)
+    }
+}
+
+/// Format the time of a clock, tenths of a second are shown under the threshold
+/// e.g `1:23` above the threshold and `0:05.3` under it
+pub fn format_clock(remaining: Duration, tenths_threshold: Duration) -> String {
+    let seconds = remaining.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if remaining < tenths_threshold {
+        let tenths = remaining.subsec_millis() / 100;
+        format!("{}:{seconds:02}.{tenths}", hours * 60 + minutes)
+    } else if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
@@ -1,6 +1,6 @@
-use crate::constants::UNDEFINED_POSITION;
+use crate::{constants::UNDEFINED_POSITION, utils::col_to_letter};
 
-#[derive(PartialEq, Clone, Debug, Eq, PartialOrd, Ord, Copy)]
+#[derive(PartialEq, Clone, Debug, Eq, PartialOrd, Ord, Copy, Hash)]
 pub struct Coord {
     /// rank, horizontal row, line, y axis
     pub row: u8,
@@ -37,4 +37,17 @@ impl Coord {
     pub fn is_valid(&self) -> bool {
         (0..8).contains(&self.col) && (0..8).contains(&self.row)
     }
+    /// name of the cell (`e4`) when the board is seen from white
+    pub fn to_algebraic(&self) -> String {
+        format!("{}{}", col_to_letter(self.col), 8 - self.row)
+    }
+    /// cell from its name (`e4`) when the board is seen from white
+    pub fn from_algebraic(cell: &str) -> Option<Self> {
+        let mut chars = cell.chars();
+        let (file, rank) = (chars.next()?, chars.next()?);
+        if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return None;
+        }
+        Some(Coord::new(b'8' - rank as u8, file as u8 - b'a'))
+    }
 }
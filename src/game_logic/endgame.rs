@@ -0,0 +1,68 @@
+use std::fmt;
+
+use super::{board::Board, coord::Coord};
+use crate::pieces::{PieceColor, PieceType};
+
+/// The theoretical result of an ending, known without searching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndgameVerdict {
+    /// This color mates with correct play
+    Winning(PieceColor),
+    Draw,
+}
+
+impl fmt::Display for EndgameVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EndgameVerdict::Winning(PieceColor::White) => write!(f, "White is winning"),
+            EndgameVerdict::Winning(PieceColor::Black) => write!(f, "Black is winning"),
+            EndgameVerdict::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+/// The result of the trivial endings: a queen or a rook against the bare king, or the two kings alone
+/// Any other material gives `None`, as does a queen or a rook the bare king can take right away
+pub fn trivial_endgame_verdict(board: &Board, player_turn: PieceColor) -> Option<EndgameVerdict> {
+    let mut kings = vec![];
+    let mut pieces = vec![];
+    for (row, line) in board.iter().enumerate() {
+        for (col, cell) in line.iter().enumerate() {
+            match cell {
+                Some((PieceType::King, color)) => {
+                    kings.push((Coord::new(row as u8, col as u8), *color))
+                }
+                Some((piece_type, color)) => {
+                    pieces.push((Coord::new(row as u8, col as u8), *piece_type, *color))
+                }
+                None => {}
+            }
+        }
+    }
+    if kings.len() != 2 {
+        return None;
+    }
+
+    match pieces[..] {
+        [] => Some(EndgameVerdict::Draw),
+        [(cell, PieceType::Queen | PieceType::Rook, color)] => {
+            let king_of = |king_color: PieceColor| {
+                kings
+                    .iter()
+                    .find(|(_, color)| *color == king_color)
+                    .map(|(cell, _)| *cell)
+            };
+            let (attacker_king, bare_king) = (king_of(color)?, king_of(color.opposite())?);
+            // The bare king to move takes an undefended piece standing next to it
+            let can_be_taken = player_turn != color
+                && is_adjacent(cell, bare_king)
+                && !is_adjacent(cell, attacker_king);
+            (!can_be_taken).then_some(EndgameVerdict::Winning(color))
+        }
+        _ => None,
+    }
+}
+
+fn is_adjacent(a: Coord, b: Coord) -> bool {
+    a != b && a.row.abs_diff(b.row) <= 1 && a.col.abs_diff(b.col) <= 1
+}
@@ -1,5 +1,25 @@
-use super::{coord::Coord, game_board::GameBoard, ui::UI};
-use crate::pieces::{PieceColor, PieceMove, PieceType};
+use std::{collections::HashSet, fmt};
+
+use super::{
+    board::Board,
+    clock::Clock,
+    coord::Coord,
+    endgame::{trivial_endgame_verdict, EndgameVerdict},
+    game_board::{castling_king_target, complete_fen, GameBoard},
+    ui::UI,
+};
+use crate::{
+    constants::{FlipPolicy, MoveNotation},
+    engine::{
+        analysis::AnalysisLine,
+        game_analysis::{capped_centipawns, move_qualities, MoveQuality},
+        uci::Score,
+        Difficulty,
+    },
+    error::ChessError,
+    pieces::{PieceColor, PieceMove, PieceType},
+    utils::flip_coord,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
 pub enum GameState {
@@ -7,6 +27,66 @@ pub enum GameState {
     Draw,
     Playing,
     Promotion,
+    /// The player to move ran out of time
+    Timeout,
+    /// The player of this color resigned
+    Resignation(PieceColor),
+}
+
+impl GameState {
+    /// No move can be played anymore
+    pub fn is_over(self) -> bool {
+        matches!(
+            self,
+            GameState::Checkmate | GameState::Draw | GameState::Timeout | GameState::Resignation(_)
+        )
+    }
+}
+
+/// A rough idea of how far the game went
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum GamePhase {
+    Opening,
+    Middlegame,
+    Endgame,
+}
+
+impl GamePhase {
+    /// Guess the phase from the non pawn material left to both players and the move number
+    /// The game starts with 62 points of non pawn material
+    pub fn classify(non_pawn_material: u32, fullmove_number: u32) -> Self {
+        if non_pawn_material <= 26 {
+            GamePhase::Endgame
+        } else if fullmove_number <= 10 && non_pawn_material >= 56 {
+            GamePhase::Opening
+        } else {
+            GamePhase::Middlegame
+        }
+    }
+}
+
+/// The number of halfmoves played once white played the move numbered `move_number`
+/// Moves are numbered like in [`Game::fullmove_number`], move 0 is the starting position
+/// When black opened the game its first move is move 1, white's first move is then move 2
+pub fn halfmoves_after_move(move_number: u32, black_moved_first: bool) -> usize {
+    let move_number = move_number as usize;
+    match (move_number, black_moved_first) {
+        (0, _) => 0,
+        // White has no move 1, the position after black's first move is shown instead
+        (1, true) => 1,
+        (_, true) => 2 * move_number - 2,
+        (_, false) => 2 * move_number - 1,
+    }
+}
+
+impl fmt::Display for GamePhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GamePhase::Opening => write!(f, "Opening"),
+            GamePhase::Middlegame => write!(f, "Middlegame"),
+            GamePhase::Endgame => write!(f, "Endgame"),
+        }
+    }
 }
 
 pub struct Game {
@@ -18,6 +98,22 @@ pub struct Game {
     pub player_turn: PieceColor,
     /// The current state of the game (Playing, Draw, Checkmate. Promotion)
     pub game_state: GameState,
+    /// The clocks of the players, `None` for an untimed game
+    pub clock: Option<Clock>,
+    /// The FEN the game started from, `None` for the standard starting position
+    pub starting_fen: Option<String>,
+    /// The number of the first move of the game, taken from the FEN it started from
+    pub starting_fullmove_number: u32,
+    /// Either side can move at any time and a finished game stays open, to explore lines
+    pub free_play: bool,
+    /// How strong the bot plays in a game against it
+    pub difficulty: Difficulty,
+    /// The best lines of the engine analysing the position shown, the best one first
+    pub engine_lines: Vec<AnalysisLine>,
+    /// The evaluation of the positions of the board history, from the point of view of the
+    /// player to move, the first position first: all of them once the game got analysed,
+    /// during the game the ones the engine scored
+    pub evaluations: Vec<Option<Score>>,
 }
 
 impl Clone for Game {
@@ -27,6 +123,13 @@ impl Clone for Game {
             ui: self.ui.clone(),
             player_turn: self.player_turn,
             game_state: self.game_state,
+            clock: self.clock,
+            starting_fen: self.starting_fen.clone(),
+            starting_fullmove_number: self.starting_fullmove_number,
+            free_play: self.free_play,
+            difficulty: self.difficulty,
+            engine_lines: self.engine_lines.clone(),
+            evaluations: self.evaluations.clone(),
         }
     }
 }
@@ -38,6 +141,13 @@ impl Default for Game {
             ui: UI::default(),
             player_turn: PieceColor::White,
             game_state: GameState::Playing,
+            clock: None,
+            starting_fen: None,
+            starting_fullmove_number: 1,
+            free_play: false,
+            difficulty: Difficulty::default(),
+            engine_lines: vec![],
+            evaluations: vec![],
         }
     }
 }
@@ -45,12 +155,37 @@ impl Default for Game {
 impl Game {
     // SETTERS
     pub fn new(game_board: GameBoard, player_turn: PieceColor) -> Self {
-        Self {
+        let mut game = Self {
             game_board,
             ui: UI::default(),
             player_turn,
             game_state: GameState::Playing,
-        }
+            clock: None,
+            starting_fen: None,
+            starting_fullmove_number: 1,
+            free_play: false,
+            difficulty: Difficulty::default(),
+            engine_lines: vec![],
+            evaluations: vec![],
+        };
+        game.update_check_highlight();
+        game
+    }
+
+    /// Build a game starting from the position described by a FEN string
+    pub fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        let (game_board, player_turn) = GameBoard::from_fen(fen)?;
+        let mut game = Game::new(game_board, player_turn);
+        let fen = complete_fen(fen);
+        game.starting_fullmove_number = fen
+            .split_whitespace()
+            .nth(5)
+            .and_then(|field| field.parse().ok())
+            .unwrap_or(1);
+        game.starting_fen = Some(fen);
+        // The position might already be over
+        game.update_game_state();
+        Ok(game)
     }
 
     /// Allows you to pass a specific GameBoard
@@ -63,6 +198,126 @@ impl Game {
         self.player_turn = player_turn;
     }
 
+    /// The result of the game in PGN notation, `None` while the game is not over
+    pub fn result(&self) -> Option<&'static str> {
+        let loser = match self.game_state {
+            GameState::Checkmate | GameState::Timeout => self.player_turn,
+            GameState::Resignation(resigned) => resigned,
+            GameState::Draw => return Some("1/2-1/2"),
+            GameState::Playing | GameState::Promotion => return None,
+        };
+        match loser {
+            PieceColor::White => Some("0-1"),
+            PieceColor::Black => Some("1-0"),
+        }
+    }
+
+    /// The cells shaded by the threats overlay: every cell the opponent controls
+    /// The opponent always plays at the top of the board so the cells match the current orientation
+    pub fn threat_overlay(&self) -> HashSet<Coord> {
+        if !self.ui.show_threats {
+            return HashSet::new();
+        }
+        self.game_board
+            .attacked_squares(self.player_turn.opposite())
+    }
+
+    /// The number of legal moves of the player to move, a handful of them hints at a zugzwang
+    pub fn legal_move_count(&self) -> usize {
+        self.game_board.legal_moves(self.player_turn).len()
+    }
+
+    /// The number of the current move, counted like in a FEN: it goes up once black played
+    /// A game set up from a FEN goes on from the move number of its FEN
+    pub fn fullmove_number(&self) -> u32 {
+        let moves = self.game_board.move_history.len() as u32;
+        let moves_played = match self.game_board.move_history.first() {
+            // Black opened a game set up from a FEN, its first move shares the number of the next one
+            Some(first_move) if first_move.piece_color == PieceColor::Black => moves.div_ceil(2),
+            _ => moves / 2,
+        };
+        self.starting_fullmove_number + moves_played
+    }
+
+    /// The index in the board history of the position after white's move `move_number`
+    /// Numbers past the end of the game give the current position
+    pub fn history_index_of_move(&self, move_number: u32) -> usize {
+        let black_moved_first = self
+            .game_board
+            .move_history
+            .first()
+            .is_some_and(|first_move| first_move.piece_color == PieceColor::Black);
+        halfmoves_after_move(move_number, black_moved_first)
+            .min(self.game_board.board_history.len().saturating_sub(1))
+    }
+
+    /// The game as it stood after `index` halfmoves, to look back at it without touching the game
+    pub fn snapshot(&self, index: usize) -> Game {
+        let index = index.min(self.game_board.board_history.len().saturating_sub(1));
+        let first_player = self
+            .game_board
+            .move_history
+            .first()
+            .map_or(self.player_turn, |first_move| first_move.piece_color);
+
+        let mut game = self.clone();
+        if let Some(board) = self.game_board.board_history.get(index) {
+            game.game_board.board = *board;
+        }
+        game.game_board.move_history.truncate(index);
+        game.game_board.board_history.truncate(index + 1);
+        game.player_turn = game
+            .game_board
+            .move_history
+            .last()
+            .map_or(first_player, |last_move| last_move.piece_color.opposite());
+        game.game_state = GameState::Playing;
+        game.ui.selected_coordinates = Coord::undefined();
+        game.ui.show_threats = false;
+        game
+    }
+
+    /// The theoretical result of the position when it is a trivial ending, while the game goes on
+    pub fn endgame_hint(&self) -> Option<EndgameVerdict> {
+        if self.game_state != GameState::Playing {
+            return None;
+        }
+        trivial_endgame_verdict(&self.game_board.board, self.player_turn)
+    }
+
+    /// The phase of the game in the current position
+    pub fn phase(&self) -> GamePhase {
+        GamePhase::classify(self.game_board.non_pawn_material(), self.fullmove_number())
+    }
+
+    /// The sentence describing the state of the game in the status bar
+    pub fn status_message(&self) -> String {
+        let player = match self.player_turn {
+            PieceColor::White => "White",
+            PieceColor::Black => "Black",
+        };
+        match self.game_state {
+            GameState::Checkmate => format!("Checkmate {}", self.result().unwrap_or_default()),
+            GameState::Draw => format!("Draw {}", self.result().unwrap_or_default()),
+            GameState::Timeout => format!(
+                "{player} ran out of time {}",
+                self.result().unwrap_or_default()
+            ),
+            GameState::Resignation(resigned) => {
+                let resigned = match resigned {
+                    PieceColor::White => "White",
+                    PieceColor::Black => "Black",
+                };
+                format!("{resigned} resigned {}", self.result().unwrap_or_default())
+            }
+            GameState::Promotion => "Choose the promoted piece".to_string(),
+            GameState::Playing if self.game_board.is_check(self.player_turn) => {
+                format!("{player} to move, check!")
+            }
+            GameState::Playing => format!("{player} to move"),
+        }
+    }
+
     /// Switch the player turn
     pub fn switch_player_turn(&mut self) {
         match self.player_turn {
@@ -71,14 +326,197 @@ impl Game {
         }
     }
 
+    /// Show a line of the engine, a deeper line replaces the one of the same rank
+    pub fn update_engine_line(&mut self, line: AnalysisLine) {
+        match self
+            .engine_lines
+            .binary_search_by_key(&line.rank, |shown| shown.rank)
+        {
+            Ok(index) => self.engine_lines[index] = line,
+            Err(index) => self.engine_lines.insert(index, line),
+        }
+    }
+
+    /// Keep the evaluation of the position at `index` in the board history
+    pub fn set_evaluation(&mut self, index: usize, score: Score) {
+        if self.evaluations.len() <= index {
+            self.evaluations.resize(index + 1, None);
+        }
+        self.evaluations[index] = Some(score);
+    }
+
+    /// The evaluations of the positions of the board history from the point of view of white,
+    /// in centipawns within [`EVALUATION_CAP`](crate::engine::game_analysis::EVALUATION_CAP)
+    pub fn white_evaluations(&self) -> Vec<Option<i32>> {
+        let move_history = &self.game_board.move_history;
+        let skipped_moves = self.skipped_moves();
+        self.evaluations
+            .iter()
+            .enumerate()
+            .map(|(index, score)| {
+                // The player to move plays the next move, or is the one to move now
+                let player_to_move = move_history
+                    .get(index + skipped_moves)
+                    .map_or(self.player_turn, |next_move| next_move.piece_color);
+                let centipawns = capped_centipawns((*score)?);
+                Some(match player_to_move {
+                    PieceColor::White => centipawns,
+                    PieceColor::Black => -centipawns,
+                })
+            })
+            .collect()
+    }
+
+    /// The mark of every move of the move history, `None` for a good move
+    /// or a move whose positions were not evaluated yet
+    pub fn move_qualities(&self) -> Vec<Option<MoveQuality>> {
+        let move_count = self.game_board.move_history.len();
+        let mut qualities = vec![None; self.skipped_moves()];
+        qualities.extend(move_qualities(&self.evaluations));
+        qualities.resize(move_count, None);
+        qualities
+    }
+
+    // The board history may start after the first moves, both end with the current position
+    fn skipped_moves(&self) -> usize {
+        self.game_board
+            .move_history
+            .len()
+            .saturating_sub(self.game_board.board_history.len().saturating_sub(1))
+    }
+
+    /// The board can't be played on anymore, a finished game stays open in free play
+    pub fn is_locked(&self) -> bool {
+        self.game_state.is_over() && !self.free_play
+    }
+
+    /// In free play, give the move to `color` when it is not its turn, the board is flipped to its orientation
+    /// Returns true when the board got flipped, cells taken from the previous orientation must be flipped too
+    fn hand_over_turn(&mut self, color: PieceColor) -> bool {
+        if !self.free_play || color == self.player_turn || self.game_state == GameState::Promotion {
+            return false;
+        }
+        self.flip_the_board();
+        self.switch_player_turn();
+        self.ui.cursor_coordinates = flip_coord(self.ui.cursor_coordinates);
+        self.ui.old_cursor_position = flip_coord(self.ui.old_cursor_position);
+        self.game_state = GameState::Playing;
+        self.update_game_state();
+        self.update_check_highlight();
+        true
+    }
+
+    /// Take back the latest move, the game is replayed from its start without it
+    /// Returns false when there is no move to take back
+    pub fn take_back(&mut self) -> bool {
+        let mut uci_moves = self.game_board.uci_moves();
+        if uci_moves.pop().is_none() {
+            return false;
+        }
+        let mut game = match &self.starting_fen {
+            Some(fen) => match Game::from_fen(fen) {
+                Ok(game) => game,
+                Err(error) => {
+                    log::error!("Could not set up the game to take back a move: {error}");
+                    return false;
+                }
+            },
+            None => Game::default(),
+        };
+        // The moves might not have been played in turn
+        game.free_play = true;
+        for uci_move in &uci_moves {
+            if let Err(error) = game.play_uci_move(uci_move) {
+                log::error!("Could not replay the game to take back a move: {error}");
+                return false;
+            }
+        }
+        game.free_play = self.free_play;
+        game.clock = self.clock;
+        // The positions left keep their evaluation
+        game.evaluations = std::mem::take(&mut self.evaluations);
+        game.evaluations
+            .truncate(game.game_board.board_history.len());
+
+        // The settings of the board are kept, only its highlights follow the position
+        let mut ui = self.ui.clone();
+        ui.unselect_cell();
+        ui.dragged_from = None;
+        ui.drag_hover = None;
+        ui.last_move_highlight = game.ui.last_move_highlight;
+        ui.check_highlight = game.ui.check_highlight;
+        game.ui = ui;
+        *self = game;
+        true
+    }
+
+    /// The color playing from the bottom of the stored board
+    /// The board only flips once a move is complete, it keeps the orientation of the mover
+    /// while choosing a promotion
+    pub fn board_orientation(&self) -> PieceColor {
+        match self.game_board.move_history.last() {
+            Some(last_move)
+                if self.game_board.board_history.last() == Some(&self.game_board.board) =>
+            {
+                last_move.piece_color
+            }
+            Some(last_move) => last_move.piece_color.opposite(),
+            None => self.player_turn,
+        }
+    }
+
+    /// The color the board should be displayed from, `own_color` is the local player
+    pub fn bottom_color(&self, flip_policy: FlipPolicy, own_color: PieceColor) -> PieceColor {
+        let first_player = self
+            .game_board
+            .move_history
+            .first()
+            .map_or(self.player_turn, |first_move| first_move.piece_color);
+        match flip_policy {
+            FlipPolicy::AlwaysFlip => self.board_orientation(),
+            FlipPolicy::NeverFlip => first_player,
+            FlipPolicy::FlipOnOwnMove => {
+                let own_moves = self
+                    .game_board
+                    .move_history
+                    .iter()
+                    .filter(|piece_move| piece_move.piece_color == own_color)
+                    .count();
+                if own_moves % 2 == 0 {
+                    first_player
+                } else {
+                    first_player.opposite()
+                }
+            }
+        }
+    }
+
+    /// Flip the board along with everything stored in its orientation
+    pub fn flip_the_board(&mut self) {
+        self.game_board.flip_the_board();
+        self.ui.flip_highlights();
+    }
+
+    /// Highlight the king of the player to move if he is getting checked
+    /// Must be called while the board is in the orientation of the player to move
+    pub fn update_check_highlight(&mut self) {
+        let board = self.game_board.board;
+        self.ui.check_highlight = if self.game_board.is_getting_checked(board, self.player_turn) {
+            Some(
+                self.game_board
+                    .get_king_coordinates(board, self.player_turn),
+            )
+        } else {
+            None
+        };
+    }
+
     // Methods to select a cell on the board
     pub fn handle_cell_click(&mut self) {
         // If we are doing a promotion the cursor is used for the popup
         if self.game_state == GameState::Promotion {
             self.handle_promotion();
-        } else if !(self.game_state == GameState::Checkmate)
-            && !(self.game_state == GameState::Draw)
-        {
+        } else if !self.is_locked() {
             if self.ui.is_cell_selected() {
                 self.already_selected_cell_action();
             } else {
@@ -88,16 +526,603 @@ impl Game {
         self.update_game_state();
     }
 
+    /// Find out how the game goes on once a move is complete, the board being in the orientation
+    /// of the player to move. A pawn waiting for its promoted piece decides nothing yet:
+    /// the piece it becomes can mate or stalemate the opponent
     fn update_game_state(&mut self) {
-        if self.game_board.is_checkmate(self.player_turn) {
+        if self.game_board.is_latest_move_promotion() {
+            self.game_state = GameState::Promotion;
+        } else if self.game_board.is_checkmate(self.player_turn) {
             self.game_state = GameState::Checkmate;
         } else if self.game_board.is_draw(self.player_turn) {
             self.game_state = GameState::Draw;
-        } else if self.game_board.is_latest_move_promotion() {
-            self.game_state = GameState::Promotion;
+        } else if self.free_play && self.game_state.is_over() {
+            // The line explored past the end of the game goes on
+            self.game_state = GameState::Playing;
+        }
+    }
+
+    /// Play a move of the player to move as if it was selected on the board,
+    /// in free play the move can be of either side
+    /// Returns false when the move is not legal
+    pub fn play_move(&mut self, from: Coord, to: Coord) -> bool {
+        let (from, to) = match self.game_board.get_piece_color(&from) {
+            Some(piece_color) if self.hand_over_turn(piece_color) => {
+                (flip_coord(from), flip_coord(to))
+            }
+            _ => (from, to),
+        };
+        if self.game_state == GameState::Promotion
+            || self.is_locked()
+            || !self
+                .game_board
+                .get_authorized_positions(self.player_turn, from)
+                .contains(&to)
+        {
+            return false;
+        }
+        // The cursor goes back where it was once the move is played
+        self.ui.old_cursor_position = self.ui.cursor_coordinates;
+        self.ui.selected_coordinates = from;
+        self.ui.cursor_coordinates = to;
+        self.already_selected_cell_action();
+        self.update_game_state();
+        true
+    }
+
+    /// The most valuable piece the player to move leaves for the taking with this move, if any
+    /// Pawns are not reported, nor pieces worth no more than the one the move captures
+    pub fn piece_left_hanging(&self, from: Coord, to: Coord) -> Option<PieceType> {
+        let captured_value = match self.game_board.board[&to] {
+            Some((piece_type, piece_color)) if piece_color != self.player_turn => {
+                piece_type.value()
+            }
+            _ => 0,
+        };
+        let mut after = self.clone();
+        if !after.play_move(from, to) || after.game_state.is_over() {
+            return None;
+        }
+        // Seen from the player who moved, at the bottom of the board
+        if after.board_orientation() != self.player_turn {
+            after.game_board.flip_the_board();
+        }
+        after
+            .game_board
+            .hanging_pieces(self.player_turn)
+            .into_iter()
+            .map(|(_, piece_type)| piece_type)
+            .filter(|piece_type| {
+                *piece_type != PieceType::Pawn && piece_type.value() > captured_value
+            })
+            .max_by_key(|piece_type| piece_type.value())
+    }
+
+    /// Play a move of the player to move written in UCI notation (`e2e4`, `e7e8q`, `e1g1`)
+    pub fn play_uci_move(&mut self, uci: &str) -> Result<(), ChessError> {
+        let (from, to, promotion_cursor) = self.uci_cells(uci)?;
+        if !self.play_move(from, to) {
+            return Err(ChessError::IllegalMove(uci.to_string()));
+        }
+        if self.game_state == GameState::Promotion {
+            self.ui.promotion_cursor = promotion_cursor;
+            self.promote_piece();
+        }
+        Ok(())
+    }
+
+    /// The legal move of the player to move written in UCI notation, the promoted piece is left out
+    pub fn piece_move_from_uci(&self, uci: &str) -> Result<PieceMove, ChessError> {
+        let (from, to, _) = self.uci_cells(uci)?;
+        match self.game_board.board[&from] {
+            Some((piece_type, piece_color))
+                if self.game_state == GameState::Playing
+                    && self
+                        .game_board
+                        .get_authorized_positions(self.player_turn, from)
+                        .contains(&to) =>
+            {
+                Ok(PieceMove {
+                    piece_type,
+                    piece_color,
+                    from,
+                    to,
+                })
+            }
+            _ => Err(ChessError::IllegalMove(uci.to_string())),
         }
     }
 
+    /// Play a move typed in standard algebraic notation (`e4`, `Nbd7`, `exd5`, `O-O`, `e8=N`)
+    /// A pawn reaching the last rank without its promoted piece waits in [`GameState::Promotion`]
+    /// for the player to choose it
+    pub fn play_algebraic_move(&mut self, san: &str) -> Result<(), ChessError> {
+        let (piece_move, promotion) = self.resolve_algebraic_move(san)?;
+        if !self.play_move(piece_move.from, piece_move.to) {
+            return Err(ChessError::IllegalMove(san.to_string()));
+        }
+        if let (GameState::Promotion, Some(promotion_cursor)) = (self.game_state, promotion) {
+            self.ui.promotion_cursor = promotion_cursor;
+            self.promote_piece();
+        }
+        Ok(())
+    }
+
+    /// The legal move of the player to move written in standard algebraic notation,
+    /// along with the promotion cursor of the promoted piece when the move gives it
+    pub fn resolve_algebraic_move(&self, san: &str) -> Result<(PieceMove, Option<i8>), ChessError> {
+        let illegal = |reason: &str| ChessError::IllegalMove(format!("{san}{reason}"));
+        if self.game_state != GameState::Playing {
+            return Err(illegal(""));
+        }
+        let notation = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        let legal_moves = self.game_board.legal_moves(self.player_turn);
+
+        // The king castles by moving on the rook cell
+        let king_side = match notation {
+            "O-O" | "0-0" => Some(true),
+            "O-O-O" | "0-0-0" => Some(false),
+            _ => None,
+        };
+        if let Some(king_side) = king_side {
+            let row = match self.player_turn {
+                PieceColor::White => 7u8,
+                PieceColor::Black => 0u8,
+            };
+            let col = self
+                .game_board
+                .castling_rights
+                .rook_file(self.player_turn, king_side);
+            let rook_cell = self.cell_in_current_orientation(Coord::new(row, col));
+            return legal_moves
+                .into_iter()
+                .find(|piece_move| {
+                    piece_move.piece_type == PieceType::King && piece_move.to == rook_cell
+                })
+                .map(|piece_move| (piece_move, None))
+                .ok_or_else(|| illegal(""));
+        }
+
+        // The promoted piece follows the last rank, with or without `=`
+        let (notation, promotion) = match notation.char_indices().last() {
+            Some((index, piece))
+                if index >= 2
+                    && "QRBNqrbn".contains(piece)
+                    && notation[..index]
+                        .trim_end_matches('=')
+                        .ends_with(|c: char| c.is_ascii_digit()) =>
+            {
+                let promotion_cursor = match piece.to_ascii_uppercase() {
+                    'Q' => 0,
+                    'R' => 1,
+                    'B' => 2,
+                    _ => 3,
+                };
+                (
+                    notation[..index].trim_end_matches('='),
+                    Some(promotion_cursor),
+                )
+            }
+            _ => (notation, None),
+        };
+
+        let (piece_type, notation) = match notation.chars().next() {
+            Some('K') => (PieceType::King, &notation[1..]),
+            Some('Q') => (PieceType::Queen, &notation[1..]),
+            Some('R') => (PieceType::Rook, &notation[1..]),
+            Some('B') => (PieceType::Bishop, &notation[1..]),
+            Some('N') => (PieceType::Knight, &notation[1..]),
+            _ => (PieceType::Pawn, notation),
+        };
+        let notation = notation.replace('x', "");
+        if notation.len() < 2 || !notation.is_ascii() {
+            return Err(illegal(""));
+        }
+        let (hint, target) = notation.split_at(notation.len() - 2);
+        let Some(target) = Coord::from_algebraic(target) else {
+            return Err(illegal(""));
+        };
+        let target = self.cell_in_current_orientation(target);
+
+        // The file and the rank before the target tell apart pieces reaching the same cell
+        let candidates: Vec<PieceMove> = legal_moves
+            .into_iter()
+            .filter(|piece_move| piece_move.piece_type == piece_type && piece_move.to == target)
+            .filter(|piece_move| {
+                let from = self
+                    .cell_in_current_orientation(piece_move.from)
+                    .to_algebraic();
+                hint.chars().all(|c| from.contains(c))
+            })
+            .collect();
+
+        let piece_move = match candidates[..] {
+            [piece_move] => piece_move,
+            [] => return Err(illegal("")),
+            _ => return Err(illegal(", several pieces can play it")),
+        };
+        // Only a pawn reaching the last rank is promoted
+        let reaches_last_rank = piece_type == PieceType::Pawn && piece_move.to.row == 0;
+        if promotion.is_some() && !reaches_last_rank {
+            return Err(illegal(""));
+        }
+        Ok((piece_move, promotion))
+    }
+
+    // The cells of a move in UCI notation in the orientation of the board, with the promotion cursor
+    fn uci_cells(&self, uci: &str) -> Result<(Coord, Coord, i8), ChessError> {
+        let illegal = || ChessError::IllegalMove(uci.to_string());
+        let (Some(from), Some(to)) = (
+            uci.get(0..2).and_then(Coord::from_algebraic),
+            uci.get(2..4).and_then(Coord::from_algebraic),
+        ) else {
+            return Err(illegal());
+        };
+        let promotion_cursor = match uci.get(4..) {
+            Some("") | Some("q") => 0,
+            Some("r") => 1,
+            Some("b") => 2,
+            Some("n") => 3,
+            _ => return Err(illegal()),
+        };
+
+        // The king castles by moving on the rook cell, a king going two cells to the side
+        // castles with the rook of that side
+        let mut to = to;
+        if from.row == to.row
+            && from.col.abs_diff(to.col) == 2
+            && self.game_board.board[&self.cell_in_current_orientation(from)]
+                .is_some_and(|(piece_type, _)| piece_type == PieceType::King)
+        {
+            to.col = self
+                .game_board
+                .castling_rights
+                .rook_file(self.player_turn, to.col > from.col);
+        }
+
+        Ok((
+            self.cell_in_current_orientation(from),
+            self.cell_in_current_orientation(to),
+            promotion_cursor,
+        ))
+    }
+
+    /// The moves of the game in standard algebraic notation (`e4`, `Nbd7`, `exd5`, `O-O`, `e8=Q+`)
+    pub fn san_moves(&self) -> Vec<String> {
+        (0..self.game_board.move_history.len())
+            .map(|index| self.san_move(index))
+            .collect()
+    }
+
+    /// The latest move in standard algebraic notation, `None` before the first move
+    pub fn latest_san_move(&self) -> Option<String> {
+        let index = self.game_board.move_history.len().checked_sub(1)?;
+        Some(self.san_move(index))
+    }
+
+    /// The move at `index` in the history written in `notation`
+    pub fn notated_move(&self, index: usize, notation: MoveNotation) -> String {
+        match notation {
+            MoveNotation::San => self.san_move(index),
+            MoveNotation::Uci => self.game_board.uci_moves().swap_remove(index),
+        }
+    }
+
+    /// The move at `index` in the history in standard algebraic notation
+    pub fn san_move(&self, index: usize) -> String {
+        let played = self.game_board.move_history[index];
+        // The position the move was played in, with its player at the bottom
+        let mut before = self.snapshot(index);
+        if before.board_orientation() != before.player_turn {
+            before.game_board.flip_the_board();
+        }
+        let game_board = &before.game_board;
+        let Some(piece_type) = game_board.get_piece_type(&played.from) else {
+            return String::new();
+        };
+        let to_white_view = |cell: Coord| match played.piece_color {
+            PieceColor::White => cell,
+            PieceColor::Black => flip_coord(cell),
+        };
+        let (from, to) = (to_white_view(played.from), to_white_view(played.to));
+        let check_suffix = self.game_board.check_suffix(index);
+        let letter = |piece_type: PieceType| {
+            PieceType::piece_to_fen_enum(Some(piece_type), Some(PieceColor::White))
+        };
+
+        // The king castles by moving on the rook cell
+        if piece_type == PieceType::King
+            && game_board.board[&played.to] == Some((PieceType::Rook, played.piece_color))
+        {
+            let castle = if to.col > from.col { "O-O" } else { "O-O-O" };
+            return format!("{castle}{check_suffix}");
+        }
+
+        let is_capture = game_board.get_piece_color(&played.to)
+            == Some(played.piece_color.opposite())
+            || (piece_type == PieceType::Pawn && from.col != to.col);
+        let from_name = from.to_algebraic();
+        let mut san = String::new();
+        if piece_type == PieceType::Pawn {
+            if is_capture {
+                san.push_str(&from_name[..1]);
+            }
+        } else {
+            san.push_str(letter(piece_type));
+            // Another piece of the same kind reaching the cell is told apart by the file, then the rank
+            let rivals: Vec<Coord> = game_board
+                .legal_moves(played.piece_color)
+                .into_iter()
+                .filter(|rival| {
+                    rival.to == played.to
+                        && rival.from != played.from
+                        && rival.piece_type == piece_type
+                })
+                .map(|rival| to_white_view(rival.from))
+                .collect();
+            if !rivals.is_empty() {
+                if rivals.iter().all(|rival| rival.col != from.col) {
+                    san.push_str(&from_name[..1]);
+                } else if rivals.iter().all(|rival| rival.row != from.row) {
+                    san.push_str(&from_name[1..]);
+                } else {
+                    san.push_str(&from_name);
+                }
+            }
+        }
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(&to.to_algebraic());
+        // The history keeps the promoted piece
+        if piece_type == PieceType::Pawn && played.piece_type != PieceType::Pawn {
+            san.push('=');
+            san.push_str(letter(played.piece_type));
+        }
+        san + check_suffix
+    }
+
+    /// The FEN of the current position, to hand it over to an engine
+    /// The move number is counted from the moves played since the game started
+    pub fn to_fen(&self) -> String {
+        let mut game_board = self.game_board.clone();
+        if self.board_orientation() == PieceColor::Black {
+            game_board.flip_the_board();
+        }
+        let board = game_board.board;
+
+        let placement = board
+            .iter()
+            .map(|row| {
+                let mut rank = String::new();
+                let mut empty_cells = 0;
+                for cell in row {
+                    match cell {
+                        Some((piece_type, piece_color)) => {
+                            if empty_cells > 0 {
+                                rank.push_str(&empty_cells.to_string());
+                                empty_cells = 0;
+                            }
+                            rank.push_str(PieceType::piece_to_fen_enum(
+                                Some(*piece_type),
+                                Some(*piece_color),
+                            ));
+                        }
+                        None => empty_cells += 1,
+                    }
+                }
+                if empty_cells > 0 {
+                    rank.push_str(&empty_cells.to_string());
+                }
+                rank
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let side_to_move = match self.player_turn {
+            PieceColor::White => "w",
+            PieceColor::Black => "b",
+        };
+
+        let castling = self
+            .game_board
+            .castling_availability(&board)
+            .to_fen_on_board(&board);
+
+        // The cell the pawn that just moved two cells jumped over, seen from white
+        let en_passant = match self.game_board.move_history.last() {
+            Some(last_move)
+                if last_move.piece_type == PieceType::Pawn
+                    && last_move.from.row.abs_diff(last_move.to.row) == 2 =>
+            {
+                let jumped = Coord::new(5, last_move.from.col);
+                match last_move.piece_color {
+                    PieceColor::White => Some(jumped),
+                    PieceColor::Black => Some(flip_coord(jumped)),
+                }
+            }
+            Some(_) => None,
+            None => game_board.en_passant_target,
+        }
+        .map_or("-".to_string(), |cell| cell.to_algebraic());
+
+        format!(
+            "{placement} {side_to_move} {castling} {en_passant} {} {}",
+            self.game_board.get_consecutive_non_pawn_or_capture(),
+            self.fullmove_number()
+        )
+    }
+
+    /// The FEN of every position of the board history, from the start to the current one
+    pub fn fen_history(&self) -> Vec<String> {
+        let game_board = &self.game_board;
+        let positions = game_board.board_history.len();
+        // Both histories end with the current position
+        let moves_before = |index: usize| {
+            game_board
+                .move_history
+                .len()
+                .saturating_sub(positions - 1 - index)
+        };
+        let count_pieces = |index: usize| {
+            game_board.board_history[index]
+                .iter()
+                .flatten()
+                .flatten()
+                .count()
+        };
+
+        // The halfmove clock goes back to 0 after a pawn move or a capture
+        let mut halfmove_clock = self
+            .starting_fen
+            .as_deref()
+            .and_then(|fen| fen.split_whitespace().nth(4))
+            .and_then(|field| field.parse().ok())
+            .unwrap_or(0);
+        let mut fens = Vec::with_capacity(positions);
+        for index in 0..positions {
+            let moves = moves_before(index);
+            if index > 0 && moves > moves_before(index - 1) {
+                let is_pawn_move = game_board.move_history[moves - 1].piece_type == PieceType::Pawn;
+                if is_pawn_move || count_pieces(index) < count_pieces(index - 1) {
+                    halfmove_clock = 0;
+                } else {
+                    halfmove_clock += 1;
+                }
+            }
+
+            let mut position = self.clone();
+            position.game_board.board = game_board.board_history[index];
+            position.game_board.move_history.truncate(moves);
+            position.game_board.board_history.truncate(index + 1);
+            position
+                .game_board
+                .set_consecutive_non_pawn_or_capture(halfmove_clock);
+            position.player_turn = position
+                .game_board
+                .move_history
+                .last()
+                .map_or(game_board.first_player, |last_move| {
+                    last_move.piece_color.opposite()
+                });
+            // The en passant target of the start follows the flips of the board
+            if self.board_orientation() != position.board_orientation() {
+                position.game_board.en_passant_target =
+                    position.game_board.en_passant_target.map(flip_coord);
+            }
+            fens.push(position.to_fen());
+        }
+        fens
+    }
+
+    /// The cells the piece on `square` (`e2`) can move to, seen from white and sorted
+    /// Only the pieces of the player to move have moves, a castling king goes to the g or c file,
+    /// or on its rook in Chess960
+    pub fn legal_destinations(&self, square: &str) -> Result<Vec<String>, ChessError> {
+        let from = Coord::from_algebraic(square)
+            .ok_or_else(|| ChessError::InvalidSquare(square.to_string()))?;
+        let cell = self.cell_in_current_orientation(from);
+
+        let mut destinations: Vec<String> = self
+            .game_board
+            .get_authorized_positions(self.player_turn, cell)
+            .into_iter()
+            .map(|to| {
+                let is_castling = self.game_board.is_latest_move_castling(cell, to);
+                let to = self.cell_in_current_orientation(to);
+                if is_castling {
+                    castling_king_target(from, to).to_algebraic()
+                } else {
+                    to.to_algebraic()
+                }
+            })
+            .collect();
+        destinations.sort();
+        Ok(destinations)
+    }
+
+    /// Put the cursor on a cell seen from white, whatever the orientation of the board
+    pub fn place_cursor(&mut self, square: Coord) {
+        self.ui.cursor_coordinates = self.cell_in_current_orientation(square);
+    }
+
+    /// A cell seen from white in the orientation of the board, which is flipped when black plays
+    fn cell_in_current_orientation(&self, coord: Coord) -> Coord {
+        match self.player_turn {
+            PieceColor::White => coord,
+            PieceColor::Black => flip_coord(coord),
+        }
+    }
+
+    /// The only legal move of the player to move when it takes back the piece that was just captured
+    pub fn forced_recapture(&self) -> Option<PieceMove> {
+        if self.game_state != GameState::Playing {
+            return None;
+        }
+        let last_move = self.game_board.move_history.last()?;
+        let board_before_last_move = self.game_board.board_history.iter().rev().nth(1)?;
+
+        // The latest move has to be the capture of one of our pieces, the boards are counted
+        // as the history does not keep them all in the same orientation
+        let our_pieces = |board: &Board| {
+            board
+                .iter()
+                .flatten()
+                .filter(|cell| matches!(cell, Some((_, color)) if *color == self.player_turn))
+                .count()
+        };
+        if our_pieces(board_before_last_move) <= our_pieces(&self.game_board.board) {
+            return None;
+        }
+
+        let capture_cell = flip_coord(last_move.to);
+        match self.game_board.legal_moves(self.player_turn).as_slice() {
+            [only_move] if only_move.to == capture_cell => Some(*only_move),
+            _ => None,
+        }
+    }
+
+    /// Grab a piece of the player to move with the mouse, its legal moves get highlighted
+    /// Returns false when there is no piece to drag on this cell
+    pub fn start_drag(&mut self, coordinates: Coord) -> bool {
+        let coordinates = match self.game_board.get_piece_color(&coordinates) {
+            Some(piece_color) if self.hand_over_turn(piece_color) => flip_coord(coordinates),
+            _ => coordinates,
+        };
+        if self.game_state == GameState::Promotion
+            || self.is_locked()
+            || self.game_board.get_piece_color(&coordinates) != Some(self.player_turn)
+        {
+            return false;
+        }
+        self.ui.selected_coordinates = coordinates;
+        self.ui.dragged_from = Some(coordinates);
+        self.ui.drag_hover = Some(coordinates);
+        true
+    }
+
+    /// The cells the selected piece can reach
+    pub fn selected_piece_positions(&mut self) -> Vec<Coord> {
+        self.ui
+            .authorized_positions(&self.game_board, self.player_turn)
+    }
+
+    /// The cells where the dragged piece can be dropped
+    pub fn drag_targets(&self) -> Vec<Coord> {
+        match self.ui.dragged_from {
+            Some(dragged_from) => self
+                .game_board
+                .get_authorized_positions(self.player_turn, dragged_from),
+            None => vec![],
+        }
+    }
+
+    /// Forget about the dragged piece
+    pub fn cancel_drag(&mut self) {
+        self.ui.dragged_from = None;
+        self.ui.drag_hover = None;
+    }
+
     pub fn handle_promotion(&mut self) {
         self.promote_piece();
     }
@@ -110,21 +1135,21 @@ impl Game {
             self.execute_move(selected_coords_usize, cursor_coords_usize);
             self.ui.unselect_cell();
             self.switch_player_turn();
+            self.ui.last_move_highlight = Some((*selected_coords_usize, *cursor_coords_usize));
+            self.ui.check_highlight = None;
 
-            if self.game_board.is_draw(self.player_turn) {
-                self.game_state = GameState::Draw;
-            }
-
-            if !self.game_board.is_latest_move_promotion()
-                || self.game_board.is_draw(self.player_turn)
-                || self.game_board.is_checkmate(self.player_turn)
-            {
-                self.game_board.flip_the_board();
+            // A promoted pawn keeps the board of its player until the piece is chosen
+            if !self.game_board.is_latest_move_promotion() {
+                self.flip_the_board();
+                self.update_check_highlight();
             }
         }
     }
 
     pub fn select_cell(&mut self) {
+        if let Some(piece_color) = self.game_board.get_piece_color(&self.ui.cursor_coordinates) {
+            self.hand_over_turn(piece_color);
+        }
         // Check if the piece on the cell can move before selecting it
         let authorized_positions = self
             .game_board
@@ -133,19 +1158,22 @@ impl Game {
         if authorized_positions.is_empty() {
             return;
         }
-        if let Some(piece_color) = self.game_board.get_piece_color(&self.ui.cursor_coordinates) {
-            let authorized_positions = self
-                .game_board
-                .get_authorized_positions(self.player_turn, self.ui.cursor_coordinates);
-
-            if piece_color == self.player_turn {
-                self.ui.selected_coordinates = self.ui.cursor_coordinates;
-                self.ui.old_cursor_position = self.ui.cursor_coordinates;
-                self.ui
-                    .move_selected_piece_cursor(true, 1, authorized_positions);
-            }
+        if self.game_board.get_piece_color(&self.ui.cursor_coordinates) == Some(self.player_turn) {
+            self.ui.selected_coordinates = self.ui.cursor_coordinates;
+            self.ui.old_cursor_position = self.ui.cursor_coordinates;
+            self.ui
+                .move_selected_piece_cursor(true, 1, authorized_positions);
         }
     }
+
+    /// The color of the pawn being promoted, taken from the latest move
+    pub fn promoting_color(&self) -> Option<PieceColor> {
+        self.game_board
+            .move_history
+            .last()
+            .map(|last_move| last_move.piece_color)
+    }
+
     // Method to promote a pawn
     pub fn promote_piece(&mut self) {
         if let Some(last_move) = self.game_board.move_history.last() {
@@ -174,11 +1202,29 @@ impl Game {
         }
         self.game_state = GameState::Playing;
         self.ui.promotion_cursor = 0;
-        if !self.game_board.is_draw(self.player_turn)
-            && !self.game_board.is_checkmate(self.player_turn)
-        {
-            self.game_board.flip_the_board();
+        // The opponent gets the board to find out what the promoted piece leaves them
+        self.flip_the_board();
+        self.update_check_highlight();
+        self.update_game_state();
+    }
+
+    // The cells of the king and the rook after castling, the king moving on the rook cell
+    // `None` when there is no rook to castle with
+    fn castling_cells(&self, from: &Coord, to: &Coord) -> Option<(Coord, Coord)> {
+        if self.game_board.board[to] != Some((PieceType::Rook, self.player_turn)) {
+            return None;
         }
+        // Whatever their files, the king and the rook end up on the g and f files or the c and d files,
+        // which are mirrored on the board flipped for black
+        let king_side = (to.col > from.col) == (self.player_turn == PieceColor::White);
+        let (col_king, col_rook) = match (king_side, self.player_turn) {
+            (true, PieceColor::White) => (6, 5),
+            (false, PieceColor::White) => (2, 3),
+            (true, PieceColor::Black) => (1, 2),
+            (false, PieceColor::Black) => (5, 4),
+        };
+
+        Some((Coord::new(to.row, col_king), Coord::new(to.row, col_rook)))
     }
 
     /// Move a piece from a cell to another
@@ -196,55 +1242,61 @@ impl Game {
             return;
         };
 
+        // A malformed state could make the move reach cells outside of the board,
+        // it is refused before anything changes instead of panicking
+        let en_passant_capture = if self.game_board.is_latest_move_en_passant(from, to) {
+            let Some(captured_pawn) = Coord::opt_new(to.row + 1, to.col) else {
+                log::error!("En passant from {from:?} to {to:?} captures outside of the board");
+                return;
+            };
+            Some(captured_pawn)
+        } else {
+            None
+        };
+        let castling_cells = if self.game_board.is_latest_move_castling(*from, *to) {
+            let Some(castling_cells) = self.castling_cells(from, to) else {
+                log::error!("Invalid castling from {from:?} to {to:?}");
+                return;
+            };
+            Some(castling_cells)
+        } else {
+            None
+        };
+
         // We increment the consecutive_non_pawn_or_capture if the piece type is a pawn or if there is no capture
+        // The king moving on its rook to castle captures nothing
+        let captured = piece_type_to.filter(|_| castling_cells.is_none());
         self.game_board
-            .increment_consecutive_non_pawn_or_capture(piece_type_from, piece_type_to);
+            .increment_consecutive_non_pawn_or_capture(piece_type_from, captured);
 
         // We check if the move is a capture and add the piece to the taken pieces
         self.game_board
             .add_piece_to_taken_pieces(from, to, self.player_turn);
 
         // We check for en passant as the latest move
-        if self.game_board.is_latest_move_en_passant(from, to) {
+        if let Some(captured_pawn) = en_passant_capture {
             // we kill the pawn
-            let row_index = to.row as i32 + 1;
-            self.game_board.board[row_index as usize][to.col as usize] = None;
+            self.game_board.board[&captured_pawn] = None;
         }
 
         // We check for castling as the latest move
-        if self.game_board.is_latest_move_castling(*from, *to) {
-            // we set the king 2 cells on where it came from
-            let from_x: i32 = from.col as i32;
-            let new_to = to;
-            let to_x: i32 = to.col as i32;
-
-            let distance = from_x - to_x;
-            // We set the direction of the rook > 0 meaning he went on the left else on the right
-            let direction_x = if distance > 0 { -1 } else { 1 };
-
-            let col_king = from_x + direction_x * 2;
-
-            // We put move the king 2 cells
-            self.game_board.board[to.row as usize][col_king as usize] = self.game_board.board[from];
+        if let Some((king_cell, rook_cell)) = castling_cells {
+            // In Chess960 the king or the rook can end up on the cell the other one left,
+            // both are lifted before being put down
+            let king = self.game_board.board[from];
+            self.game_board.board[from] = None;
+            self.game_board.board[to] = None;
 
-            // We put the rook 3 cells from it's position if it's a big castling else 2 cells
-            let col_rook = if distance > 0 {
-                col_king + 1
-            } else {
-                col_king - 1
-            };
+            // We put the king on the g or c file
+            self.game_board.board[&king_cell] = king;
 
-            self.game_board.board[new_to.row as usize][col_rook as usize] =
-                Some((PieceType::Rook, self.player_turn));
-
-            // We remove the latest rook
-            self.game_board.board[new_to] = None;
+            // We put the rook on the other side of the king
+            self.game_board.board[&rook_cell] = Some((PieceType::Rook, self.player_turn));
         } else {
             self.game_board.board[to] = self.game_board.board[from];
+            self.game_board.board[from] = None;
         }
 
-        self.game_board.board[from] = None;
-
         // We store it in the history
         self.game_board.move_history.push(PieceMove {
             piece_type: piece_type_from,
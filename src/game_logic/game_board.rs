@@ -1,13 +1,266 @@
+use std::collections::{HashMap, HashSet};
+
 use super::{
     board::{init_board, Board},
     coord::Coord,
     game::Game,
 };
 use crate::{
+    error::ChessError,
     pieces::{pawn::Pawn, PieceColor, PieceMove, PieceType},
-    utils::col_to_letter,
+    utils::{col_to_letter, flip_coord, letter_to_col},
 };
 
+/// The FEN with its fields separated by single spaces
+/// FENs copied from a lichess editor URL use underscores instead of spaces
+/// A placement only FEN (`4k3/8/8/8/8/8/8/4K2R`) is a quick setup rather than a full position:
+/// white is to move, nobody can castle and no pawn can be taken en passant
+pub fn complete_fen(fen: &str) -> String {
+    let fen = fen.replace('_', " ");
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    match fields[..] {
+        [placement] => format!("{placement} w - - 0 1"),
+        _ => fields.join(" "),
+    }
+}
+
+/// Castling availabilities of a position setup (FEN)
+/// Castles lost by moving the king or a rook are tracked through the move history,
+/// these flags only forbid castles the starting position itself does not allow
+/// The files of the castling rooks let Chess960 positions castle with rooks off the corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CastlingRights {
+    pub white_king_side: bool,
+    pub white_queen_side: bool,
+    pub black_king_side: bool,
+    pub black_queen_side: bool,
+    /// The files (0 for a) of the rooks castling king side, white first
+    pub king_side_rook_files: [u8; 2],
+    /// The files of the rooks castling queen side, white first
+    pub queen_side_rook_files: [u8; 2],
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self {
+            white_king_side: true,
+            white_queen_side: true,
+            black_king_side: true,
+            black_queen_side: true,
+            king_side_rook_files: [7, 7],
+            queen_side_rook_files: [0, 0],
+        }
+    }
+}
+
+impl CastlingRights {
+    /// No castle allowed, the rooks would castle from the corners
+    pub fn none() -> Self {
+        Self {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+            ..Default::default()
+        }
+    }
+
+    /// Whether `color` may castle on the king side or on the queen side
+    pub fn is_allowed(&self, color: PieceColor, king_side: bool) -> bool {
+        match (color, king_side) {
+            (PieceColor::White, true) => self.white_king_side,
+            (PieceColor::White, false) => self.white_queen_side,
+            (PieceColor::Black, true) => self.black_king_side,
+            (PieceColor::Black, false) => self.black_queen_side,
+        }
+    }
+
+    /// The file (0 for a) of the rook `color` castles with on the king side or on the queen side
+    pub fn rook_file(&self, color: PieceColor, king_side: bool) -> u8 {
+        let files = if king_side {
+            self.king_side_rook_files
+        } else {
+            self.queen_side_rook_files
+        };
+        files[color_index(color)]
+    }
+
+    /// Allow the castle of `color` with the rook on `file`
+    pub fn allow(&mut self, color: PieceColor, king_side: bool, file: u8) {
+        match (color, king_side) {
+            (PieceColor::White, true) => self.white_king_side = true,
+            (PieceColor::White, false) => self.white_queen_side = true,
+            (PieceColor::Black, true) => self.black_king_side = true,
+            (PieceColor::Black, false) => self.black_queen_side = true,
+        }
+        let files = if king_side {
+            &mut self.king_side_rook_files
+        } else {
+            &mut self.queen_side_rook_files
+        };
+        files[color_index(color)] = file;
+    }
+
+    /// Whether castling with the rook standing on `rook_col` is allowed
+    /// `rook_col` is in the orientation of the player to move (the board is flipped for black)
+    pub fn allows(&self, color: PieceColor, rook_col: u8) -> bool {
+        let file = match color {
+            PieceColor::White => rook_col,
+            PieceColor::Black => 7 - rook_col,
+        };
+        [true, false].into_iter().any(|king_side| {
+            self.is_allowed(color, king_side) && self.rook_file(color, king_side) == file
+        })
+    }
+
+    /// Parse the castling field of a FEN string (`KQkq` or `-`) of the standard starting position
+    pub fn from_fen(field: &str) -> Result<Self, ChessError> {
+        Self::from_fen_and_board(field, &init_board())
+    }
+
+    /// Parse the castling field of a FEN string whose pieces are on `board`, seen from white
+    /// `KQkq` castle with the outermost rook of their side (X-FEN), the file letters of
+    /// Shredder-FEN (`HAha`) name the rook, on the side of the king it stands
+    pub fn from_fen_and_board(field: &str, board: &Board) -> Result<Self, ChessError> {
+        let mut rights = CastlingRights::none();
+        if field == "-" {
+            return Ok(rights);
+        }
+        for c in field.chars() {
+            let (color, back_rank) = if c.is_ascii_uppercase() {
+                (PieceColor::White, 7)
+            } else {
+                (PieceColor::Black, 0)
+            };
+            let files_of = |piece_type: PieceType| {
+                (0..8u8).filter(move |&file| {
+                    board[back_rank][file as usize] == Some((piece_type, color))
+                })
+            };
+            let king_file = files_of(PieceType::King).next();
+            // Without a rook to castle with, the castle of the standard position is kept
+            let (king_side, rook_file) = match c.to_ascii_lowercase() {
+                'k' => (
+                    true,
+                    king_file
+                        .and_then(|king| files_of(PieceType::Rook).rfind(|&rook| rook > king))
+                        .unwrap_or(7),
+                ),
+                'q' => (
+                    false,
+                    king_file
+                        .and_then(|king| files_of(PieceType::Rook).find(|&rook| rook < king))
+                        .unwrap_or(0),
+                ),
+                letter @ 'a'..='h' => {
+                    let file = letter as u8 - b'a';
+                    match king_file {
+                        Some(king) if king != file => (file > king, file),
+                        _ => {
+                            return Err(ChessError::InvalidFen(format!(
+                                "no king beside the castling rook '{c}'"
+                            )))
+                        }
+                    }
+                }
+                _ => {
+                    return Err(ChessError::InvalidFen(format!(
+                        "unknown castling availability '{c}'"
+                    )))
+                }
+            };
+            rights.allow(color, king_side, rook_file);
+        }
+        Ok(rights)
+    }
+
+    /// Castling availabilities guessed from a board seen by white: kings and rooks must be on their original cells
+    pub fn from_board(board: &Board) -> Self {
+        let is_on =
+            |row: usize, col: usize, piece: (PieceType, PieceColor)| board[row][col] == Some(piece);
+        let white_king = is_on(7, 4, (PieceType::King, PieceColor::White));
+        let black_king = is_on(0, 4, (PieceType::King, PieceColor::Black));
+        CastlingRights {
+            white_king_side: white_king && is_on(7, 7, (PieceType::Rook, PieceColor::White)),
+            white_queen_side: white_king && is_on(7, 0, (PieceType::Rook, PieceColor::White)),
+            black_king_side: black_king && is_on(0, 7, (PieceType::Rook, PieceColor::Black)),
+            black_queen_side: black_king && is_on(0, 0, (PieceType::Rook, PieceColor::Black)),
+            ..Default::default()
+        }
+    }
+
+    /// The castling field of a FEN string of the standard starting position, king side first
+    /// (`KQkq` or `-`), the rooks off the corners are named by their file
+    pub fn to_fen(&self) -> String {
+        self.to_fen_on_board(&init_board())
+    }
+
+    /// The castling field of a FEN string whose pieces are on `placement`, seen from white
+    /// The outermost rooks are written `KQkq`, a rook with another one further out is named
+    /// by its file like in Shredder-FEN
+    pub fn to_fen_on_board(&self, placement: &Board) -> String {
+        let mut field = String::new();
+        for (color, back_rank) in [(PieceColor::White, 7), (PieceColor::Black, 0)] {
+            for king_side in [true, false] {
+                if !self.is_allowed(color, king_side) {
+                    continue;
+                }
+                let file = self.rook_file(color, king_side);
+                let outer_files = if king_side { file + 1..8 } else { 0..file };
+                let is_outermost = !outer_files.into_iter().any(|col| {
+                    placement[back_rank][col as usize] == Some((PieceType::Rook, color))
+                });
+                let letter = match (is_outermost, king_side) {
+                    (true, true) => 'k',
+                    (true, false) => 'q',
+                    (false, _) => (b'a' + file) as char,
+                };
+                field.push(match color {
+                    PieceColor::White => letter.to_ascii_uppercase(),
+                    PieceColor::Black => letter,
+                });
+            }
+        }
+        if field.is_empty() {
+            "-".to_string()
+        } else {
+            field
+        }
+    }
+}
+
+// The index of the player in the files of the castling rooks
+fn color_index(color: PieceColor) -> usize {
+    match color {
+        PieceColor::White => 0,
+        PieceColor::Black => 1,
+    }
+}
+
+/// The cell a castling king is written to go to, seen from white like `rook`: the g or c file
+/// like engines expect when castling from the standard starting cells, the cell of the rook
+/// otherwise, as Chess960 engines expect
+pub fn castling_king_target(king: Coord, rook: Coord) -> Coord {
+    match (king.col, rook.col) {
+        (4, 7) => Coord::new(rook.row, 6),
+        (4, 0) => Coord::new(rook.row, 2),
+        _ => rook,
+    }
+}
+
+/// Everything that makes two positions the same one when counting repetitions
+/// Move counters are left out, as is an en passant square no pawn can take on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionKey {
+    /// The pieces, seen from white
+    pub placement: Board,
+    pub side_to_move: PieceColor,
+    /// The castles still available, whether or not they can be played right now
+    pub castling_rights: CastlingRights,
+    /// The file (0 for a) of the pawn that can be taken en passant
+    pub en_passant_file: Option<u8>,
+}
+
 /// ## visual representation
 ///
 /// ### how it's stored:
@@ -51,6 +304,12 @@ pub struct GameBoard {
     pub white_taken_pieces: Vec<PieceType>,
     // The black piece that got taken
     pub black_taken_pieces: Vec<PieceType>,
+    // The castles allowed by the starting position
+    pub castling_rights: CastlingRights,
+    // The en passant target square of the starting position, only used before the first move
+    pub en_passant_target: Option<Coord>,
+    // The player to move in the starting position
+    pub first_player: PieceColor,
 }
 
 impl Default for GameBoard {
@@ -62,6 +321,9 @@ impl Default for GameBoard {
             consecutive_non_pawn_or_capture: 0,
             white_taken_pieces: vec![],
             black_taken_pieces: vec![],
+            castling_rights: CastlingRights::default(),
+            en_passant_target: None,
+            first_player: PieceColor::White,
         }
     }
 }
@@ -75,9 +337,223 @@ impl GameBoard {
             consecutive_non_pawn_or_capture: 0,
             white_taken_pieces: vec![],
             black_taken_pieces: vec![],
+            castling_rights: CastlingRights::default(),
+            en_passant_target: None,
+            first_player: PieceColor::White,
         }
     }
 
+    /// Build a board from a FEN string, returning it along with the player to move
+    /// The board is stored from the point of view of the player to move, like during a game
+    /// A FEN holding only the piece placement is completed by [`complete_fen`]
+    pub fn from_fen(fen: &str) -> Result<(Self, PieceColor), ChessError> {
+        let fen = complete_fen(fen);
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() < 2 {
+            return Err(ChessError::InvalidFen(format!(
+                "expected at least the piece placement and the side to move, found {} field(s)",
+                fields.len()
+            )));
+        }
+
+        let mut board: Board = [[None; 8]; 8];
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(ChessError::InvalidFen(format!(
+                "expected 8 ranks, found {}",
+                ranks.len()
+            )));
+        }
+        for (row, rank) in ranks.iter().enumerate() {
+            let mut col = 0usize;
+            for c in rank.chars() {
+                if let Some(empty_cells) = c.to_digit(10) {
+                    if !(1..=8).contains(&empty_cells) {
+                        return Err(ChessError::InvalidFen(format!(
+                            "invalid empty cells count '{c}'"
+                        )));
+                    }
+                    col += empty_cells as usize;
+                } else {
+                    let Some(piece) = PieceType::piece_from_fen_char(c) else {
+                        return Err(ChessError::InvalidFen(format!("unknown piece '{c}'")));
+                    };
+                    if col < 8 {
+                        board[row][col] = Some(piece);
+                    }
+                    col += 1;
+                }
+            }
+            if col != 8 {
+                return Err(ChessError::InvalidFen(format!(
+                    "rank {} does not describe 8 cells",
+                    8 - row
+                )));
+            }
+        }
+
+        for color in [PieceColor::White, PieceColor::Black] {
+            let kings = board
+                .iter()
+                .flatten()
+                .filter(|cell| **cell == Some((PieceType::King, color)))
+                .count();
+            if kings != 1 {
+                return Err(ChessError::InvalidFen(format!(
+                    "expected one {color:?} king, found {kings}"
+                )));
+            }
+        }
+        if board[0]
+            .iter()
+            .chain(board[7].iter())
+            .any(|cell| matches!(cell, Some((PieceType::Pawn, _))))
+        {
+            return Err(ChessError::InvalidFen(
+                "pawns can't stand on the first or last rank".to_string(),
+            ));
+        }
+
+        let player_turn = match fields[1] {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            other => {
+                return Err(ChessError::InvalidFen(format!(
+                    "unknown side to move '{other}'"
+                )))
+            }
+        };
+
+        // Without castling availabilities every king and rook on its original cell can castle
+        let castling_rights = match fields.get(2) {
+            Some(field) => CastlingRights::from_fen_and_board(field, &board)?,
+            None => CastlingRights::from_board(&board),
+        };
+
+        let en_passant_target = match fields.get(3).copied().unwrap_or("-") {
+            "-" => None,
+            square => {
+                let mut chars = square.chars();
+                let (file, rank) = (chars.next(), chars.next());
+                let expected_rank = if player_turn == PieceColor::White {
+                    '6'
+                } else {
+                    '3'
+                };
+                if chars.next().is_some()
+                    || !matches!(file, Some('a'..='h'))
+                    || rank != Some(expected_rank)
+                {
+                    return Err(ChessError::InvalidFen(format!(
+                        "invalid en passant square '{square}'"
+                    )));
+                }
+                Some(Coord::new(
+                    8 - (expected_rank as u8 - b'0'),
+                    letter_to_col(file) as u8,
+                ))
+            }
+        };
+
+        // Some sites only give the move number after the en passant square
+        // Missing counters default to a fresh position and trailing fields are ignored
+        let (halfmove_field, fullmove_field) = match fields.len() {
+            0..=4 => ("0", "1"),
+            5 => ("0", fields[4]),
+            _ => (fields[4], fields[5]),
+        };
+        let halfmove_clock: i32 = halfmove_field.parse().map_err(|_| {
+            ChessError::InvalidFen(format!("invalid halfmove clock '{halfmove_field}'"))
+        })?;
+        if halfmove_clock < 0 {
+            return Err(ChessError::InvalidFen(format!(
+                "invalid halfmove clock '{halfmove_field}'"
+            )));
+        }
+        match fullmove_field.parse::<u32>() {
+            Ok(fullmove_number) if fullmove_number >= 1 => {}
+            _ => {
+                return Err(ChessError::InvalidFen(format!(
+                    "invalid fullmove number '{fullmove_field}'"
+                )))
+            }
+        }
+
+        let mut game_board = GameBoard::new(board, vec![], vec![]);
+        game_board.castling_rights = castling_rights;
+        game_board.en_passant_target = en_passant_target;
+        game_board.first_player = player_turn;
+        game_board.set_consecutive_non_pawn_or_capture(halfmove_clock);
+        // The side to move is always rendered at the bottom of the board
+        if player_turn == PieceColor::Black {
+            game_board.flip_the_board();
+        }
+        game_board.board_history.push(game_board.board);
+
+        // The player who just moved can't have left his king in check
+        let mut opponent_view = game_board.clone();
+        opponent_view.flip_the_board();
+        if opponent_view.is_getting_checked(opponent_view.board, player_turn.opposite()) {
+            return Err(ChessError::InvalidFen(
+                "the side not to move is in check".to_string(),
+            ));
+        }
+
+        Ok((game_board, player_turn))
+    }
+
+    /// The moves of the game in UCI notation (`e2e4`, `e7e8q`), seen from white
+    /// Castles are written with the destination of the king (`e1g1`) like engines expect,
+    /// or as the king taking its rook (`b1a1`) in Chess960
+    pub fn uci_moves(&self) -> Vec<String> {
+        self.move_history
+            .iter()
+            .enumerate()
+            .map(|(i, piece_move)| {
+                // Every move is stored from the point of view of the player who played it
+                let (mut from, mut to) = (piece_move.from, piece_move.to);
+                if piece_move.piece_color == PieceColor::Black {
+                    (from, to) = (flip_coord(from), flip_coord(to));
+                }
+
+                // The board before the move was stored in the orientation of the previous player,
+                // who is the same one when a side moved twice in free play
+                let previous_player = match i {
+                    0 => self.first_player,
+                    _ => self.move_history[i - 1].piece_color,
+                };
+                let before_move = |cell: Coord| {
+                    let cell = if previous_player == piece_move.piece_color {
+                        cell
+                    } else {
+                        flip_coord(cell)
+                    };
+                    self.board_history.get(i).map(|board| board[&cell])
+                };
+                let is_castling = piece_move.piece_type == PieceType::King
+                    && before_move(piece_move.to)
+                        == Some(Some((PieceType::Rook, piece_move.piece_color)));
+                if is_castling {
+                    to = castling_king_target(from, to);
+                }
+                let piece_before_move = before_move(piece_move.from);
+                let promotion = match piece_before_move {
+                    Some(Some((PieceType::Pawn, _)))
+                        if piece_move.piece_type != PieceType::Pawn =>
+                    {
+                        PieceType::piece_to_fen_enum(
+                            Some(piece_move.piece_type),
+                            Some(PieceColor::Black),
+                        )
+                    }
+                    _ => "",
+                };
+
+                format!("{}{}{promotion}", from.to_algebraic(), to.to_algebraic())
+            })
+            .collect()
+    }
+
     pub fn get_last_move_piece_type_as_string(&self) -> String {
         if let Some(last_move) = self.move_history.last() {
             match last_move.piece_type {
@@ -116,12 +592,9 @@ impl GameBoard {
         let piece_type_to = self.get_piece_type(to);
         let piece_color = self.get_piece_color(to);
         // We check if there is a piece and we are not doing a castle
-        if piece_color.is_some()
-            && piece_type_to.is_some()
-            && (piece_type_to != Some(PieceType::Rook) && piece_color != Some(player_turn))
-        {
-            if let Some(piece_type) = piece_type_to {
-                self.push_to_taken_piece(piece_type, piece_color.unwrap())
+        if let (Some(piece_type), Some(piece_color)) = (piece_type_to, piece_color) {
+            if piece_type != PieceType::Rook && piece_color != player_turn {
+                self.push_to_taken_piece(piece_type, piece_color)
             }
         }
     }
@@ -145,6 +618,7 @@ impl GameBoard {
         self.board_history.clear();
         self.board_history.push(init_board());
         self.consecutive_non_pawn_or_capture = 0;
+        self.first_player = PieceColor::White;
     }
 
     // Method to get the authorized positions for a piece
@@ -162,12 +636,17 @@ impl GameBoard {
                 return vec![];
             }
 
-            piece_type.authorized_positions(
-                &coordinates,
-                piece_color,
-                self,
-                self.is_getting_checked(self.board, player_turn),
-            )
+            piece_type
+                .authorized_positions(
+                    &coordinates,
+                    piece_color,
+                    self,
+                    self.is_getting_checked(self.board, player_turn),
+                )
+                .into_iter()
+                // A king is never taken, a position where it can be is already lost for its side
+                .filter(|cell| self.get_piece_type(cell) != Some(PieceType::King))
+                .collect()
         } else {
             vec![]
         }
@@ -184,6 +663,7 @@ impl GameBoard {
             }
         }
         self.board = flipped_board;
+        self.en_passant_target = self.en_passant_target.map(flip_coord);
     }
 
     // Check if the latest move is en passant
@@ -204,17 +684,13 @@ impl GameBoard {
         }
     }
 
-    // Check if the latest move is castling
+    // Check if the latest move is castling, the king moving on the cell of one of its rooks
+    // A king going further than a cell can only be castling too
     pub fn is_latest_move_castling(&self, from: Coord, to: Coord) -> bool {
-        let piece_type_from = self.get_piece_type(&from);
-        let piece_type_to = self.get_piece_type(&to);
-
-        let from_x: i32 = from.col as i32;
-        let to_x: i32 = to.col as i32;
-        let distance = (from_x - to_x).abs();
-
-        match (piece_type_from, piece_type_to) {
-            (Some(PieceType::King), _) => distance > 1,
+        match self.board[&from] {
+            Some((PieceType::King, color)) => {
+                self.board[&to] == Some((PieceType::Rook, color)) || from.col.abs_diff(to.col) > 1
+            }
             _ => false,
         }
     }
@@ -234,30 +710,91 @@ impl GameBoard {
         false
     }
 
-    // Method to get the number of authorized positions for the current player (used for the end condition)
-    pub fn number_of_authorized_positions(&self, player_turn: PieceColor) -> usize {
-        let mut possible_moves: Vec<Coord> = vec![];
+    /// Every legal move of a player
+    /// The board has to be in the orientation of `player_turn`, which is always the case for the player to move
+    pub fn legal_moves(&self, player_turn: PieceColor) -> Vec<PieceMove> {
+        let mut legal_moves: Vec<PieceMove> = vec![];
 
         for i in 0..8 {
             for j in 0..8 {
-                let coord = Coord::new(i, j);
-                if let Some((_piece_type, piece_color)) = self.board[&coord] {
+                let from = Coord::new(i, j);
+                if let Some((piece_type, piece_color)) = self.board[&from] {
                     if piece_color == player_turn {
-                        possible_moves.extend(self.get_authorized_positions(player_turn, coord));
+                        legal_moves.extend(
+                            self.get_authorized_positions(player_turn, from)
+                                .into_iter()
+                                .map(|to| PieceMove {
+                                    piece_type,
+                                    piece_color,
+                                    from,
+                                    to,
+                                }),
+                        );
                     }
                 }
             }
         }
-        possible_moves.len()
+        legal_moves
     }
 
-    // Check if the game is checkmate
-    pub fn is_checkmate(&self, player_turn: PieceColor) -> bool {
-        if !self.is_getting_checked(self.board, player_turn) {
-            return false;
+    // Method to get the number of authorized positions for the current player (used for the end condition)
+    pub fn number_of_authorized_positions(&self, player_turn: PieceColor) -> usize {
+        self.legal_moves(player_turn).len()
+    }
+
+    /// Check if the king of `color` is attacked
+    /// The board has to be in the orientation of `color`, which is always the case for the player to move
+    pub fn is_check(&self, color: PieceColor) -> bool {
+        self.is_getting_checked(self.board, color)
+    }
+
+    /// The cells of every king getting checked, whichever side is to move
+    /// `bottom_color` is the side playing from the bottom of the board in its current orientation
+    pub fn checked_kings(&self, bottom_color: PieceColor) -> Vec<Coord> {
+        let mut checked_kings = vec![];
+        if self.is_check(bottom_color) {
+            checked_kings.push(self.get_king_coordinates(self.board, bottom_color));
+        }
+        // The attacks are computed with the king at the bottom, so the other side is checked on a flipped board
+        let top_color = bottom_color.opposite();
+        let mut flipped = self.clone();
+        flipped.flip_the_board();
+        if flipped.is_check(top_color) {
+            checked_kings.push(flip_coord(
+                flipped.get_king_coordinates(flipped.board, top_color),
+            ));
         }
+        checked_kings
+    }
 
-        self.number_of_authorized_positions(player_turn) == 0
+    /// Check if the game is checkmate: the king is attacked and no move can save it
+    pub fn is_checkmate(&self, player_turn: PieceColor) -> bool {
+        self.is_check(player_turn) && self.number_of_authorized_positions(player_turn) == 0
+    }
+
+    /// The `+` or `#` suffix of a move of the history when it checked or mated the opponent
+    pub fn check_suffix(&self, move_index: usize) -> &'static str {
+        let (Some(played_move), Some(board)) = (
+            self.move_history.get(move_index),
+            self.board_history.get(move_index + 1),
+        ) else {
+            return "";
+        };
+
+        // The position after the move, seen from the opponent
+        let mut position =
+            GameBoard::new(*board, self.move_history[..=move_index].to_vec(), vec![]);
+        position.castling_rights = self.castling_rights;
+        position.flip_the_board();
+
+        let opponent = played_move.piece_color.opposite();
+        if position.is_checkmate(opponent) {
+            "#"
+        } else if position.is_check(opponent) {
+            "+"
+        } else {
+            ""
+        }
     }
 
     // Check if the game is a draw
@@ -270,9 +807,11 @@ impl GameBoard {
         }
 
         // Index mapping
-        let mut position_counts = std::collections::HashMap::new();
-        for board in self.board_history.iter() {
-            let count = position_counts.entry(board).or_insert(0);
+        let mut position_counts = HashMap::new();
+        for index in 0..self.board_history.len() {
+            let count = position_counts
+                .entry(self.position_key_at(index))
+                .or_insert(0);
             *count += 1;
 
             if *count >= 3 {
@@ -283,6 +822,132 @@ impl GameBoard {
         false
     }
 
+    /// The player to move: the opponent of the last mover, or the first player before any move
+    pub fn side_to_move(&self) -> PieceColor {
+        self.move_history
+            .last()
+            .map_or(self.first_player, |last_move| {
+                last_move.piece_color.opposite()
+            })
+    }
+
+    // The color at the bottom of the stored board, the board is flipped once a move is complete
+    fn orientation(&self) -> PieceColor {
+        match self.move_history.last() {
+            Some(last_move) if self.board_history.last() == Some(&self.board) => {
+                last_move.piece_color
+            }
+            _ => self.side_to_move(),
+        }
+    }
+
+    /// The identity of the current position for repetitions, whatever the orientation of the board
+    pub fn position_key(&self) -> PositionKey {
+        let side_to_move = self.side_to_move();
+        let orientation = self.orientation();
+        let to_white_view = |cell: Coord| match orientation {
+            PieceColor::White => cell,
+            PieceColor::Black => flip_coord(cell),
+        };
+
+        let mut placement: Board = [[None; 8]; 8];
+        for row in 0..8u8 {
+            for col in 0..8u8 {
+                let cell = to_white_view(Coord::new(row, col));
+                placement[cell.row as usize][cell.col as usize] =
+                    self.board[row as usize][col as usize];
+            }
+        }
+
+        // The pawn that just moved two cells only counts when a pawn stands next to it
+        let pushed_file = match self.move_history.last() {
+            Some(last_move) if Pawn::did_pawn_move_two_cells(Some(last_move)) => {
+                Some(match last_move.piece_color {
+                    PieceColor::White => last_move.to.col,
+                    PieceColor::Black => 7 - last_move.to.col,
+                })
+            }
+            Some(_) => None,
+            None => self.en_passant_target.map(|cell| to_white_view(cell).col),
+        };
+        let pushed_row = match side_to_move {
+            PieceColor::White => 3,
+            PieceColor::Black => 4,
+        };
+        let en_passant_file = pushed_file.filter(|&file| {
+            [
+                file.checked_sub(1),
+                file.checked_add(1).filter(|&col| col < 8),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|col| placement[pushed_row][col as usize] == Some((PieceType::Pawn, side_to_move)))
+        });
+
+        PositionKey {
+            placement,
+            side_to_move,
+            castling_rights: self.castling_availability(&placement),
+            en_passant_file,
+        }
+    }
+
+    // The key of the position stored at `index` in the board history
+    fn position_key_at(&self, index: usize) -> PositionKey {
+        // Both histories end with the current position
+        let moves = self
+            .move_history
+            .len()
+            .saturating_sub(self.board_history.len() - 1 - index);
+        let mut position = self.clone();
+        position.board = self.board_history[index];
+        position.move_history.truncate(moves);
+        position.board_history.truncate(index + 1);
+        // The en passant target of the start follows the flips of the board
+        if self.orientation() != position.orientation() {
+            position.en_passant_target = position.en_passant_target.map(flip_coord);
+        }
+        position.position_key()
+    }
+
+    /// The castles still available given the moves played, `placement` is the board seen from white
+    pub fn castling_availability(&self, placement: &Board) -> CastlingRights {
+        let mut availability = CastlingRights::none();
+        for (color, back_rank) in [(PieceColor::White, 7), (PieceColor::Black, 0)] {
+            let king_moved = self
+                .move_history
+                .iter()
+                .any(|entry| entry.piece_type == PieceType::King && entry.piece_color == color);
+            let king_file = (0..8u8).find(|&file| {
+                placement[back_rank][file as usize] == Some((PieceType::King, color))
+            });
+            let Some(king_file) = king_file.filter(|_| !king_moved) else {
+                continue;
+            };
+            for king_side in [true, false] {
+                let file = self.castling_rights.rook_file(color, king_side);
+                // The moves are stored in the orientation of their player, where the files of black are mirrored
+                let rook_col = match color {
+                    PieceColor::White => file,
+                    PieceColor::Black => 7 - file,
+                };
+                let rook_moved = self.did_piece_already_move((
+                    Some(PieceType::Rook),
+                    Some(color),
+                    Coord::new(7, rook_col),
+                ));
+                if self.castling_rights.is_allowed(color, king_side)
+                    && !rook_moved
+                    && (file > king_file) == king_side
+                    && placement[back_rank][file as usize] == Some((PieceType::Rook, color))
+                {
+                    availability.allow(color, king_side, file);
+                }
+            }
+        }
+        availability
+    }
+
     // Check if the game is a draw
     pub fn is_draw(&mut self, player_turn: PieceColor) -> bool {
         self.number_of_authorized_positions(player_turn) == 0
@@ -298,28 +963,82 @@ impl GameBoard {
         self.consecutive_non_pawn_or_capture
     }
 
-    /// We get all the cells that are getting put in 'check'
-    pub fn get_all_protected_cells(&self, player_turn: PieceColor) -> Vec<Coord> {
-        let mut check_cells: Vec<Coord> = vec![];
+    /// Every cell controlled by the pieces of a color, whether it is empty or not
+    /// The pieces of `by` are expected at the top of the board, like the opponent of the player to move
+    pub fn attacked_squares(&self, by: PieceColor) -> HashSet<Coord> {
+        let mut attacked_cells: HashSet<Coord> = HashSet::new();
         for i in 0..8u8 {
             for j in 0..8u8 {
-                if self.get_piece_color(&Coord::new(i, j)) == Some(player_turn) {
+                let coordinates = Coord::new(i, j);
+                if self.get_piece_color(&coordinates) != Some(by) {
                     continue;
                 }
-                // get the current cell piece color and type protecting positions
-                if let Some(piece_color) = self.get_piece_color(&Coord::new(i, j)) {
-                    if let Some(piece_type) = self.get_piece_type(&Coord::new(i, j)) {
-                        check_cells.extend(PieceType::protected_positions(
-                            &Coord::new(i, j),
-                            piece_type,
-                            piece_color,
-                            self,
-                        ));
+                // get the current cell piece type protecting positions
+                if let Some(piece_type) = self.get_piece_type(&coordinates) {
+                    attacked_cells.extend(PieceType::protected_positions(
+                        &coordinates,
+                        piece_type,
+                        by,
+                        self,
+                    ));
+                }
+            }
+        }
+        attacked_cells
+    }
+
+    /// The pieces of `color` the opponent wins by taking them, kings left aside:
+    /// attacked and either undefended or attacked by a cheaper piece
+    /// `color` is expected at the bottom of the board, its opponent at the top
+    pub fn hanging_pieces(&self, color: PieceColor) -> Vec<(Coord, PieceType)> {
+        let opponent = color.opposite();
+        // The cells defended by `color` are the ones it attacks once the board is turned around
+        let mut flipped = self.clone();
+        flipped.flip_the_board();
+        let defended: HashSet<Coord> = flipped
+            .attacked_squares(color)
+            .into_iter()
+            .map(flip_coord)
+            .collect();
+
+        // The value of the cheapest piece attacking each cell, a king only takes undefended pieces
+        let mut cheapest_attacker: HashMap<Coord, u32> = HashMap::new();
+        for (cell, piece_type) in self.pieces_of(opponent) {
+            let value = match piece_type {
+                PieceType::King => u32::MAX,
+                _ => piece_type.value(),
+            };
+            for target in PieceType::protected_positions(&cell, piece_type, opponent, self) {
+                let cheapest = cheapest_attacker.entry(target).or_insert(value);
+                *cheapest = (*cheapest).min(value);
+            }
+        }
+
+        self.pieces_of(color)
+            .into_iter()
+            .filter(|(cell, piece_type)| {
+                *piece_type != PieceType::King
+                    && cheapest_attacker.get(cell).is_some_and(|&attacker| {
+                        !defended.contains(cell) || attacker < piece_type.value()
+                    })
+            })
+            .collect()
+    }
+
+    // Every piece of a color with its cell
+    fn pieces_of(&self, color: PieceColor) -> Vec<(Coord, PieceType)> {
+        let mut pieces = vec![];
+        for i in 0..8u8 {
+            for j in 0..8u8 {
+                let cell = Coord::new(i, j);
+                if let Some((piece_type, piece_color)) = self.board[&cell] {
+                    if piece_color == color {
+                        pieces.push((cell, piece_type));
                     }
                 }
             }
         }
-        check_cells
+        pieces
     }
 
     /// Method returning the coordinates of the king of a certain color
@@ -344,16 +1063,12 @@ impl GameBoard {
 
         let fake_game_board = GameBoard {
             board,
-            move_history: self.move_history.clone(),
-            board_history: self.board_history.clone(),
-            consecutive_non_pawn_or_capture: self.consecutive_non_pawn_or_capture,
-            white_taken_pieces: self.white_taken_pieces.clone(),
-            black_taken_pieces: self.black_taken_pieces.clone(),
+            ..self.clone()
         };
 
-        let checked_cells = fake_game_board.get_all_protected_cells(player_turn);
-
-        checked_cells.contains(&coordinates)
+        fake_game_board
+            .attacked_squares(player_turn.opposite())
+            .contains(&coordinates)
     }
 
     /// Check if a piece already moved on the board
@@ -413,6 +1128,54 @@ impl GameBoard {
         self.board[coordinates].map(|(piece_type, _)| piece_type)
     }
 
+    /// The value of the pieces left on the board besides the pawns, for both players
+    pub fn non_pawn_material(&self) -> u32 {
+        self.board
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|(piece_type, _)| *piece_type != PieceType::Pawn)
+            .map(|(piece_type, _)| piece_type.value())
+            .sum()
+    }
+
+    /// The material balance in pawns from the point of view of `color`, positive when it is ahead
+    pub fn evaluate(&self, color: PieceColor) -> i32 {
+        self.board
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|(piece_type, piece_color)| {
+                let value = piece_type.value() as i32;
+                if *piece_color == color {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum()
+    }
+
+    /// An 8 line diagram of the board in the orientation it is stored in, for the logs
+    /// White pieces are uppercase, black pieces lowercase and empty cells are dots
+    pub fn to_ascii_art(&self) -> String {
+        self.board
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Some((piece_type, piece_color)) => {
+                            PieceType::piece_to_fen_enum(Some(*piece_type), Some(*piece_color))
+                        }
+                        None => ".",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     // Convert the history and game status to a FEN string
     pub fn fen_position(&mut self, is_bot_starting: bool, _player_turn: PieceColor) -> String {
         let mut result = String::new();
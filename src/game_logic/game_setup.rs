@@ -0,0 +1,155 @@
+use std::{str::FromStr, time::Duration};
+
+use super::{board::Board, coord::Coord};
+use crate::{
+    error::ChessError,
+    pieces::{PieceColor, PieceType},
+};
+
+/// Time given to each player, written `minutes+increment` like `5+3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Time on each clock when the game starts
+    pub initial: Duration,
+    /// Time added to the clock of a player after each of their moves
+    pub increment: Duration,
+}
+
+impl FromStr for TimeControl {
+    type Err = ChessError;
+
+    fn from_str(time_control: &str) -> Result<Self, Self::Err> {
+        let invalid = || ChessError::InvalidTimeControl(time_control.to_string());
+
+        let (minutes, increment) = time_control
+            .trim()
+            .split_once('+')
+            .unwrap_or((time_control.trim(), "0"));
+        let minutes: u64 = minutes.parse().map_err(|_| invalid())?;
+        let increment: u64 = increment.parse().map_err(|_| invalid())?;
+        if minutes == 0 {
+            return Err(invalid());
+        }
+
+        Ok(TimeControl {
+            initial: Duration::from_secs(minutes * 60),
+            increment: Duration::from_secs(increment),
+        })
+    }
+}
+
+/// Everything chosen before a game starts, kept to start a rematch with the same settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GameSetup {
+    /// Color of the local player, `None` when both players share the keyboard
+    pub player_color: Option<PieceColor>,
+    /// Play the other color in the next game
+    pub swap_colors_on_rematch: bool,
+    /// `None` for an untimed game
+    pub time_control: Option<TimeControl>,
+    /// Advantages given to the player against the bot
+    pub handicap: Handicap,
+}
+
+impl GameSetup {
+    /// The setup of the next game, the colors are swapped if configured
+    pub fn rematch(&self) -> Self {
+        let player_color = match self.player_color {
+            Some(color) if self.swap_colors_on_rematch => Some(color.opposite()),
+            player_color => player_color,
+        };
+        GameSetup {
+            player_color,
+            ..*self
+        }
+    }
+}
+
+/// A piece the bot starts the game without
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialOdds {
+    /// The f pawn
+    Pawn,
+    /// The queen side knight
+    Knight,
+    /// The queen side rook
+    Rook,
+    Queen,
+}
+
+impl MaterialOdds {
+    /// The cell of the removed piece on a starting board seen from white
+    pub fn cell(self, color: PieceColor) -> Coord {
+        let (back_rank, pawn_rank) = match color {
+            PieceColor::White => (7, 6),
+            PieceColor::Black => (0, 1),
+        };
+        match self {
+            MaterialOdds::Pawn => Coord::new(pawn_rank, 5),
+            MaterialOdds::Knight => Coord::new(back_rank, 1),
+            MaterialOdds::Rook => Coord::new(back_rank, 0),
+            MaterialOdds::Queen => Coord::new(back_rank, 3),
+        }
+    }
+
+    fn piece_type(self) -> PieceType {
+        match self {
+            MaterialOdds::Pawn => PieceType::Pawn,
+            MaterialOdds::Knight => PieceType::Knight,
+            MaterialOdds::Rook => PieceType::Rook,
+            MaterialOdds::Queen => PieceType::Queen,
+        }
+    }
+
+    /// Take the piece of `color` off a starting board seen from white
+    /// The board is left untouched unless the piece is found and both kings remain
+    pub fn remove_from(self, board: &mut Board, color: PieceColor) -> Result<(), ChessError> {
+        let cell = self.cell(color);
+        if board[&cell] != Some((self.piece_type(), color)) {
+            return Err(ChessError::InvalidHandicap(format!(
+                "no {self:?} to remove on {}",
+                cell.to_algebraic()
+            )));
+        }
+        let mut handicapped = *board;
+        handicapped[&cell] = None;
+        for king_color in [PieceColor::White, PieceColor::Black] {
+            if !handicapped
+                .iter()
+                .flatten()
+                .any(|cell| *cell == Some((PieceType::King, king_color)))
+            {
+                return Err(ChessError::InvalidHandicap(format!(
+                    "the {king_color:?} king is missing"
+                )));
+            }
+        }
+        *board = handicapped;
+        Ok(())
+    }
+}
+
+impl FromStr for MaterialOdds {
+    type Err = ChessError;
+
+    fn from_str(odds: &str) -> Result<Self, Self::Err> {
+        match odds.trim().to_ascii_lowercase().as_str() {
+            "pawn" => Ok(MaterialOdds::Pawn),
+            "knight" => Ok(MaterialOdds::Knight),
+            "rook" => Ok(MaterialOdds::Rook),
+            "queen" => Ok(MaterialOdds::Queen),
+            _ => Err(ChessError::InvalidHandicap(format!(
+                "{odds} is not one of pawn, knight, rook or queen"
+            ))),
+        }
+    }
+}
+
+/// Advantages given to a weaker player against the bot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Handicap {
+    /// Time taken off the clock of the bot in timed games
+    pub time_odds: Duration,
+    /// The piece the bot plays without
+    pub material_odds: Option<MaterialOdds>,
+}
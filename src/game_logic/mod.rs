@@ -1,5 +1,10 @@
 pub mod board;
+pub mod chess960;
+pub mod clock;
 pub mod coord;
+pub mod endgame;
 pub mod game;
 pub mod game_board;
+pub mod game_setup;
+pub mod puzzle;
 pub mod ui;
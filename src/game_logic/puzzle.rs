@@ -0,0 +1,84 @@
+use std::{fs, path::Path};
+
+use super::game::Game;
+use crate::error::ChessError;
+
+/// A position to solve by finding its best move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub fen: String,
+    /// The expected answer in UCI notation (`e2e4`, `e7e8q`)
+    pub best_move: String,
+}
+
+impl Puzzle {
+    /// Is this move, in UCI notation, the solution of the puzzle
+    pub fn is_solution(&self, uci: &str) -> bool {
+        self.best_move.eq_ignore_ascii_case(uci.trim())
+    }
+}
+
+/// The puzzles of a file, solved one after the other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleSet {
+    pub puzzles: Vec<Puzzle>,
+    /// Index of the puzzle being solved
+    pub current: usize,
+}
+
+impl PuzzleSet {
+    /// Read the puzzles from their text form
+    /// Every line holds a FEN followed by the best move, empty lines and `#` comments are skipped
+    /// The positions are checked so a broken line is reported before the first puzzle starts
+    pub fn parse(content: &str) -> Result<Self, ChessError> {
+        let mut puzzles = vec![];
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((fen, best_move)) = line.rsplit_once(char::is_whitespace) else {
+                return Err(ChessError::InvalidPuzzle(format!(
+                    "line {} has no best move",
+                    i + 1
+                )));
+            };
+            let fen = fen.trim();
+            Game::from_fen(fen)
+                .map_err(|e| ChessError::InvalidPuzzle(format!("line {}: {e}", i + 1)))?;
+            puzzles.push(Puzzle {
+                fen: fen.to_string(),
+                best_move: best_move.to_string(),
+            });
+        }
+        if puzzles.is_empty() {
+            return Err(ChessError::InvalidPuzzle("no puzzle found".to_string()));
+        }
+        Ok(Self {
+            puzzles,
+            current: 0,
+        })
+    }
+
+    /// Read the puzzles of a file
+    pub fn load(path: &Path) -> Result<Self, ChessError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ChessError::InvalidPuzzle(format!("{}: {e}", path.display())))?;
+        Self::parse(&content)
+    }
+
+    /// The puzzle being solved
+    pub fn current(&self) -> &Puzzle {
+        &self.puzzles[self.current]
+    }
+
+    /// Move on to the next puzzle, returns false when they are all solved
+    pub fn advance(&mut self) -> bool {
+        if self.current + 1 < self.puzzles.len() {
+            self.current += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
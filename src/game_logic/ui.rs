@@ -1,18 +1,42 @@
-use super::{coord::Coord, game::Game};
+use super::{board::Board, coord::Coord, game::Game, game_board::GameBoard};
 use crate::{
-    constants::{DisplayMode, BLACK, UNDEFINED_POSITION, WHITE},
+    constants::{DisplayMode, Focus, MoveNotation, UNDEFINED_POSITION, WHITE},
+    engine::game_analysis::MoveQuality,
+    pgn::PlayerNames,
     pieces::{PieceColor, PieceType},
-    ui::{main_ui::render_cell, prompt::Prompt},
-    utils::{convert_position_into_notation, get_cell_paragraph, invert_position},
+    ui::{main_ui::render_cell, prompt::Prompt, theme::BoardTheme},
+    utils::{flip_coord, get_cell_paragraph},
 };
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Padding, Paragraph},
+    widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
 
+/// The cells a selected piece can reach, along with the position they were computed in
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorizedPositions {
+    pub selected: Coord,
+    pub player_turn: PieceColor,
+    pub board: Board,
+    pub moves_played: usize,
+    pub positions: Vec<Coord>,
+}
+
+/// How the board is drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoardRenderSettings {
+    /// Draw the board upside down compared to the way it is stored
+    pub flipped: bool,
+    pub theme: BoardTheme,
+    /// Mark every king in check, not only the one of the player to move
+    pub highlight_all_checks: bool,
+    /// Draw cells twice as wide as tall instead of filling the area
+    pub compact: bool,
+}
+
 #[derive(Clone)]
 pub struct UI {
     /// The cursor position
@@ -37,6 +61,30 @@ pub struct UI {
     pub display_mode: DisplayMode,
     // The prompt for the player
     pub prompt: Prompt,
+    /// The cells of the latest move, in the current orientation of the board
+    pub last_move_highlight: Option<(Coord, Coord)>,
+    /// The cell of the king getting checked, in the current orientation of the board
+    pub check_highlight: Option<Coord>,
+    /// The cells of the move suggested by the engine, in the current orientation of the board
+    pub hint_highlight: Option<(Coord, Coord)>,
+    /// Number of lines scrolled in the history panel
+    pub history_scroll: u16,
+    /// Number of lines scrolled in the panel of the pieces taken by white
+    pub white_material_scroll: u16,
+    /// Number of lines scrolled in the panel of the pieces taken by black
+    pub black_material_scroll: u16,
+    /// Shade every cell controlled by the opponent
+    pub show_threats: bool,
+    /// Tell how many legal moves the player to move has in the status bar
+    pub show_legal_move_count: bool,
+    /// The cell of the piece being dragged with the mouse
+    pub dragged_from: Option<Coord>,
+    /// The cell under the mouse while dragging a piece
+    pub drag_hover: Option<Coord>,
+    /// The side panels with their area from the latest render, to scroll the one under the mouse
+    pub panel_areas: Vec<(Focus, Rect)>,
+    /// The cells the selected piece can reach, until the selection or the position changes
+    pub authorized_positions_cache: Option<AuthorizedPositions>,
 }
 
 impl Default for UI {
@@ -54,6 +102,18 @@ impl Default for UI {
             mouse_used: false,
             display_mode: DisplayMode::DEFAULT,
             prompt: Prompt::new(),
+            last_move_highlight: None,
+            hint_highlight: None,
+            check_highlight: None,
+            history_scroll: 0,
+            white_material_scroll: 0,
+            black_material_scroll: 0,
+            show_threats: false,
+            show_legal_move_count: false,
+            dragged_from: None,
+            drag_hover: None,
+            panel_areas: vec![],
+            authorized_positions_cache: None,
         }
     }
 }
@@ -70,6 +130,59 @@ impl UI {
         self.width = 0;
         self.height = 0;
         self.mouse_used = false;
+        self.last_move_highlight = None;
+        self.check_highlight = None;
+        self.hint_highlight = None;
+        self.history_scroll = 0;
+        self.white_material_scroll = 0;
+        self.black_material_scroll = 0;
+        self.dragged_from = None;
+        self.drag_hover = None;
+        self.panel_areas.clear();
+        self.authorized_positions_cache = None;
+    }
+
+    /// The cells the selected piece can reach, they are only computed again
+    /// once another cell is selected or the position changed
+    pub fn authorized_positions(
+        &mut self,
+        game_board: &GameBoard,
+        player_turn: PieceColor,
+    ) -> Vec<Coord> {
+        let moves_played = game_board.move_history.len();
+        match &self.authorized_positions_cache {
+            Some(cache)
+                if cache.selected == self.selected_coordinates
+                    && cache.player_turn == player_turn
+                    && cache.moves_played == moves_played
+                    && cache.board == game_board.board =>
+            {
+                cache.positions.clone()
+            }
+            _ => {
+                let positions =
+                    game_board.get_authorized_positions(player_turn, self.selected_coordinates);
+                self.authorized_positions_cache = Some(AuthorizedPositions {
+                    selected: self.selected_coordinates,
+                    player_turn,
+                    board: game_board.board,
+                    moves_played,
+                    positions: positions.clone(),
+                });
+                positions
+            }
+        }
+    }
+
+    /// Move the highlighted cells along with the board when it flips
+    pub fn flip_highlights(&mut self) {
+        self.last_move_highlight = self
+            .last_move_highlight
+            .map(|(from, to)| (flip_coord(from), flip_coord(to)));
+        self.check_highlight = self.check_highlight.map(flip_coord);
+        self.hint_highlight = self
+            .hint_highlight
+            .map(|(from, to)| (flip_coord(from), flip_coord(to)));
     }
 
     /// Check if a cell has been selected
@@ -161,6 +274,16 @@ impl UI {
         self.promotion_cursor = (self.promotion_cursor + 1) % 4;
     }
 
+    /// Show or hide the cells controlled by the opponent
+    pub fn toggle_threats(&mut self) {
+        self.show_threats = !self.show_threats;
+    }
+
+    /// Show or hide the number of legal moves of the player to move
+    pub fn toggle_legal_move_count(&mut self) {
+        self.show_legal_move_count = !self.show_legal_move_count;
+    }
+
     /// Method to unselect a cell
     pub fn unselect_cell(&mut self) {
         if self.is_cell_selected() {
@@ -170,64 +293,70 @@ impl UI {
         }
     }
 
-    /// Method to render the right panel history
-    pub fn history_render(&self, area: Rect, frame: &mut Frame, game: &Game) {
+    /// Method to render the right panel history, titled with the names of the players when known
+    pub fn history_render(
+        &self,
+        area: Rect,
+        frame: &mut Frame,
+        game: &Game,
+        players: &PlayerNames,
+        notation: MoveNotation,
+        focused: bool,
+    ) {
+        let title = if players.white.is_some() || players.black.is_some() {
+            format!(
+                "{} vs {}",
+                players.name_or_unknown(PieceColor::White),
+                players.name_or_unknown(PieceColor::Black)
+            )
+        } else {
+            "History".to_string()
+        };
         // We write the history board on the side
         let history_block = Block::default()
-            .title("History")
+            .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(WHITE))
+            .border_style(panel_border_style(focused))
             .border_type(BorderType::Rounded)
             .padding(Padding::new(5, 10, 1, 2));
 
         let mut lines: Vec<Line> = vec![];
+        // The marks of the analysis of the game follow the moves
+        let qualities = game.move_qualities();
+        let mark = |i: usize| match qualities.get(i).copied().flatten() {
+            Some(quality) => Span::styled(quality.symbol(), quality_style(quality)),
+            None => Span::raw(""),
+        };
 
+        // Only the moves in view are written out, the others are blank lines to scroll past
+        let first_shown = 2 * self.history_scroll as usize;
+        let shown = first_shown..first_shown + 2 * area.height as usize;
         for i in (0..game.game_board.move_history.len()).step_by(2) {
-            let piece_type_from = game.game_board.move_history[i].piece_type;
-
-            let utf_icon_white =
-                PieceType::piece_to_utf_enum(&piece_type_from, Some(PieceColor::White));
-            let move_white = convert_position_into_notation(&format!(
-                "{}{}{}{}",
-                game.game_board.move_history[i].from.row,
-                game.game_board.move_history[i].from.col,
-                game.game_board.move_history[i].to.row,
-                game.game_board.move_history[i].to.col
-            ));
-
-            let mut utf_icon_black = "   ";
-            let mut move_black: String = "   ".to_string();
-
+            if !shown.contains(&i) {
+                lines.push(Line::default());
+                continue;
+            }
+            let mut move_black = String::new();
+            let mut mark_black = Span::raw("");
             // If there is something for black
             if i + 1 < game.game_board.move_history.len() {
-                let piece_type_to = game.game_board.move_history[i + 1].piece_type;
-                let black_move = &game.game_board.move_history[i + 1];
-
-                // Invert black moves if not playing against bot
-                let (from, to) = (
-                    invert_position(&black_move.from),
-                    invert_position(&black_move.to),
-                );
-
-                move_black = convert_position_into_notation(&format!(
-                    "{}{}{}{}",
-                    from.row, from.col, to.row, to.col
-                ));
-                utf_icon_black =
-                    PieceType::piece_to_utf_enum(&piece_type_to, Some(PieceColor::Black));
+                move_black = game.notated_move(i + 1, notation);
+                mark_black = mark(i + 1);
             }
 
             lines.push(Line::from(vec![
-                Span::raw(format!("{}.  ", i / 2 + 1)), // line number
-                Span::styled(format!("{utf_icon_white} "), Style::default().fg(WHITE)), // white symbol
-                Span::raw(move_white.to_string()), // white move
-                Span::raw("     "),                // separator
-                Span::styled(format!("{utf_icon_black} "), Style::default().fg(WHITE)), // black symbol
-                Span::raw(move_black.to_string()), // black move
+                Span::raw(format!("{}. ", i / 2 + 1)), // line number
+                Span::raw(format!("{:<7}", game.notated_move(i, notation))), // white move
+                mark(i),
+                Span::raw("  "),                       // separator
+                Span::raw(format!("{move_black:<7}")), // black move
+                mark_black,
             ]));
         }
 
-        let history_paragraph = Paragraph::new(lines).alignment(Alignment::Center);
+        let history_paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .scroll((self.history_scroll, 0));
 
         let height = area.height;
 
@@ -243,18 +372,32 @@ impl UI {
         );
     }
 
+    /// Method to render the status bar below the board
+    pub fn status_bar_render(&self, area: Rect, frame: &mut Frame, message: String) {
+        let status_paragraph = Paragraph::new(Line::from(message))
+            .alignment(Alignment::Center)
+            .add_modifier(Modifier::BOLD);
+        frame.render_widget(status_paragraph, area);
+    }
+
     /// Method to render the white material
     pub fn white_material_render(
         &self,
         area: Rect,
         frame: &mut Frame,
         white_taken_pieces: &[PieceType],
+        focused: bool,
+        clock: Option<Line<'static>>,
     ) {
-        let white_block = Block::default()
+        let mut white_block = Block::default()
             .title("White material")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(WHITE))
+            .border_style(panel_border_style(focused))
             .border_type(BorderType::Rounded);
+        // The clock of the player is displayed on the right of the title
+        if let Some(clock) = clock {
+            white_block = white_block.title_top(clock.right_aligned());
+        }
 
         let mut pieces: String = String::new();
 
@@ -265,7 +408,9 @@ impl UI {
         }
         let white_material_paragraph = Paragraph::new(pieces)
             .alignment(Alignment::Center)
-            .add_modifier(Modifier::BOLD);
+            .add_modifier(Modifier::BOLD)
+            .wrap(Wrap { trim: true })
+            .scroll((self.white_material_scroll, 0));
 
         let height = area.height;
 
@@ -293,12 +438,18 @@ impl UI {
         area: Rect,
         frame: &mut Frame,
         black_taken_pieces: &Vec<PieceType>,
+        focused: bool,
+        clock: Option<Line<'static>>,
     ) {
-        let black_block = Block::default()
+        let mut black_block = Block::default()
             .title("Black material")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(WHITE))
+            .border_style(panel_border_style(focused))
             .border_type(BorderType::Rounded);
+        // The clock of the player is displayed on the right of the title
+        if let Some(clock) = clock {
+            black_block = black_block.title_top(clock.right_aligned());
+        }
 
         let mut pieces: String = String::new();
 
@@ -310,7 +461,9 @@ impl UI {
 
         let black_material_paragraph = Paragraph::new(pieces)
             .alignment(Alignment::Center)
-            .add_modifier(Modifier::BOLD);
+            .add_modifier(Modifier::BOLD)
+            .wrap(Wrap { trim: true })
+            .scroll((self.black_material_scroll, 0));
 
         let height = area.height;
 
@@ -327,9 +480,21 @@ impl UI {
     }
 
     /// Method to render the board
-    pub fn board_render(&mut self, area: Rect, frame: &mut Frame<'_>, game: &Game) {
-        let width = area.width / 8;
-        let height = area.height / 8;
+    pub fn board_render(
+        &mut self,
+        area: Rect,
+        frame: &mut Frame<'_>,
+        game: &Game,
+        settings: &BoardRenderSettings,
+    ) {
+        let BoardRenderSettings {
+            flipped,
+            ref theme,
+            highlight_all_checks,
+            compact,
+        } = *settings;
+        let (width, height) = cell_size(area, compact);
+        let threatened_cells = game.threat_overlay();
         let border_height = area.height / 2 - (4 * height);
         let border_width = area.width / 2 - (4 * width);
 
@@ -359,8 +524,24 @@ impl UI {
             )
             .split(area);
 
+        // Kings in check are marked even when their side is not the one to move
+        let checked_kings = if highlight_all_checks {
+            game.game_board.checked_kings(game.board_orientation())
+        } else {
+            vec![]
+        };
+
+        // The available moves of the selected piece, only drawn when it is the turn of its player
+        let positions: Vec<Coord> = if self.is_cell_selected()
+            && game.game_board.get_piece_color(&self.selected_coordinates) == Some(game.player_turn)
+        {
+            self.authorized_positions(&game.game_board, game.player_turn)
+        } else {
+            vec![]
+        };
+
         // For each line we set 8 layout
-        for i in 0..8u8 {
+        for display_row in 0..8u8 {
             let lines = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(
@@ -378,93 +559,94 @@ impl UI {
                     ]
                     .as_ref(),
                 )
-                .split(columns[i as usize + 1]);
-            for j in 0..8u8 {
+                .split(columns[display_row as usize + 1]);
+            for display_col in 0..8u8 {
+                let Coord { row: i, col: j } = if flipped {
+                    flip_coord(Coord::new(display_row, display_col))
+                } else {
+                    Coord::new(display_row, display_col)
+                };
                 // Color of the cell to draw the board
-                let cell_color: Color = if (i + j) % 2 == 0 { WHITE } else { BLACK };
-
-                let last_move;
-                let mut last_move_from = Coord::undefined();
-                let mut last_move_to = Coord::undefined();
-                if !game.game_board.move_history.is_empty() {
-                    last_move = game.game_board.move_history.last();
+                let is_light_cell = (i + j) % 2 == 0;
+                let cell_color: Color = if is_light_cell {
+                    theme.light_cell
+                } else {
+                    theme.dark_cell
+                };
 
-                    last_move_from = invert_position(&last_move.map(|m| m.from).unwrap());
-                    last_move_to = invert_position(&last_move.map(|m| m.to).unwrap());
-                }
+                let (last_move_from, last_move_to) = self
+                    .last_move_highlight
+                    .unwrap_or((Coord::undefined(), Coord::undefined()));
 
-                let mut positions: Vec<Coord> = vec![];
                 let is_cell_in_positions = |positions: &Vec<Coord>, i: u8, j: u8| {
                     positions.iter().any(|&coord| coord == Coord::new(i, j))
                 };
-                // Draw the available moves for the selected piece
-                if self.is_cell_selected() {
-                    let selected_piece_color: Option<PieceColor> =
-                        game.game_board.get_piece_color(&self.selected_coordinates);
-                    // only draw available moves if it is the right players turn
-                    if match selected_piece_color {
-                        Some(color) => color == game.player_turn,
-                        None => false,
-                    } {
-                        positions = game
-                            .game_board
-                            .get_authorized_positions(game.player_turn, self.selected_coordinates);
-
-                        // Draw grey if the color is in the authorized positions
-                        for coords in positions.clone() {
-                            if i == coords.row && j == coords.col {
-                                // cell_color = Color::Rgb(100, 100, 100);
-                            }
-                        }
-                    }
-                }
 
-                let square = lines[j as usize + 1];
+                let square = lines[display_col as usize + 1];
                 // Here we have all the possibilities for a cell:
-                // - selected cell: green
-                // - cursor cell: blue
+                // - selected cell: the selected cell color of the theme
+                // - cursor cell or drop cell of a dragged piece: blue
                 // - available move cell: grey
                 // - checked king cell: magenta
+                // - cell of the move suggested by the engine: cyan
                 // - last move cell: green
+                // - cell controlled by the opponent (threats overlay): red
                 // - default cell: white or black
                 // Draw the cell blue if this is the current cursor cell
-                if i == self.cursor_coordinates.row
+                // or if a dragged piece can be dropped on it
+                if (i == self.cursor_coordinates.row
                     && j == self.cursor_coordinates.col
-                    && !self.mouse_used
+                    && !self.mouse_used)
+                    || (self.drag_hover == Some(Coord::new(i, j))
+                        && is_cell_in_positions(&positions, i, j))
                 {
                     render_cell(frame, square, Color::LightBlue, None);
                 }
+                // Keep the cell of the selected piece lit while the cursor visits its targets
+                else if i == self.selected_coordinates.row && j == self.selected_coordinates.col {
+                    render_cell(frame, square, theme.selected_cell, None);
+                }
                 // Draw the cell magenta if the king is getting checked
-                else if game
-                    .game_board
-                    .is_getting_checked(game.game_board.board, game.player_turn)
-                    && Coord::new(i, j)
-                        == game
-                            .game_board
-                            .get_king_coordinates(game.game_board.board, game.player_turn)
+                else if self.check_highlight == Some(Coord::new(i, j))
+                    || checked_kings.contains(&Coord::new(i, j))
                 {
                     render_cell(frame, square, Color::Magenta, Some(Modifier::SLOW_BLINK));
                 }
-                // Draw the cell green if this is the selected cell or if the cell is part of the last move
-                else if (i == self.selected_coordinates.row && j == self.selected_coordinates.col)
-                    || (last_move_from == Coord::new(i, j) // If the last move from
-                        || (last_move_to == Coord::new(i, j) // If last move to
-                            && !is_cell_in_positions(&positions, i, j)))
+                // Draw the cells of the hint cyan while it lasts
+                else if self
+                    .hint_highlight
+                    .is_some_and(|(from, to)| from == Coord::new(i, j) || to == Coord::new(i, j))
+                {
+                    render_cell(frame, square, Color::LightCyan, None);
+                }
+                // Draw the cell green if the cell is part of the last move
+                else if last_move_from == Coord::new(i, j) // If the last move from
+                    || (last_move_to == Coord::new(i, j) // If last move to
+                        && !is_cell_in_positions(&positions, i, j))
                 // and not in the authorized positions (grey instead of green)
                 {
                     render_cell(frame, square, Color::LightGreen, None);
                 } else if is_cell_in_positions(&positions, i, j) {
                     render_cell(frame, square, Color::Rgb(100, 100, 100), None);
                 }
+                // Draw the cell red if the opponent controls it
+                else if threatened_cells.contains(&Coord::new(i, j)) {
+                    let threat_color = if is_light_cell {
+                        theme.light_threat
+                    } else {
+                        theme.dark_threat
+                    };
+                    render_cell(frame, square, threat_color, None);
+                }
                 // else as a last resort we draw the cell with the default color either white or black
                 else {
                     let mut cell = Block::default();
                     cell = match self.display_mode {
                         DisplayMode::DEFAULT => cell.bg(cell_color),
-                        DisplayMode::ASCII => match cell_color {
-                            WHITE => cell.bg(Color::White).fg(Color::Black),
-                            BLACK => cell.bg(Color::Black).fg(Color::White),
-                            _ => cell.bg(cell_color),
+                        // Plain colors keep the letters readable whatever the theme
+                        DisplayMode::ASCII => match is_light_cell {
+                            true => cell.bg(Color::White).fg(Color::Black),
+                            false => cell.bg(Color::Black).fg(Color::White),
                         },
                     };
                     frame.render_widget(cell.clone(), square);
@@ -472,10 +654,41 @@ impl UI {
 
                 // Get piece and color
                 let coord = Coord::new(i, j);
-                let paragraph = get_cell_paragraph(game, &coord, square);
+                let paragraph = get_cell_paragraph(game, &coord, square, compact);
 
                 frame.render_widget(paragraph, square);
             }
         }
     }
 }
+
+/// The width and height of a cell of the board drawn in `area`
+/// Compact cells are twice as wide as tall, which looks square in most terminals,
+/// the other ones stretch to fill the area
+pub fn cell_size(area: Rect, compact: bool) -> (u16, u16) {
+    if compact {
+        let height = (area.height / 8).min(area.width / 16);
+        (height * 2, height)
+    } else {
+        (area.width / 8, area.height / 8)
+    }
+}
+
+/// The border of a side panel is highlighted when the panel has the focus
+fn panel_border_style(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(Color::LightBlue)
+    } else {
+        Style::default().fg(WHITE)
+    }
+}
+
+/// The worse the move, the warmer the color of its mark
+fn quality_style(quality: MoveQuality) -> Style {
+    let color = match quality {
+        MoveQuality::Inaccuracy => Color::Yellow,
+        MoveQuality::Mistake => Color::LightRed,
+        MoveQuality::Blunder => Color::Red,
+    };
+    Style::default().fg(color).add_modifier(Modifier::BOLD)
+}
@@ -0,0 +1,343 @@
+use crate::{
+    game_logic::{board::Board, coord::Coord, game::Game},
+    pieces::{PieceColor, PieceType},
+    ui::theme::BoardTheme,
+    utils::flip_coord,
+};
+use ratatui::style::Color;
+
+/// Time each position stays on screen when the configuration does not set it
+pub const DEFAULT_FRAME_DELAY_MS: u32 = 1000;
+
+/// Pixels on each side of a piece drawing, every cell is `SCALE` times larger
+pub const SPRITE_SIZE: usize = 12;
+const SCALE: usize = 3;
+/// Pixels on each side of a cell
+pub const CELL_PIXELS: usize = SPRITE_SIZE * SCALE;
+
+// The indexes of the colors in the palette of the frames
+pub const LIGHT_CELL: u8 = 0;
+pub const DARK_CELL: u8 = 1;
+pub const WHITE_PIECE: u8 = 2;
+pub const BLACK_PIECE: u8 = 3;
+pub const OUTLINE: u8 = 4;
+pub const LIGHT_LAST_MOVE: u8 = 5;
+pub const DARK_LAST_MOVE: u8 = 6;
+
+/// The drawings of the pieces: `#` is the outline, `o` the color of the piece, `.` the cell
+const PAWN: [&str; SPRITE_SIZE] = [
+    "............",
+    "............",
+    ".....##.....",
+    "....#oo#....",
+    "....#oo#....",
+    ".....##.....",
+    "....#oo#....",
+    "...#oooo#...",
+    "...#oooo#...",
+    "..#oooooo#..",
+    "..########..",
+    "............",
+];
+const ROOK: [&str; SPRITE_SIZE] = [
+    "............",
+    "..##.##.##..",
+    "..#o#oo#o#..",
+    "..#oooooo#..",
+    "...#oooo#...",
+    "...#oooo#...",
+    "...#oooo#...",
+    "...#oooo#...",
+    "..#oooooo#..",
+    ".#oooooooo#.",
+    ".##########.",
+    "............",
+];
+const KNIGHT: [&str; SPRITE_SIZE] = [
+    "............",
+    ".....#.#....",
+    "....#o#o#...",
+    "...#ooooo#..",
+    "..#oo#oooo#.",
+    ".#oooooooo#.",
+    ".#oo##oooo#.",
+    "..##.#oooo#.",
+    "....#oooo#..",
+    "...#oooooo#.",
+    "..##########",
+    "............",
+];
+const BISHOP: [&str; SPRITE_SIZE] = [
+    "............",
+    ".....##.....",
+    "....#oo#....",
+    "...#o#oo#...",
+    "...#oo#o#...",
+    "...#oooo#...",
+    "....#oo#....",
+    ".....##.....",
+    "....#oo#....",
+    "..#oooooo#..",
+    "..########..",
+    "............",
+];
+const QUEEN: [&str; SPRITE_SIZE] = [
+    "............",
+    ".#...##...#.",
+    ".#o.#oo#.o#.",
+    ".#oo#oo#oo#.",
+    ".#oooooooo#.",
+    "..#oooooo#..",
+    "..#oooooo#..",
+    "...#oooo#...",
+    "...#oooo#...",
+    "..#oooooo#..",
+    ".##########.",
+    "............",
+];
+const KING: [&str; SPRITE_SIZE] = [
+    ".....##.....",
+    "....####....",
+    ".....##.....",
+    "..##.##.##..",
+    ".#oo#oo#oo#.",
+    ".#oooooooo#.",
+    ".#oooooooo#.",
+    "..#oooooo#..",
+    "...#oooo#...",
+    "...#oooo#...",
+    "..########..",
+    "............",
+];
+
+/// A picture of the board, each pixel is an index in the palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// The drawing of a piece, one string per line of pixels
+pub fn sprite(piece_type: PieceType) -> &'static [&'static str; SPRITE_SIZE] {
+    match piece_type {
+        PieceType::Pawn => &PAWN,
+        PieceType::Rook => &ROOK,
+        PieceType::Knight => &KNIGHT,
+        PieceType::Bishop => &BISHOP,
+        PieceType::Queen => &QUEEN,
+        PieceType::King => &KING,
+    }
+}
+
+/// The colors of the frames, taken from the cells of a board theme
+pub fn palette(theme: &BoardTheme) -> [[u8; 3]; 8] {
+    [
+        rgb(theme.light_cell),
+        rgb(theme.dark_cell),
+        [255, 255, 255],
+        [40, 40, 40],
+        [0, 0, 0],
+        [170, 210, 120],
+        [120, 170, 80],
+        [0, 0, 0],
+    ]
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(red, green, blue) => [red, green, blue],
+        Color::White => [255, 255, 255],
+        _ => [0, 0, 0],
+    }
+}
+
+/// One frame for every position of the game, from the start to the current one, seen from white
+pub fn board_frames(game: &Game) -> Vec<Frame> {
+    white_view_positions(game)
+        .iter()
+        .map(|(board, last_move)| render_position(board, *last_move))
+        .collect()
+}
+
+/// Every position of the game seen from white, along with the cells of the move leading to it
+pub fn white_view_positions(game: &Game) -> Vec<(Board, Option<(Coord, Coord)>)> {
+    let game_board = &game.game_board;
+    game_board
+        .board_history
+        .iter()
+        .enumerate()
+        .map(|(index, board)| {
+            // Each board is stored in the orientation of the player who just moved
+            let orientation = match index {
+                0 => game_board.first_player,
+                _ => game_board.move_history[index - 1].piece_color,
+            };
+            let to_white_view = |coord: Coord| match orientation {
+                PieceColor::White => coord,
+                PieceColor::Black => flip_coord(coord),
+            };
+            let last_move = index
+                .checked_sub(1)
+                .map(|last| &game_board.move_history[last])
+                .map(|last_move| (to_white_view(last_move.from), to_white_view(last_move.to)));
+            let mut white_view = [[None; 8]; 8];
+            for row in 0..8u8 {
+                for col in 0..8u8 {
+                    let coord = Coord::new(row, col);
+                    white_view[row as usize][col as usize] = board[&to_white_view(coord)];
+                }
+            }
+            (white_view, last_move)
+        })
+        .collect()
+}
+
+/// The index in the palette of the color of a cell, the cells of the latest move stand out
+pub fn cell_color(coord: Coord, last_move: Option<(Coord, Coord)>) -> u8 {
+    let is_light_cell = (coord.row + coord.col).is_multiple_of(2);
+    let in_last_move = last_move.is_some_and(|(from, to)| from == coord || to == coord);
+    match (is_light_cell, in_last_move) {
+        (true, false) => LIGHT_CELL,
+        (false, false) => DARK_CELL,
+        (true, true) => LIGHT_LAST_MOVE,
+        (false, true) => DARK_LAST_MOVE,
+    }
+}
+
+/// Draw a board seen from white, the cells of the latest move stand out
+pub fn render_position(board: &Board, last_move: Option<(Coord, Coord)>) -> Frame {
+    let size = 8 * CELL_PIXELS;
+    let mut pixels = vec![LIGHT_CELL; size * size];
+    for row in 0..8u8 {
+        for col in 0..8u8 {
+            let cell_color = cell_color(Coord::new(row, col), last_move);
+            let piece = board[row as usize][col as usize];
+            for y in 0..CELL_PIXELS {
+                for x in 0..CELL_PIXELS {
+                    let sprite_pixel = piece
+                        .map(|(piece_type, _)| sprite(piece_type)[y / SCALE].as_bytes()[x / SCALE]);
+                    let color = match (sprite_pixel, piece) {
+                        (Some(b'#'), _) => OUTLINE,
+                        (Some(b'o'), Some((_, PieceColor::White))) => WHITE_PIECE,
+                        (Some(b'o'), Some((_, PieceColor::Black))) => BLACK_PIECE,
+                        _ => cell_color,
+                    };
+                    let pixel_row = row as usize * CELL_PIXELS + y;
+                    let pixel_col = col as usize * CELL_PIXELS + x;
+                    pixels[pixel_row * size + pixel_col] = color;
+                }
+            }
+        }
+    }
+    Frame {
+        width: size,
+        height: size,
+        pixels,
+    }
+}
+
+/// Assemble the frames in an animated GIF looping forever, showing each one for `delay_ms`
+#[cfg(feature = "gif")]
+pub fn encode_gif(frames: &[Frame], palette: &[[u8; 3]; 8], delay_ms: u32) -> Vec<u8> {
+    let (width, height) = frames
+        .first()
+        .map_or((0, 0), |frame| (frame.width as u16, frame.height as u16));
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    // A global palette of 8 colors
+    gif.extend([0xF2, 0, 0]);
+    gif.extend(palette.iter().flatten());
+    // Loop forever
+    gif.extend(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    // The delay is counted in hundredths of a second
+    let delay = (delay_ms / 10).min(u32::from(u16::MAX)) as u16;
+    for frame in frames {
+        gif.extend([0x21, 0xF9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2C);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((frame.width as u16).to_le_bytes());
+        gif.extend((frame.height as u16).to_le_bytes());
+        gif.push(0x00);
+
+        gif.push(MIN_CODE_SIZE);
+        for block in lzw_encode(&frame.pixels).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0x00);
+    }
+    gif.push(0x3B);
+    gif
+}
+
+/// Bits of the smallest codes, enough for the 8 colors of the palette
+#[cfg(feature = "gif")]
+const MIN_CODE_SIZE: u8 = 3;
+
+// The pixels compressed with the variable length LZW of the GIF format
+#[cfg(feature = "gif")]
+fn lzw_encode(pixels: &[u8]) -> Vec<u8> {
+    use std::collections::HashMap;
+
+    const MAX_CODE: u16 = 4095;
+    let clear_code: u16 = 1 << MIN_CODE_SIZE;
+    let end_code = clear_code + 1;
+
+    let mut output = Vec::new();
+    let mut bit_buffer: u32 = 0;
+    let mut bit_count = 0;
+    let mut write_code = |code: u16, code_size: u8, output: &mut Vec<u8>| {
+        bit_buffer |= u32::from(code) << bit_count;
+        bit_count += code_size;
+        while bit_count >= 8 {
+            output.push(bit_buffer as u8);
+            bit_buffer >>= 8;
+            bit_count -= 8;
+        }
+    };
+
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end_code + 1;
+    let mut code_size = MIN_CODE_SIZE + 1;
+    write_code(clear_code, code_size, &mut output);
+
+    let Some((&first, rest)) = pixels.split_first() else {
+        write_code(end_code, code_size, &mut output);
+        write_code(0, 7, &mut output);
+        return output;
+    };
+    let mut prefix = u16::from(first);
+    for &pixel in rest {
+        if let Some(&code) = codes.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        write_code(prefix, code_size, &mut output);
+        if next_code <= MAX_CODE {
+            codes.insert((prefix, pixel), next_code);
+            next_code += 1;
+            // The decoder reads wider codes once the table outgrows the current size
+            if next_code > (1 << code_size) && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            // The table is full, it starts over
+            write_code(clear_code, code_size, &mut output);
+            codes.clear();
+            next_code = end_code + 1;
+            code_size = MIN_CODE_SIZE + 1;
+        }
+        prefix = u16::from(pixel);
+    }
+    write_code(prefix, code_size, &mut output);
+    write_code(end_code, code_size, &mut output);
+    // Pad the last byte
+    write_code(0, 7, &mut output);
+    output
+}
@@ -1,20 +1,63 @@
-use crate::constants::Popups;
+use crate::constants::{Focus, Popups};
+use crate::engine::Difficulty;
 use crate::game_logic::coord::Coord;
 use crate::game_logic::game::GameState;
+use crate::utils::flip_coord;
 use crate::{
     app::{App, AppResult},
     constants::Pages,
+    event::Event,
+    network::opponent::Opponent,
+    ui::toast::Toast,
 };
 use ratatui::crossterm::event::{
     KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 
+/// Handles every event of the main loop.
+pub fn handle_event(event: Event, app: &mut App) -> AppResult<()> {
+    match event {
+        Event::Tick => app.tick(),
+        Event::Key(key_event) => handle_key_events(key_event, app)?,
+        Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, app)?,
+        Event::Resize(_, _) => {}
+        Event::Paste(text) => app.paste_text(&text),
+        Event::EngineMove(piece_move) => {
+            if !app.apply_engine_move(piece_move) {
+                log::warn!("Ignored illegal engine move {piece_move:?}");
+            }
+        }
+        Event::EngineInfo(engine_info) => app.update_engine_info(engine_info),
+        Event::EnginePonder(ponder_move) => app.ponder_move = Some(ponder_move),
+        Event::EngineFailure(error) => app.apply_engine_failure(error),
+        Event::EngineBestMove(best_move) => {
+            if let Err(error) = app.apply_engine_best_move(&best_move) {
+                log::warn!("Ignored engine move: {error}");
+            }
+        }
+        Event::AnalysisLine(search, line) => app.update_analysis(search, line),
+        Event::EngineHint(fen, hint) => app.apply_hint(&fen, hint),
+        Event::PositionEvaluation(fen, evaluation) => app.apply_evaluation(&fen, evaluation),
+        Event::NetworkMove(piece_move) => {
+            if !app.apply_network_move(piece_move) {
+                log::warn!("Ignored illegal move from the opponent {piece_move:?}");
+            }
+        }
+        Event::NetworkChat(message) => app.show_toast(Toast::info(message)),
+    }
+    Ok(())
+}
+
 /// Handles the key events and updates the state of [`App`].
 pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
     if key_event.kind != KeyEventKind::Press {
         // crossterm on Windows sends Release and Repeat events as well, which we ignore.
         return Ok(());
     }
+    // The key press only wakes the home page up from its idle animation
+    if app.idle.reset() {
+        return Ok(());
+    }
     if app.game.ui.mouse_used {
         app.game.ui.mouse_used = false;
         if app.game.ui.selected_coordinates != Coord::undefined() {
@@ -26,78 +69,393 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
         }
     }
 
+    // Only leaving is possible while connecting to an online game
+    if app.handshake.is_some() {
+        match key_event.code {
+            KeyCode::Esc => app.cancel_handshake(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The game waits while the connection to the opponent comes back
+    if app.opponent.as_ref().is_some_and(Opponent::is_reconnecting) {
+        match key_event.code {
+            KeyCode::Esc => app.give_up_reconnecting(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The board only browses the history during a review
+    if app.review_index.is_some() {
+        match key_event.code {
+            KeyCode::Char(digit) if digit.is_ascii_digit() => app.review_type_digit(digit),
+            KeyCode::Backspace => app.review_delete_digit(),
+            KeyCode::Enter => app.review_jump(),
+            KeyCode::Left | KeyCode::Char('h') => app.review_step_back(),
+            KeyCode::Right | KeyCode::Char('l') => app.review_step_forward(),
+            KeyCode::Char(']') => app.enter_sideline(),
+            KeyCode::Char('[') => app.leave_sideline(),
+            KeyCode::Esc | KeyCode::Char('v') => app.toggle_review(),
+            KeyCode::Char('a') => app.analyze_reviewed_position(),
+            KeyCode::Char('e') => app.toggle_analysis_panel(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The opponent has to answer a draw offer before anything else
+    if app.current_popup == Some(Popups::DrawOffer) {
+        match key_event.code {
+            KeyCode::Char('y' | 'Y') => app.accept_draw(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => app.decline_draw(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Keys are typed in the move prompt until the move is played or the prompt is closed
+    if app.current_popup == Some(Popups::MoveInput) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_move_input(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // A game of the PGN file is chosen with the arrows
+    if app.current_popup == Some(Popups::GameList) {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => app.game_list_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.game_list_down(),
+            KeyCode::Enter => app.select_pgn_game(),
+            KeyCode::Esc => app.current_popup = None,
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The arrows scroll the log viewer while it is open
+    if app.current_popup == Some(Popups::LogViewer) {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => app.log_scroll_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.log_scroll_down(),
+            KeyCode::Esc | KeyCode::Char('L') => app.toggle_log_viewer(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The arrows scroll the engine console while it is open
+    if app.current_popup == Some(Popups::EngineConsole) {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => app.engine_console_scroll_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.engine_console_scroll_down(),
+            KeyCode::Esc | KeyCode::Char('E') => app.toggle_engine_console(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The FEN of the position to analyse is typed in the prompt
+    if app.current_popup == Some(Popups::FenInput) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_analysis_input(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The FEN of the position to set up is typed or pasted in the prompt
+    if app.current_popup == Some(Popups::PositionSetup) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_position_setup(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The code of the online game to join is typed in the prompt
+    if app.current_popup == Some(Popups::GameCodeInput) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_game_code_input(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The path of the PGN file to replay is typed in the prompt
+    if app.current_popup == Some(Popups::PgnInput) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_pgn_input(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The headers of the exported game are edited one after the other
+    if app.current_popup == Some(Popups::PgnTags) {
+        match key_event.code {
+            KeyCode::Char(c) => app.type_pgn_tag(c),
+            KeyCode::Backspace => app.delete_pgn_tag_char(),
+            KeyCode::Up | KeyCode::BackTab => app.pgn_tag_cursor_up(),
+            KeyCode::Down | KeyCode::Tab => app.pgn_tag_cursor_down(),
+            KeyCode::Enter => app.submit_pgn_tags(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The name of the file the game is saved to is typed in the prompt
+    if app.current_popup == Some(Popups::SaveInput) {
+        match key_event.code {
+            KeyCode::Char(c) => app.game.ui.prompt.enter_char(c),
+            KeyCode::Backspace => app.game.ui.prompt.delete_char(),
+            KeyCode::Left => app.game.ui.prompt.move_cursor_left(),
+            KeyCode::Right => app.game.ui.prompt.move_cursor_right(),
+            KeyCode::Enter => app.submit_save_input(),
+            KeyCode::Esc => app.current_popup = None,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The game waits until the engine is restarted or replaced
+    if app.current_popup == Some(Popups::EngineFailure) {
+        match key_event.code {
+            KeyCode::Char('r') | KeyCode::Enter => app.restart_engine(),
+            KeyCode::Esc => app.replace_engine_with_random_bot(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // An existing file is only replaced once the player agrees
+    if app.current_popup == Some(Popups::OverwriteConfirmation) {
+        match key_event.code {
+            KeyCode::Char('y' | 'Y') => app.confirm_overwrite(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => app.cancel_overwrite(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Nothing else happens until the resignation is confirmed or cancelled
+    if app.current_popup == Some(Popups::ResignConfirmation) {
+        match key_event.code {
+            KeyCode::Char('y' | 'Y') => app.confirm_resign(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => app.cancel_resign(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The held back move is played or given up
+    if app.current_popup == Some(Popups::BlunderWarning) {
+        match key_event.code {
+            KeyCode::Char('y' | 'Y') => app.confirm_blunder(),
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => app.cancel_blunder(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The saved games are browsed with the arrows, `Enter` opens the selected one
+    if app.current_page == Pages::MyGames && app.current_popup.is_none() {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => app.my_games_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.my_games_down(),
+            KeyCode::Enter | KeyCode::Char(' ') => app.open_my_game(),
+            KeyCode::Esc | KeyCode::Char('b') => app.current_page = Pages::Home,
+            KeyCode::Char('?') => app.toggle_help_popup(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The games found on the network are browsed with the arrows, `Enter` joins the selected one
+    if app.current_page == Pages::LanGames && app.current_popup.is_none() {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => app.lan_games_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.lan_games_down(),
+            KeyCode::Enter | KeyCode::Char(' ') => app.join_lan_game(),
+            KeyCode::Char('c') => app.open_game_code_input(),
+            KeyCode::Esc | KeyCode::Char('b') => app.close_lan_games(),
+            KeyCode::Char('?') => app.toggle_help_popup(),
+            KeyCode::Char('q') => app.quit(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // The arrow keys choose what happens after a finished game
+    if app.current_page.is_game() && app.game.is_locked() && app.current_popup.is_none() {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.end_popup_cursor_up();
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.end_popup_cursor_down();
+                return Ok(());
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                app.select_end_popup_choice();
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
+    // The arrow keys scroll the focused side panel instead of moving the board cursor
+    if app.current_page.is_game() && app.focus != Focus::Board {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.scroll_focused_panel_up();
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                app.scroll_focused_panel_down();
+                return Ok(());
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Right | KeyCode::Char('l') => {
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
     match key_event.code {
         // Exit application on `q`
         KeyCode::Char('q') => {
             app.quit();
         }
         // Exit application on `Ctrl-C`
-        KeyCode::Char('c' | 'C') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
-            }
+        KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
+        }
+        // Load a FEN from the clipboard on `Ctrl-V`
+        KeyCode::Char('v' | 'V') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.paste_fen();
+        }
+        // Copy the moves in UCI notation on `Ctrl-U`
+        KeyCode::Char('u' | 'U') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.copy_uci_moves();
+        }
+        // Copy the position as a FEN on `Ctrl-F`
+        KeyCode::Char('f' | 'F') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.copy_fen();
+        }
+        // Set up a position from its FEN on `F`
+        KeyCode::Char('F') => {
+            app.open_position_setup();
+        }
+        // Give the focus to the next panel on `Tab`
+        KeyCode::Tab => {
+            app.cycle_focus();
+        }
+        KeyCode::BackTab => {
+            app.cycle_focus_back();
         }
-        // Counter handlers
         // Counter handlers
         KeyCode::Right | KeyCode::Char('l') => {
-            if app.selected_color.is_none() {
+            if app.current_popup == Some(Popups::ColorSelection) {
                 app.menu_cursor_right(2);
+            } else if app.current_popup == Some(Popups::DifficultySelection) {
+                app.menu_cursor_right(Difficulty::ALL.len() as u8);
             } else if app.game.game_state == GameState::Promotion {
                 app.game.ui.cursor_right_promotion();
-            } else if !(app.game.game_state == GameState::Checkmate)
-                && !(app.game.game_state == GameState::Draw)
-            {
-                let authorized_positions = app.game.game_board.get_authorized_positions(
-                    app.game.player_turn,
-                    app.game.ui.selected_coordinates,
-                );
-                app.game.ui.cursor_right(authorized_positions);
+            } else if !app.game.is_locked() {
+                let authorized_positions = app.game.selected_piece_positions();
+                if app.is_board_flipped() {
+                    app.game.ui.cursor_left(authorized_positions);
+                } else {
+                    app.game.ui.cursor_right(authorized_positions);
+                }
             }
         }
 
         KeyCode::Left | KeyCode::Char('h') => {
-            if app.selected_color.is_none() {
+            if app.current_popup == Some(Popups::ColorSelection) {
                 app.menu_cursor_left(2);
+            } else if app.current_popup == Some(Popups::DifficultySelection) {
+                app.menu_cursor_left(Difficulty::ALL.len() as u8);
             } else if app.game.game_state == GameState::Promotion {
                 app.game.ui.cursor_left_promotion();
-            } else if !(app.game.game_state == GameState::Checkmate)
-                && !(app.game.game_state == GameState::Draw)
-            {
-                let authorized_positions = app.game.game_board.get_authorized_positions(
-                    app.game.player_turn,
-                    app.game.ui.selected_coordinates,
-                );
+            } else if !app.game.is_locked() {
+                let authorized_positions = app.game.selected_piece_positions();
 
-                app.game.ui.cursor_left(authorized_positions);
+                if app.is_board_flipped() {
+                    app.game.ui.cursor_right(authorized_positions);
+                } else {
+                    app.game.ui.cursor_left(authorized_positions);
+                }
             }
         }
         KeyCode::Up | KeyCode::Char('k') => {
             if app.current_page == Pages::Home {
                 app.menu_cursor_up(Pages::variant_count() as u8);
-            } else if !(app.game.game_state == GameState::Checkmate)
-                && !(app.game.game_state == GameState::Draw)
-                && !(app.game.game_state == GameState::Promotion)
-            {
-                let authorized_positions = app.game.game_board.get_authorized_positions(
-                    app.game.player_turn,
-                    app.game.ui.selected_coordinates,
-                );
-                app.game.ui.cursor_up(authorized_positions);
+            } else if app.current_popup == Some(Popups::EngineSelection) {
+                app.menu_cursor_up(app.engine_profiles.len() as u8);
+            } else if app.current_popup == Some(Popups::DifficultySelection) {
+                app.change_engine_elo(1);
+            } else if !app.game.is_locked() && !(app.game.game_state == GameState::Promotion) {
+                let authorized_positions = app.game.selected_piece_positions();
+                if app.is_board_flipped() {
+                    app.game.ui.cursor_down(authorized_positions);
+                } else {
+                    app.game.ui.cursor_up(authorized_positions);
+                }
             }
         }
         KeyCode::Down | KeyCode::Char('j') => {
             if app.current_page == Pages::Home {
                 app.menu_cursor_down(Pages::variant_count() as u8);
-            } else if !(app.game.game_state == GameState::Checkmate)
-                && !(app.game.game_state == GameState::Draw)
-                && !(app.game.game_state == GameState::Promotion)
-            {
-                let authorized_positions = app.game.game_board.get_authorized_positions(
-                    app.game.player_turn,
-                    app.game.ui.selected_coordinates,
-                );
-
-                app.game.ui.cursor_down(authorized_positions);
+            } else if app.current_popup == Some(Popups::EngineSelection) {
+                app.menu_cursor_down(app.engine_profiles.len() as u8);
+            } else if app.current_popup == Some(Popups::DifficultySelection) {
+                app.change_engine_elo(-1);
+            } else if !app.game.is_locked() && !(app.game.game_state == GameState::Promotion) {
+                let authorized_positions = app.game.selected_piece_positions();
+                if app.is_board_flipped() {
+                    app.game.ui.cursor_up(authorized_positions);
+                } else {
+                    app.game.ui.cursor_down(authorized_positions);
+                }
             }
         }
         KeyCode::Char(' ') | KeyCode::Enter => match app.current_page {
@@ -107,21 +465,119 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
             Pages::Credit => {
                 app.current_page = Pages::Home;
             }
+            _ if app.current_popup == Some(Popups::ColorSelection) => {
+                app.color_selection();
+            }
+            _ if app.current_popup == Some(Popups::DifficultySelection) => {
+                app.difficulty_selection();
+            }
+            _ if app.current_popup == Some(Popups::EngineSelection) => {
+                app.engine_selection();
+            }
             _ => {
-                app.game.handle_cell_click();
+                if app.pending_recapture.is_some() {
+                    app.confirm_auto_recapture();
+                } else {
+                    app.handle_cell_click();
+                }
             }
         },
-        KeyCode::Char('?') => {
-            if app.current_page != Pages::Credit {
-                app.toggle_help_popup();
-            }
+        KeyCode::Char('?') if app.current_page != Pages::Credit => {
+            app.toggle_help_popup();
         }
         KeyCode::Char('r') => {
-            app.restart();
+            app.rematch();
+        }
+        KeyCode::Char('d') => {
+            app.offer_draw();
+        }
+        KeyCode::Char('x') => {
+            app.request_resign();
+        }
+        // Take over the side of the bot and give it yours
+        KeyCode::Char('S') => {
+            app.switch_sides();
+        }
+        // Switch to the next board theme on `Ctrl-T` and remember it
+        KeyCode::Char('t' | 'T') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.cycle_theme();
+            app.update_config();
+        }
+        // Show the latest entries of the log file
+        KeyCode::Char('L') => {
+            app.toggle_log_viewer();
+        }
+        // Show the lines exchanged with the engine
+        KeyCode::Char('E') => {
+            app.toggle_engine_console();
+        }
+        // Type a move in algebraic notation
+        KeyCode::Char('m') if app.current_page.is_game() => {
+            app.open_move_input();
+        }
+        // Look back at the previous positions of the game
+        KeyCode::Char('v') if app.current_page.is_game() => {
+            app.toggle_review();
+        }
+        // The engine plays the best move it found so far
+        KeyCode::Char('s') if app.engine_search.is_some() => {
+            app.stop_engine_search();
+        }
+        // Save the game in Portable Game Notation once its headers are edited
+        KeyCode::Char('p') if app.current_page.is_game() => {
+            app.open_pgn_tags();
+        }
+        // Save the game to a file to resume it later
+        KeyCode::Char('w') if app.current_page.is_game() => {
+            app.open_save_input();
+        }
+        // Save the positions of the game as an animated GIF
+        KeyCode::Char('g') if app.current_page.is_game() => {
+            app.export_gif();
+        }
+        // Save the position on the board as SVG and PNG pictures
+        KeyCode::Char('P') if app.current_page.is_game() => {
+            app.export_diagram();
+        }
+        // Show or hide the cells controlled by the opponent
+        KeyCode::Char('t' | 'T') => {
+            app.game.ui.toggle_threats();
+        }
+        // Show or hide the number of legal moves of the player to move
+        KeyCode::Char('c') => {
+            app.game.ui.toggle_legal_move_count();
+        }
+        // The move the engine would play, for a few seconds
+        KeyCode::Char('H') => {
+            app.show_hint();
+        }
+        // The best lines of the engine next to the board
+        KeyCode::Char('e') => {
+            app.toggle_analysis_panel();
+        }
+        // Write the moves in algebraic or in coordinate notation
+        KeyCode::Char('n') if app.current_page.is_game() => {
+            app.toggle_move_notation();
+        }
+        // Move either side freely and take moves back
+        KeyCode::Char('f') => {
+            app.toggle_free_play();
+        }
+        KeyCode::Char('u') => {
+            app.take_back();
         }
         KeyCode::Esc => {
+            // The first `Esc` lets go of the selected piece
+            if app.current_page == Pages::Analyze
+                && app.current_popup.is_none()
+                && !app.game.ui.is_cell_selected()
+            {
+                app.leave_analysis();
+            }
             match app.current_popup {
-                Some(Popups::ColorSelection) => {
+                Some(
+                    Popups::ColorSelection | Popups::DifficultySelection | Popups::EngineSelection,
+                ) => {
                     app.current_popup = None;
                     app.selected_color = None;
                     app.current_page = Pages::Home;
@@ -137,16 +593,13 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
                 app.current_page = Pages::Home;
             }
 
+            app.cancel_auto_recapture();
+            app.focus = Focus::Board;
+            app.game.cancel_drag();
             app.game.ui.unselect_cell();
         }
         KeyCode::Char('b') => {
-            let display_mode = app.game.ui.display_mode;
-            app.selected_color = None;
-
-            app.go_to_home();
-            app.game.game_board.reset();
-            app.game.ui.reset();
-            app.game.ui.display_mode = display_mode;
+            app.return_to_menu();
         }
         // Other handlers you could add here.
         _ => {}
@@ -156,12 +609,27 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
 }
 
 pub fn handle_mouse_events(mouse_event: MouseEvent, app: &mut App) -> AppResult<()> {
-    // Mouse control only implemented for actual game
-    if app.current_page == Pages::Home || app.current_page == Pages::Credit {
+    // The terminal keeps the mouse, for instance to select text
+    if !app.mouse_capture {
+        return Ok(());
+    }
+    if app.idle.reset() {
+        return Ok(());
+    }
+    if let MouseEventKind::ScrollUp | MouseEventKind::ScrollDown = mouse_event.kind {
+        handle_mouse_scroll(mouse_event, app);
+        return Ok(());
+    }
+    // Mouse control only implemented for actual game, the reviewed positions can't be played
+    if matches!(
+        app.current_page,
+        Pages::Home | Pages::MyGames | Pages::LanGames | Pages::Credit
+    ) || app.review_index.is_some()
+    {
         return Ok(());
     }
     if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
-        if app.game.game_state == GameState::Checkmate || app.game.game_state == GameState::Draw {
+        if app.game.is_locked() {
             return Ok(());
         }
 
@@ -172,46 +640,93 @@ pub fn handle_mouse_events(mouse_event: MouseEvent, app: &mut App) -> AppResult<
         // If there is a promotion to be done the top_x, top_y, width and height
         // values are updated accordingly
         if app.game.game_state == GameState::Promotion {
-            let x = (mouse_event.column - app.game.ui.top_x) / app.game.ui.width;
-            let y = (mouse_event.row - app.game.ui.top_y) / app.game.ui.height;
+            let ui = &app.game.ui;
+            if mouse_event.column < ui.top_x
+                || mouse_event.row < ui.top_y
+                || ui.width == 0
+                || ui.height == 0
+            {
+                return Ok(());
+            }
+            let x = (mouse_event.column - ui.top_x) / ui.width;
+            let y = (mouse_event.row - ui.top_y) / ui.height;
             if x > 3 || y > 0 {
                 return Ok(());
             }
             app.game.ui.promotion_cursor = x as i8;
-            app.game.promote_piece();
-        }
-        if mouse_event.column < app.game.ui.top_x || mouse_event.row < app.game.ui.top_y {
+            app.handle_cell_click();
+            // The click picked the piece, it must not also select a cell of the board
             return Ok(());
         }
-        let x = (mouse_event.column - app.game.ui.top_x) / app.game.ui.width;
-        let y = (mouse_event.row - app.game.ui.top_y) / app.game.ui.height;
-        if x > 7 || y > 7 {
+        let Some(coords) = board_cell_under_mouse(&mouse_event, app) else {
             return Ok(());
-        }
+        };
         app.game.ui.mouse_used = true;
-        let coords: Coord = Coord::new(y as u8, x as u8);
-
-        let authorized_positions = app
-            .game
-            .game_board
-            .get_authorized_positions(app.game.player_turn, app.game.ui.selected_coordinates);
-
-        let piece_color = app
-            .game
-            .game_board
-            .get_piece_color(&app.game.ui.selected_coordinates);
-
-        if authorized_positions.contains(&coords)
-            && match piece_color {
-                Some(piece) => Some(piece) == piece_color,
-                None => false,
-            }
-        {
-            app.game.ui.cursor_coordinates = coords;
-            app.game.handle_cell_click();
-        } else {
-            app.game.ui.selected_coordinates = coords;
+        app.click_board_cell(coords);
+    } else if mouse_event.kind == MouseEventKind::Drag(MouseButton::Left) {
+        // The legal targets of the dragged piece stay highlighted, the one under the mouse stands out
+        if app.game.ui.dragged_from.is_some() {
+            app.game.ui.drag_hover = board_cell_under_mouse(&mouse_event, app);
         }
+    } else if mouse_event.kind == MouseEventKind::Up(MouseButton::Left) {
+        let target = board_cell_under_mouse(&mouse_event, app);
+        app.drop_dragged_piece(target);
     }
     Ok(())
 }
+
+/// The wheel scrolls the log viewer or the engine console while it is open,
+/// otherwise the side panel under the mouse
+fn handle_mouse_scroll(mouse_event: MouseEvent, app: &mut App) {
+    let up = mouse_event.kind == MouseEventKind::ScrollUp;
+    if app.current_popup == Some(Popups::LogViewer) {
+        if up {
+            app.log_scroll_up();
+        } else {
+            app.log_scroll_down();
+        }
+        return;
+    }
+    if app.current_popup == Some(Popups::EngineConsole) {
+        if up {
+            app.engine_console_scroll_up();
+        } else {
+            app.engine_console_scroll_down();
+        }
+        return;
+    }
+    if app.current_popup.is_some() || !app.current_page.is_game() {
+        return;
+    }
+    let Some(panel) = app.panel_at(mouse_event.column, mouse_event.row) else {
+        return;
+    };
+    if up {
+        app.scroll_panel_up(panel);
+    } else {
+        app.scroll_panel_down(panel);
+    }
+}
+
+/// The cell of the board under the mouse, if any, in the orientation the board is stored in
+fn board_cell_under_mouse(mouse_event: &MouseEvent, app: &App) -> Option<Coord> {
+    let ui = &app.game.ui;
+    if mouse_event.column < ui.top_x
+        || mouse_event.row < ui.top_y
+        || ui.width == 0
+        || ui.height == 0
+    {
+        return None;
+    }
+    let x = (mouse_event.column - ui.top_x) / ui.width;
+    let y = (mouse_event.row - ui.top_y) / ui.height;
+    if x > 7 || y > 7 {
+        return None;
+    }
+    let coords = Coord::new(y as u8, x as u8);
+    if app.is_board_flipped() {
+        Some(flip_coord(coords))
+    } else {
+        Some(coords)
+    }
+}
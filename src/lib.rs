@@ -24,3 +24,36 @@ pub mod utils;
 
 // Logging
 pub mod logging;
+
+// Errors of the game logic
+pub mod error;
+
+// System clipboard access
+pub mod clipboard;
+
+// Chess engines and bots
+pub mod engine;
+
+// Seedable random numbers for the bots and Chess960
+pub mod rng;
+
+// Online play
+pub mod network;
+
+// Saved games
+pub mod save;
+
+// Games exported in Portable Game Notation
+pub mod pgn;
+
+// The saved games listed on the My games page
+pub mod library;
+
+// Sounds of the game events
+pub mod sound;
+
+// Games exported as animated GIFs
+pub mod gif;
+
+// Positions exported as SVG and PNG diagrams
+pub mod diagram;
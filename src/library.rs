@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::{pgn::parse_games, save::SavedGame};
+
+/// How a game listed on the My games page is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameSource {
+    /// The game at this index of a PGN file
+    Pgn(usize),
+    /// A game saved with `w` to be resumed
+    Save,
+}
+
+/// A game of the games folder, as listed on the My games page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryGame {
+    pub path: PathBuf,
+    pub source: GameSource,
+    /// In PGN notation, `*` while the game goes on
+    pub result: String,
+    /// The other player, both players when the local one is not known
+    pub opponent: String,
+    /// The date of the game, or of its file when the game has none
+    pub date: String,
+}
+
+impl LibraryGame {
+    /// Whether the game can go on from its last position
+    pub fn is_unfinished(&self) -> bool {
+        self.result == "*"
+    }
+}
+
+/// The games of the PGN files and saved games of `folder`, the latest files first
+/// `player_name` tells the local player apart from the opponent
+/// Files that can't be read are skipped with a warning in the logs
+pub fn scan_games(folder: &Path, player_name: Option<&str>) -> Vec<LibraryGame> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+    let mut files: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "pgn" || extension == "toml")
+        })
+        .map(|path| {
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, path)
+        })
+        .collect();
+    files.sort_by(|(a_time, a_path), (b_time, b_path)| {
+        b_time.cmp(a_time).then_with(|| a_path.cmp(b_path))
+    });
+
+    let mut games = vec![];
+    for (modified, path) in files {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Could not read {}: {e}", path.display());
+                continue;
+            }
+        };
+        let file_date = chrono::DateTime::<chrono::Local>::from(modified)
+            .format("%Y.%m.%d")
+            .to_string();
+        if path.extension().is_some_and(|extension| extension == "pgn") {
+            for (index, pgn_game) in parse_games(&content).into_iter().enumerate() {
+                let date = pgn_game
+                    .header("Date")
+                    .filter(|date| !date.starts_with('?'))
+                    .map_or(file_date.clone(), str::to_string);
+                games.push(LibraryGame {
+                    path: path.clone(),
+                    source: GameSource::Pgn(index),
+                    result: pgn_game.header("Result").unwrap_or("*").to_string(),
+                    opponent: opponent(
+                        pgn_game.header("White").unwrap_or("?"),
+                        pgn_game.header("Black").unwrap_or("?"),
+                        player_name,
+                    ),
+                    date,
+                });
+            }
+            continue;
+        }
+        let saved = SavedGame::from_toml(&content)
+            .and_then(|saved| Ok((saved.to_game()?, saved.player_color)));
+        match saved {
+            Ok((game, player_color)) => games.push(LibraryGame {
+                path,
+                source: GameSource::Save,
+                result: game.result().unwrap_or("*").to_string(),
+                opponent: match player_color {
+                    Some(_) => "Bot".to_string(),
+                    None => "Local game".to_string(),
+                },
+                date: file_date,
+            }),
+            Err(e) => log::warn!("Skipped {}: {e}", path.display()),
+        }
+    }
+    games
+}
+
+// The player facing `player_name`, or both players when it played neither side
+fn opponent(white: &str, black: &str, player_name: Option<&str>) -> String {
+    match player_name {
+        Some(name) if name == white => black.to_string(),
+        Some(name) if name == black => white.to_string(),
+        _ => format!("{white} vs {black}"),
+    }
+}
@@ -1,15 +1,18 @@
 use chrono::Local;
 use log::LevelFilter;
 use simplelog::{CombinedLogger, Config, WriteLogger};
-use std::fs;
-use std::path::Path;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
+/// Start writing the logs in a new file of the config folder, its path is returned
 pub fn setup_logging(
     config_dir: &Path,
     log_level: &LevelFilter,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
     match log_level {
-        LevelFilter::Off => Ok(()), // No logging setup needed
+        LevelFilter::Off => Ok(None), // No logging setup needed
         level => {
             // Create logs directory
             let log_dir = config_dir.join("logs");
@@ -22,11 +25,25 @@ pub fn setup_logging(
             CombinedLogger::init(vec![WriteLogger::new(
                 *level,
                 Config::default(),
-                fs::File::create(log_file)?,
+                File::create(&log_file)?,
             )])?;
 
             log::info!("Logging initialized at {level} level");
-            Ok(())
+            Ok(Some(log_file))
         }
     }
 }
+
+/// The last `count` lines of the file, only that many lines are kept in memory while reading
+pub fn tail_lines(path: &Path, count: usize) -> io::Result<Vec<String>> {
+    let mut lines = VecDeque::with_capacity(count);
+    for line in BufReader::new(File::open(path)?).lines() {
+        if lines.len() == count {
+            lines.pop_front();
+        }
+        if count > 0 {
+            lines.push_back(line?);
+        }
+    }
+    Ok(lines.into())
+}
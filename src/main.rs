@@ -2,17 +2,38 @@
 extern crate chess_tui;
 
 use chess_tui::app::{App, AppResult};
-use chess_tui::constants::home_dir;
-use chess_tui::event::{Event, EventHandler};
-use chess_tui::handler::{handle_key_events, handle_mouse_events};
+use chess_tui::constants::{config_path, home_dir, FlipPolicy, MoveNotation, TICK_RATE_MS};
+use chess_tui::engine::analysis::DEFAULT_ANALYSIS_LINES;
+use chess_tui::engine::engine_match::run_match;
+use chess_tui::engine::random_bot::RandomBot;
+use chess_tui::engine::uci::DEFAULT_MOVETIME;
+use chess_tui::engine::{SearchConstraints, DEFAULT_ELO};
+use chess_tui::error::ChessError;
+use chess_tui::event::EventHandler;
+use chess_tui::game_logic::game::Game;
+use chess_tui::game_logic::puzzle::PuzzleSet;
+use chess_tui::gif::DEFAULT_FRAME_DELAY_MS;
+use chess_tui::handler::handle_event;
 use chess_tui::logging;
+use chess_tui::network::bot_server::{accept_within, BotServer};
+use chess_tui::network::discovery::{local_ip, Advertiser};
+use chess_tui::network::handshake::DEFAULT_CONNECTION_TIMEOUT;
+use chess_tui::network::lobby::{decode_game_code, encode_game_code};
+use chess_tui::network::opponent::RECONNECT_TIMEOUT;
+use chess_tui::network::spectate::{start_spectator_server, GameSnapshot};
+use chess_tui::pgn::load_games;
+use chess_tui::pieces::PieceColor;
+use chess_tui::rng::Rng;
+use chess_tui::save::{SavedGame, AUTOSAVE_FILE};
+use chess_tui::ui::theme::THEMES;
 use chess_tui::ui::tui::Tui;
 use clap::Parser;
 use log::LevelFilter;
 use std::fs::{self, File};
 use std::io::Write;
+use std::net::{SocketAddr, TcpListener};
 use std::panic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use toml::Value;
 
 /// Simple program to greet a person
@@ -22,45 +43,170 @@ struct Args {
     /// Path for the chess engine
     #[arg(short, long, default_value = "")]
     engine_path: String,
+    /// Seed of the random choices of the bot, to replay the same games
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Host a game against the bot on this port without opening the interface, the bot plays black
+    #[arg(long)]
+    serve_bot: Option<u16>,
+    /// Join the online game hosted at this address, written host:port, or with its game code
+    #[arg(long)]
+    join: Option<String>,
+    /// File of puzzles to solve, one FEN followed by its best move in UCI notation per line
+    #[arg(long)]
+    puzzles: Option<String>,
+    /// Leave the mouse to the terminal, to select text for instance
+    #[arg(long)]
+    no_mouse: bool,
+    /// PGN file of games to replay, a list to choose from opens when it holds several
+    #[arg(long, value_name = "FILE")]
+    pgn: Option<String>,
+    /// Position to start from instead of the initial one, as a FEN
+    #[arg(long)]
+    fen: Option<String>,
+    /// Play against the bot instead of a local game, from the position of `--fen` when given
+    #[arg(long)]
+    bot: bool,
+    /// Print the cells the piece on this square (`e2`) can move to and exit, without opening the interface
+    #[arg(long, value_name = "SQUARE")]
+    legal: Option<String>,
+    /// Name of the white player, written in the exported games
+    #[arg(long, value_name = "NAME")]
+    white: Option<String>,
+    /// Name of the black player, written in the exported games
+    #[arg(long, value_name = "NAME")]
+    black: Option<String>,
+    /// Save the position of `--fen`, or the initial one, to this file and exit without opening the interface
+    #[arg(long, value_name = "FILE")]
+    save: Option<PathBuf>,
+    /// Let `--save` replace a file that already exists
+    #[arg(long, requires = "save")]
+    force: bool,
+    /// Let browsers watch the game on this port, with the `server` feature
+    #[arg(long, value_name = "PORT")]
+    spectate: Option<u16>,
+    /// Play this many games between the first two engines of the config and exit, printing the score after each game
+    #[arg(long = "match", value_name = "GAMES")]
+    match_games: Option<u32>,
+    /// Configuration file to use instead of `~/.config/chess-tui/config.toml`, `CHESS_TUI_CONFIG` also sets it
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
 }
 
 fn main() -> AppResult<()> {
-    // Used to enable mouse capture
-    ratatui::crossterm::execute!(
-        std::io::stdout(),
-        ratatui::crossterm::event::EnableMouseCapture
-    )?;
     // Parse the cli arguments
     let args = Args::parse();
 
+    if let Some(port) = args.serve_bot {
+        return serve_bot(port, args.seed);
+    }
+    if let Some(square) = &args.legal {
+        return print_legal_moves(square, args.fen.as_deref());
+    }
+    if let Some(path) = &args.save {
+        return save_position(path, args.fen.as_deref(), args.force);
+    }
+
+    // A broken puzzle file is reported before the interface opens
+    let puzzles = args
+        .puzzles
+        .as_deref()
+        .map(|path| PuzzleSet::load(Path::new(path)))
+        .transpose()?;
+
     let home_dir = home_dir()?;
     let folder_path = home_dir.join(".config/chess-tui");
-    let config_path = home_dir.join(".config/chess-tui/config.toml");
+    let config_path = config_path(args.config.as_deref())?;
 
     // Create the configuration file
-    config_create(&args, &folder_path, &config_path)?;
+    config_create(&args, &config_path)?;
 
     // Create an application.
-    let mut app = App::default();
+    let mut app = App {
+        seed: args.seed,
+        autosave_path: Some(config_path.with_file_name(AUTOSAVE_FILE)),
+        ..Default::default()
+    };
+    app.load_config(config_path);
 
-    // Setup logging
-    if let Err(e) = logging::setup_logging(&folder_path, &app.log_level) {
-        eprintln!("Failed to initialize logging: {}", e);
+    // Setup logging, the games skipped in a PGN file are reported in the logs
+    match logging::setup_logging(&folder_path, &app.log_level) {
+        Ok(log_file) => app.log_file = log_file,
+        Err(e) => eprintln!("Failed to initialize logging: {}", e),
     }
 
+    if let Some(games) = args.match_games {
+        return play_match(&app, games);
+    }
+    if args.white.is_some() {
+        app.player_names.white = args.white;
+    }
+    if args.black.is_some() {
+        app.player_names.black = args.black;
+    }
+    if let Some(fen) = &args.fen {
+        app.load_fen(fen)?;
+    }
+    if args.bot {
+        app.open_bot_setup();
+    }
+    if let Some(puzzles) = puzzles {
+        app.start_puzzles(puzzles)?;
+    }
+    if let Some(path) = &args.pgn {
+        app.open_pgn_games(load_games(Path::new(path))?)?;
+    }
+    if let Some(port) = args.spectate {
+        let spectators = start_spectator_server(port)?;
+        spectators.broadcast(&GameSnapshot::from_game(&app.game));
+        app.spectators = Some(spectators);
+    }
+    if args.no_mouse {
+        app.mouse_capture = false;
+    }
+    // A malformed game code is reported before the interface opens
+    if let Some(host) = &args.join {
+        let address = match host.parse::<SocketAddr>() {
+            Ok(address) => address,
+            Err(_) => decode_game_code(host)?,
+        };
+        app.join_game(address);
+    }
+    // The mouse is only released at the end if it was captured
+    let mouse_capture = app.mouse_capture;
+    if mouse_capture {
+        ratatui::crossterm::execute!(
+            std::io::stdout(),
+            ratatui::crossterm::event::EnableMouseCapture
+        )?;
+    }
+
+    // Pasted text comes in one piece instead of key presses
+    ratatui::crossterm::execute!(
+        std::io::stdout(),
+        ratatui::crossterm::event::EnableBracketedPaste
+    )?;
+
     // Initialize the terminal user interface.
     let terminal = ratatui::try_init()?;
-    let events = EventHandler::new(250);
+    let events = EventHandler::new(TICK_RATE_MS);
+    app.event_sender = Some(events.sender());
     let mut tui = Tui::new(terminal, events);
 
     let default_panic = std::panic::take_hook();
     panic::set_hook(Box::new(move |info| {
         ratatui::restore();
-        ratatui::crossterm::execute!(
+        let _ = ratatui::crossterm::execute!(
             std::io::stdout(),
-            ratatui::crossterm::event::DisableMouseCapture
-        )
-        .unwrap();
+            ratatui::crossterm::event::DisableBracketedPaste
+        );
+        if mouse_capture {
+            ratatui::crossterm::execute!(
+                std::io::stdout(),
+                ratatui::crossterm::event::DisableMouseCapture
+            )
+            .unwrap();
+        }
         default_panic(info);
     }));
 
@@ -69,27 +215,115 @@ fn main() -> AppResult<()> {
         // Render the user interface.
         tui.draw(&mut app)?;
         // Handle events.
-        match tui.events.next()? {
-            Event::Tick => app.tick(),
-            Event::Key(key_event) => handle_key_events(key_event, &mut app)?,
-            Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, &mut app)?,
-            Event::Resize(_, _) => {}
-        }
+        handle_event(tui.events.next()?, &mut app)?;
     }
 
     // Exit the user interface.
     ratatui::try_restore()?;
-    // Free up the mouse, otherwise it will remain linked to the terminal
     ratatui::crossterm::execute!(
         std::io::stdout(),
-        ratatui::crossterm::event::DisableMouseCapture
+        ratatui::crossterm::event::DisableBracketedPaste
     )?;
+    // Free up the mouse, otherwise it will remain linked to the terminal
+    if mouse_capture {
+        ratatui::crossterm::execute!(
+            std::io::stdout(),
+            ratatui::crossterm::event::DisableMouseCapture
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Wait for a player to join and let the bot play against them, until the end of the game
+fn serve_bot(port: u16, seed: Option<u64>) -> AppResult<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    println!("Waiting for a player on port {port}");
+    // The joining player can type this code instead of the address
+    match encode_game_code(SocketAddr::new(local_ip().into(), port)) {
+        Ok(code) => println!("Game code: {code}"),
+        Err(e) => println!("No game code for this address: {e}"),
+    }
+
+    // The players of the local network find the game without typing the address
+    let advertiser = Advertiser::start(&format!("Random bot on port {port}"), port)
+        .inspect_err(|e| println!("Could not announce the game on the network: {e}"))
+        .ok();
+    let (stream, address) = listener.accept()?;
+    drop(advertiser);
+    println!("{address} joined the game");
 
+    let rng = seed.map_or_else(Rng::from_entropy, Rng::from_seed);
+    let mut server = BotServer::new(RandomBot::new(rng), PieceColor::Black);
+    server.serve(stream)?;
+    // The player losing the connection has some time to come back with its session token
+    while !server.game.game_state.is_over() {
+        println!(
+            "The player left, waiting {} seconds for them to come back",
+            RECONNECT_TIMEOUT.as_secs()
+        );
+        let Some(stream) = accept_within(&listener, RECONNECT_TIMEOUT)? else {
+            println!("The player did not come back");
+            break;
+        };
+        if !server.resume(stream)? {
+            println!("Someone else tried to join the game");
+        }
+    }
+    println!("{}", server.game.status_message());
+    Ok(())
+}
+
+/// Play an engine match without opening the interface, the engines think as long as the config lets them
+fn play_match(app: &App, games: u32) -> AppResult<()> {
+    let players = app.match_players()?;
+    let names = players.each_ref().map(|player| player.name());
+    let constraints = SearchConstraints::default().within(app.engine_limits);
+    println!("{} against {}, {games} games", names[0], names[1]);
+    run_match(players, games, constraints, |game, score| {
+        println!(
+            "\nGame {}: {} - {} {} ({})",
+            game.number,
+            names[game.white],
+            names[1 - game.white],
+            game.result,
+            game.reason
+        );
+        print!("{}", score.table(&names));
+    });
     Ok(())
 }
 
-fn config_create(args: &Args, folder_path: &Path, config_path: &Path) -> AppResult<()> {
-    std::fs::create_dir_all(folder_path)?;
+/// Print the legal destinations of a piece on one line, in the initial position or the given one
+fn print_legal_moves(square: &str, fen: Option<&str>) -> AppResult<()> {
+    let game = match fen {
+        Some(fen) => Game::from_fen(fen)?,
+        None => Game::default(),
+    };
+    println!("{}", game.legal_destinations(square)?.join(" "));
+    Ok(())
+}
+
+/// Write the initial position or the given one to a save file, an existing file is only replaced with `force`
+fn save_position(path: &Path, fen: Option<&str>, force: bool) -> AppResult<()> {
+    let game = match fen {
+        Some(fen) => Game::from_fen(fen)?,
+        None => Game::default(),
+    };
+    match SavedGame::from_game(&game).write_to(path, force) {
+        Err(error @ ChessError::SaveFileExists(_)) => {
+            return Err(format!("{error}, add --force to replace it").into())
+        }
+        result => result?,
+    }
+    println!("Game saved to {}", path.display());
+    Ok(())
+}
+
+fn config_create(args: &Args, config_path: &Path) -> AppResult<()> {
+    if let Some(folder_path) = config_path.parent() {
+        std::fs::create_dir_all(folder_path)?;
+    }
 
     if !config_path.exists() {
         //write to console
@@ -126,6 +360,84 @@ fn config_create(args: &Args, folder_path: &Path, config_path: &Path) -> AppResu
         table
             .entry("log_level".to_string())
             .or_insert(Value::String(LevelFilter::Off.to_string()));
+        table
+            .entry("idle_animation_timeout".to_string())
+            .or_insert(Value::Integer(0));
+        table
+            .entry("auto_recapture".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("clock_tenths_threshold".to_string())
+            .or_insert(Value::Integer(20));
+        table
+            .entry("clock_flash".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("keep_selection_on_illegal_target".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("confirm_resign".to_string())
+            .or_insert(Value::Boolean(true));
+        table
+            .entry("blunder_warning".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("flip_policy".to_string())
+            .or_insert(Value::String(FlipPolicy::default().to_string()));
+        table
+            .entry("autosave".to_string())
+            .or_insert(Value::Boolean(true));
+        table
+            .entry("move_notation".to_string())
+            .or_insert(Value::String(MoveNotation::default().to_string()));
+        table
+            .entry("flip_delay_ms".to_string())
+            .or_insert(Value::Integer(0));
+        table
+            .entry("mouse_capture".to_string())
+            .or_insert(Value::Boolean(true));
+        table
+            .entry("bot_time_odds".to_string())
+            .or_insert(Value::Integer(0));
+        table
+            .entry("bot_draw_threshold".to_string())
+            .or_insert(Value::Integer(0));
+        table
+            .entry("highlight_all_checks".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("compact_cells".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("menu_wrap".to_string())
+            .or_insert(Value::Boolean(true));
+        table
+            .entry("analysis_lines".to_string())
+            .or_insert(Value::Integer(DEFAULT_ANALYSIS_LINES as i64));
+        table
+            .entry("engine_movetime_ms".to_string())
+            .or_insert(Value::Integer(DEFAULT_MOVETIME.as_millis() as i64));
+        table
+            .entry("engine_depth".to_string())
+            .or_insert(Value::Integer(0));
+        table
+            .entry("engine_elo".to_string())
+            .or_insert(Value::Integer(i64::from(DEFAULT_ELO)));
+        table
+            .entry("engine_limit_strength".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("enable_pondering".to_string())
+            .or_insert(Value::Boolean(false));
+        table
+            .entry("gif_frame_delay_ms".to_string())
+            .or_insert(Value::Integer(i64::from(DEFAULT_FRAME_DELAY_MS)));
+        table
+            .entry("theme".to_string())
+            .or_insert(Value::String(THEMES[0].name.to_string()));
+        table
+            .entry("connection_timeout".to_string())
+            .or_insert(Value::Integer(DEFAULT_CONNECTION_TIMEOUT.as_secs() as i64));
     }
 
     let mut file = File::create(config_path)?;
@@ -144,13 +456,29 @@ mod tests {
     fn test_config_create() {
         let args = Args {
             engine_path: "test_engine_path".to_string(),
+            seed: None,
+            serve_bot: None,
+            join: None,
+            puzzles: None,
+            no_mouse: false,
+            pgn: None,
+            fen: None,
+            bot: false,
+            legal: None,
+            white: None,
+            black: None,
+            save: None,
+            force: false,
+            spectate: None,
+            match_games: None,
+            config: None,
         };
 
-        let home_dir = home_dir().expect("Failed to get home directory");
-        let folder_path = home_dir.join(".test/chess-tui");
-        let config_path = home_dir.join(".test/chess-tui/config.toml");
+        let folder_path =
+            std::env::temp_dir().join(format!("chess-tui-config-{}", std::process::id()));
+        let config_path = folder_path.join("chess-tui/config.toml");
 
-        let result = config_create(&args, &folder_path, &config_path);
+        let result = config_create(&args, &config_path);
 
         assert!(result.is_ok());
         assert!(config_path.exists());
@@ -167,7 +495,7 @@ mod tests {
             table.get("display_mode").unwrap().as_str().unwrap(),
             "DEFAULT"
         );
-        let removed = fs::remove_dir_all(home_dir.join(".test"));
+        let removed = fs::remove_dir_all(folder_path);
         assert!(removed.is_ok());
     }
 }
@@ -0,0 +1,166 @@
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::protocol::Message;
+use crate::{
+    engine::{random_bot::RandomBot, Engine},
+    game_logic::game::{Game, GameState},
+    pieces::PieceColor,
+};
+
+/// A headless host playing the bot against the player who joins it
+pub struct BotServer {
+    pub game: Game,
+    bot: RandomBot,
+    bot_color: PieceColor,
+    /// The name the player sent when joining
+    pub player_name: Option<String>,
+    /// The token the player sent to come back to the game after losing the connection
+    pub session_token: Option<String>,
+}
+
+impl BotServer {
+    pub fn new(bot: RandomBot, bot_color: PieceColor) -> Self {
+        BotServer {
+            game: Game::default(),
+            bot,
+            bot_color,
+            player_name: None,
+            session_token: None,
+        }
+    }
+
+    /// The move of the bot when it is its turn to play
+    pub fn bot_reply(&mut self) -> Option<Message> {
+        if self.game.player_turn != self.bot_color || self.game.game_state != GameState::Playing {
+            return None;
+        }
+        let piece_move = self.bot.choose_move(&self.game)?;
+        self.game.play_move(piece_move.from, piece_move.to);
+        if self.game.game_state == GameState::Promotion {
+            self.game.promote_piece();
+        }
+        self.game
+            .game_board
+            .uci_moves()
+            .last()
+            .cloned()
+            .map(Message::Move)
+    }
+
+    /// Apply a message of the player, the messages to send back are returned
+    pub fn handle_message(&mut self, message: Message) -> Vec<Message> {
+        match message {
+            Message::Move(uci_move) => {
+                if self.game.player_turn == self.bot_color {
+                    return vec![Message::Error("it is not your turn".to_string())];
+                }
+                match self.game.play_uci_move(&uci_move) {
+                    Ok(()) => self.bot_reply().into_iter().collect(),
+                    Err(error) => vec![Message::Error(error.to_string())],
+                }
+            }
+            Message::Name(name) => {
+                self.player_name = Some(name);
+                vec![]
+            }
+            Message::Session(token) => {
+                self.session_token = Some(token);
+                vec![]
+            }
+            Message::Resign => {
+                if self.game.game_state == GameState::Playing {
+                    self.game.game_state = GameState::Resignation(self.bot_color.opposite());
+                }
+                vec![]
+            }
+            Message::Start(_)
+            | Message::Chat(_)
+            | Message::Error(_)
+            | Message::Resume(_)
+            | Message::History(_) => vec![],
+        }
+    }
+
+    /// Play the game with the player connected on `stream`, until it ends or the player leaves
+    pub fn serve(&mut self, stream: TcpStream) -> io::Result<()> {
+        let mut writer = stream.try_clone()?;
+        writeln!(writer, "{}", Message::Start(self.bot_color.opposite()))?;
+        writeln!(writer, "{}", Message::Name(self.bot.name()))?;
+        if let Some(opening_move) = self.bot_reply() {
+            writeln!(writer, "{opening_move}")?;
+        }
+        self.play(BufReader::new(stream), writer);
+        Ok(())
+    }
+
+    /// Take back the player who lost the connection, true when it gave the token of its session
+    /// The moves played so far are sent first, the game then goes on like in `serve`
+    pub fn resume(&mut self, stream: TcpStream) -> io::Result<bool> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let known_session = match Message::parse(&line) {
+            Some(Message::Resume(token)) => self.session_token.as_ref() == Some(&token),
+            _ => false,
+        };
+        if !known_session {
+            writeln!(writer, "{}", Message::Error("unknown session".to_string()))?;
+            return Ok(false);
+        }
+
+        writeln!(writer, "{}", Message::Start(self.bot_color.opposite()))?;
+        writeln!(writer, "{}", Message::Name(self.bot.name()))?;
+        let moves = self.game.game_board.uci_moves();
+        writeln!(writer, "{}", Message::History(moves))?;
+        self.play(reader, writer);
+        Ok(true)
+    }
+
+    // Answer the player until the game is over, a broken connection means the player left
+    fn play(&mut self, reader: impl BufRead, mut writer: TcpStream) {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            let replies = match Message::parse(&line) {
+                Some(message) => self.handle_message(message),
+                None => vec![Message::Error(format!("unknown message: {line}"))],
+            };
+            for reply in replies {
+                if writeln!(writer, "{reply}").is_err() {
+                    return;
+                }
+            }
+            if self.game.game_state.is_over() {
+                return;
+            }
+        }
+    }
+}
+
+/// The next player connecting to `listener` within `timeout`, `None` when nobody came
+pub fn accept_within(listener: &TcpListener, timeout: Duration) -> io::Result<Option<TcpStream>> {
+    listener.set_nonblocking(true)?;
+    let started_at = Instant::now();
+    let accepted = loop {
+        match listener.accept() {
+            Ok((stream, _)) => break Some(stream),
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                if started_at.elapsed() >= timeout {
+                    break None;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            Err(error) => return Err(error),
+        }
+    };
+    listener.set_nonblocking(false)?;
+    if let Some(stream) = &accepted {
+        stream.set_nonblocking(false)?;
+    }
+    Ok(accepted)
+}
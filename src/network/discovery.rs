@@ -0,0 +1,383 @@
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// The multicast group and port of mDNS
+pub const MDNS_ADDRESS: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(224, 0, 0, 251), 5353);
+
+/// The service announced by the hosts of chess-tui games
+pub const SERVICE_TYPE: &str = "_chess-tui._tcp.local";
+
+/// Time between two queries of the players looking for games
+pub const QUERY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Seconds the announced records stay valid
+const RECORD_TTL: u32 = 120;
+
+/// Time the background threads wait for a packet before checking if they must stop
+const RECEIVE_TIMEOUT: Duration = Duration::from_millis(200);
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+const CLASS_IN: u16 = 1;
+/// The top bit of the class of a question asks for the answer to be sent back directly
+const UNICAST_RESPONSE: u16 = 0x8000;
+
+/// A game hosted on the local network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanGame {
+    /// The name the host gave to its game
+    pub name: String,
+    pub address: SocketAddr,
+}
+
+/// The mDNS query looking for the hosts of chess-tui games, answered directly to its sender
+pub fn query_packet() -> Vec<u8> {
+    // No id, no flags and a single question
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    packet.extend(encode_name(SERVICE_TYPE.split('.')));
+    packet.extend(TYPE_PTR.to_be_bytes());
+    packet.extend((CLASS_IN | UNICAST_RESPONSE).to_be_bytes());
+    packet
+}
+
+/// The mDNS response announcing the game `name` hosted on `port` of the machine at `ip`
+/// The host name given to the machine is made of its address, like `chess-tui-192-168-1-2.local`,
+/// for the other mDNS tools as chess-tui joins the machine that answered
+pub fn announcement_packet(name: &str, ip: Ipv4Addr, port: u16) -> Vec<u8> {
+    // A label can't be longer than 63 bytes
+    let mut name = name.to_string();
+    while name.len() > 63 {
+        name.pop();
+    }
+    let instance = encode_name([name.as_str()].into_iter().chain(SERVICE_TYPE.split('.')));
+    let host_label = format!("chess-tui-{}", ip.to_string().replace('.', "-"));
+    let host = encode_name([host_label.as_str(), "local"]);
+
+    // A response with the pointer to the game, where it is hosted and the address of the host
+    let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 3, 0, 0, 0, 0];
+    push_record(
+        &mut packet,
+        &encode_name(SERVICE_TYPE.split('.')),
+        TYPE_PTR,
+        &instance,
+    );
+    let mut service = vec![0, 0, 0, 0];
+    service.extend(port.to_be_bytes());
+    service.extend(&host);
+    push_record(&mut packet, &instance, TYPE_SRV, &service);
+    push_record(&mut packet, &host, TYPE_A, &ip.octets());
+    packet
+}
+
+/// Whether the mDNS packet asks for the hosts of chess-tui games
+pub fn is_discovery_query(packet: &[u8]) -> bool {
+    let mut reader = PacketReader::new(packet);
+    let (Some(flags), Some(questions)) = (reader.u16_at(2), reader.u16_at(4)) else {
+        return false;
+    };
+    // The top bit of the flags marks the responses
+    if flags & 0x8000 != 0 {
+        return false;
+    }
+    reader.position = 12;
+    (0..questions).any(|_| {
+        let (Some(name), Some(record_type)) = (reader.name(), reader.u16()) else {
+            return false;
+        };
+        reader.position += 2;
+        is_service(&name) && matches!(record_type, TYPE_PTR | TYPE_ANY)
+    })
+}
+
+/// The game announced by an mDNS response received from `sender`, `None` when it announces none
+/// The game is hosted on the machine that sent the response
+pub fn parse_announcement(packet: &[u8], sender: IpAddr) -> Option<LanGame> {
+    let mut reader = PacketReader::new(packet);
+    let flags = reader.u16_at(2)?;
+    if flags & 0x8000 == 0 {
+        return None;
+    }
+    let questions = reader.u16_at(4)?;
+    let records: u32 = [6, 8, 10]
+        .into_iter()
+        .map(|offset| reader.u16_at(offset).map(u32::from))
+        .sum::<Option<u32>>()?;
+    reader.position = 12;
+    for _ in 0..questions {
+        reader.name()?;
+        reader.position += 4;
+    }
+
+    let (mut instance, mut service) = (None, vec![]);
+    for _ in 0..records {
+        let name = reader.name()?;
+        let record_type = reader.u16()?;
+        reader.position += 6;
+        let length = reader.u16()? as usize;
+        let data_start = reader.position;
+        let data = packet.get(data_start..data_start + length)?;
+        match record_type {
+            TYPE_PTR if is_service(&name) => {
+                let mut data_reader = PacketReader::new(packet);
+                data_reader.position = data_start;
+                instance = data_reader.name();
+            }
+            TYPE_SRV if length > 6 => {
+                let port = u16::from_be_bytes([data[4], data[5]]);
+                service.push((name, port));
+            }
+            _ => {}
+        }
+        reader.position = data_start + length;
+    }
+
+    let instance = instance?;
+    let (_, port) = service
+        .into_iter()
+        .find(|(name, _)| same_name(name, &instance))?;
+    Some(LanGame {
+        name: instance.first()?.clone(),
+        address: SocketAddr::new(sender, port),
+    })
+}
+
+// A name of the DNS wire format, each label preceded by its length
+fn encode_name<'a>(labels: impl IntoIterator<Item = &'a str>) -> Vec<u8> {
+    let mut name = vec![];
+    for label in labels {
+        name.push(label.len() as u8);
+        name.extend(label.as_bytes());
+    }
+    name.push(0);
+    name
+}
+
+// A record of a response: its name, type, class, time to live and data
+fn push_record(packet: &mut Vec<u8>, name: &[u8], record_type: u16, data: &[u8]) {
+    packet.extend(name);
+    packet.extend(record_type.to_be_bytes());
+    packet.extend(CLASS_IN.to_be_bytes());
+    packet.extend(RECORD_TTL.to_be_bytes());
+    packet.extend((data.len() as u16).to_be_bytes());
+    packet.extend(data);
+}
+
+fn is_service(name: &[String]) -> bool {
+    same_name(name, &SERVICE_TYPE.split('.').collect::<Vec<_>>())
+}
+
+// Names are compared without case
+fn same_name(a: &[String], b: &[impl AsRef<str>]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.eq_ignore_ascii_case(b.as_ref()))
+}
+
+// Reads the fields of a packet one after the other, out of bounds reads give `None`
+struct PacketReader<'a> {
+    packet: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PacketReader<'a> {
+    fn new(packet: &'a [u8]) -> Self {
+        PacketReader {
+            packet,
+            position: 0,
+        }
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes = self.packet.get(offset..offset + 2)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let value = self.u16_at(self.position)?;
+        self.position += 2;
+        Some(value)
+    }
+
+    // The labels of a name, which can end with a pointer to a name written earlier in the packet
+    fn name(&mut self) -> Option<Vec<String>> {
+        let mut labels = vec![];
+        let mut position = self.position;
+        let mut end = None;
+        // Every pointer goes back, more jumps than bytes can only be a loop
+        for _ in 0..self.packet.len() {
+            let length = *self.packet.get(position)? as usize;
+            match length {
+                0 => {
+                    self.position = end.unwrap_or(position + 1);
+                    return Some(labels);
+                }
+                length if length >= 0xc0 => {
+                    let target = ((length & 0x3f) << 8) | *self.packet.get(position + 1)? as usize;
+                    end.get_or_insert(position + 2);
+                    if target >= position {
+                        return None;
+                    }
+                    position = target;
+                }
+                length => {
+                    let label = self.packet.get(position + 1..position + 1 + length)?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    position += 1 + length;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Announces a hosted game on the local network until it is dropped
+/// The players looking for games get the announcement when the game starts and in answer
+/// to their queries
+pub struct Advertiser {
+    running: Arc<AtomicBool>,
+}
+
+impl Advertiser {
+    /// Announce the game `name` hosted on `port` of this machine
+    pub fn start(name: &str, port: u16) -> io::Result<Self> {
+        let socket = mdns_socket()?;
+        socket.set_read_timeout(Some(RECEIVE_TIMEOUT))?;
+        let announcement = announcement_packet(name, local_ip(), port);
+        socket.send_to(&announcement, MDNS_ADDRESS)?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let still_running = Arc::clone(&running);
+        thread::spawn(move || {
+            let mut buffer = [0u8; 9000];
+            while still_running.load(Ordering::Relaxed) {
+                let Ok((length, sender)) = socket.recv_from(&mut buffer) else {
+                    continue;
+                };
+                if !is_discovery_query(&buffer[..length]) {
+                    continue;
+                }
+                // A query sent from another port than the one of mDNS waits for the answer there
+                let destination = if sender.port() == MDNS_ADDRESS.port() {
+                    SocketAddr::V4(MDNS_ADDRESS)
+                } else {
+                    sender
+                };
+                if let Err(e) = socket.send_to(&announcement, destination) {
+                    log::warn!("Could not answer {sender} looking for games: {e}");
+                }
+            }
+        });
+        Ok(Advertiser { running })
+    }
+}
+
+impl Drop for Advertiser {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+/// The search for games on the local network, queries are sent from a background thread
+/// until it is dropped and the main loop collects the answers on every tick
+pub struct Discovery {
+    /// The games found so far, in the order they answered
+    pub games: Vec<LanGame>,
+    receiver: Receiver<LanGame>,
+    running: Arc<AtomicBool>,
+}
+
+impl Discovery {
+    /// Start asking the network for games every `QUERY_INTERVAL`
+    pub fn start() -> io::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_read_timeout(Some(RECEIVE_TIMEOUT))?;
+        let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let still_running = Arc::clone(&running);
+        thread::spawn(move || {
+            let query = query_packet();
+            let mut buffer = [0u8; 9000];
+            let mut last_query: Option<Instant> = None;
+            while still_running.load(Ordering::Relaxed) {
+                if last_query.is_none_or(|time| time.elapsed() >= QUERY_INTERVAL) {
+                    if let Err(e) = socket.send_to(&query, MDNS_ADDRESS) {
+                        log::warn!("Could not look for games on the network: {e}");
+                    }
+                    last_query = Some(Instant::now());
+                }
+                let Ok((length, address)) = socket.recv_from(&mut buffer) else {
+                    continue;
+                };
+                if let Some(game) = parse_announcement(&buffer[..length], address.ip()) {
+                    // Nobody looks at the games anymore
+                    if sender.send(game).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Discovery {
+            games: vec![],
+            receiver,
+            running,
+        })
+    }
+
+    /// Add the games that answered since the last tick, a host answering again is only updated
+    pub fn poll(&mut self) {
+        for game in self.receiver.try_iter() {
+            match self
+                .games
+                .iter_mut()
+                .find(|known| known.address == game.address)
+            {
+                Some(known) => *known = game,
+                None => self.games.push(game),
+            }
+        }
+    }
+}
+
+impl Drop for Discovery {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+// A socket receiving the mDNS packets of the local network, next to the other mDNS programs of the machine
+fn mdns_socket() -> io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, MDNS_ADDRESS.port()).into())?;
+    socket.join_multicast_v4(MDNS_ADDRESS.ip(), &Ipv4Addr::UNSPECIFIED)?;
+    socket.set_multicast_loop_v4(true)?;
+    Ok(socket.into())
+}
+
+/// The address of this machine on the local network, the one packets to the mDNS group leave from
+/// Nothing is sent to find it out
+pub fn local_ip() -> Ipv4Addr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect(MDNS_ADDRESS)?;
+            socket.local_addr()
+        })
+        .ok()
+        .and_then(|address| match address.ip() {
+            IpAddr::V4(ip) if !ip.is_unspecified() => Some(ip),
+            _ => None,
+        })
+        .unwrap_or(Ipv4Addr::LOCALHOST)
+}
@@ -0,0 +1,196 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::protocol::Message;
+use crate::{error::ChessError, pgn::PlayerNames, pieces::PieceColor, rng::Rng};
+
+/// Time given to the host to accept the connection and start the game
+pub const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Frames of the spinner shown while connecting, one per tick
+pub const SPINNER_FRAMES: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+/// Where the connection to the opponent stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeState {
+    /// Waiting for the host to accept the connection and start the game
+    Connecting,
+    /// The game started, the joining player plays this color against the named host
+    Connected(PieceColor, String),
+    /// The connection failed or timed out
+    Failed(ChessError),
+}
+
+/// The connection to the host of an online game
+/// The socket is opened on a background thread so the interface keeps running,
+/// the main loop polls it on every tick
+pub struct Handshake {
+    pub state: HandshakeState,
+    /// Sent to the host once the game started, to come back to it after losing the connection
+    pub session_token: String,
+    receiver: Receiver<Result<(TcpStream, PieceColor, String), ChessError>>,
+    started_at: Instant,
+    timeout: Duration,
+    ticks: usize,
+    stream: Option<TcpStream>,
+}
+
+impl Handshake {
+    /// Connect to the host at `address`, giving up after `timeout`
+    /// The name of `names` matching the color given by the host is sent to it
+    pub fn connect(address: SocketAddr, timeout: Duration, names: PlayerNames) -> Self {
+        let session_token = format!("{:016x}", Rng::from_entropy().next_u64());
+        let session = Message::Session(session_token.clone());
+        let mut handshake = Handshake::spawn(timeout, move || {
+            let (mut stream, color, host_name) = wait_for_game_start(address, timeout, &names)?;
+            writeln!(stream, "{session}")
+                .map_err(|error| ChessError::ConnectionFailed(error.to_string()))?;
+            Ok((stream, color, host_name))
+        });
+        handshake.session_token = session_token;
+        handshake
+    }
+
+    /// Run `handshake` on a background thread, it must return the stream,
+    /// the color of the player and the name of the host
+    pub fn spawn<F>(timeout: Duration, handshake: F) -> Self
+    where
+        F: FnOnce() -> Result<(TcpStream, PieceColor, String), ChessError> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // Nobody listens anymore when the handshake was cancelled
+            let _ = sender.send(handshake());
+        });
+        Handshake {
+            state: HandshakeState::Connecting,
+            session_token: String::new(),
+            receiver,
+            started_at: Instant::now(),
+            timeout,
+            ticks: 0,
+            stream: None,
+        }
+    }
+
+    /// Check on the background thread, a handshake is only ever over once
+    pub fn poll(&mut self, now: Instant) -> &HandshakeState {
+        if self.state != HandshakeState::Connecting {
+            return &self.state;
+        }
+        self.ticks += 1;
+
+        self.state = match self.receiver.try_recv() {
+            Ok(Ok((stream, color, host_name))) => {
+                self.stream = Some(stream);
+                HandshakeState::Connected(color, host_name)
+            }
+            Ok(Err(error)) => HandshakeState::Failed(error),
+            Err(TryRecvError::Disconnected) => HandshakeState::Failed(
+                ChessError::ConnectionFailed("the connection was interrupted".to_string()),
+            ),
+            Err(TryRecvError::Empty) if now.duration_since(self.started_at) >= self.timeout => {
+                HandshakeState::Failed(ChessError::ConnectionFailed(format!(
+                    "no answer after {} seconds",
+                    self.timeout.as_secs()
+                )))
+            }
+            Err(TryRecvError::Empty) => HandshakeState::Connecting,
+        };
+        &self.state
+    }
+
+    /// The spinner frame of the current tick
+    pub fn spinner(&self) -> &'static str {
+        SPINNER_FRAMES[self.ticks % SPINNER_FRAMES.len()]
+    }
+
+    /// The connection to the opponent, once the game started
+    pub fn take_stream(&mut self) -> Option<TcpStream> {
+        self.stream.take()
+    }
+}
+
+/// Open the connection and block until the host sends the start of the game and its name
+/// The name of `names` for the color given by the host is sent back, `?` when unknown
+pub fn wait_for_game_start(
+    address: SocketAddr,
+    timeout: Duration,
+    names: &PlayerNames,
+) -> Result<(TcpStream, PieceColor, String), ChessError> {
+    let failure = |error: std::io::Error| ChessError::ConnectionFailed(error.to_string());
+
+    let mut stream = TcpStream::connect_timeout(&address, timeout).map_err(failure)?;
+    stream.set_read_timeout(Some(timeout)).map_err(failure)?;
+
+    let Some(Message::Start(color)) = read_message(&stream)? else {
+        return Err(ChessError::ConnectionFailed(
+            "the host did not start the game".to_string(),
+        ));
+    };
+    let Some(Message::Name(host_name)) = read_message(&stream)? else {
+        return Err(ChessError::ConnectionFailed(
+            "the host did not give its name".to_string(),
+        ));
+    };
+    stream.set_read_timeout(None).map_err(failure)?;
+
+    let name = Message::Name(names.name_or_unknown(color).to_string());
+    writeln!(stream, "{name}").map_err(failure)?;
+    Ok((stream, color, host_name))
+}
+
+/// Open the connection again and block until the host takes the player of `session_token` back
+/// The moves the host knows of are returned with the stream, in UCI notation
+pub fn resume_game(
+    address: SocketAddr,
+    timeout: Duration,
+    session_token: &str,
+) -> Result<(TcpStream, Vec<String>), ChessError> {
+    let failure = |error: std::io::Error| ChessError::ConnectionFailed(error.to_string());
+
+    let mut stream = TcpStream::connect_timeout(&address, timeout).map_err(failure)?;
+    stream.set_read_timeout(Some(timeout)).map_err(failure)?;
+    writeln!(stream, "{}", Message::Resume(session_token.to_string())).map_err(failure)?;
+
+    match read_message(&stream)? {
+        Some(Message::Start(_)) => {}
+        Some(Message::Error(reason)) => return Err(ChessError::ConnectionFailed(reason)),
+        _ => {
+            return Err(ChessError::ConnectionFailed(
+                "the host did not take the game back".to_string(),
+            ))
+        }
+    }
+    let Some(Message::Name(_)) = read_message(&stream)? else {
+        return Err(ChessError::ConnectionFailed(
+            "the host did not give its name".to_string(),
+        ));
+    };
+    let Some(Message::History(moves)) = read_message(&stream)? else {
+        return Err(ChessError::ConnectionFailed(
+            "the host did not send the moves of the game".to_string(),
+        ));
+    };
+    stream.set_read_timeout(None).map_err(failure)?;
+    Ok((stream, moves))
+}
+
+// One line of the host, read byte by byte as a buffered reader could swallow the first move
+fn read_message(mut stream: &TcpStream) -> Result<Option<Message>, ChessError> {
+    let mut line = Vec::new();
+    let mut byte = [0u8];
+    loop {
+        stream
+            .read_exact(&mut byte)
+            .map_err(|error| ChessError::ConnectionFailed(error.to_string()))?;
+        match byte[0] {
+            b'\n' => break,
+            byte => line.push(byte),
+        }
+    }
+    Ok(Message::parse(&String::from_utf8_lossy(&line)))
+}
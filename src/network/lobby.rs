@@ -0,0 +1,105 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use crate::error::ChessError;
+
+/// Crockford's base32 alphabet, it has no I, L, O or U to avoid reading mistakes
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Number of characters encoding the address (4 bytes of IPv4 and 2 bytes of port)
+const ADDRESS_CHARACTERS: usize = 10;
+
+/// Number of characters in a group of the displayed code
+const GROUP_SIZE: usize = 4;
+
+/// Turn the host address into a short code the joining player can type
+/// e.g 192.168.1.2:2308 becomes `60N0-0G42-841`
+pub fn encode_game_code(address: SocketAddr) -> Result<String, ChessError> {
+    let IpAddr::V4(ip) = address.ip() else {
+        return Err(ChessError::InvalidGameCode(
+            "only IPv4 addresses can be shared with a game code".to_string(),
+        ));
+    };
+
+    let mut bits = u64::from(u32::from(ip)) << 16 | u64::from(address.port());
+    let mut values = [0u8; ADDRESS_CHARACTERS];
+    for value in values.iter_mut().rev() {
+        *value = (bits & 0x1f) as u8;
+        bits >>= 5;
+    }
+
+    let code: Vec<u8> = values
+        .iter()
+        .copied()
+        .chain(std::iter::once(checksum(&values)))
+        .map(|value| CODE_ALPHABET[value as usize])
+        .collect();
+
+    Ok(code
+        .chunks(GROUP_SIZE)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect::<Vec<String>>()
+        .join("-"))
+}
+
+/// Read back the host address from a game code
+/// The code is case insensitive, dashes and spaces are ignored
+pub fn decode_game_code(code: &str) -> Result<SocketAddr, ChessError> {
+    let values = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(code_character_value)
+        .collect::<Result<Vec<u8>, ChessError>>()?;
+
+    if values.len() != ADDRESS_CHARACTERS + 1 {
+        return Err(ChessError::InvalidGameCode(format!(
+            "expected {} characters, got {}",
+            ADDRESS_CHARACTERS + 1,
+            values.len()
+        )));
+    }
+
+    let (address_values, checksum_value) = values.split_at(ADDRESS_CHARACTERS);
+    if checksum(address_values) != checksum_value[0] {
+        return Err(ChessError::InvalidGameCode(
+            "the code contains a typo".to_string(),
+        ));
+    }
+
+    let bits = address_values
+        .iter()
+        .fold(0u64, |bits, value| bits << 5 | u64::from(*value));
+    // 10 characters hold 50 bits, the address only uses the lower 48
+    if bits >> 48 != 0 {
+        return Err(ChessError::InvalidGameCode(
+            "the code does not describe an address".to_string(),
+        ));
+    }
+
+    let ip = Ipv4Addr::from((bits >> 16) as u32);
+    let port = (bits & 0xffff) as u16;
+    Ok(SocketAddr::new(IpAddr::V4(ip), port))
+}
+
+/// Value of a code character, commonly confused characters are accepted
+fn code_character_value(c: char) -> Result<u8, ChessError> {
+    let c = match c.to_ascii_uppercase() {
+        'O' => '0',
+        'I' | 'L' => '1',
+        c => c,
+    };
+    CODE_ALPHABET
+        .iter()
+        .position(|alphabet_char| *alphabet_char as char == c)
+        .map(|position| position as u8)
+        .ok_or_else(|| ChessError::InvalidGameCode(format!("unexpected character '{c}'")))
+}
+
+/// Weighted sum of the values so that swapped characters are detected too
+fn checksum(values: &[u8]) -> u8 {
+    let sum: usize = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (i + 1) * *value as usize)
+        .sum();
+    (sum % 32) as u8
+}
@@ -0,0 +1,7 @@
+pub mod bot_server;
+pub mod discovery;
+pub mod handshake;
+pub mod lobby;
+pub mod opponent;
+pub mod protocol;
+pub mod spectate;
@@ -0,0 +1,172 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{
+    handshake::{resume_game, SPINNER_FRAMES},
+    protocol::Message,
+};
+use crate::error::ChessError;
+
+/// Time between two attempts to reach the host again after the connection dropped
+pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time after which a dropped connection is given up on, the host waits as long for the player
+pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What happened on the connection to the opponent since the last poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpponentEvent {
+    /// A line sent by the opponent
+    Message(Message),
+    /// The connection dropped, it is being opened again
+    Disconnected,
+    /// The host took the player back, with the moves of the game it knows of
+    Reconnected(Vec<String>),
+    /// The host could not be reached again in time
+    Lost(ChessError),
+}
+
+// What the background thread tells the opponent, the new stream is only kept by the opponent
+enum Update {
+    Message(Message),
+    Disconnected,
+    Reconnected(TcpStream, Vec<String>),
+    Lost(ChessError),
+}
+
+/// The connection to the opponent of an online game
+/// Its lines are read on a background thread, which also opens the connection again with the
+/// session token when it drops; the main loop polls it on every tick
+pub struct Opponent {
+    /// Since when the connection is being opened again, `None` while connected
+    pub reconnecting_since: Option<Instant>,
+    writer: TcpStream,
+    receiver: Receiver<Update>,
+    running: Arc<AtomicBool>,
+    ticks: usize,
+}
+
+impl Opponent {
+    /// Follow the connection on `stream`, opened with the host as the player of `session_token`
+    pub fn new(stream: TcpStream, session_token: String) -> Result<Self, ChessError> {
+        let failure = |error: std::io::Error| ChessError::ConnectionFailed(error.to_string());
+        let address = stream.peer_addr().map_err(failure)?;
+        let writer = stream.try_clone().map_err(failure)?;
+        let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+        thread::spawn(move || {
+            follow_connection(stream, address, &session_token, &sender, &thread_running);
+        });
+        Ok(Opponent {
+            reconnecting_since: None,
+            writer,
+            receiver,
+            running,
+            ticks: 0,
+        })
+    }
+
+    /// Send a line to the opponent
+    /// Lines sent while the connection is down are lost, the game is caught up once it is back
+    pub fn send(&mut self, message: &Message) {
+        if self.reconnecting_since.is_none() {
+            // A failed write shows up as a dropped connection on the reading side
+            let _ = writeln!(self.writer, "{message}");
+        }
+    }
+
+    /// Whether the connection dropped and is being opened again
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnecting_since.is_some()
+    }
+
+    /// What happened since the last poll
+    pub fn poll(&mut self, now: Instant) -> Vec<OpponentEvent> {
+        self.ticks += 1;
+        let mut events = vec![];
+        while let Ok(update) = self.receiver.try_recv() {
+            events.push(match update {
+                Update::Message(message) => OpponentEvent::Message(message),
+                Update::Disconnected => {
+                    self.reconnecting_since = Some(now);
+                    OpponentEvent::Disconnected
+                }
+                Update::Reconnected(writer, moves) => {
+                    self.writer = writer;
+                    self.reconnecting_since = None;
+                    OpponentEvent::Reconnected(moves)
+                }
+                Update::Lost(error) => OpponentEvent::Lost(error),
+            });
+        }
+        events
+    }
+
+    /// The spinner frame of the current tick
+    pub fn spinner(&self) -> &'static str {
+        SPINNER_FRAMES[self.ticks % SPINNER_FRAMES.len()]
+    }
+}
+
+impl Drop for Opponent {
+    fn drop(&mut self) {
+        // The background thread stops once its connection is closed
+        self.running.store(false, Ordering::Relaxed);
+        let _ = self.writer.shutdown(Shutdown::Both);
+    }
+}
+
+// Read the lines of the opponent, and open the connection again each time it drops
+fn follow_connection(
+    mut stream: TcpStream,
+    address: SocketAddr,
+    session_token: &str,
+    sender: &Sender<Update>,
+    running: &AtomicBool,
+) {
+    loop {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(message) = Message::parse(&line) {
+                if sender.send(Update::Message(message)).is_err() {
+                    return;
+                }
+            }
+        }
+        if !running.load(Ordering::Relaxed) || sender.send(Update::Disconnected).is_err() {
+            return;
+        }
+
+        let started_at = Instant::now();
+        stream = loop {
+            let error = match resume_game(address, RECONNECT_INTERVAL, session_token) {
+                Ok((stream, moves)) => match stream.try_clone() {
+                    Ok(writer) => {
+                        if sender.send(Update::Reconnected(writer, moves)).is_err() {
+                            return;
+                        }
+                        break stream;
+                    }
+                    Err(error) => ChessError::ConnectionFailed(error.to_string()),
+                },
+                Err(error) => error,
+            };
+            if started_at.elapsed() >= RECONNECT_TIMEOUT {
+                let _ = sender.send(Update::Lost(error));
+                return;
+            }
+            thread::sleep(RECONNECT_INTERVAL);
+            if !running.load(Ordering::Relaxed) {
+                return;
+            }
+        };
+    }
+}
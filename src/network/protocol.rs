@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::pieces::PieceColor;
+
+/// A line sent between the two players of an online game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// The host starts the game, giving the color of the joining player
+    Start(PieceColor),
+    /// The name of the player sending it, exchanged right after the start of the game
+    Name(String),
+    /// A move in UCI notation
+    Move(String),
+    /// A chat message
+    Chat(String),
+    /// The last message could not be applied
+    Error(String),
+    /// The token the joining player sends after its name, to come back to the game later
+    Session(String),
+    /// The first line of a player coming back with the token of its session
+    Resume(String),
+    /// The moves played so far in UCI notation, sent by the host to the player coming back
+    History(Vec<String>),
+    /// The player sending it resigned
+    Resign,
+}
+
+impl Message {
+    /// Parse a received line, `None` for anything that is not a message
+    pub fn parse(line: &str) -> Option<Message> {
+        // The history of a game without moves has no content
+        match line.trim() {
+            "history" => return Some(Message::History(vec![])),
+            "resign" => return Some(Message::Resign),
+            _ => {}
+        }
+        let (kind, content) = line.trim().split_once(' ')?;
+        let content = content.trim().to_string();
+        match kind {
+            "start" => match content.as_str() {
+                "white" => Some(Message::Start(PieceColor::White)),
+                "black" => Some(Message::Start(PieceColor::Black)),
+                _ => None,
+            },
+            "name" if !content.is_empty() => Some(Message::Name(content)),
+            "move" if !content.is_empty() => Some(Message::Move(content)),
+            "chat" => Some(Message::Chat(content)),
+            "error" => Some(Message::Error(content)),
+            "session" if !content.is_empty() => Some(Message::Session(content)),
+            "resume" if !content.is_empty() => Some(Message::Resume(content)),
+            "history" => Some(Message::History(
+                content.split_whitespace().map(str::to_string).collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Messages are sent one per line
+        let line = |content: &str| content.replace(['\n', '\r'], " ");
+        match self {
+            Message::Start(PieceColor::White) => write!(f, "start white"),
+            Message::Start(PieceColor::Black) => write!(f, "start black"),
+            Message::Name(name) => write!(f, "name {}", line(name)),
+            Message::Move(uci_move) => write!(f, "move {}", line(uci_move)),
+            Message::Chat(text) => write!(f, "chat {}", line(text)),
+            Message::Error(reason) => write!(f, "error {}", line(reason)),
+            Message::Session(token) => write!(f, "session {}", line(token)),
+            Message::Resume(token) => write!(f, "resume {}", line(token)),
+            Message::History(moves) if moves.is_empty() => write!(f, "history"),
+            Message::History(moves) => write!(f, "history {}", moves.join(" ")),
+            Message::Resign => write!(f, "resign"),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use crate::game_logic::game::Game;
+
+/// What a spectator needs to follow the game: the position and the moves leading to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSnapshot {
+    pub fen: String,
+    /// The moves played since the start, in UCI notation
+    pub moves: Vec<String>,
+}
+
+impl GameSnapshot {
+    pub fn from_game(game: &Game) -> Self {
+        GameSnapshot {
+            fen: game.to_fen(),
+            moves: game.game_board.uci_moves(),
+        }
+    }
+
+    /// The snapshot as a JSON object, FEN and UCI moves never hold characters to escape
+    pub fn to_json(&self) -> String {
+        let moves: Vec<String> = self.moves.iter().map(|uci| format!("\"{uci}\"")).collect();
+        format!(
+            "{{\"fen\":\"{}\",\"moves\":[{}]}}",
+            self.fen,
+            moves.join(",")
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct SpectatorList {
+    subscribers: Vec<Sender<String>>,
+    /// The latest snapshot, in JSON, sent to the spectators as soon as they arrive
+    latest: Option<String>,
+}
+
+/// The spectators watching the game, each one receives the snapshots in JSON as moves are applied
+/// Clones share the same spectators, so the server threads can add new ones
+#[derive(Debug, Clone, Default)]
+pub struct Spectators {
+    list: Arc<Mutex<SpectatorList>>,
+}
+
+impl Spectators {
+    /// A new spectator, it starts with the latest snapshot when there is one
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        let mut list = self.list.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(latest) = &list.latest {
+            let _ = sender.send(latest.clone());
+        }
+        list.subscribers.push(sender);
+        receiver
+    }
+
+    /// Send the snapshot to every spectator, the ones who left are forgotten
+    pub fn broadcast(&self, snapshot: &GameSnapshot) {
+        let json = snapshot.to_json();
+        let mut list = self.list.lock().unwrap_or_else(|e| e.into_inner());
+        list.subscribers
+            .retain(|subscriber| subscriber.send(json.clone()).is_ok());
+        list.latest = Some(json);
+    }
+
+    /// The latest snapshot sent, in JSON
+    pub fn latest(&self) -> Option<String> {
+        self.list
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .latest
+            .clone()
+    }
+
+    pub fn count(&self) -> usize {
+        self.list
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .subscribers
+            .len()
+    }
+}
+
+/// The page following the game, it draws the board of every snapshot received on `/events`
+#[cfg(feature = "server")]
+const SPECTATOR_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>chess-tui</title></head>
+<body style="font-family: sans-serif">
+<pre id="board" style="font-size: 2em; line-height: 1.1"></pre>
+<p id="fen"></p>
+<p id="moves"></p>
+<script>
+const pieces = { K: "♔", Q: "♕", R: "♖", B: "♗", N: "♘", P: "♙",
+                 k: "♚", q: "♛", r: "♜", b: "♝", n: "♞", p: "♟" };
+new EventSource("/events").onmessage = (event) => {
+  const state = JSON.parse(event.data);
+  document.getElementById("board").textContent = state.fen.split(" ")[0].split("/")
+    .map((rank) => rank.replace(/\d/g, (n) => "·".repeat(n)).replace(/./g, (c) => pieces[c] || c))
+    .join("\n");
+  document.getElementById("fen").textContent = state.fen;
+  document.getElementById("moves").textContent = state.moves.join(" ");
+};
+</script>
+</body>
+</html>
+"#;
+
+/// Serve the game to browsers on `port`: the page on `/`, the latest snapshot on `/state`
+/// and a stream of Server-Sent Events on `/events`, pushed every time a move is applied
+#[cfg(feature = "server")]
+pub fn start_spectator_server(port: u16) -> io::Result<Spectators> {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    let spectators = Spectators::default();
+    let server_spectators = spectators.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let spectators = server_spectators.clone();
+            std::thread::spawn(move || {
+                if let Err(error) = serve_spectator(stream, &spectators) {
+                    log::debug!("Spectator left: {error}");
+                }
+            });
+        }
+    });
+    Ok(spectators)
+}
+
+// Answer one request, the event stream stays open until the browser leaves
+#[cfg(feature = "server")]
+fn serve_spectator(stream: std::net::TcpStream, spectators: &Spectators) -> io::Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers are not needed
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim() != "" {
+        header.clear();
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (content_type, body) = match path {
+        "/" => ("text/html; charset=utf-8", SPECTATOR_PAGE.to_string()),
+        "/state" => ("application/json", spectators.latest().unwrap_or_default()),
+        "/events" => {
+            write!(
+                writer,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nAccess-Control-Allow-Origin: *\r\n\r\n"
+            )?;
+            writer.flush()?;
+            for json in spectators.subscribe() {
+                write!(writer, "data: {json}\n\n")?;
+                writer.flush()?;
+            }
+            return Ok(());
+        }
+        _ => {
+            return write!(
+                writer,
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+        }
+    };
+    write!(
+        writer,
+        "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Without the `server` feature the game can't be watched from a browser
+#[cfg(not(feature = "server"))]
+pub fn start_spectator_server(_port: u16) -> io::Result<Spectators> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "chess-tui was built without the `server` feature",
+    ))
+}
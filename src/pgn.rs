@@ -0,0 +1,507 @@
+use std::{fs, path::Path};
+
+use crate::{error::ChessError, game_logic::game::Game, pieces::PieceColor};
+
+/// Longest line of the moves, as advised by the PGN standard
+const MAX_LINE_LENGTH: usize = 79;
+
+/// The tokens ending the moves of a game
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// The names of the players written in the PGN headers, `None` when unknown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerNames {
+    pub white: Option<String>,
+    pub black: Option<String>,
+}
+
+impl PlayerNames {
+    /// The name of the player of a color, if known
+    pub fn get(&self, color: PieceColor) -> Option<&str> {
+        match color {
+            PieceColor::White => self.white.as_deref(),
+            PieceColor::Black => self.black.as_deref(),
+        }
+    }
+
+    /// The name of the player of a color, `?` when unknown like PGN expects
+    pub fn name_or_unknown(&self, color: PieceColor) -> &str {
+        self.get(color).unwrap_or("?")
+    }
+}
+
+/// The headers of an exported game the player can edit before it is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnTags {
+    pub white: String,
+    pub black: String,
+    pub event: String,
+    /// Written `YYYY.MM.DD`
+    pub date: String,
+}
+
+impl PgnTags {
+    /// The tags in the order of the editor
+    pub const EDITABLE: [&'static str; 4] = ["White", "Black", "Event", "Date"];
+
+    /// The tags of a casual game between `names` played on `date`, unknown players are left empty
+    pub fn new(names: &PlayerNames, date: &str) -> Self {
+        PgnTags {
+            white: names.white.clone().unwrap_or_default(),
+            black: names.black.clone().unwrap_or_default(),
+            event: "Casual game".to_string(),
+            date: date.to_string(),
+        }
+    }
+
+    /// The value of the tag at `index` in [`PgnTags::EDITABLE`]
+    pub fn value(&self, index: usize) -> &str {
+        match index {
+            0 => &self.white,
+            1 => &self.black,
+            2 => &self.event,
+            _ => &self.date,
+        }
+    }
+
+    pub fn value_mut(&mut self, index: usize) -> &mut String {
+        match index {
+            0 => &mut self.white,
+            1 => &mut self.black,
+            2 => &mut self.event,
+            _ => &mut self.date,
+        }
+    }
+}
+
+/// The game in Portable Game Notation under the headers of `tags`, an empty tag is written `?`
+/// Games set up from a FEN carry it in the `SetUp` and `FEN` headers
+pub fn to_pgn(game: &Game, tags: &PgnTags) -> String {
+    to_annotated_pgn(game, tags, &[], &[])
+}
+
+/// The game in Portable Game Notation like [`to_pgn`], along with the `{comments}` of its
+/// positions, the initial one first, and its `(sidelines)`
+pub fn to_annotated_pgn(
+    game: &Game,
+    tags: &PgnTags,
+    comments: &[Option<String>],
+    variations: &[PgnVariation],
+) -> String {
+    let result = game.result().unwrap_or("*");
+    let or_unknown = |value: &str| match value.trim() {
+        "" => "?".to_string(),
+        value => value.to_string(),
+    };
+    let (event, date) = (or_unknown(&tags.event), or_unknown(&tags.date));
+    let (white, black) = (or_unknown(&tags.white), or_unknown(&tags.black));
+    let mut headers = vec![
+        ("Event", event.as_str()),
+        ("Site", "chess-tui"),
+        ("Date", date.as_str()),
+        ("Round", "-"),
+        ("White", white.as_str()),
+        ("Black", black.as_str()),
+        ("Result", result),
+    ];
+    if let Some(starting_fen) = &game.starting_fen {
+        headers.push(("SetUp", "1"));
+        headers.push(("FEN", starting_fen));
+    }
+
+    let mut pgn: String = headers
+        .iter()
+        .map(|(tag, value)| format!("[{tag} \"{}\"]\n", value.replace('"', "'")))
+        .collect();
+    pgn.push('\n');
+
+    // Black opened a game set up from a FEN, its move comes with an ellipsis
+    let black_moved_first = game
+        .game_board
+        .move_history
+        .first()
+        .is_some_and(|first_move| first_move.piece_color == PieceColor::Black);
+    let main_line = PgnLine {
+        moves: game.san_moves(),
+        comments: comments.to_vec(),
+        variations: variations.to_vec(),
+    };
+    let mut tokens = vec![];
+    line_tokens(&main_line, usize::from(black_moved_first), &mut tokens);
+    tokens.push(result.to_string());
+
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+            pgn.push_str(&line);
+            pgn.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&token);
+    }
+    pgn.push_str(&line);
+    pgn.push('\n');
+    pgn
+}
+
+// The words of the moves of `line` numbered from `first_halfmove`, with their comments
+// and sidelines, a black move gets its number again after either of them
+fn line_tokens(line: &PgnLine, first_halfmove: usize, tokens: &mut Vec<String>) {
+    let comment_token = |comment: &str| format!("{{{}}}", comment.replace('}', ")"));
+    if let Some(Some(comment)) = line.comments.first() {
+        tokens.push(comment_token(comment));
+    }
+    let mut needs_number = true;
+    for (index, san) in line.moves.iter().enumerate() {
+        let halfmove = first_halfmove + index;
+        let number = halfmove / 2 + 1;
+        match (halfmove % 2, needs_number) {
+            (0, _) => tokens.push(format!("{number}. {san}")),
+            (_, true) => tokens.push(format!("{number}... {san}")),
+            _ => tokens.push(san.clone()),
+        }
+        needs_number = false;
+        if let Some(Some(comment)) = line.comments.get(index + 1) {
+            tokens.push(comment_token(comment));
+            needs_number = true;
+        }
+        for variation in line.variations.iter().filter(|v| v.branch == index) {
+            let mut variation_tokens = vec![];
+            line_tokens(&variation.line, halfmove, &mut variation_tokens);
+            if let Some(first) = variation_tokens.first_mut() {
+                first.insert(0, '(');
+            }
+            if let Some(last) = variation_tokens.last_mut() {
+                last.push(')');
+                needs_number = true;
+            }
+            tokens.extend(variation_tokens);
+        }
+    }
+}
+
+/// Moves in standard algebraic notation with the `{comments}` of their positions and the
+/// `(sidelines)` branching off them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgnLine {
+    pub moves: Vec<String>,
+    /// The comments of the position before the first move then of the one after each move
+    pub comments: Vec<Option<String>>,
+    pub variations: Vec<PgnVariation>,
+}
+
+/// A sideline of a PGN game, played instead of a move of the line it branches off
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnVariation {
+    /// Index of the move of the parent line the sideline replaces, it starts from the
+    /// position before that move
+    pub branch: usize,
+    pub line: PgnLine,
+}
+
+impl PgnVariation {
+    /// The sideline counted from the start of the game instead of from the line it branches
+    /// off, which starts `offset` moves into the game
+    pub fn shifted(&self, offset: usize) -> PgnVariation {
+        PgnVariation {
+            branch: self.branch + offset,
+            line: self.line.clone(),
+        }
+    }
+}
+
+/// A game read from a PGN file, its moves are in standard algebraic notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The tag pairs in the order of the file
+    pub headers: Vec<(String, String)>,
+    pub moves: Vec<String>,
+    /// The `{comments}` of each position, the initial one first then the one after each move
+    pub comments: Vec<Option<String>>,
+    /// The `(sidelines)` branching off the moves, which may hold sidelines of their own
+    pub variations: Vec<PgnVariation>,
+}
+
+impl PgnGame {
+    /// The value of a header, if the game has it
+    pub fn header(&self, tag: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name == tag)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The comment on the position reached after `index` moves, if the file has one
+    pub fn comment(&self, index: usize) -> Option<&str> {
+        self.comments.get(index)?.as_deref()
+    }
+
+    /// The players and the result of the game, to tell the games of a file apart
+    pub fn title(&self) -> String {
+        format!(
+            "{} vs {} ({})",
+            self.header("White").unwrap_or("?"),
+            self.header("Black").unwrap_or("?"),
+            self.header("Result").unwrap_or("*")
+        )
+    }
+
+    /// Play the moves from the initial position, or the one of the `FEN` header
+    pub fn to_game(&self) -> Result<Game, ChessError> {
+        let mut game = self.starting_game()?;
+        for san in &self.moves {
+            game.play_algebraic_move(san)?;
+        }
+        Ok(game)
+    }
+
+    // The initial position, or the one of the `FEN` header
+    fn starting_game(&self) -> Result<Game, ChessError> {
+        match self.header("FEN") {
+            Some(fen) => Game::from_fen(fen),
+            None => Ok(Game::default()),
+        }
+    }
+}
+
+/// The game following a sideline of `start`: its first `branch` moves, then the moves of
+/// `variation`
+pub fn sideline_game(start: &Game, branch: usize, variation: &PgnLine) -> Result<Game, ChessError> {
+    let mut game = match &start.starting_fen {
+        Some(fen) => Game::from_fen(fen)?,
+        None => Game::default(),
+    };
+    for san in start
+        .san_moves()
+        .iter()
+        .take(branch)
+        .chain(variation.moves.iter())
+    {
+        game.play_algebraic_move(san)?;
+    }
+    Ok(game)
+}
+
+// Keep the sidelines of `line` whose moves can be played from the position they branch off,
+// `start` being the position before the first move of the line
+fn retain_legal_variations(line: &mut PgnLine, start: &Game) {
+    let mut positions = vec![start.clone()];
+    let mut game = start.clone();
+    for san in &line.moves {
+        if game.play_algebraic_move(san).is_err() {
+            break;
+        }
+        positions.push(game.clone());
+    }
+    line.variations.retain_mut(|variation| {
+        let Some(branch_position) = positions.get(variation.branch) else {
+            return false;
+        };
+        let mut game = branch_position.clone();
+        if let Some(Err(e)) = variation
+            .line
+            .moves
+            .iter()
+            .map(|san| game.play_algebraic_move(san))
+            .find(Result::is_err)
+        {
+            log::warn!("Skipped a sideline of the PGN game: {e}");
+            return false;
+        }
+        retain_legal_variations(&mut variation.line, branch_position);
+        true
+    });
+}
+
+// The moves, comments and sidelines of the movetext up to the end of the current sideline
+fn parse_line(tokens: &mut impl Iterator<Item = MovetextToken>) -> PgnLine {
+    let mut line = PgnLine {
+        comments: vec![None],
+        ..Default::default()
+    };
+    while let Some(token) = tokens.next() {
+        let token = match token {
+            MovetextToken::Comment(comment) => {
+                let position_comment = line
+                    .comments
+                    .last_mut()
+                    .expect("the initial position is there");
+                *position_comment = Some(match position_comment.take() {
+                    Some(previous) => format!("{previous} {comment}"),
+                    None => comment,
+                });
+                continue;
+            }
+            // A sideline replaces the latest move
+            MovetextToken::VariationStart => {
+                let variation = parse_line(tokens);
+                if let Some(branch) = line.moves.len().checked_sub(1) {
+                    if !variation.moves.is_empty() {
+                        line.variations.push(PgnVariation {
+                            branch,
+                            line: variation,
+                        });
+                    }
+                }
+                continue;
+            }
+            MovetextToken::VariationEnd => break,
+            MovetextToken::Word(token) => token,
+        };
+        // A move number may be glued to the move (`12.e4`, `12...e5`)
+        let san = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        if san.is_empty() || san.starts_with('$') || RESULTS.contains(&token.as_str()) {
+            continue;
+        }
+        line.moves
+            .push(san.trim_end_matches(['!', '?']).to_string());
+        line.comments.push(None);
+    }
+    line
+}
+
+/// Read a single game, its moves must be legal
+/// Comments are kept with the position they follow and sidelines with the move they replace,
+/// a sideline with an illegal move is left out with a warning in the logs
+pub fn parse_pgn(text: &str) -> Result<PgnGame, ChessError> {
+    let mut headers = vec![];
+    let mut movetext = String::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(tag_pair) = line.strip_prefix('[') {
+            headers.push(parse_tag_pair(tag_pair)?);
+        } else {
+            movetext.push_str(line);
+            movetext.push('\n');
+        }
+    }
+
+    let mut main_line = parse_line(&mut movetext_tokens(&movetext).into_iter());
+    if headers.is_empty() && main_line.moves.is_empty() {
+        return Err(ChessError::InvalidPgn("the game is empty".to_string()));
+    }
+
+    let mut game = PgnGame {
+        headers,
+        moves: main_line.moves.clone(),
+        comments: main_line.comments.clone(),
+        variations: vec![],
+    };
+    game.to_game()?;
+    retain_legal_variations(&mut main_line, &game.starting_game()?);
+    game.variations = main_line.variations;
+    Ok(game)
+}
+
+/// The games of a PGN file, each one starts with its `[Event` header
+pub fn split_games(text: &str) -> Vec<String> {
+    let mut games: Vec<String> = vec![];
+    for line in text.lines() {
+        match games.last_mut() {
+            Some(game) if !line.trim_start().starts_with("[Event ") => {
+                game.push_str(line);
+                game.push('\n');
+            }
+            _ => games.push(format!("{line}\n")),
+        }
+    }
+    games.retain(|game| !game.trim().is_empty());
+    games
+}
+
+/// Every game of a PGN file that could be read, the other ones are skipped with a warning in the logs
+pub fn parse_games(text: &str) -> Vec<PgnGame> {
+    split_games(text)
+        .iter()
+        .enumerate()
+        .filter_map(|(index, game)| match parse_pgn(game) {
+            Ok(game) => Some(game),
+            Err(e) => {
+                log::warn!("Skipped game {} of the PGN file: {e}", index + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Read the games of the PGN file at `path`
+pub fn load_games(path: &Path) -> Result<Vec<PgnGame>, ChessError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ChessError::InvalidPgn(format!("{}: {e}", path.display())))?;
+    Ok(parse_games(&content))
+}
+
+// `Tag "value"]` without its opening bracket
+fn parse_tag_pair(tag_pair: &str) -> Result<(String, String), ChessError> {
+    let invalid = || ChessError::InvalidPgn(format!("[{tag_pair} is not a header"));
+    let (tag, value) = tag_pair
+        .strip_suffix(']')
+        .and_then(|tag_pair| tag_pair.split_once(' '))
+        .ok_or_else(invalid)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    Ok((tag.to_string(), value.replace("\\\"", "\"")))
+}
+
+// A piece of movetext: a move, a move number or a result, a comment or the bounds of a sideline
+enum MovetextToken {
+    Word(String),
+    Comment(String),
+    VariationStart,
+    VariationEnd,
+}
+
+// The words, the `{comments}` or `; comments` and the parentheses of the `(variations)`,
+// which may be nested, of the movetext, a closing parenthesis without its opening one is left out
+fn movetext_tokens(movetext: &str) -> Vec<MovetextToken> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    let mut variation_depth = 0usize;
+    let mut chars = movetext.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '{' => Some(MovetextToken::Comment(
+                chars.by_ref().take_while(|&c| c != '}').collect(),
+            )),
+            ';' => Some(MovetextToken::Comment(
+                chars.by_ref().take_while(|&c| c != '\n').collect(),
+            )),
+            '(' => {
+                variation_depth += 1;
+                Some(MovetextToken::VariationStart)
+            }
+            ')' if variation_depth > 0 => {
+                variation_depth -= 1;
+                Some(MovetextToken::VariationEnd)
+            }
+            c if !c.is_whitespace() && c != ')' => {
+                word.push(c);
+                continue;
+            }
+            _ => None,
+        };
+        if !word.is_empty() {
+            tokens.push(MovetextToken::Word(std::mem::take(&mut word)));
+        }
+        match token {
+            Some(MovetextToken::Comment(comment)) => {
+                let comment = comment.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !comment.is_empty() {
+                    tokens.push(MovetextToken::Comment(comment));
+                }
+            }
+            Some(token) => tokens.push(token),
+            None => {}
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(MovetextToken::Word(word));
+    }
+    tokens
+}
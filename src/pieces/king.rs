@@ -3,6 +3,7 @@ use crate::constants::DisplayMode;
 use crate::game_logic::coord::Coord;
 use crate::game_logic::game_board::GameBoard;
 use crate::utils::{cleaned_positions, is_cell_color_ally};
+use std::collections::HashSet;
 pub struct King;
 
 impl Movable for King {
@@ -21,6 +22,9 @@ impl Movable for King {
         for &dy in &[-1i8, 0, 1] {
             for &dx in &[-1i8, 0, 1] {
                 // Skip the case where both dx and dy are zero (the current position)
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
                 let new_x = x as i8 + dx;
                 let new_y = y as i8 + dy;
 
@@ -46,48 +50,45 @@ impl Position for King {
         is_king_checked: bool,
     ) -> Vec<Coord> {
         let mut positions: Vec<Coord> = vec![];
-        let checked_cells = game_board.get_all_protected_cells(color);
+        let checked_cells = game_board.attacked_squares(color.opposite());
 
-        let rook_big_castle_x = 0;
-        let rook_small_castle_x = 7;
         let king_row = 7;
-        let king_col = if color == PieceColor::White { 4 } else { 3 };
-
-        // We check the condition for small and big castling
-        if !game_board.did_piece_already_move((
-            Some(PieceType::King),
-            Some(color),
-            Coord::new(king_row, king_col),
-        )) && !is_king_checked
-        {
-            // We check if there is no pieces between tower and king
-            // Big castle check
-            if !game_board.did_piece_already_move((
-                Some(PieceType::Rook),
-                Some(color),
-                Coord::new(king_row, rook_big_castle_x),
-            )) && King::check_castling_condition(
-                game_board,
-                color,
-                0,
-                king_col as i8 - 1,
-                &checked_cells,
-            ) {
-                positions.push(Coord::new(king_row, 0));
-            }
-            // Small castle check
-            if !game_board.did_piece_already_move((
-                Some(PieceType::Rook),
-                Some(color),
-                Coord::new(king_row, rook_small_castle_x),
-            )) && King::check_castling_condition(
-                game_board,
-                color,
-                king_col as i8 + 1,
-                7,
-                &checked_cells,
-            ) {
-                positions.push(Coord::new(king_row, 7));
+        // The castling rights give files seen from white, the board is flipped for black
+        let col_of = |file: u8| match color {
+            PieceColor::White => file,
+            PieceColor::Black => 7 - file,
+        };
+        let king_moved = game_board
+            .move_history
+            .iter()
+            .any(|entry| entry.piece_type == PieceType::King && entry.piece_color == color);
+
+        // We check the condition for small and big castling, the king moving on the rook cell
+        if coordinates.row == king_row && !king_moved && !is_king_checked {
+            for king_side in [true, false] {
+                if !game_board.castling_rights.is_allowed(color, king_side) {
+                    continue;
+                }
+                let rook_col = col_of(game_board.castling_rights.rook_file(color, king_side));
+                // Whatever their files, the king and the rook end up on the g and f files or the c and d files
+                let (king_target, rook_target) = if king_side { (6, 5) } else { (2, 3) };
+                let is_on_its_side = (col_of(rook_col) > col_of(coordinates.col)) == king_side;
+                if is_on_its_side
+                    && !game_board.did_piece_already_move((
+                        Some(PieceType::Rook),
+                        Some(color),
+                        Coord::new(king_row, rook_col),
+                    ))
+                    && King::check_castling_condition(
+                        game_board,
+                        color,
+                        (coordinates.col, col_of(king_target)),
+                        (rook_col, col_of(rook_target)),
+                        &checked_cells,
+                    )
+                {
+                    positions.push(Coord::new(king_row, rook_col));
+                }
             }
         }
 
@@ -129,33 +130,31 @@ impl King {
         }
     }
 
-    // Check if nothing is in between the king and a rook and if none of those cells are getting checked
+    // Check if the rook is there and nothing but the king and the rook stands between them and their cells
+    // after castling, given as (from, to) columns, and if none of the cells the king goes through is getting checked
     pub fn check_castling_condition(
         game_board: &GameBoard,
         color: PieceColor,
-        start: i8,
-        end: i8,
-        checked_cells: &[Coord],
+        king: (u8, u8),
+        rook: (u8, u8),
+        checked_cells: &HashSet<Coord>,
     ) -> bool {
         let king_row = 7;
-
-        let mut valid_for_castling = true;
-
-        for i in start..=end {
-            let new_coordinates = Coord::new(king_row, i as u8);
-
-            if checked_cells.contains(&new_coordinates) {
-                valid_for_castling = false;
-            }
-            if (i == 7 || i == 0)
-                && (game_board.get_piece_type(&new_coordinates) != Some(PieceType::Rook)
-                    || !is_cell_color_ally(game_board, &new_coordinates, color))
-                || (i != 7 && i != 0 && game_board.get_piece_type(&new_coordinates).is_some())
-            {
-                valid_for_castling = false;
-            }
-        }
-
-        valid_for_castling
+        let span = |(from, to): (u8, u8)| from.min(to)..=from.max(to);
+
+        let rook_cell = Coord::new(king_row, rook.0);
+        let has_rook = game_board.get_piece_type(&rook_cell) == Some(PieceType::Rook)
+            && is_cell_color_ally(game_board, &rook_cell, color);
+        let is_path_empty = span(king).chain(span(rook)).all(|col| {
+            col == king.0
+                || col == rook.0
+                || game_board
+                    .get_piece_type(&Coord::new(king_row, col))
+                    .is_none()
+        });
+        let is_path_safe =
+            span(king).all(|col| !checked_cells.contains(&Coord::new(king_row, col)));
+
+        has_rook && is_path_empty && is_path_safe
     }
 }
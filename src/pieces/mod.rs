@@ -74,6 +74,29 @@ impl PieceType {
         }
     }
 
+    /// The usual material value of a piece in pawns, the king is priceless and counts for nothing
+    pub fn value(self) -> u32 {
+        match self {
+            PieceType::Pawn => 1,
+            PieceType::Knight | PieceType::Bishop => 3,
+            PieceType::Rook => 5,
+            PieceType::Queen => 9,
+            PieceType::King => 0,
+        }
+    }
+
+    /// The name of the piece in a sentence
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceType::Pawn => "pawn",
+            PieceType::Knight => "knight",
+            PieceType::Bishop => "bishop",
+            PieceType::Rook => "rook",
+            PieceType::Queen => "queen",
+            PieceType::King => "king",
+        }
+    }
+
     /// Convert a PieceType to a symbol
     pub fn piece_to_utf_enum(
         piece_type: &PieceType,
@@ -119,6 +142,25 @@ impl PieceType {
         }
     }
 
+    /// Convert a fen character to a PieceType and its color
+    pub fn piece_from_fen_char(c: char) -> Option<(PieceType, PieceColor)> {
+        let piece_type = match c.to_ascii_lowercase() {
+            'p' => PieceType::Pawn,
+            'r' => PieceType::Rook,
+            'n' => PieceType::Knight,
+            'b' => PieceType::Bishop,
+            'q' => PieceType::Queen,
+            'k' => PieceType::King,
+            _ => return None,
+        };
+        let piece_color = if c.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+        Some((piece_type, piece_color))
+    }
+
     pub fn piece_type_to_string_enum(
         piece_type: Option<PieceType>,
         display_mode: &DisplayMode,
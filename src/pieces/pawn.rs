@@ -2,7 +2,7 @@ use super::{Movable, PieceColor, PieceMove, PieceType, Position};
 use crate::constants::DisplayMode;
 use crate::game_logic::coord::Coord;
 use crate::game_logic::game_board::GameBoard;
-use crate::utils::{cleaned_positions, invert_position, is_cell_color_ally};
+use crate::utils::{cleaned_positions, flip_coord, is_cell_color_ally};
 
 pub struct Pawn;
 
@@ -98,7 +98,7 @@ impl Movable for Pawn {
         if let Some(latest_move) = game_board.move_history.last() {
             let number_of_cells_move = latest_move.to.row as i8 - latest_move.from.row as i8;
 
-            let last_coords = invert_position(&Coord::new(latest_move.to.row, latest_move.to.col));
+            let last_coords = flip_coord(latest_move.to);
             // We check if the latest move was on the right start cell
             // if it moved 2 cells
             // and if the current pawn is next to this pawn latest position
@@ -111,6 +111,14 @@ impl Movable for Pawn {
                 let new_x = last_coords.col;
                 positions.push(Coord::new(new_y, new_x));
             }
+        } else if let Some(target) = game_board.en_passant_target {
+            // The position comes from a FEN, the double pawn push is not in the history
+            if !allow_move_on_ally_positions
+                && y == target.row + 1
+                && (x as i8 - target.col as i8).abs() == 1
+            {
+                positions.push(target);
+            }
         }
         cleaned_positions(&positions)
     }
@@ -0,0 +1,40 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small seedable random number generator (SplitMix64)
+/// Games started with the same seed make the same random choices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// A generator seeded differently on every run
+    pub fn from_entropy() -> Self {
+        // The hasher keys of the standard library are random for each process
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
+        Rng::from_seed(hasher.finish())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, `bound` has to be positive
+    pub fn below(&mut self, bound: usize) -> usize {
+        // The modulo bias is negligible for the small bounds used in a chess game
+        (self.next_u64() % bound as u64) as usize
+    }
+}
@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use toml::{value::Table, Value};
+
+use crate::{
+    error::ChessError,
+    game_logic::{clock::Clock, game::Game},
+    pieces::PieceColor,
+};
+
+/// Version of the saved games written by this version of chess-tui
+/// Bump it when the format changes and add the matching step to [`migrate`]
+pub const SAVE_FORMAT_VERSION: u32 = 3;
+
+/// The file next to the configuration the game in progress is written to after every move
+pub const AUTOSAVE_FILE: &str = "autosave.toml";
+
+/// The FEN of the standard starting position
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// A game written to disk, in the latest format
+///
+/// ## Format history
+/// - version 1: the moves played from the standard starting position
+/// - version 2: the starting position is stored as a FEN to resume games loaded from a FEN
+/// - version 3: the time left on the clocks of a timed game and the color played against the bot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedGame {
+    /// The position the game started from
+    pub starting_fen: String,
+    /// The moves played since the starting position, in UCI notation
+    pub moves: Vec<String>,
+    /// The time left to white and black and the increment, in a timed game
+    pub clock: Option<SavedClock>,
+    /// The color of the player in a game against the bot, `None` for a local game
+    pub player_color: Option<PieceColor>,
+}
+
+/// The clocks of a timed game when it was saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedClock {
+    pub remaining: [Duration; 2],
+    pub increment: Duration,
+}
+
+impl SavedGame {
+    pub fn from_game(game: &Game) -> Self {
+        let now = Instant::now();
+        SavedGame {
+            starting_fen: game
+                .starting_fen
+                .clone()
+                .unwrap_or_else(|| STARTING_FEN.to_string()),
+            moves: game.game_board.uci_moves(),
+            clock: game.clock.map(|clock| SavedClock {
+                remaining: [PieceColor::White, PieceColor::Black]
+                    .map(|color| clock.remaining(color, now)),
+                increment: clock.increment(),
+            }),
+            player_color: None,
+        }
+    }
+
+    /// Replay the saved moves from the starting position, the clocks wait for the next move
+    pub fn to_game(&self) -> Result<Game, ChessError> {
+        let mut game = if self.starting_fen == STARTING_FEN {
+            Game::default()
+        } else {
+            Game::from_fen(&self.starting_fen)?
+        };
+        for uci_move in &self.moves {
+            game.play_uci_move(uci_move)?;
+        }
+        game.clock = self
+            .clock
+            .map(|clock| Clock::resumed(clock.remaining, clock.increment));
+        Ok(game)
+    }
+
+    /// Serialize the game along with the format version
+    pub fn to_toml(&self) -> String {
+        let mut table = Table::new();
+        table.insert(
+            "version".to_string(),
+            Value::Integer(SAVE_FORMAT_VERSION.into()),
+        );
+        table.insert(
+            "starting_fen".to_string(),
+            Value::String(self.starting_fen.clone()),
+        );
+        table.insert(
+            "moves".to_string(),
+            Value::Array(self.moves.iter().cloned().map(Value::String).collect()),
+        );
+        if let Some(player_color) = self.player_color {
+            table.insert(
+                "player_color".to_string(),
+                Value::String(color_name(player_color).to_string()),
+            );
+        }
+        if let Some(clock) = self.clock {
+            let millis = |duration: Duration| Value::Integer(duration.as_millis() as i64);
+            let mut clock_table = Table::new();
+            clock_table.insert("white_ms".to_string(), millis(clock.remaining[0]));
+            clock_table.insert("black_ms".to_string(), millis(clock.remaining[1]));
+            clock_table.insert("increment_ms".to_string(), millis(clock.increment));
+            table.insert("clock".to_string(), Value::Table(clock_table));
+        }
+        Value::Table(table).to_string()
+    }
+
+    /// Write the game to a file, one that already exists is only replaced with `overwrite`
+    pub fn write_to(&self, path: &Path, overwrite: bool) -> Result<(), ChessError> {
+        if path.exists() && !overwrite {
+            return Err(ChessError::SaveFileExists(path.display().to_string()));
+        }
+        let folder = path
+            .parent()
+            .filter(|folder| !folder.as_os_str().is_empty());
+        folder
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, self.to_toml()))
+            .map_err(|e| ChessError::SaveFailed(e.to_string()))
+    }
+
+    /// Read a saved game written by any version of chess-tui up to this one
+    pub fn from_toml(content: &str) -> Result<Self, ChessError> {
+        let value = content
+            .parse::<Value>()
+            .map_err(|e| ChessError::InvalidSave(e.to_string()))?;
+        let Value::Table(table) = value else {
+            return Err(ChessError::InvalidSave("expected a table".to_string()));
+        };
+        let version = table
+            .get("version")
+            .and_then(Value::as_integer)
+            .ok_or_else(|| ChessError::InvalidSave("missing format version".to_string()))?;
+        let version = u32::try_from(version)
+            .map_err(|_| ChessError::InvalidSave(format!("invalid format version {version}")))?;
+
+        let table = migrate(table, version)?;
+
+        let starting_fen = table
+            .get("starting_fen")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ChessError::InvalidSave("missing starting position".to_string()))?
+            .to_string();
+        let moves = table
+            .get("moves")
+            .and_then(Value::as_array)
+            .ok_or_else(|| ChessError::InvalidSave("missing moves".to_string()))?
+            .iter()
+            .map(|uci_move| {
+                uci_move
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| ChessError::InvalidSave(format!("invalid move {uci_move}")))
+            })
+            .collect::<Result<Vec<String>, ChessError>>()?;
+        let player_color = match table.get("player_color").map(Value::as_str) {
+            None => None,
+            Some(Some("White")) => Some(PieceColor::White),
+            Some(Some("Black")) => Some(PieceColor::Black),
+            Some(_) => return Err(ChessError::InvalidSave("invalid player color".to_string())),
+        };
+        let clock = table
+            .get("clock")
+            .map(|clock| {
+                let millis = |name: &str| {
+                    clock
+                        .get(name)
+                        .and_then(Value::as_integer)
+                        .and_then(|millis| u64::try_from(millis).ok())
+                        .map(Duration::from_millis)
+                        .ok_or_else(|| ChessError::InvalidSave(format!("invalid clock {name}")))
+                };
+                Ok(SavedClock {
+                    remaining: [millis("white_ms")?, millis("black_ms")?],
+                    increment: millis("increment_ms")?,
+                })
+            })
+            .transpose()?;
+
+        Ok(SavedGame {
+            starting_fen,
+            moves,
+            clock,
+            player_color,
+        })
+    }
+}
+
+fn color_name(color: PieceColor) -> &'static str {
+    match color {
+        PieceColor::White => "White",
+        PieceColor::Black => "Black",
+    }
+}
+
+/// Upgrade a saved game written in an older format, one version at a time
+pub fn migrate(mut table: Table, version: u32) -> Result<Table, ChessError> {
+    match version {
+        0 => Err(ChessError::InvalidSave(
+            "invalid format version 0".to_string(),
+        )),
+        // Every game of version 1 started from the standard position
+        1 => {
+            table.insert(
+                "starting_fen".to_string(),
+                Value::String(STARTING_FEN.to_string()),
+            );
+            table.insert("version".to_string(), Value::Integer(2));
+            migrate(table, 2)
+        }
+        // Version 2 kept neither the clocks nor the bot, the game resumes untimed and local
+        2 => {
+            table.insert("version".to_string(), Value::Integer(3));
+            migrate(table, 3)
+        }
+        SAVE_FORMAT_VERSION => Ok(table),
+        _ => Err(ChessError::UnsupportedSaveVersion(version)),
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::NaiveTime;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use toml::Value;
+
+/// The moments of the game making a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEvent {
+    Move,
+    Capture,
+    Check,
+    /// A move ending the game with a checkmate or a draw
+    GameEnd,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 4] = [
+        SoundEvent::Move,
+        SoundEvent::Capture,
+        SoundEvent::Check,
+        SoundEvent::GameEnd,
+    ];
+
+    /// The key of the event in the `[sounds]` table of the configuration
+    pub fn config_key(self) -> &'static str {
+        match self {
+            SoundEvent::Move => "move",
+            SoundEvent::Capture => "capture",
+            SoundEvent::Check => "check",
+            SoundEvent::GameEnd => "game_end",
+        }
+    }
+
+    /// The sound chess-tui makes for the event when the configuration does not pick one
+    pub fn default_sound(self) -> Sound {
+        match self {
+            SoundEvent::Move | SoundEvent::Capture => Sound::Silent,
+            SoundEvent::Check | SoundEvent::GameEnd => Sound::Bell,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sound {
+    Silent,
+    /// The bell of the terminal
+    Bell,
+    /// A sound file, played by the audio player of the system
+    File(PathBuf),
+}
+
+/// A time window during which the game stays quiet, it can go past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `time` falls in the window, the start included and the end left out
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            // The window goes past midnight
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+/// The sound of every event, each one can be replaced by a file in the configuration
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SoundMap {
+    overrides: HashMap<SoundEvent, Sound>,
+    /// No sound is played during these hours
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl SoundMap {
+    pub fn get(&self, event: SoundEvent) -> Sound {
+        self.overrides
+            .get(&event)
+            .cloned()
+            .unwrap_or_else(|| event.default_sound())
+    }
+
+    /// Replace the sounds of the events found in the `[sounds]` table of the configuration
+    /// An empty path silences the event, a file that can't be found keeps its default sound
+    /// `quiet_hours_start` and `quiet_hours_end` set the quiet hours
+    pub fn apply_config(&mut self, sounds: &toml::value::Table) {
+        for event in SoundEvent::ALL {
+            let Some(path) = sounds.get(event.config_key()).and_then(Value::as_str) else {
+                continue;
+            };
+            if path.is_empty() {
+                self.overrides.insert(event, Sound::Silent);
+                continue;
+            }
+            let path = PathBuf::from(path);
+            if path.is_file() {
+                self.overrides.insert(event, Sound::File(path));
+            } else {
+                log::warn!(
+                    "Sound file {} of `{}` not found, the default sound is kept",
+                    path.display(),
+                    event.config_key()
+                );
+                self.overrides.remove(&event);
+            }
+        }
+
+        // Quiet hours need both ends, written `HH:MM`
+        let quiet_hour = |key: &str| {
+            let time = sounds.get(key).and_then(Value::as_str)?;
+            let parsed = NaiveTime::parse_from_str(time, "%H:%M");
+            if parsed.is_err() {
+                log::warn!("`{key}` should be written HH:MM, found {time}");
+            }
+            parsed.ok()
+        };
+        if sounds.contains_key("quiet_hours_start") || sounds.contains_key("quiet_hours_end") {
+            self.quiet_hours = match (
+                quiet_hour("quiet_hours_start"),
+                quiet_hour("quiet_hours_end"),
+            ) {
+                (Some(start), Some(end)) => Some(QuietHours { start, end }),
+                _ => None,
+            };
+        }
+    }
+
+    /// Play the sound of an event without waiting for it to end, unless it is quiet hours
+    pub fn play(&self, event: SoundEvent) {
+        if !self.is_quiet_at(chrono::Local::now().time()) {
+            play(&self.get(event));
+        }
+    }
+
+    pub fn is_quiet_at(&self, time: NaiveTime) -> bool {
+        self.quiet_hours
+            .is_some_and(|quiet_hours| quiet_hours.contains(time))
+    }
+}
+
+#[cfg(all(feature = "sound", target_os = "macos"))]
+const SOUND_PLAYER: &str = "afplay";
+#[cfg(all(feature = "sound", not(target_os = "macos")))]
+const SOUND_PLAYER: &str = "paplay";
+
+#[cfg(feature = "sound")]
+fn play(sound: &Sound) {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    match sound {
+        Sound::Silent => {}
+        Sound::Bell => {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+        Sound::File(path) => {
+            let child = Command::new(SOUND_PLAYER)
+                .arg(path)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match child {
+                // Waited for in the background so that it does not linger once done
+                Ok(mut child) => {
+                    std::thread::spawn(move || child.wait());
+                }
+                Err(error) => log::warn!("Could not play {}: {error}", path.display()),
+            }
+        }
+    }
+}
+
+/// Without the `sound` feature the game stays quiet
+#[cfg(not(feature = "sound"))]
+fn play(_sound: &Sound) {}
@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use crate::{constants::WHITE, engine::console::SENT_PREFIX, ui::main_ui::centered_rect};
+
+/// Render the UCI lines exchanged with the engine, the commands sent in cyan
+/// `scroll` is the number of lines the panel is scrolled up from the latest one
+pub fn render_engine_console(frame: &mut Frame, lines: &[String], scroll: usize) {
+    let block = Block::default()
+        .title("Engine console")
+        .title_bottom(Line::from("↑/k ↓/j: Scroll, `Esc`: Close").alignment(Alignment::Center))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(80, 70, frame.area());
+
+    // The lines above the area are skipped, scrolling up brings them back
+    let visible_lines = block.inner(area).height as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible_lines);
+    let text: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with(SENT_PREFIX) {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+            Line::from(line.as_str()).style(style)
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(text).block(block.clone());
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::constants::WHITE;
+
+/// The animation only moves every few ticks to keep the CPU usage low
+pub const ANIMATION_FRAME_TICKS: u32 = 2;
+
+/// The pieces drifting across the home page
+const DRIFTING_PIECES: &str = "♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜   ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟   ";
+
+/// State machine starting the home page animation after a period without input
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdleTracker {
+    /// Number of ticks without input before the animation starts, `None` disables it
+    pub timeout_ticks: Option<u32>,
+    /// Number of ticks since the latest input
+    pub idle_ticks: u32,
+    /// Current frame of the animation, `None` while the animation is not running
+    pub animation_frame: Option<u32>,
+}
+
+impl IdleTracker {
+    pub fn new(timeout_ticks: Option<u32>) -> Self {
+        Self {
+            timeout_ticks,
+            idle_ticks: 0,
+            animation_frame: None,
+        }
+    }
+
+    /// Count a tick without input, starting or advancing the animation
+    pub fn tick(&mut self) {
+        let Some(timeout_ticks) = self.timeout_ticks else {
+            return;
+        };
+        self.idle_ticks = self.idle_ticks.saturating_add(1);
+
+        if self.idle_ticks >= timeout_ticks {
+            let elapsed = self.idle_ticks - timeout_ticks;
+            self.animation_frame = Some(elapsed / ANIMATION_FRAME_TICKS);
+        }
+    }
+
+    /// An input happened: stop the animation and restart the countdown
+    /// Returns whether the animation was running
+    pub fn reset(&mut self) -> bool {
+        let was_animating = self.is_animating();
+        self.idle_ticks = 0;
+        self.animation_frame = None;
+        was_animating
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animation_frame.is_some()
+    }
+}
+
+// This renders the pieces drifting from the left to the right of the area
+pub fn render_idle_animation(frame: &mut Frame, area: Rect, animation_frame: u32) {
+    let pattern: Vec<char> = DRIFTING_PIECES.chars().collect();
+    let width = area.width as usize;
+    let offset = animation_frame as usize % pattern.len();
+
+    let drifting_line: String = (0..width)
+        .map(|i| pattern[(i + pattern.len() - offset) % pattern.len()])
+        .collect();
+
+    let paragraph = Paragraph::new(vec![Line::from(""), Line::from(drifting_line)])
+        .alignment(Alignment::Left)
+        .style(Style::default().fg(WHITE))
+        .dim();
+    frame.render_widget(paragraph, area);
+}
@@ -2,33 +2,59 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     prelude::{Alignment, Rect},
     style::{Color, Modifier, Style, Stylize},
+    symbols::Marker,
     text::Line,
-    widgets::{Block, Paragraph},
+    widgets::{Axis, Block, BorderType, Chart, Dataset, GraphType, Padding, Paragraph, Wrap},
     Frame,
 };
 
+use std::time::Instant;
+
 use crate::{
     constants::Popups,
-    game_logic::game::GameState,
-    ui::popups::{
-        render_color_selection_popup, render_credit_popup, render_end_popup, render_help_popup,
-        render_promotion_popup,
+    engine::game_analysis::EVALUATION_CAP,
+    game_logic::{clock::format_clock, game::GameState},
+    ui::{
+        engine_console::render_engine_console,
+        idle_animation::render_idle_animation,
+        popups::{
+            render_blunder_warning_popup, render_color_selection_popup, render_connecting_popup,
+            render_credit_popup, render_difficulty_selection_popup, render_draw_offer_popup,
+            render_end_popup, render_engine_failure_popup, render_engine_selection_popup,
+            render_fen_input_popup, render_game_code_input_popup, render_game_list_popup,
+            render_help_popup, render_log_viewer_popup, render_move_input_popup,
+            render_overwrite_confirmation_popup, render_pgn_input_popup, render_pgn_tags_popup,
+            render_position_setup_popup, render_promotion_popup, render_reconnecting_popup,
+            render_resign_confirmation_popup, render_save_input_popup,
+        },
+        toast::render_toast,
     },
 };
 
 use crate::{
     app::App,
-    constants::{DisplayMode, Pages, TITLE},
+    constants::{DisplayMode, Focus, Pages, TITLE},
+    pgn::PgnGame,
     pieces::PieceColor,
 };
 
+/// Rows of the evaluation graph below the move list, borders included
+const EVALUATION_GRAPH_HEIGHT: u16 = 8;
+
+/// Rows of the comment of a replayed game below the move list, borders included
+const COMMENT_PANEL_HEIGHT: u16 = 6;
+
 /// Renders the user interface widgets.
 pub fn render(app: &mut App, frame: &mut Frame<'_>) {
     let main_area = frame.area();
 
-    // Solo game
-    if app.current_page == Pages::Solo {
+    // Solo game or game against the bot
+    if app.current_page.is_game() {
         render_game_ui(frame, app, main_area);
+    } else if app.current_page == Pages::MyGames {
+        render_my_games_ui(frame, app, main_area);
+    } else if app.current_page == Pages::LanGames {
+        render_lan_games_ui(frame, app, main_area);
     }
     // Render menu
     else {
@@ -44,11 +70,84 @@ pub fn render(app: &mut App, frame: &mut Frame<'_>) {
         Some(Popups::ColorSelection) => {
             render_color_selection_popup(frame, app);
         }
+        Some(Popups::DifficultySelection) => {
+            render_difficulty_selection_popup(frame, app);
+        }
+        Some(Popups::EngineSelection) => {
+            render_engine_selection_popup(frame, app);
+        }
         Some(Popups::Help) => {
             render_help_popup(frame);
         }
+        Some(Popups::DrawOffer) => {
+            render_draw_offer_popup(frame, app.game.player_turn);
+        }
+        Some(Popups::ResignConfirmation) => {
+            render_resign_confirmation_popup(frame, app.local_player());
+        }
+        Some(Popups::MoveInput) => {
+            render_move_input_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::FenInput) => {
+            render_fen_input_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::PositionSetup) => {
+            render_position_setup_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::GameCodeInput) => {
+            render_game_code_input_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::SaveInput) => {
+            render_save_input_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::PgnInput) => {
+            render_pgn_input_popup(frame, &app.game.ui.prompt);
+        }
+        Some(Popups::PgnTags) => {
+            render_pgn_tags_popup(frame, &app.pgn_tags, app.pgn_tag_cursor);
+        }
+        Some(Popups::OverwriteConfirmation) => {
+            if let Some(path) = &app.pending_save {
+                render_overwrite_confirmation_popup(frame, path);
+            }
+        }
+        Some(Popups::EngineFailure) => {
+            if let Some(failure) = &app.engine_failure {
+                render_engine_failure_popup(frame, failure);
+            }
+        }
+        Some(Popups::BlunderWarning) => {
+            if let Some(pending_blunder) = &app.pending_blunder {
+                render_blunder_warning_popup(frame, pending_blunder.hanging_piece);
+            }
+        }
+        Some(Popups::LogViewer) => {
+            render_log_viewer_popup(frame, &app.log_lines, app.log_scroll);
+        }
+        Some(Popups::EngineConsole) => {
+            render_engine_console(frame, &app.engine_console_lines, app.engine_console_scroll);
+        }
+        Some(Popups::GameList) => {
+            let titles: Vec<String> = app.pgn_games.iter().map(PgnGame::title).collect();
+            render_game_list_popup(frame, &titles, app.game_list_cursor);
+        }
         _ => {}
     }
+
+    if let Some(handshake) = &app.handshake {
+        render_connecting_popup(frame, handshake.spinner());
+    }
+
+    if let Some(opponent) = app.opponent.as_ref().filter(|o| o.is_reconnecting()) {
+        let waited = opponent
+            .reconnecting_since
+            .map_or(0, |since| since.elapsed().as_secs());
+        render_reconnecting_popup(frame, opponent.spinner(), waited);
+    }
+
+    if let Some(toast) = &app.toast {
+        render_toast(frame, toast);
+    }
 }
 
 /// Helper function to create a centered rect using up certain percentage of the available rect `r`
@@ -107,6 +206,16 @@ pub fn render_menu_ui(frame: &mut Frame, app: &App, main_area: Rect) {
         .block(Block::default());
     frame.render_widget(sub_title, main_layout_horizontal[1]);
 
+    if let Some(animation_frame) = app.idle.animation_frame {
+        let sub_title_area = main_layout_horizontal[1];
+        let animation_area = Rect {
+            y: sub_title_area.y + 2,
+            height: sub_title_area.height.saturating_sub(2),
+            ..sub_title_area
+        };
+        render_idle_animation(frame, animation_area, animation_frame);
+    }
+
     // Determine the "display mode" text
     let display_mode_menu = {
         let display_mode = match app.game.ui.display_mode {
@@ -117,7 +226,18 @@ pub fn render_menu_ui(frame: &mut Frame, app: &App, main_area: Rect) {
     };
 
     // Board block representing the full board div
-    let menu_items = ["Normal game", &display_mode_menu, "Help", "Credits"];
+    let menu_items = [
+        "Normal game",
+        "Play against a bot",
+        "Analyze a position",
+        "Load a PGN file",
+        "Resume last game",
+        "My games",
+        "Find games on my network",
+        &display_mode_menu,
+        "Help",
+        "Credits",
+    ];
     let mut menu_body: Vec<Line<'_>> = vec![];
 
     for (i, menu_item) in menu_items.iter().enumerate() {
@@ -138,6 +258,100 @@ pub fn render_menu_ui(frame: &mut Frame, app: &App, main_area: Rect) {
     frame.render_widget(sub_title, main_layout_horizontal[2]);
 }
 
+// Method to render the saved games, one per line with its date, result and opponent
+pub fn render_my_games_ui(frame: &mut Frame, app: &App, main_area: Rect) {
+    let block = Block::bordered()
+        .title("My games")
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1));
+
+    let mut text = vec![
+        Line::from("Use the arrows and `Enter` to replay a game or play on, `Esc` to go back")
+            .alignment(Alignment::Center),
+        Line::from(""),
+    ];
+    if app.my_games.is_empty() {
+        text.push(
+            Line::from("No game saved in ~/.config/chess-tui/games yet")
+                .alignment(Alignment::Center),
+        );
+    } else {
+        text.push(
+            Line::from(format!(
+                "{:<10}  {:<8}  {:<30}  File",
+                "Date", "Result", "Opponent"
+            ))
+            .bold(),
+        );
+    }
+    for (index, game) in app.my_games.iter().enumerate() {
+        let style = if index == app.my_games_cursor {
+            Style::default().bg(Color::Blue)
+        } else {
+            Style::default()
+        };
+        let result = if game.is_unfinished() {
+            "ongoing"
+        } else {
+            game.result.as_str()
+        };
+        let file = game
+            .path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+        text.push(
+            Line::from(format!(
+                "{:<10}  {result:<8}  {:<30}  {file}",
+                game.date, game.opponent
+            ))
+            .style(style),
+        );
+    }
+
+    // The selected game stays in sight in long lists
+    let visible_games = block.inner(main_area).height.saturating_sub(3) as usize;
+    let scroll = (app.my_games_cursor + 1).saturating_sub(visible_games) as u16;
+    let paragraph = Paragraph::new(text).block(block).scroll((scroll, 0));
+    frame.render_widget(paragraph, main_area);
+}
+
+// Method to render the games found on the local network, one per line with the address of its host
+pub fn render_lan_games_ui(frame: &mut Frame, app: &App, main_area: Rect) {
+    let block = Block::bordered()
+        .title("Find games on my network")
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1));
+
+    let mut text = vec![
+        Line::from("Use the arrows and `Enter` to join a game, `Esc` to go back")
+            .alignment(Alignment::Center),
+        Line::from("Press `c` to join with the game code of its host").alignment(Alignment::Center),
+        Line::from(""),
+    ];
+    let games = app.lan_games();
+    if games.is_empty() {
+        text.push(
+            Line::from("Looking for games hosted on your network...").alignment(Alignment::Center),
+        );
+    } else {
+        text.push(Line::from(format!("{:<40}  Address", "Game")).bold());
+    }
+    for (index, game) in games.iter().enumerate() {
+        let style = if index == app.lan_games_cursor {
+            Style::default().bg(Color::Blue)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(format!("{:<40}  {}", game.name, game.address)).style(style));
+    }
+
+    // The selected game stays in sight in long lists
+    let visible_games = block.inner(main_area).height.saturating_sub(3) as usize;
+    let scroll = (app.lan_games_cursor + 1).saturating_sub(visible_games) as u16;
+    let paragraph = Paragraph::new(text).block(block).scroll((scroll, 0));
+    frame.render_widget(paragraph, main_area);
+}
+
 // Method to render the game board and handle game popups
 pub fn render_game_ui(frame: &mut Frame<'_>, app: &mut App, main_area: Rect) {
     let main_layout_horizontal = Layout::default()
@@ -182,36 +396,189 @@ pub fn render_game_ui(frame: &mut Frame<'_>, app: &mut App, main_area: Rect) {
     // We render the board_block in the center layout made above
     frame.render_widget(board_block.clone(), main_layout_vertical[1]);
 
-    let game_clone = app.game.clone();
+    let game_clone = app.displayed_game();
+    let settings = app.board_render_settings();
     app.game.ui.board_render(
         board_block.inner(main_layout_vertical[1]),
         frame,
         &game_clone,
+        &settings,
     ); // Mutable borrow now allowed
 
+    if app.is_check_alert_visible() {
+        render_check_alert(frame, app, main_layout_vertical[1]);
+    }
+
+    app.game.ui.panel_areas = vec![
+        (Focus::BlackMaterial, right_box_layout[0]),
+        (Focus::MoveHistory, right_box_layout[1]),
+        (Focus::WhiteMaterial, right_box_layout[2]),
+    ];
+
     //top box for white material
     app.game.ui.black_material_render(
         board_block.inner(right_box_layout[0]),
         frame,
         &app.game.game_board.black_taken_pieces,
+        app.focus == Focus::BlackMaterial,
+        clock_line(app, PieceColor::Black),
     );
 
+    // The lines of the engine share the space of the move list
+    let history_area = if app.current_page == Pages::Analyze || app.analysis.is_some() {
+        let analysis_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
+            .split(right_box_layout[1]);
+        render_analysis_panel(frame, app, analysis_layout[1]);
+        analysis_layout[0]
+    } else {
+        right_box_layout[1]
+    };
+
+    // The evaluation graph takes the bottom of the move list once the engine scored a position
+    let history_area = if app.game.evaluations.iter().any(Option::is_some) {
+        let graph_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(0),
+                    Constraint::Length(EVALUATION_GRAPH_HEIGHT),
+                ]
+                .as_ref(),
+            )
+            .split(history_area);
+        render_evaluation_graph(frame, app, graph_layout[1]);
+        graph_layout[0]
+    } else {
+        history_area
+    };
+
+    // The comment of the replayed game on the reviewed position sits below the move list
+    let history_area = match app.reviewed_comment() {
+        Some(comment) => {
+            let comment_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(
+                    [Constraint::Min(0), Constraint::Length(COMMENT_PANEL_HEIGHT)].as_ref(),
+                )
+                .split(history_area);
+            render_comment_panel(frame, comment, comment_layout[1]);
+            comment_layout[0]
+        }
+        None => history_area,
+    };
+
     // We make the inside of the board
+    app.game.ui.history_render(
+        board_block.inner(history_area),
+        frame,
+        &app.game,
+        &app.players(),
+        app.move_notation,
+        app.focus == Focus::MoveHistory,
+    );
+
+    // The status bar is right below the board
+    let status_bar_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Ratio(2, 17),
+                Constraint::Ratio(9, 17),
+                Constraint::Ratio(6, 17),
+            ]
+            .as_ref(),
+        )
+        .split(main_layout_horizontal[2]);
+    // The move number and the phase keep long games readable
+    let status_bar = match app.review_index {
+        Some(index) => {
+            // The sidelines of a replayed game are entered with `]` and left with `[`
+            let sideline = if app.parent_lines.is_empty() {
+                String::new()
+            } else {
+                " in a sideline (`[` leaves)".to_string()
+            };
+            let sidelines = match app.reviewed_sidelines().len() {
+                0 => String::new(),
+                1 => " - `]` for the sideline".to_string(),
+                count => format!(" - `]` for {count} sidelines"),
+            };
+            format!(
+                "Review {}/{}{sideline}{sidelines} - Go to move: {}_ (`Enter`, `Esc` to resume)",
+                index,
+                app.game.game_board.board_history.len().saturating_sub(1),
+                app.review_input
+            )
+        }
+        None => {
+            let status_bar = format!(
+                "Move {} ({}) - {}",
+                app.game.fullmove_number(),
+                app.game.phase(),
+                app.status_message()
+            );
+            let status_bar =
+                if app.game.ui.show_legal_move_count && app.game.game_state == GameState::Playing {
+                    let count = app.game.legal_move_count();
+                    let plural = if count == 1 { "" } else { "s" };
+                    format!("{status_bar} - {count} legal move{plural}")
+                } else {
+                    status_bar
+                };
+            let status_bar = if app.game.free_play {
+                format!("{status_bar} - Free play, `u` to take back")
+            } else {
+                status_bar
+            };
+            // The engine tells what it thought of its last move
+            let status_bar = match &app.engine_report {
+                Some(report) => format!("{status_bar} - {report}"),
+                None => status_bar,
+            };
+            // Trivial endings tell how they should end
+            match app.game.endgame_hint() {
+                Some(verdict) => format!("{status_bar} - {verdict}"),
+                None => status_bar,
+            }
+        }
+    };
+    // The analysis of a finished game tells how far it went
+    let status_bar = if app.pending_evaluations.is_empty() {
+        status_bar
+    } else {
+        let total = app.game.game_board.board_history.len();
+        let evaluated = total.saturating_sub(app.pending_evaluations.len());
+        format!("{status_bar} - Analysing the game {evaluated}/{total}")
+    };
     app.game
         .ui
-        .history_render(board_block.inner(right_box_layout[1]), frame, &app.game);
+        .status_bar_render(status_bar_layout[1], frame, status_bar);
 
     //bottom box for black matetrial
     app.game.ui.white_material_render(
         board_block.inner(right_box_layout[2]),
         frame,
         &app.game.game_board.white_taken_pieces,
+        app.focus == Focus::WhiteMaterial,
+        clock_line(app, PieceColor::White),
     );
 
+    // The reviewed position stays visible, even once the game is over
+    if app.review_index.is_some() {
+        return;
+    }
+
     if app.game.game_state == GameState::Promotion {
         render_promotion_popup(frame, app);
     }
 
+    // The status bar tells how the game ended, the board stays open in free play
+    if app.game.free_play {
+        return;
+    }
+
     if app.game.game_state == GameState::Checkmate {
         let victorious_player = app.game.player_turn.opposite();
 
@@ -220,10 +587,165 @@ pub fn render_game_ui(frame: &mut Frame<'_>, app: &mut App, main_area: Rect) {
             PieceColor::Black => "Black",
         };
 
-        render_end_popup(frame, &format!("{string_color} Won !!!"));
+        render_end_popup(
+            frame,
+            &format!("{string_color} Won !!!"),
+            app.end_popup_choice(),
+        );
     }
 
     if app.game.game_state == GameState::Draw {
-        render_end_popup(frame, "That's a draw");
+        render_end_popup(frame, "That's a draw", app.end_popup_choice());
+    }
+
+    if app.game.game_state == GameState::Timeout {
+        let string_color = match app.game.player_turn.opposite() {
+            PieceColor::White => "White",
+            PieceColor::Black => "Black",
+        };
+
+        render_end_popup(
+            frame,
+            &format!("{string_color} Won on time !!!"),
+            app.end_popup_choice(),
+        );
+    }
+
+    if let GameState::Resignation(resigned) = app.game.game_state {
+        let string_color = match resigned.opposite() {
+            PieceColor::White => "White",
+            PieceColor::Black => "Black",
+        };
+
+        render_end_popup(
+            frame,
+            &format!("{string_color} Won by resignation !!!"),
+            app.end_popup_choice(),
+        );
+    }
+}
+
+// The best lines found by the engine for the position on the board, the best one first
+fn render_analysis_panel(frame: &mut Frame, app: &App, area: Rect) {
+    let engine_lines = &app.game.engine_lines;
+    let (title, lines) = match &app.analysis {
+        Some(analysis) if engine_lines.is_empty() => (
+            format!("Analysis - {}", analysis.engine_name()),
+            vec![Line::from("Thinking...")],
+        ),
+        Some(analysis) => (
+            format!("Analysis - {}", analysis.engine_name()),
+            engine_lines
+                .iter()
+                .map(|line| Line::from(line.to_string()))
+                .collect(),
+        ),
+        None => (
+            "Analysis".to_string(),
+            vec![Line::from("The engine is not running")],
+        ),
+    };
+    let panel = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::bordered()
+            .title(title)
+            .border_type(BorderType::Rounded),
+    );
+    frame.render_widget(panel, area);
+}
+
+// The comment of the PGN file on the reviewed position, wrapped to the width of the panel
+fn render_comment_panel(frame: &mut Frame, comment: &str, area: Rect) {
+    let panel = Paragraph::new(comment).wrap(Wrap { trim: true }).block(
+        Block::bordered()
+            .title("Comment")
+            .border_type(BorderType::Rounded),
+    );
+    frame.render_widget(panel, area);
+}
+
+// The evaluation of every scored position from the point of view of white, the reviewed one marked
+fn render_evaluation_graph(frame: &mut Frame, app: &App, area: Rect) {
+    let points: Vec<(f64, f64)> = app
+        .game
+        .white_evaluations()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, centipawns)| Some((index as f64, f64::from((*centipawns)?))))
+        .collect();
+    let last_index = app
+        .game
+        .game_board
+        .board_history
+        .len()
+        .saturating_sub(1)
+        .max(1) as f64;
+    let even = [(0.0, 0.0), (last_index, 0.0)];
+    let reviewed: Vec<(f64, f64)> = app
+        .review_index
+        .and_then(|index| points.iter().find(|(x, _)| *x == index as f64).copied())
+        .into_iter()
+        .collect();
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&even),
+        Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&points),
+        Dataset::default()
+            .marker(Marker::Dot)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&reviewed),
+    ];
+    let cap = f64::from(EVALUATION_CAP);
+    let chart = Chart::new(datasets)
+        .block(
+            Block::bordered()
+                .title("Evaluation")
+                .border_type(BorderType::Rounded),
+        )
+        .x_axis(Axis::default().bounds([0.0, last_index]))
+        .y_axis(Axis::default().bounds([-cap, cap]));
+    frame.render_widget(chart, area);
+}
+
+// Flash a border around the cells of the board, within the board area
+fn render_check_alert(frame: &mut Frame, app: &App, area: Rect) {
+    let ui = &app.game.ui;
+    let board_area = Rect::new(
+        ui.top_x.saturating_sub(1),
+        ui.top_y.saturating_sub(1),
+        ui.width * 8 + 2,
+        ui.height * 8 + 2,
+    )
+    .intersection(area);
+    let border = Block::bordered()
+        .border_type(BorderType::Thick)
+        .border_style(Style::default().fg(Color::Magenta));
+    frame.render_widget(border, board_area);
+}
+
+// The remaining time of a player, highlighted while it is running and red under pressure
+fn clock_line(app: &App, color: PieceColor) -> Option<Line<'static>> {
+    let clock = app.game.clock.as_ref()?;
+    let remaining = clock.remaining(color, Instant::now());
+    let mut style = Style::default();
+    if clock.is_running() && app.game.player_turn == color {
+        style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+    }
+    if remaining < app.clock_tenths_threshold {
+        style = style.fg(Color::Red);
+        if app.clock_flash && clock.is_running() && app.game.player_turn == color {
+            style = style.add_modifier(Modifier::SLOW_BLINK);
+        }
     }
+    Some(Line::styled(
+        format!(" {} ", format_clock(remaining, app.clock_tenths_threshold)),
+        style,
+    ))
 }
@@ -1,4 +1,8 @@
+pub mod engine_console;
+pub mod idle_animation;
 pub mod main_ui;
 pub mod popups;
 pub mod prompt;
+pub mod theme;
+pub mod toast;
 pub mod tui;
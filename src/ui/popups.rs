@@ -1,9 +1,17 @@
 use crate::{
     app::App,
-    constants::WHITE,
-    pieces::{bishop::Bishop, knight::Knight, pawn::Pawn, queen::Queen, rook::Rook},
-    ui::main_ui::centered_rect,
+    constants::{DisplayMode, EndGameChoice, WHITE},
+    engine::Difficulty,
+    network::opponent::RECONNECT_TIMEOUT,
+    pgn::PgnTags,
+    pieces::{
+        bishop::Bishop, knight::Knight, pawn::Pawn, queen::Queen, rook::Rook, PieceColor, PieceType,
+    },
+    ui::{main_ui::centered_rect, prompt::Prompt},
+    utils::color_to_ratatui_enum,
 };
+use std::path::Path;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style, Stylize},
@@ -12,8 +20,438 @@ use ratatui::{
     Frame,
 };
 
+// This renders a popup asking the player to move to confirm their resignation
+pub fn render_resign_confirmation_popup(frame: &mut Frame, resigning_player: PieceColor) {
+    let block = Block::default()
+        .title("Resign")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let resigning = match resigning_player {
+        PieceColor::White => "White",
+        PieceColor::Black => "Black",
+    };
+
+    let text = vec![
+        Line::from(format!("{resigning}, do you really want to resign ?"))
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Press `y` to resign or `n` to keep playing").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the confirmation of a move leaving a piece for the taking
+pub fn render_blunder_warning_popup(frame: &mut Frame, hanging_piece: PieceType) {
+    let block = Block::default()
+        .title("Blunder")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!(
+            "This hangs your {} — continue?",
+            hanging_piece.name()
+        ))
+        .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Press `y` to play the move or `n` to take it back")
+            .alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the games of a PGN file to choose from
+pub fn render_game_list_popup(frame: &mut Frame, titles: &[String], selected: usize) {
+    let block = Block::default()
+        .title("Games of the PGN file")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(60, 60, frame.area());
+
+    let mut text = vec![
+        Line::from("Use the arrows and `Enter` to replay a game").alignment(Alignment::Center),
+        Line::from(""),
+    ];
+    for (index, title) in titles.iter().enumerate() {
+        let style = if index == selected {
+            Style::default().bg(Color::Blue)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(format!("{}. {title}", index + 1)).style(style));
+    }
+
+    // The selected game stays in sight in long lists
+    let visible_games = block.inner(area).height.saturating_sub(2) as usize;
+    let scroll = (selected + 1).saturating_sub(visible_games) as u16;
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .scroll((scroll, 0));
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the prompt of a move typed in algebraic notation
+pub fn render_move_input_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Type a move")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("e4, Nf3, exd5, O-O, e8=Q...").alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to play or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the prompt naming the file the game is saved to
+pub fn render_save_input_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Save the game")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Saved in ~/.config/chess-tui/games unless the path is absolute")
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to save or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup to type the path of the PGN file to replay
+pub fn render_pgn_input_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Load a PGN file")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Looked for in ~/.config/chess-tui/games unless the path is absolute")
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to replay or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup to type or paste the FEN of the position to set up
+pub fn render_position_setup_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Set up position")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Type or paste a FEN, the current game is replaced")
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to set it up or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup to edit the headers of the game saved in PGN
+pub fn render_pgn_tags_popup(frame: &mut Frame, tags: &PgnTags, selected: usize) {
+    let block = Block::default()
+        .title("Save as PGN")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let mut text = vec![Line::from("")];
+    for (index, tag) in PgnTags::EDITABLE.iter().enumerate() {
+        let (style, cursor) = if index == selected {
+            (Style::default().bg(Color::Blue), "_")
+        } else {
+            (Style::default(), "")
+        };
+        text.push(
+            Line::from(format!("{tag}: {}{cursor}", tags.value(index)))
+                .alignment(Alignment::Center)
+                .style(style),
+        );
+    }
+    text.extend([
+        Line::from(""),
+        Line::from("`Up`/`Down`: choose a header, an empty one is saved as `?`")
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to save or `Esc` to cancel").alignment(Alignment::Center),
+    ]);
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup to type the FEN of the position to analyse
+pub fn render_fen_input_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Analyze a position")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Type a FEN, or nothing for the starting position").alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to analyse or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup to type the code of the online game to join
+pub fn render_game_code_input_popup(frame: &mut Frame, prompt: &Prompt) {
+    let block = Block::default()
+        .title("Join a game")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("> {}_", prompt.input)).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Type the game code shown by the host, like 60N0-0G42-841")
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Enter` to join or `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the confirmation before saving the game over an existing file
+pub fn render_overwrite_confirmation_popup(frame: &mut Frame, path: &Path) {
+    let block = Block::default()
+        .title("Overwrite")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("{} already exists, replace it ?", path.display()))
+            .alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Press `y` to replace it or `n` to keep it").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders what happened to the engine and how the game can go on
+pub fn render_engine_failure_popup(frame: &mut Frame, failure: &str) {
+    let block = Block::default()
+        .title("Engine stopped")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("The engine failed: {failure}")).alignment(Alignment::Center),
+        Line::from("The game is kept as it is").alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `r` to restart the engine or `Esc` to play on against the random bot")
+            .alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders the latest lines of the log file, the newest at the bottom
+pub fn render_log_viewer_popup(frame: &mut Frame, lines: &[String], scroll: usize) {
+    let block = Block::default()
+        .title("Logs")
+        .title_bottom(Line::from("↑/k ↓/j: Scroll, `Esc`: Close").alignment(Alignment::Center))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(80, 70, frame.area());
+
+    // The lines above the area are skipped, scrolling up brings them back
+    let visible_lines = block.inner(area).height as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible_lines);
+    let text: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+
+    let paragraph = Paragraph::new(text).block(block.clone());
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup while the host of an online game is being reached
+pub fn render_connecting_popup(frame: &mut Frame, spinner: &str) {
+    let block = Block::default()
+        .title("Online game")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(format!("{spinner} Connecting to opponent...")).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from("Press `Esc` to cancel").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
 // This renders a popup for a promotion
-pub fn render_end_popup(frame: &mut Frame, sentence: &str) {
+pub fn render_end_popup(frame: &mut Frame, sentence: &str, selected: EndGameChoice) {
     let block = Block::default()
         .title("Game ended")
         .borders(Borders::ALL)
@@ -22,11 +460,91 @@ pub fn render_end_popup(frame: &mut Frame, sentence: &str) {
         .border_style(Style::default().fg(WHITE));
     let area = centered_rect(40, 40, frame.area());
 
-    let text = vec![
+    let mut text = vec![
         Line::from(sentence).alignment(Alignment::Center),
         Line::from(""),
         Line::from(""),
-        Line::from("Press `R` to restart a new game").alignment(Alignment::Center),
+    ];
+    for choice in EndGameChoice::ALL {
+        let style = if choice == selected {
+            Style::default().bg(Color::Blue)
+        } else {
+            Style::default()
+        };
+        text.push(
+            Line::from(choice.label())
+                .style(style)
+                .alignment(Alignment::Center),
+        );
+    }
+    text.push(Line::from(""));
+    text.push(Line::from("Use the arrows and `Enter` to choose").alignment(Alignment::Center));
+    text.push(Line::from("`v` reviews the game, `p` saves it as PGN").alignment(Alignment::Center));
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup while the connection to the opponent is opened again after it dropped
+pub fn render_reconnecting_popup(frame: &mut Frame, spinner: &str, waited_seconds: u64) {
+    let block = Block::default()
+        .title("Online game")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let remaining = RECONNECT_TIMEOUT.as_secs().saturating_sub(waited_seconds);
+    let text = vec![
+        Line::from(format!("{spinner} Reconnecting…")).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("The connection to the opponent dropped, the game is kept")
+            .alignment(Alignment::Center),
+        Line::from(format!("Giving up in {remaining} seconds")).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Press `Esc` to stop waiting").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+}
+
+// This renders a popup asking the opponent to accept a draw offer
+pub fn render_draw_offer_popup(frame: &mut Frame, offering_player: PieceColor) {
+    let block = Block::default()
+        .title("Draw offer")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let (offering, answering) = match offering_player {
+        PieceColor::White => ("White", "Black"),
+        PieceColor::Black => ("Black", "White"),
+    };
+
+    let text = vec![
+        Line::from(format!("{offering} offers a draw")).alignment(Alignment::Center),
+        Line::from(""),
+        Line::from(""),
+        Line::from(format!(
+            "{answering}, press `y` to accept or `n` to decline"
+        ))
+        .alignment(Alignment::Center),
     ];
 
     let paragraph = Paragraph::new(text)
@@ -95,43 +613,36 @@ pub fn render_promotion_popup(frame: &mut Frame, app: &mut App) {
     app.game.ui.height = inner_popup_layout_horizontal[0].height;
 
     let display_mode = &app.game.ui.display_mode;
+    // The options are drawn with the pieces of the promoting player
+    let promoting_color = app.game.promoting_color();
 
-    let queen_p = Paragraph::new(Queen::to_string(display_mode))
-        .block(Block::default())
-        .alignment(Alignment::Center)
-        .style(Style::default().bg(if app.game.ui.promotion_cursor == 0 {
-            Color::LightBlue
-        } else {
-            Color::Reset // Set to the default background color when the condition is false
-        }));
-    frame.render_widget(queen_p, inner_popup_layout_horizontal[0]);
-    let rook_p = Paragraph::new(Rook::to_string(display_mode))
-        .block(Block::default())
-        .alignment(Alignment::Center)
-        .style(Style::default().bg(if app.game.ui.promotion_cursor == 1 {
-            Color::LightBlue
-        } else {
-            Color::Reset // Set to the default background color when the condition is false
-        }));
-    frame.render_widget(rook_p, inner_popup_layout_horizontal[1]);
-    let bishop_p = Paragraph::new(Bishop::to_string(display_mode))
-        .block(Block::default())
-        .alignment(Alignment::Center)
-        .style(Style::default().bg(if app.game.ui.promotion_cursor == 2 {
-            Color::LightBlue
-        } else {
-            Color::Reset // Set to the default background color when the condition is false
-        }));
-    frame.render_widget(bishop_p, inner_popup_layout_horizontal[2]);
-    let knight_p = Paragraph::new(Knight::to_string(display_mode))
-        .block(Block::default())
-        .alignment(Alignment::Center)
-        .style(Style::default().bg(if app.game.ui.promotion_cursor == 3 {
-            Color::LightBlue
-        } else {
-            Color::Reset // Set to the default background color when the condition is false
-        }));
-    frame.render_widget(knight_p, inner_popup_layout_horizontal[3]);
+    let promotion_options = [
+        Queen::to_string(display_mode),
+        Rook::to_string(display_mode),
+        Bishop::to_string(display_mode),
+        Knight::to_string(display_mode),
+    ];
+    for (i, piece_string) in promotion_options.into_iter().enumerate() {
+        let paragraph = match display_mode {
+            DisplayMode::DEFAULT => {
+                Paragraph::new(piece_string).fg(color_to_ratatui_enum(promoting_color))
+            }
+            DisplayMode::ASCII => match promoting_color {
+                Some(PieceColor::Black) => Paragraph::new(piece_string.to_lowercase()),
+                _ => Paragraph::new(piece_string.to_uppercase().underlined()),
+            },
+        };
+        let paragraph = paragraph
+            .block(Block::default())
+            .alignment(Alignment::Center)
+            .bg(if app.game.ui.promotion_cursor == i as i8 {
+                Color::LightBlue
+            } else {
+                // Light background so that both white and black pieces stand out
+                WHITE
+            });
+        frame.render_widget(paragraph, inner_popup_layout_horizontal[i]);
+    }
 }
 
 // This render the credit popup
@@ -202,20 +713,88 @@ pub fn render_help_popup(frame: &mut Frame) {
         Line::from(""),
         Line::from("b: Go to the home menu / reset the game"),
         Line::from(""),
+        Line::from("d: Offer a draw to your opponent, the bot answers right away"),
+        Line::from(""),
+        Line::from("x: Resign the game"),
+        Line::from(""),
+        Line::from("S: Switch sides with the bot, it plays on from your position"),
+        Line::from(""),
+        Line::from("t: Show or hide the cells controlled by your opponent"),
+        Line::from(""),
+        Line::from("c: Show or hide the number of legal moves of the player to move"),
+        Line::from(""),
+        Line::from("n: Write the moves in algebraic (Nf3) or coordinate (g1f3) notation"),
+        Line::from(""),
+        Line::from("f: Free play, either side can move in a local game"),
+        Line::from(""),
+        Line::from("u: Take back the latest move in free play"),
+        Line::from(""),
+        Line::from("F: Set up a position from a FEN, typed or pasted in the terminal"),
+        Line::from(""),
+        Line::from("v: Review the game, type a move number then `Enter` to jump to it"),
+        Line::from(""),
+        Line::from("] / [: During the review of a PGN game, enter or leave a sideline"),
+        Line::from(""),
+        Line::from("a: During a review, analyse the position with the engine (`Esc` comes back)"),
+        Line::from(""),
+        Line::from("e: Show or hide the best lines of the engine next to the board"),
+        Line::from(""),
+        Line::from("H: Light up the move the engine suggests for a few seconds"),
+        Line::from(""),
+        Line::from("m: Type a move in algebraic notation (Nf3, exd5, O-O, e8=Q)"),
+        Line::from(""),
+        Line::from(
+            "p: Save the game as PGN in ~/.config/chess-tui/games, after editing its headers",
+        ),
+        Line::from(""),
+        Line::from("w: Save the game to a file named in ~/.config/chess-tui/games"),
+        Line::from(""),
+        Line::from("g: Save the game as an animated GIF in ~/.config/chess-tui/games"),
+        Line::from(""),
+        Line::from("P: Save the position as SVG and PNG pictures in the same folder"),
+        Line::from(""),
+        Line::from("s: Make the engine play the best move it found so far"),
+        Line::from(""),
+        Line::from("L: Show the latest entries of the log file"),
+        Line::from(""),
+        Line::from("E: Show the UCI lines exchanged with the engine"),
+        Line::from(""),
+        Line::from("`Tab`: Focus the next panel, the arrows then scroll it"),
+        Line::from(""),
+        Line::from("`Ctrl` 'v': Load a FEN position from the clipboard"),
+        Line::from(""),
+        Line::from("`Ctrl` 'u': Copy the moves in UCI notation to the clipboard"),
+        Line::from(""),
+        Line::from("`Ctrl` 'f': Copy the position as a FEN to the clipboard"),
+        Line::from(""),
+        Line::from("`Ctrl` 't': Switch to the next board theme"),
+        Line::from(""),
         Line::from(""),
         Line::from("Color codes:".underlined().bold()),
         Line::from(""),
         Line::from(vec!["Blue cell".blue(), ": Your cursor ".into()]),
         Line::from(""),
-        Line::from(vec!["Green cell".green(), ": Selected Piece ".into()]),
+        Line::from(vec!["Yellow cell".yellow(), ": Selected Piece ".into()]),
+        Line::from(""),
+        Line::from(vec!["Green cell".green(), ": Latest move ".into()]),
         Line::from(""),
         Line::from(vec![
             "Purple cell".magenta(),
             ": The king is getting checked ".into(),
         ]),
         Line::from(""),
+        Line::from(vec![
+            "Cyan cell".cyan(),
+            ": Move suggested with `H` ".into(),
+        ]),
+        Line::from(""),
         Line::from("Grey cell: Available cells for the selected piece"),
         Line::from(""),
+        Line::from(vec![
+            "Red cell".red(),
+            ": Controlled by your opponent (threats overlay)".into(),
+        ]),
+        Line::from(""),
         Line::from(""),
         Line::from("Press `Esc` to close the popup.").alignment(Alignment::Center),
     ];
@@ -268,14 +847,7 @@ pub fn render_color_selection_popup(frame: &mut Frame, app: &App) {
 
     let inner_popup_layout_horizontal = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-            ]
-            .as_ref(),
-        )
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
         .split(inner_popup_layout_vertical[1]);
 
     let display_mode = &app.game.ui.display_mode;
@@ -306,5 +878,105 @@ pub fn render_color_selection_popup(frame: &mut Frame, app: &App) {
                     Color::Reset // Set to the default background color when the condition is false
                 }),
         );
-    frame.render_widget(black_pawn, inner_popup_layout_horizontal[2]);
+    frame.render_widget(black_pawn, inner_popup_layout_horizontal[1]);
+}
+
+// This renders a popup for the difficulty of the bot, right after the color selection
+pub fn render_difficulty_selection_popup(frame: &mut Frame, app: &App) {
+    let block = Block::default()
+        .title("Difficulty selection")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let text = vec![
+        Line::from(""),
+        Line::from("-- Choose the strength of the bot --").alignment(Alignment::Center),
+        Line::from(""),
+        Line::from("Up/Down: change the Elo").alignment(Alignment::Center),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::default())
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
+
+    let inner_popup_layout_vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let inner_popup_layout_horizontal = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(Difficulty::ALL.map(|_| Constraint::Ratio(1, Difficulty::ALL.len() as u32)))
+        .split(inner_popup_layout_vertical[1]);
+
+    for (index, difficulty) in Difficulty::ALL.iter().enumerate() {
+        let label = match difficulty {
+            Difficulty::Elo(_) => Difficulty::Elo(app.engine_elo).label(),
+            _ => difficulty.label(),
+        };
+        let choice = Paragraph::new(label)
+            .block(Block::default())
+            .alignment(Alignment::Center)
+            .style(Style::default().bg(if app.menu_cursor as usize == index {
+                Color::Blue
+            } else {
+                Color::Reset
+            }));
+        frame.render_widget(choice, inner_popup_layout_horizontal[index]);
+    }
+}
+
+// This renders the engines of the config to choose the bot from
+pub fn render_engine_selection_popup(frame: &mut Frame, app: &App) {
+    let block = Block::default()
+        .title("Engine selection")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .border_style(Style::default().fg(WHITE));
+    let area = centered_rect(40, 40, frame.area());
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from("-- Choose the engine of the bot --").alignment(Alignment::Center),
+        Line::from(""),
+    ];
+    for (index, profile) in app.engine_profiles.iter().enumerate() {
+        let style = if app.menu_cursor as usize == index {
+            Style::default().bg(Color::Blue)
+        } else {
+            Style::default()
+        };
+        text.push(
+            Line::from(profile.name.as_str())
+                .alignment(Alignment::Center)
+                .style(style),
+        );
+    }
+
+    // The selected engine stays in sight in long lists
+    let visible_engines = block.inner(area).height.saturating_sub(3) as usize;
+    let scroll = (app.menu_cursor as usize + 1).saturating_sub(visible_engines) as u16;
+    let paragraph = Paragraph::new(text)
+        .block(block.clone())
+        .alignment(Alignment::Left)
+        .scroll((scroll, 0));
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(block, area);
+    frame.render_widget(paragraph, area);
 }
@@ -1,6 +1,11 @@
+/// Characters a prompt takes unless it is made for a longer input
+pub const PROMPT_MAX_LENGTH: usize = 40;
+/// Characters of the longest FEN, with its move counters
+pub const FEN_MAX_LENGTH: usize = 100;
+
 /// App holds the state of the application
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Prompt {
     /// Current value of the input box
     pub input: String,
@@ -8,6 +13,14 @@ pub struct Prompt {
     pub character_index: usize,
     /// The prompt entry message
     pub message: String,
+    /// Characters typed past this length are dropped
+    pub max_length: usize,
+}
+
+impl Default for Prompt {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Prompt {
@@ -16,6 +29,15 @@ impl Prompt {
             input: "".to_string(),
             character_index: 0,
             message: String::new(),
+            max_length: PROMPT_MAX_LENGTH,
+        }
+    }
+
+    /// A prompt long enough to hold a whole FEN
+    pub fn for_fen() -> Self {
+        Self {
+            max_length: FEN_MAX_LENGTH,
+            ..Self::new()
         }
     }
 
@@ -45,12 +67,19 @@ impl Prompt {
 
     pub fn enter_char(&mut self, new_char: char) {
         let index = self.byte_index();
-        if index < 40 {
+        if index < self.max_length {
             self.input.insert(index, new_char);
             self.move_cursor_right();
         }
     }
 
+    /// Type pasted text at the cursor, its line breaks become spaces
+    pub fn enter_str(&mut self, text: &str) {
+        for c in text.trim().chars() {
+            self.enter_char(if c.is_whitespace() { ' ' } else { c });
+        }
+    }
+
     /// Returns the byte index based on the character position.
     ///
     /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
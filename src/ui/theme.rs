@@ -0,0 +1,62 @@
+use ratatui::style::Color;
+
+use crate::constants::{BLACK, THREAT_BLACK, THREAT_WHITE, WHITE};
+
+/// The colors of the cells of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardTheme {
+    /// Name written in the config file
+    pub name: &'static str,
+    pub light_cell: Color,
+    pub dark_cell: Color,
+    /// Light cells controlled by the opponent when the threats overlay is shown
+    pub light_threat: Color,
+    /// Dark cells controlled by the opponent when the threats overlay is shown
+    pub dark_threat: Color,
+    /// The cell of the selected piece, it stays lit while the cursor visits the targets
+    pub selected_cell: Color,
+}
+
+/// The themes available with `Ctrl-t`, the first one is the default
+pub const THEMES: [BoardTheme; 4] = [
+    BoardTheme {
+        name: "Default",
+        light_cell: WHITE,
+        dark_cell: BLACK,
+        light_threat: THREAT_WHITE,
+        dark_threat: THREAT_BLACK,
+        selected_cell: Color::LightYellow,
+    },
+    BoardTheme {
+        name: "Wood",
+        light_cell: Color::Rgb(222, 184, 135),
+        dark_cell: Color::Rgb(139, 90, 43),
+        light_threat: Color::Rgb(230, 140, 110),
+        dark_threat: Color::Rgb(160, 60, 40),
+        selected_cell: Color::Rgb(240, 210, 90),
+    },
+    BoardTheme {
+        name: "Ocean",
+        light_cell: Color::Rgb(170, 200, 220),
+        dark_cell: Color::Rgb(70, 110, 150),
+        light_threat: Color::Rgb(210, 150, 170),
+        dark_threat: Color::Rgb(130, 70, 110),
+        selected_cell: Color::Rgb(120, 210, 200),
+    },
+    BoardTheme {
+        name: "Forest",
+        light_cell: Color::Rgb(215, 220, 180),
+        dark_cell: Color::Rgb(95, 130, 80),
+        light_threat: Color::Rgb(220, 150, 120),
+        dark_threat: Color::Rgb(140, 80, 50),
+        selected_cell: Color::Rgb(230, 220, 110),
+    },
+];
+
+/// The index of the theme with this name, the default theme for unknown names
+pub fn theme_index(name: &str) -> usize {
+    THEMES
+        .iter()
+        .position(|theme| theme.name.eq_ignore_ascii_case(name))
+        .unwrap_or(0)
+}
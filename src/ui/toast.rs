@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::constants::WHITE;
+
+/// Number of ticks a toast stays on screen
+pub const TOAST_DURATION_TICKS: u16 = 12;
+
+/// A short notification displayed in the bottom right corner
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// The text of the notification
+    pub message: String,
+    /// Errors are rendered in red
+    pub is_error: bool,
+    /// Number of ticks before the toast disappears
+    pub remaining_ticks: u16,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: false,
+            remaining_ticks: TOAST_DURATION_TICKS,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: true,
+            remaining_ticks: TOAST_DURATION_TICKS,
+        }
+    }
+}
+
+// This renders a toast in the bottom right corner of the screen
+pub fn render_toast(frame: &mut Frame, toast: &Toast) {
+    let frame_area = frame.area();
+    let width = (toast.message.chars().count() as u16 + 4).min(frame_area.width);
+    let height = 3.min(frame_area.height);
+    let area = Rect::new(
+        frame_area.x + frame_area.width - width,
+        frame_area.y + frame_area.height - height,
+        width,
+        height,
+    );
+
+    let color = if toast.is_error { Color::Red } else { WHITE };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color));
+    let paragraph = Paragraph::new(toast.message.as_str())
+        .block(block)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(color));
+
+    frame.render_widget(Clear, area); //this clears out the background
+    frame.render_widget(paragraph, area);
+}
@@ -106,6 +106,7 @@ pub fn get_cell_paragraph<'a>(
     game: &'a Game,
     cell_coordinates: &'a Coord,
     bounding_rect: Rect,
+    compact: bool,
 ) -> Paragraph<'a> {
     // Get piece and color
     let piece_color = game.game_board.get_piece_color(cell_coordinates);
@@ -113,6 +114,15 @@ pub fn get_cell_paragraph<'a>(
     let piece_enum = PieceType::piece_type_to_string_enum(piece_type, &game.ui.display_mode);
 
     let paragraph = match game.ui.display_mode {
+        // The drawings do not fit in compact cells, a single symbol does
+        DisplayMode::DEFAULT if compact => {
+            let symbol = piece_type.map_or(" ", |piece_type| {
+                PieceType::piece_to_utf_enum(&piece_type, Some(PieceColor::White))
+            });
+            Paragraph::new(symbol)
+                .fg(color_to_ratatui_enum(piece_color))
+                .block(Block::new().padding(Padding::vertical(bounding_rect.height / 2)))
+        }
         DisplayMode::DEFAULT => {
             let color_enum = color_to_ratatui_enum(piece_color);
 
@@ -138,6 +148,11 @@ pub fn get_cell_paragraph<'a>(
     paragraph.alignment(Alignment::Center)
 }
 
-pub fn invert_position(coord: &Coord) -> Coord {
+/// Get the same cell seen from the other side of the board
+/// Every coordinate stored in the orientation of the board goes through it when the board flips
+pub fn flip_coord(coord: Coord) -> Coord {
+    if !coord.is_valid() {
+        return coord;
+    }
     Coord::new(7 - coord.row, 7 - coord.col)
 }
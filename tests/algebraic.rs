@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::handler::handle_key_events;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    // White is about to promote the e7 pawn, with a black rook to take on d8
+    const PROMOTION_FEN: &str = "3r4/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+
+    fn last_piece_type(game: &Game) -> PieceType {
+        game.game_board.move_history.last().unwrap().piece_type
+    }
+
+    #[test]
+    fn bare_last_rank_pawn_move_asks_for_the_piece() {
+        let mut game = Game::from_fen(PROMOTION_FEN).unwrap();
+
+        game.play_algebraic_move("e8").unwrap();
+
+        assert_eq!(game.game_state, GameState::Promotion);
+        assert_eq!(
+            game.game_board.board[&Coord::new(0, 4)],
+            Some((PieceType::Pawn, PieceColor::White))
+        );
+
+        // The piece is then chosen like after a move played on the board
+        game.ui.promotion_cursor = 1;
+        game.promote_piece();
+        assert_eq!(last_piece_type(&game), PieceType::Rook);
+        assert_eq!(game.player_turn, PieceColor::Black);
+    }
+
+    #[test]
+    fn suffixed_pawn_move_promotes_directly() {
+        for (san, piece_type) in [
+            ("e8=N", PieceType::Knight),
+            ("e8B", PieceType::Bishop),
+            ("exd8=R+", PieceType::Rook),
+            ("exd8q", PieceType::Queen),
+        ] {
+            let mut game = Game::from_fen(PROMOTION_FEN).unwrap();
+
+            game.play_algebraic_move(san).unwrap();
+
+            assert_eq!(game.game_state, GameState::Playing, "{san}");
+            assert_eq!(last_piece_type(&game), piece_type, "{san}");
+            assert_eq!(game.player_turn, PieceColor::Black, "{san}");
+        }
+    }
+
+    #[test]
+    fn black_promotes_on_the_first_rank() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/p7/4K3 b - - 0 1").unwrap();
+
+        game.play_algebraic_move("a1=N").unwrap();
+
+        assert_eq!(last_piece_type(&game), PieceType::Knight);
+        assert_eq!(game.player_turn, PieceColor::White);
+        assert_eq!(game.game_board.uci_moves(), vec!["a2a1n".to_string()]);
+    }
+
+    #[test]
+    fn promotion_suffix_is_refused_before_the_last_rank() {
+        let mut game = Game::default();
+        assert!(matches!(
+            game.play_algebraic_move("e4=Q"),
+            Err(ChessError::IllegalMove(_))
+        ));
+        assert!(game.game_board.move_history.is_empty());
+    }
+
+    #[test]
+    fn usual_moves_are_resolved() {
+        let mut game = Game::default();
+        for san in ["e4", "e5", "Nf3", "Nc6", "Bc4", "Bc5", "O-O", "Nf6"] {
+            game.play_algebraic_move(san).unwrap();
+        }
+
+        assert_eq!(
+            game.game_board.uci_moves(),
+            ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "f8c5", "e1g1", "g8f6"]
+        );
+    }
+
+    #[test]
+    fn ambiguous_moves_need_a_hint() {
+        // Both knights can reach d2
+        let mut game = Game::from_fen("4k3/8/8/8/8/5N2/8/1N2K3 w - - 0 1").unwrap();
+
+        assert!(game.play_algebraic_move("Nd2").is_err());
+        game.play_algebraic_move("Nbd2").unwrap();
+
+        assert_eq!(game.game_board.uci_moves(), vec!["b1d2".to_string()]);
+    }
+
+    #[test]
+    fn prompt_plays_the_typed_move() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        let press = |app: &mut App, code: KeyCode| {
+            handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+        };
+
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.current_popup, Some(Popups::MoveInput));
+
+        // An illegal move keeps the prompt open
+        for c in "e5".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, Some(Popups::MoveInput));
+        assert!(app.toast.as_ref().is_some_and(|toast| toast.is_error));
+
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["e2e4".to_string()]);
+    }
+}
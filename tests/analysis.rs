@@ -0,0 +1,231 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::{press, write_fake_engine};
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::analysis::{parse_analysis_line, AnalysisLine};
+    use chess_tui::engine::uci::Score;
+    use chess_tui::event::Event;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::handler::handle_event;
+    use ratatui::crossterm::event::KeyCode;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    // An engine giving its two best lines right away and thinking until it is stopped
+    const ANALYSING_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Analyst"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    "go infinite")
+      echo "info depth 12 multipv 1 score cp 30 pv e2e4 e7e5 g1f3"
+      echo "info depth 12 multipv 2 score cp 20 pv d2d4 d7d5" ;;
+    stop) echo "bestmove e2e4" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn analysing_engine() -> String {
+        write_fake_engine("analysing", ANALYSING_ENGINE).to_string()
+    }
+
+    fn analysis_app() -> (App, Receiver<Event>) {
+        let (sender, receiver) = mpsc::channel();
+        let app = App {
+            engine_path: Some(analysing_engine()),
+            event_sender: Some(sender),
+            ..Default::default()
+        };
+        (app, receiver)
+    }
+
+    // Handle the two lines of the latest search
+    fn receive_lines(app: &mut App, receiver: &Receiver<Event>) {
+        for _ in 0..2 {
+            handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), app).unwrap();
+        }
+    }
+
+    fn shown_lines(app: &App) -> Vec<String> {
+        app.game
+            .engine_lines
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn info_lines_give_the_ranked_variations() {
+        assert_eq!(
+            parse_analysis_line(
+                "info depth 20 seldepth 28 multipv 2 score mate -3 nodes 10 pv g1f3 b8c6"
+            ),
+            Some(AnalysisLine {
+                rank: 2,
+                score: Some(Score::Mate(-3)),
+                depth: Some(20),
+                moves: vec!["g1f3".to_string(), "b8c6".to_string()],
+            })
+        );
+        // Without multipv it is the best line
+        assert_eq!(
+            parse_analysis_line("info depth 5 score cp -12 pv e7e5").map(|line| line.rank),
+            Some(1)
+        );
+        assert_eq!(
+            parse_analysis_line("info depth 5 score cp -12 nodes 300"),
+            None
+        );
+        assert_eq!(parse_analysis_line("info string NNUE enabled"), None);
+    }
+
+    #[test]
+    fn long_variations_are_cut() {
+        let line = AnalysisLine {
+            rank: 1,
+            score: Some(Score::Centipawns(35)),
+            depth: Some(18),
+            moves: "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7"
+                .split(' ')
+                .map(str::to_string)
+                .collect(),
+        };
+        assert_eq!(
+            line.to_string(),
+            "1. +0.35 d18 e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 ..."
+        );
+    }
+
+    #[test]
+    fn position_typed_on_the_home_menu_is_analysed() {
+        let (mut app, receiver) = analysis_app();
+        app.menu_cursor = 2;
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, Some(Popups::FenInput));
+
+        // An empty prompt is the starting position
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_page, Pages::Analyze);
+        assert_eq!(app.current_popup, None);
+        assert!(app.game.free_play);
+        receive_lines(&mut app, &receiver);
+        assert_eq!(
+            shown_lines(&app),
+            ["1. +0.30 d12 e2e4 e7e5 g1f3", "2. +0.20 d12 d2d4 d7d5"]
+        );
+
+        // Another position starts the analysis over, the lines of the previous one are dropped
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.tick();
+        assert!(shown_lines(&app).is_empty());
+        app.update_analysis(
+            1,
+            parse_analysis_line("info depth 30 score cp 50 pv d2d4").unwrap(),
+        );
+        assert!(shown_lines(&app).is_empty());
+        receive_lines(&mut app, &receiver);
+        assert_eq!(shown_lines(&app).len(), 2);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_page, Pages::Home);
+        assert!(app.analysis.is_none());
+    }
+
+    #[test]
+    fn reviewed_position_is_analysed_and_the_review_goes_on() {
+        let (mut app, receiver) = analysis_app();
+        app.current_page = Pages::Solo;
+        for uci in ["e2e4", "e7e5", "g1f3"] {
+            app.game.play_uci_move(uci).unwrap();
+        }
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Left);
+
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.current_page, Pages::Analyze);
+        assert!(app
+            .game
+            .to_fen()
+            .starts_with("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6"));
+        receive_lines(&mut app, &receiver);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.review_index, Some(2));
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5", "g1f3"]);
+        assert!(app.analysis.is_none());
+    }
+
+    #[test]
+    fn analysis_needs_an_engine() {
+        let mut app = App {
+            menu_cursor: 2,
+            ..Default::default()
+        };
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn invalid_fen_keeps_the_prompt_open() {
+        let (mut app, _receiver) = analysis_app();
+        app.open_analysis_input();
+        for c in "not a fen".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_popup, Some(Popups::FenInput));
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn panel_follows_the_game_until_it_is_hidden() {
+        let (mut app, receiver) = analysis_app();
+        app.current_page = Pages::Solo;
+
+        press(&mut app, KeyCode::Char('e'));
+        assert!(app.analysis.is_some());
+        receive_lines(&mut app, &receiver);
+        assert_eq!(app.game.engine_lines[0].moves[0], "e2e4");
+        // The score of the best line is kept for the evaluation graph
+        assert_eq!(app.game.evaluations, [Some(Score::Centipawns(30))]);
+
+        // Stepping through the review analyses each position
+        app.game.play_uci_move("e2e4").unwrap();
+        app.tick();
+        receive_lines(&mut app, &receiver);
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Left);
+        app.tick();
+        assert!(app.game.engine_lines.is_empty());
+        receive_lines(&mut app, &receiver);
+        assert_eq!(app.game.engine_lines.len(), 2);
+
+        press(&mut app, KeyCode::Char('e'));
+        assert!(app.analysis.is_none());
+        assert!(app.game.engine_lines.is_empty());
+        assert_eq!(app.current_page, Pages::Solo);
+    }
+
+    #[test]
+    fn panel_needs_an_engine() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.toggle_analysis_panel();
+
+        assert!(app.analysis.is_none());
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+
+    #[test]
+    fn starting_position_diagram() {
+        let game = Game::default();
+
+        assert_eq!(
+            game.game_board.to_ascii_art(),
+            "\
+r n b q k b n r
+p p p p p p p p
+. . . . . . . .
+. . . . . . . .
+. . . . . . . .
+. . . . . . . .
+P P P P P P P P
+R N B Q K B N R"
+        );
+    }
+
+    #[test]
+    fn diagram_follows_the_orientation_of_the_board() {
+        let mut game = Game::default();
+        assert!(game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+
+        // Black is to move, the board is stored from their point of view
+        let diagram = game.game_board.to_ascii_art();
+        let lines: Vec<&str> = diagram.lines().collect();
+
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "R N B K Q B N R");
+        assert_eq!(lines[3], ". . . P . . . .");
+        assert_eq!(lines[7], "r n b k q b n r");
+    }
+}
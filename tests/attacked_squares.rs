@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::game_logic::game_board::GameBoard;
+    use chess_tui::pieces::PieceColor;
+    use chess_tui::utils::flip_coord;
+
+    #[test]
+    fn attacked_squares_starting_position() {
+        let game_board = GameBoard::default();
+
+        let attacked_squares = game_board.attacked_squares(PieceColor::Black);
+
+        // Every cell of the third rank from black is controlled by a pawn
+        for col in 0..8 {
+            assert!(attacked_squares.contains(&Coord::new(2, col)));
+        }
+        // Every pawn is protected
+        for col in 0..8 {
+            assert!(attacked_squares.contains(&Coord::new(1, col)));
+        }
+        // Nothing protects the rooks
+        assert!(!attacked_squares.contains(&Coord::new(0, 0)));
+        assert!(!attacked_squares.contains(&Coord::new(0, 7)));
+        assert_eq!(attacked_squares.len(), 22);
+        assert!(attacked_squares.iter().all(|coord| coord.row < 3));
+    }
+
+    #[test]
+    fn attacked_squares_fianchettoed_bishop() {
+        // Black to move, so white plays at the top of the board
+        let game = Game::from_fen("4k3/8/8/8/8/6P1/6B1/6K1 b - - 0 1").unwrap();
+
+        let attacked_squares: HashSet<String> = game
+            .game_board
+            .attacked_squares(PieceColor::White)
+            .into_iter()
+            .map(|coord| flip_coord(coord).to_algebraic())
+            .collect();
+
+        let expected: HashSet<String> = [
+            // The bishop on the long diagonal
+            "f3", "e4", "d5", "c6", "b7", "a8", "h3", "h1", "f1", // The pawn
+            "f4", "h4", // The king
+            "f1", "f2", "g2", "h2", "h1",
+        ]
+        .iter()
+        .map(|cell| cell.to_string())
+        .collect();
+        assert_eq!(attacked_squares, expected);
+    }
+
+    #[test]
+    fn attacked_squares_detect_check() {
+        let game = Game::from_fen("4k3/8/8/8/8/6P1/6B1/6K1 b - - 0 1").unwrap();
+        assert!(!game.game_board.is_check(PieceColor::Black));
+
+        let game = Game::from_fen("k7/8/8/8/8/6P1/6B1/6K1 b - - 0 1").unwrap();
+        assert!(game.game_board.is_check(PieceColor::Black));
+        assert!(game
+            .game_board
+            .attacked_squares(PieceColor::White)
+            .contains(&flip_coord(Coord::new(0, 0))));
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+
+    fn select(game: &mut Game, cell: Coord) {
+        game.ui.cursor_coordinates = cell;
+        game.handle_cell_click();
+        assert_eq!(game.ui.selected_coordinates, cell);
+    }
+
+    #[test]
+    fn positions_of_a_selection_are_computed_once() {
+        let mut game = Game::default();
+        select(&mut game, Coord::new(6, 4));
+
+        assert_eq!(
+            game.selected_piece_positions(),
+            [Coord::new(5, 4), Coord::new(4, 4)]
+        );
+        // The next calls read the cache instead of looking for the moves again
+        if let Some(cache) = &mut game.ui.authorized_positions_cache {
+            cache.positions = vec![Coord::new(4, 4)];
+        }
+        assert_eq!(game.selected_piece_positions(), [Coord::new(4, 4)]);
+    }
+
+    #[test]
+    fn cache_follows_the_selection() {
+        let mut game = Game::default();
+        select(&mut game, Coord::new(6, 4));
+        game.selected_piece_positions();
+
+        game.ui.unselect_cell();
+        select(&mut game, Coord::new(7, 6));
+
+        assert_eq!(
+            game.selected_piece_positions(),
+            [Coord::new(5, 5), Coord::new(5, 7)]
+        );
+        let cache = game.ui.authorized_positions_cache.as_ref().unwrap();
+        assert_eq!(cache.selected, Coord::new(7, 6));
+    }
+
+    #[test]
+    fn cache_follows_the_board() {
+        let mut game = Game::default();
+        // The bishop is stuck behind its pawns
+        game.ui.selected_coordinates = Coord::new(7, 2);
+        assert!(game.selected_piece_positions().is_empty());
+
+        game.game_board.board[6][3] = None;
+
+        assert_eq!(game.selected_piece_positions().len(), 5);
+    }
+
+    #[test]
+    fn cache_is_not_reused_after_a_move() {
+        let mut game = Game::default();
+        select(&mut game, Coord::new(6, 4));
+        game.selected_piece_positions();
+
+        // 1.e4 e5, white selects the same cell where nothing stands anymore
+        game.play_uci_move("e2e4").unwrap();
+        game.play_uci_move("e7e5").unwrap();
+        game.ui.selected_coordinates = Coord::new(6, 4);
+
+        assert!(game.selected_piece_positions().is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::{App, AUTO_RECAPTURE_DELAY_TICKS};
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    // Black's queen can take the knight on g1, the white king then has to take back
+    const RECAPTURE_FEN: &str = "7k/8/8/8/8/8/6PP/q5NK b - - 0 1";
+
+    fn app_after_capture(auto_recapture: bool) -> App {
+        let mut app = App {
+            auto_recapture,
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.load_fen(RECAPTURE_FEN).unwrap();
+
+        // Qa1xg1, seen from black
+        app.game.ui.cursor_coordinates = Coord::new(0, 7);
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = Coord::new(0, 1);
+        app.handle_cell_click();
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        app
+    }
+
+    #[test]
+    fn forced_recapture_is_offered() {
+        let app = app_after_capture(true);
+
+        let pending_recapture = app.pending_recapture.unwrap();
+        assert_eq!(pending_recapture.piece_move.from, Coord::new(7, 7));
+        assert_eq!(pending_recapture.piece_move.to, Coord::new(7, 6));
+        assert!(app.status_message().starts_with("Recapturing on g1"));
+    }
+
+    #[test]
+    fn forced_recapture_is_played_after_the_delay() {
+        let mut app = app_after_capture(true);
+
+        for _ in 0..AUTO_RECAPTURE_DELAY_TICKS {
+            app.tick();
+        }
+
+        assert!(app.pending_recapture.is_none());
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        // The white king stands on g1, seen from black
+        assert_eq!(
+            app.game.game_board.board[0][1],
+            Some((PieceType::King, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn forced_recapture_can_be_played_right_away() {
+        let mut app = app_after_capture(true);
+
+        app.confirm_auto_recapture();
+
+        assert!(app.pending_recapture.is_none());
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+    }
+
+    #[test]
+    fn forced_recapture_can_be_cancelled() {
+        let mut app = app_after_capture(true);
+
+        app.cancel_auto_recapture();
+        for _ in 0..AUTO_RECAPTURE_DELAY_TICKS {
+            app.tick();
+        }
+
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(app.status_message(), "White to move, check!");
+    }
+
+    #[test]
+    fn forced_recapture_is_not_offered_when_disabled() {
+        let app = app_after_capture(false);
+
+        assert!(app.pending_recapture.is_none());
+    }
+
+    #[test]
+    fn forced_recapture_is_offered_later_in_the_game() {
+        let mut app = App {
+            auto_recapture: true,
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.load_fen(&RECAPTURE_FEN.replace(" b ", " w ")).unwrap();
+
+        app.play_algebraic_move("g3").unwrap();
+        app.play_algebraic_move("Qxg1+").unwrap();
+
+        let pending_recapture = app.pending_recapture.unwrap();
+        assert_eq!(pending_recapture.piece_move.from, Coord::new(7, 7));
+        assert_eq!(pending_recapture.piece_move.to, Coord::new(7, 6));
+    }
+
+    #[test]
+    fn recapture_is_not_offered_without_capture() {
+        let mut app = App {
+            auto_recapture: true,
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+
+        // e2-e4
+        app.game.ui.cursor_coordinates = Coord::new(6, 4);
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = Coord::new(4, 4);
+        app.handle_cell_click();
+
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert!(app.pending_recapture.is_none());
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::{App, PendingBlunder};
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::PieceType;
+    use toml::Value;
+
+    // The black pawn of d5 guards e4
+    const PAWN_GUARD_FEN: &str = "4k3/8/8/3p4/8/3Q4/8/4K3 w - - 0 1";
+
+    fn cell(name: &str) -> Coord {
+        Coord::from_algebraic(name).unwrap()
+    }
+
+    fn hanging(fen: &str, from: &str, to: &str) -> Option<PieceType> {
+        Game::from_fen(fen)
+            .unwrap()
+            .piece_left_hanging(cell(from), cell(to))
+    }
+
+    #[test]
+    fn queen_left_undefended_hangs() {
+        assert_eq!(hanging(PAWN_GUARD_FEN, "d3", "e4"), Some(PieceType::Queen));
+        assert_eq!(hanging(PAWN_GUARD_FEN, "d3", "d2"), None);
+    }
+
+    #[test]
+    fn defended_piece_hangs_to_a_cheaper_attacker() {
+        // The rook is defended by the king but the knight is worth less
+        let game = Game::from_fen("k7/8/8/8/8/6n1/4R3/3K4 w - - 0 1").unwrap();
+        assert_eq!(
+            game.game_board.hanging_pieces(game.player_turn),
+            vec![(cell("e2"), PieceType::Rook)]
+        );
+        // A defended knight attacked by a rook is safe
+        let game = Game::from_fen("k3r3/8/8/8/8/8/4N3/3K4 w - - 0 1").unwrap();
+        assert!(game.game_board.hanging_pieces(game.player_turn).is_empty());
+    }
+
+    #[test]
+    fn even_trades_and_pawns_are_not_reported() {
+        // The queen is taken back by the king after taking the other queen
+        assert_eq!(hanging("3qk3/8/8/8/8/8/8/3QK3 w - - 0 1", "d1", "d8"), None);
+        // The pawn of e4 can be taken by the pawn of d5
+        assert_eq!(
+            hanging("4k3/8/8/3p4/8/8/4P3/4K3 w - - 0 1", "e2", "e4"),
+            None
+        );
+    }
+
+    fn warned_app() -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            blunder_warning: true,
+            ..Default::default()
+        };
+        app.load_fen(PAWN_GUARD_FEN).unwrap();
+        app.game.ui.cursor_coordinates = cell("d3");
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = cell("e4");
+        app.handle_cell_click();
+        app
+    }
+
+    #[test]
+    fn blunder_waits_for_a_confirmation() {
+        let mut app = warned_app();
+        assert_eq!(app.current_popup, Some(Popups::BlunderWarning));
+        assert_eq!(
+            app.pending_blunder,
+            Some(PendingBlunder {
+                from: cell("d3"),
+                to: cell("e4"),
+                hanging_piece: PieceType::Queen,
+                promotion: None,
+            })
+        );
+        assert!(app.game.game_board.move_history.is_empty());
+
+        app.confirm_blunder();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["d3e4".to_string()]);
+    }
+
+    #[test]
+    fn cancelled_blunder_keeps_the_piece_selected() {
+        let mut app = warned_app();
+        app.cancel_blunder();
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.pending_blunder, None);
+        assert!(app.game.game_board.move_history.is_empty());
+        assert_eq!(app.game.ui.selected_coordinates, cell("d3"));
+    }
+
+    #[test]
+    fn typed_blunder_waits_for_a_confirmation() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            blunder_warning: true,
+            ..Default::default()
+        };
+        app.load_fen(PAWN_GUARD_FEN).unwrap();
+        app.open_move_input();
+        app.game.ui.prompt.input = "Qe4".to_string();
+        app.submit_move_input();
+
+        assert_eq!(app.current_popup, Some(Popups::BlunderWarning));
+        assert!(app.game.game_board.move_history.is_empty());
+
+        app.confirm_blunder();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["d3e4".to_string()]);
+    }
+
+    #[test]
+    fn warning_is_off_by_default() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.load_fen(PAWN_GUARD_FEN).unwrap();
+        app.game.ui.cursor_coordinates = cell("d3");
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = cell("e4");
+        app.handle_cell_click();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_board.move_history.len(), 1);
+
+        app.apply_config(&"blunder_warning = true".parse::<Value>().unwrap());
+        assert!(app.blunder_warning);
+    }
+}
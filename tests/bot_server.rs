@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::network::bot_server::BotServer;
+    use chess_tui::network::handshake::{wait_for_game_start, DEFAULT_CONNECTION_TIMEOUT};
+    use chess_tui::network::protocol::Message;
+    use chess_tui::pgn::PlayerNames;
+    use chess_tui::pieces::PieceColor;
+    use chess_tui::rng::Rng;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    // A started game on a free loopback port with the bot playing `bot_color`
+    fn start_server(bot_color: PieceColor) -> TcpStream {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let bot = RandomBot::new(Rng::from_seed(1));
+            BotServer::new(bot, bot_color).serve(stream).unwrap();
+        });
+        let (stream, color, host_name) =
+            wait_for_game_start(address, DEFAULT_CONNECTION_TIMEOUT, &PlayerNames::default())
+                .unwrap();
+        assert_eq!(color, bot_color.opposite());
+        assert_eq!(host_name, "Random bot");
+        stream
+    }
+
+    fn receive(reader: &mut impl BufRead) -> Message {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        Message::parse(&line).unwrap()
+    }
+
+    #[test]
+    fn bot_replies_with_a_legal_move() {
+        let mut stream = start_server(PieceColor::Black);
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut game = Game::default();
+
+        for human_move in ["e2e4", "d2d4"] {
+            writeln!(stream, "{}", Message::Move(human_move.to_string())).unwrap();
+            game.play_uci_move(human_move).unwrap();
+
+            let Message::Move(bot_move) = receive(&mut reader) else {
+                panic!("the bot should answer with a move");
+            };
+            assert!(game.play_uci_move(&bot_move).is_ok(), "{bot_move}");
+            assert_eq!(game.player_turn, PieceColor::White);
+        }
+    }
+
+    #[test]
+    fn bot_playing_white_opens_the_game() {
+        let stream = start_server(PieceColor::White);
+        let mut reader = BufReader::new(stream);
+        let mut game = Game::default();
+
+        let Message::Move(bot_move) = receive(&mut reader) else {
+            panic!("the bot should open with a move");
+        };
+        assert!(game.play_uci_move(&bot_move).is_ok());
+    }
+
+    #[test]
+    fn illegal_move_is_reported() {
+        let mut stream = start_server(PieceColor::Black);
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        writeln!(stream, "move e2e5").unwrap();
+        assert_eq!(
+            receive(&mut reader),
+            Message::Error("Illegal move: e2e5".to_string())
+        );
+
+        writeln!(stream, "hello").unwrap();
+        assert!(matches!(receive(&mut reader), Message::Error(_)));
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        for message in [
+            Message::Start(PieceColor::White),
+            Message::Start(PieceColor::Black),
+            Message::Move("e7e8q".to_string()),
+            Message::Chat("good game".to_string()),
+            Message::Error("it is not your turn".to_string()),
+            Message::Resign,
+        ] {
+            assert_eq!(Message::parse(&message.to_string()), Some(message));
+        }
+        assert_eq!(
+            Message::Chat("two\nlines".to_string()).to_string(),
+            "chat two lines"
+        );
+        assert_eq!(Message::parse("move"), None);
+        assert_eq!(Message::parse("start red"), None);
+    }
+}
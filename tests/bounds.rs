@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    fn game_from(fen: &str) -> Game {
+        Game::from_fen(fen).unwrap()
+    }
+
+    #[test]
+    fn castling_without_rook_is_refused() {
+        // The king "castles" towards h1 although the rook is gone
+        let mut game = game_from("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+        let board = game.game_board.board;
+
+        game.execute_move(&Coord::new(7, 4), &Coord::new(7, 7));
+
+        assert_eq!(game.game_board.board, board);
+        assert!(game.game_board.move_history.is_empty());
+    }
+
+    #[test]
+    fn castling_far_from_the_rook_does_not_panic() {
+        // A king on b1 castling with the rook on h1 goes to g1 like in Chess960
+        let mut game = game_from("4k3/8/8/8/8/8/8/1K5R w - - 0 1");
+
+        game.execute_move(&Coord::new(7, 1), &Coord::new(7, 7));
+
+        assert_eq!(
+            game.game_board.board[7][6],
+            Some((PieceType::King, PieceColor::White))
+        );
+        assert_eq!(
+            game.game_board.board[7][5],
+            Some((PieceType::Rook, PieceColor::White))
+        );
+        assert_eq!(game.game_board.board[7][7], None);
+    }
+
+    #[test]
+    fn en_passant_off_the_board_is_refused() {
+        // A pawn taking "en passant" backwards on the first rank would remove a pawn below the board
+        let mut game = game_from("4k3/8/8/8/8/8/P7/4K3 w - - 0 1");
+        let board = game.game_board.board;
+
+        game.execute_move(&Coord::new(6, 0), &Coord::new(7, 1));
+
+        assert_eq!(game.game_board.board, board);
+        assert!(game.game_board.move_history.is_empty());
+    }
+
+    #[test]
+    fn undefined_cells_are_ignored() {
+        let mut game = Game::default();
+        let board = game.game_board.board;
+
+        game.execute_move(&Coord::undefined(), &Coord::new(4, 4));
+        game.execute_move(&Coord::new(6, 4), &Coord::undefined());
+
+        assert_eq!(game.game_board.board, board);
+    }
+}
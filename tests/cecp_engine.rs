@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::engine::cecp::{
+        limit_commands, parse_features, parse_move, parse_thinking, stream_cecp_output,
+    };
+    use chess_tui::engine::uci::{EngineInfo, Score};
+    use chess_tui::engine::SearchConstraints;
+    use chess_tui::event::Event;
+    use std::io::Cursor;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn features_are_read_with_their_quoted_values() {
+        assert_eq!(
+            parse_features(r#"feature myname="GNU Chess 6.2.9" setboard=1 ping=1 done=1"#),
+            [
+                ("myname".to_string(), "GNU Chess 6.2.9".to_string()),
+                ("setboard".to_string(), "1".to_string()),
+                ("ping".to_string(), "1".to_string()),
+                ("done".to_string(), "1".to_string()),
+            ]
+        );
+        assert!(parse_features("tellics say hello").is_empty());
+    }
+
+    #[test]
+    fn limits_are_given_in_whole_seconds() {
+        assert_eq!(
+            limit_commands(SearchConstraints {
+                depth: Some(8),
+                movetime: None,
+            }),
+            ["sd 8"]
+        );
+        assert_eq!(limit_commands(SearchConstraints::default()), ["st 1"]);
+        assert_eq!(
+            limit_commands(SearchConstraints {
+                depth: Some(2),
+                movetime: Some(Duration::from_millis(2500)),
+            }),
+            ["sd 2", "st 3"]
+        );
+    }
+
+    #[test]
+    fn moves_and_thinking_are_parsed() {
+        assert_eq!(parse_move("move e7e5"), Some("e7e5".to_string()));
+        assert_eq!(parse_move("Illegal move: e7e5"), None);
+        assert_eq!(
+            parse_thinking("  9    -25     113    284301 Nf6 Nc3 d5"),
+            Some(EngineInfo {
+                best_move: "Nf6".to_string(),
+                score: Some(Score::Centipawns(-25)),
+                depth: Some(9),
+            })
+        );
+        assert_eq!(
+            parse_thinking("12. 100003 40 9000 Qh4"),
+            Some(EngineInfo {
+                best_move: "Qh4".to_string(),
+                score: Some(Score::Mate(3)),
+                depth: Some(12),
+            })
+        );
+        assert_eq!(parse_thinking("feature done=1"), None);
+    }
+
+    #[test]
+    fn resigning_engine_fails_its_search() {
+        let (sender, receiver) = mpsc::channel();
+        stream_cecp_output(Cursor::new("5 -900 10 500 Kg1\nresign\n"), &sender);
+
+        assert!(matches!(receiver.recv().unwrap(), Event::EngineInfo(_)));
+        assert!(matches!(receiver.recv().unwrap(), Event::EngineFailure(_)));
+        assert!(receiver.try_recv().is_err());
+    }
+}
+
+#[cfg(all(test, unix))]
+mod engine_tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::cecp::CecpEngine;
+    use chess_tui::engine::uci::{Score, SearchReport};
+    use chess_tui::engine::{Engine, SearchConstraints};
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::PieceColor;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use toml::Value;
+
+    // Writes the commands it receives next to itself
+    const XBOARD_ENGINE: &str = r#"#!/bin/sh
+log="$0.log"
+while read -r command; do
+  echo "$command" >> "$log"
+  case "$command" in
+    "protover 2") echo 'feature myname="Old Faithful 1.0" setboard=1 ping=1'; echo "feature done=1" ;;
+    ping*) echo "pong ${command#ping }" ;;
+    go) echo " 4    35      2     1200 e5 Nf3"; echo "move e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    // Only knows how to play from the start
+    const SETBOARDLESS_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    "protover 2") echo "feature setboard=0 done=1" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn write_engine(name: &str, script: &str) -> (PathBuf, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("chess-tui-{name}-engine-{}.sh", std::process::id()));
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+        (path, log)
+    }
+
+    #[test]
+    fn engine_gets_the_position_and_the_limits_of_the_search() {
+        let (path, log) = write_engine("xboard", XBOARD_ENGINE);
+        let mut engine = CecpEngine::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(engine.name(), "Old Faithful 1.0");
+
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+        let best_move = engine
+            .best_move(
+                fen,
+                SearchConstraints {
+                    depth: Some(4),
+                    movetime: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            best_move,
+            Game::from_fen(fen)
+                .unwrap()
+                .piece_move_from_uci("e7e5")
+                .unwrap()
+        );
+        assert_eq!(
+            engine.last_report(),
+            Some(SearchReport {
+                score: Score::Centipawns(35),
+                depth: Some(4),
+            })
+        );
+        drop(engine);
+        let commands = fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            commands.lines().collect::<Vec<_>>(),
+            [
+                "xboard",
+                "protover 2",
+                "accepted myname",
+                "accepted setboard",
+                "accepted ping",
+                "new",
+                "force",
+                "easy",
+                "post",
+                "ping 1",
+                "force",
+                &format!("setboard {fen}"),
+                "sd 4",
+                "go",
+                "quit",
+            ]
+        );
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+
+    #[test]
+    fn engines_without_setboard_are_refused() {
+        let (path, _) = write_engine("setboardless", SETBOARDLESS_ENGINE);
+
+        assert!(CecpEngine::new(path.to_str().unwrap()).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn xboard_profile_plays_the_bot() {
+        let (path, log) = write_engine("xboard-profile", XBOARD_ENGINE);
+        let config: Value = toml::from_str(&format!(
+            "[[engines]]\npath = \"{}\"\nprotocol = \"xboard\"\noptions = {{ Style = \"Solid\" }}\n",
+            path.display()
+        ))
+        .unwrap();
+        let mut app = App {
+            current_page: Pages::Bot,
+            ..Default::default()
+        };
+        app.apply_config(&config);
+        app.menu_cursor = 0;
+        app.color_selection();
+        app.difficulty_selection();
+        app.engine_selection();
+        assert_eq!(app.selected_color, Some(PieceColor::White));
+        assert_eq!(app.bot.as_ref().unwrap().name(), "Old Faithful 1.0");
+
+        app.game.play_uci_move("e2e4").unwrap();
+        app.play_bot_move();
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        let commands = fs::read_to_string(&log).unwrap();
+        assert!(commands.lines().any(|line| line == "option Style=Solid"));
+        drop(app);
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+}
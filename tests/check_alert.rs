@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::{App, CHECK_ALERT_TICKS};
+    use chess_tui::error::ChessError;
+    use chess_tui::pieces::PieceColor;
+
+    fn app_with_black_to_move() -> App {
+        let mut app = App::default();
+        app.load_fen("r3k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        app
+    }
+
+    #[test]
+    fn received_check_sets_the_alert() {
+        let mut app = app_with_black_to_move();
+
+        app.apply_received_move("a8a1").unwrap();
+
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(app.check_alert, Some(CHECK_ALERT_TICKS));
+        assert!(app.is_check_alert_visible());
+    }
+
+    #[test]
+    fn quiet_move_does_not_set_the_alert() {
+        let mut app = app_with_black_to_move();
+
+        app.apply_received_move("a8a7").unwrap();
+
+        assert_eq!(app.check_alert, None);
+        assert!(!app.is_check_alert_visible());
+    }
+
+    #[test]
+    fn alert_flashes_then_expires() {
+        let mut app = app_with_black_to_move();
+        app.apply_received_move("a8a1").unwrap();
+
+        app.tick();
+        assert!(!app.is_check_alert_visible());
+        app.tick();
+        assert!(app.is_check_alert_visible());
+
+        for _ in 2..CHECK_ALERT_TICKS {
+            app.tick();
+        }
+        assert_eq!(app.check_alert, None);
+    }
+
+    #[test]
+    fn illegal_received_move_is_rejected() {
+        let mut app = app_with_black_to_move();
+
+        assert_eq!(
+            app.apply_received_move("a8b7"),
+            Err(ChessError::IllegalMove("a8b7".to_string()))
+        );
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.check_alert, None);
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::board::Board;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::game_logic::game_board::GameBoard;
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    #[test]
+    fn no_king_is_checked_in_the_starting_position() {
+        let game = Game::default();
+
+        assert!(game.game_board.checked_kings(PieceColor::White).is_empty());
+    }
+
+    #[test]
+    fn checked_king_of_the_player_to_move_is_reported() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2r w - - 0 1").unwrap();
+
+        assert_eq!(
+            game.game_board.checked_kings(PieceColor::White),
+            vec![Coord::new(7, 4)]
+        );
+    }
+
+    #[test]
+    fn checked_king_of_the_side_at_the_top_is_reported() {
+        // White just checked, the board is now stored from the point of view of black
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        assert!(game.play_move(Coord::new(7, 0), Coord::new(0, 0)));
+
+        // The black king on e8 is at the bottom of the flipped board
+        assert_eq!(
+            game.game_board.checked_kings(PieceColor::Black),
+            vec![Coord::new(7, 3)]
+        );
+        // Seen from white again, it is back at the top
+        game.game_board.flip_the_board();
+        assert_eq!(
+            game.game_board.checked_kings(PieceColor::White),
+            vec![Coord::new(0, 4)]
+        );
+    }
+
+    #[test]
+    fn both_kings_are_reported_when_both_are_checked() {
+        // Not reachable in a game, each rook checks the king on the other side
+        let mut board: Board = [[None; 8]; 8];
+        board[0][0] = Some((PieceType::King, PieceColor::Black));
+        board[0][4] = Some((PieceType::Rook, PieceColor::Black));
+        board[7][0] = Some((PieceType::Rook, PieceColor::White));
+        board[7][4] = Some((PieceType::King, PieceColor::White));
+        let game_board = GameBoard::new(board, vec![], vec![board]);
+
+        assert_eq!(
+            game_board.checked_kings(PieceColor::White),
+            vec![Coord::new(7, 4), Coord::new(0, 0)]
+        );
+    }
+}
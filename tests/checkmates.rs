@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use chess_tui::game_logic::coord::Coord;
     use chess_tui::game_logic::game::Game;
     use chess_tui::game_logic::game_board::GameBoard;
     use chess_tui::pieces::{PieceColor, PieceType};
@@ -355,4 +356,70 @@ mod tests {
 
         assert!(!game.game_board.is_checkmate(game.player_turn));
     }
+
+    #[test]
+    fn is_check_but_not_checkmate() {
+        // The black king can escape the queen check
+        let game = Game::from_fen("4k3/8/8/8/8/8/4Q3/4K3 b - - 0 1").unwrap();
+
+        assert!(game.game_board.is_check(PieceColor::Black));
+        assert!(!game.game_board.is_checkmate(PieceColor::Black));
+        assert_eq!(game.status_message(), "Black to move, check!");
+    }
+
+    #[test]
+    fn is_check_and_checkmate() {
+        let game = Game::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+
+        assert!(game.game_board.is_check(PieceColor::Black));
+        assert!(game.game_board.is_checkmate(PieceColor::Black));
+        assert_eq!(game.status_message(), "Checkmate 1-0");
+    }
+
+    #[test]
+    fn is_not_check() {
+        let game = Game::default();
+
+        assert!(!game.game_board.is_check(PieceColor::White));
+        assert!(!game.game_board.is_checkmate(PieceColor::White));
+        assert_eq!(game.status_message(), "White to move");
+    }
+
+    #[test]
+    fn check_suffix_in_history() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1").unwrap();
+
+        // Queen a1 to a3, no check
+        game.ui.cursor_coordinates = Coord::new(7, 0);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(5, 0);
+        game.handle_cell_click();
+        assert_eq!(game.game_board.check_suffix(0), "");
+
+        // King e8 to d8 seen from black
+        game.ui.cursor_coordinates = Coord::new(7, 3);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(7, 4);
+        game.handle_cell_click();
+
+        // Queen a3 to a8, check
+        game.ui.cursor_coordinates = Coord::new(5, 0);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(0, 0);
+        game.handle_cell_click();
+        assert_eq!(game.game_board.check_suffix(2), "+");
+        assert_eq!(game.game_board.check_suffix(3), "");
+    }
+
+    #[test]
+    fn checkmate_suffix_in_history() {
+        let mut game = Game::from_fen("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1").unwrap();
+
+        // Queen b1 to b8 mates
+        game.ui.cursor_coordinates = Coord::new(7, 1);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(0, 1);
+        game.handle_cell_click();
+        assert_eq!(game.game_board.check_suffix(0), "#");
+    }
 }
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::chess960::chess960_fen;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use chess_tui::rng::Rng;
+
+    const START: &str = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1";
+
+    #[test]
+    fn x_fen_castles_with_the_outermost_rooks() {
+        let game = Game::from_fen(START).unwrap();
+
+        let rights = game.game_board.castling_rights;
+        assert_eq!(rights.king_side_rook_files, [6, 6]);
+        assert_eq!(rights.queen_side_rook_files, [4, 4]);
+        assert_eq!(game.to_fen(), START);
+    }
+
+    #[test]
+    fn shredder_fen_names_the_rook_files() {
+        let shredder = Game::from_fen(&START.replace("KQkq", "GEge")).unwrap();
+        let x_fen = Game::from_fen(START).unwrap();
+
+        assert_eq!(
+            shredder.game_board.castling_rights,
+            x_fen.game_board.castling_rights
+        );
+        assert_eq!(shredder.to_fen(), START);
+    }
+
+    #[test]
+    fn a_rook_with_another_one_further_out_is_named_by_its_file() {
+        let fen = "r3k2r/8/8/8/8/8/8/1R2K1RR w GQkq - 0 1";
+        let game = Game::from_fen(fen).unwrap();
+
+        assert_eq!(game.game_board.castling_rights.king_side_rook_files[0], 6);
+        assert_eq!(game.game_board.castling_rights.queen_side_rook_files[0], 1);
+        assert_eq!(game.to_fen(), fen);
+    }
+
+    #[test]
+    fn castling_rook_without_a_king_is_refused() {
+        assert!(Game::from_fen("4k3/8/8/8/8/8/4K3/R6R w H - 0 1").is_err());
+    }
+
+    #[test]
+    fn king_castles_with_the_rook_beside_it() {
+        let mut game = Game::from_fen(START).unwrap();
+
+        game.play_algebraic_move("O-O").unwrap();
+
+        assert_eq!(game.game_board.uci_moves(), ["f1g1"]);
+        assert_eq!(game.san_moves(), ["O-O"]);
+        assert_eq!(
+            game.to_fen(),
+            "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRRKN b kq - 1 1"
+        );
+    }
+
+    #[test]
+    fn both_players_castle_with_rooks_off_the_corners() {
+        let mut game = Game::from_fen("1r2k1r1/8/8/8/8/8/8/1R2K3 w Qkq - 0 1").unwrap();
+
+        // The destination of the king is understood like the king taking its rook
+        game.play_uci_move("e1c1").unwrap();
+        game.play_algebraic_move("O-O").unwrap();
+
+        assert_eq!(game.game_board.uci_moves(), ["e1b1", "e8g8"]);
+        assert_eq!(game.san_moves(), ["O-O-O", "O-O"]);
+        assert_eq!(game.to_fen(), "1r3rk1/8/8/8/8/8/8/2KR4 w - - 2 2");
+    }
+
+    #[test]
+    fn castling_is_refused_when_the_king_goes_through_a_check() {
+        // The bishop on c4 sees f1
+        let mut game = Game::from_fen("4k3/8/8/8/2b5/8/8/4K2R w K - 0 1").unwrap();
+
+        assert!(game.play_algebraic_move("O-O").is_err());
+    }
+
+    #[test]
+    fn rook_cell_attacked_does_not_forbid_castling() {
+        // The rook on b8 only sees the cell the rook on a1 goes through
+        let game = Game::from_fen("1r2k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+
+        assert!(game
+            .legal_destinations("e1")
+            .unwrap()
+            .contains(&"c1".to_string()));
+    }
+
+    #[test]
+    fn random_starting_positions_allow_every_castle() {
+        let mut rng = Rng::from_seed(3);
+        for _ in 0..20 {
+            let fen = chess960_fen(&mut rng);
+            let game = Game::from_fen(&fen).unwrap();
+
+            assert_eq!(game.to_fen(), fen);
+            let king_file = game.game_board.board[7]
+                .iter()
+                .position(|cell| *cell == Some((PieceType::King, PieceColor::White)))
+                .unwrap() as u8;
+            let rights = game.game_board.castling_rights;
+            assert!(rights.queen_side_rook_files[0] < king_file);
+            assert!(rights.king_side_rook_files[0] > king_file);
+        }
+    }
+}
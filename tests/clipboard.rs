@@ -0,0 +1,23 @@
+#[cfg(all(test, not(feature = "clipboard")))]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::handler::handle_key_events;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn copying_the_fen_needs_the_clipboard_feature() {
+        let mut app = App::default();
+
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL),
+            &mut app,
+        )
+        .unwrap();
+
+        let toast = app.toast.as_ref().unwrap();
+        assert!(toast.is_error);
+        assert!(toast.message.contains("clipboard"));
+        // `f` alone still toggles free play
+        assert!(!app.game.free_play);
+    }
+}
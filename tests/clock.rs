@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::clock::{format_clock, Clock};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::game_logic::game_setup::{GameSetup, TimeControl};
+    use chess_tui::pieces::PieceColor;
+
+    const THRESHOLD: Duration = Duration::from_secs(20);
+
+    fn blitz() -> TimeControl {
+        TimeControl {
+            initial: Duration::from_secs(180),
+            increment: Duration::from_secs(2),
+        }
+    }
+
+    #[test]
+    fn format_clock_above_threshold() {
+        assert_eq!(format_clock(Duration::from_secs(83), THRESHOLD), "1:23");
+        assert_eq!(
+            format_clock(Duration::from_millis(20_900), THRESHOLD),
+            "0:20"
+        );
+        assert_eq!(
+            format_clock(Duration::from_secs(3_723), THRESHOLD),
+            "1:02:03"
+        );
+    }
+
+    #[test]
+    fn format_clock_under_threshold() {
+        assert_eq!(
+            format_clock(Duration::from_millis(5_360), THRESHOLD),
+            "0:05.3"
+        );
+        assert_eq!(
+            format_clock(Duration::from_millis(19_999), THRESHOLD),
+            "0:19.9"
+        );
+        assert_eq!(format_clock(Duration::ZERO, THRESHOLD), "0:00.0");
+        // A threshold over a minute keeps the minutes
+        assert_eq!(
+            format_clock(Duration::from_millis(65_400), Duration::from_secs(90)),
+            "1:05.4"
+        );
+    }
+
+    #[test]
+    fn clock_starts_after_the_first_move() {
+        let start = Instant::now();
+        let mut clock = Clock::new(blitz());
+        let later = start + Duration::from_secs(10);
+
+        assert!(!clock.is_running());
+        assert_eq!(clock.remaining(PieceColor::White, later), blitz().initial);
+
+        // White played, black is thinking
+        clock.switch_to(PieceColor::Black, start);
+        assert_eq!(clock.remaining(PieceColor::White, later), blitz().initial);
+        assert_eq!(
+            clock.remaining(PieceColor::Black, later),
+            Duration::from_secs(170)
+        );
+    }
+
+    #[test]
+    fn clock_adds_increment_and_flags() {
+        let start = Instant::now();
+        let mut clock = Clock::new(blitz());
+        clock.switch_to(PieceColor::Black, start);
+
+        // Black plays after 10 seconds
+        clock.switch_to(PieceColor::White, start + Duration::from_secs(10));
+        assert_eq!(
+            clock.remaining(PieceColor::Black, start + Duration::from_secs(60)),
+            Duration::from_secs(172)
+        );
+
+        assert_eq!(clock.flagged(start + Duration::from_secs(100)), None);
+        assert_eq!(
+            clock.flagged(start + Duration::from_secs(200)),
+            Some(PieceColor::White)
+        );
+
+        clock.stop(start + Duration::from_secs(100));
+        assert_eq!(clock.flagged(start + Duration::from_secs(200)), None);
+        assert_eq!(
+            clock.remaining(PieceColor::White, start + Duration::from_secs(200)),
+            Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn app_runs_the_clock_of_the_player_to_move() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            setup: GameSetup {
+                time_control: Some(blitz()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.restart();
+        assert!(!app.game.clock.unwrap().is_running());
+
+        // e2-e4
+        app.game.ui.cursor_coordinates = Coord::new(6, 4);
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = Coord::new(4, 4);
+        app.handle_cell_click();
+
+        let clock = app.game.clock.unwrap();
+        assert!(clock.is_running());
+        let now = Instant::now();
+        assert_eq!(clock.remaining(PieceColor::White, now), blitz().initial);
+        assert!(clock.remaining(PieceColor::Black, now) <= blitz().initial);
+    }
+
+    #[test]
+    fn app_ends_the_game_on_time() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        let mut clock = Clock::new(blitz());
+        // Black has been thinking for longer than the whole time control
+        clock.switch_to(PieceColor::Black, Instant::now() - Duration::from_secs(200));
+        app.game.clock = Some(clock);
+        app.game.player_turn = PieceColor::Black;
+
+        app.tick();
+
+        assert_eq!(app.game.game_state, GameState::Timeout);
+        assert_eq!(app.game.result(), Some("1-0"));
+        assert!(!app.game.clock.unwrap().is_running());
+    }
+}
@@ -0,0 +1,41 @@
+// Helpers shared by the test suites, each suite only uses some of them
+#![allow(dead_code)]
+
+use chess_tui::app::App;
+use chess_tui::handler::handle_key_events;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn press(app: &mut App, code: KeyCode) {
+    handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+}
+
+/// The path of an executable running `script`, to stand in for a chess engine
+/// Each script is written once per run, so that no test starts the engine while another one is
+/// writing it. It keeps the same path from one run to the next and replaces the script of the
+/// previous run, so scripts don't pile up in the temporary folder
+#[cfg(unix)]
+pub fn write_fake_engine(name: &str, script: &str) -> &'static str {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::{Mutex, OnceLock};
+
+    static WRITTEN: OnceLock<Mutex<HashMap<String, &'static str>>> = OnceLock::new();
+    let mut written = WRITTEN.get_or_init(Default::default).lock().unwrap();
+    if let Some(path) = written.get(name) {
+        return path;
+    }
+
+    let folder = std::env::temp_dir().join("chess-tui-fake-engines");
+    fs::create_dir_all(&folder).unwrap();
+    let path = folder.join(format!("{name}.sh"));
+    // Another run might be starting the script of the same name, it keeps the one it opened
+    let new_path = folder.join(format!("{name}.sh.{}", std::process::id()));
+    fs::write(&new_path, script).unwrap();
+    fs::set_permissions(&new_path, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::rename(&new_path, &path).unwrap();
+
+    let path: &'static str = Box::leak(path.to_str().unwrap().to_string().into_boxed_str());
+    written.insert(name.to_string(), path);
+    path
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::ui::{cell_size, BoardRenderSettings};
+    use chess_tui::handler::handle_mouse_events;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::layout::Rect;
+    use ratatui::Terminal;
+
+    // Wider than tall, so compact cells leave a border on both sides
+    const BOARD_AREA: Rect = Rect {
+        x: 3,
+        y: 2,
+        width: 100,
+        height: 40,
+    };
+
+    // A local game whose board was drawn in `BOARD_AREA`, the geometry of the mouse comes from it
+    fn rendered_app(compact: bool) -> (App, Terminal<TestBackend>) {
+        let mut app = App {
+            current_page: Pages::Solo,
+            compact_cells: compact,
+            ..Default::default()
+        };
+        let mut terminal = Terminal::new(TestBackend::new(110, 45)).unwrap();
+        terminal
+            .draw(|frame| {
+                let game = app.game.clone();
+                let settings = BoardRenderSettings {
+                    flipped: false,
+                    theme: *app.theme(),
+                    highlight_all_checks: false,
+                    compact,
+                };
+                app.game
+                    .ui
+                    .board_render(BOARD_AREA, frame, &game, &settings);
+            })
+            .unwrap();
+        (app, terminal)
+    }
+
+    fn click(app: &mut App, column: u16, row: u16) {
+        let mouse_event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, app).unwrap();
+    }
+
+    #[test]
+    fn compact_cells_are_twice_as_wide_as_tall() {
+        assert_eq!(cell_size(BOARD_AREA, true), (10, 5));
+        assert_eq!(cell_size(BOARD_AREA, false), (12, 5));
+        // The width limits the height in narrow areas
+        assert_eq!(cell_size(Rect::new(0, 0, 48, 40), true), (6, 3));
+    }
+
+    #[test]
+    fn board_is_centered_with_compact_cells() {
+        let (app, _) = rendered_app(true);
+
+        assert_eq!((app.game.ui.width, app.game.ui.height), (10, 5));
+        assert_eq!((app.game.ui.top_x, app.game.ui.top_y), (13, 2));
+    }
+
+    #[test]
+    fn click_picks_the_cell_under_the_mouse() {
+        let (mut app, _) = rendered_app(true);
+
+        // The middle of e2
+        click(&mut app, 13 + 4 * 10 + 5, 2 + 6 * 5 + 2);
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(6, 4));
+
+        // The last terminal cell of e4 moves the pawn there
+        click(&mut app, 13 + 5 * 10 - 1, 2 + 5 * 5 - 1);
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+    }
+
+    #[test]
+    fn clicks_beside_the_compact_board_are_ignored() {
+        let (mut app, _) = rendered_app(true);
+
+        // Left of a2, in the border that normal cells would fill
+        click(&mut app, 12, 2 + 6 * 5 + 2);
+        assert!(!app.game.ui.is_cell_selected());
+
+        // Right of h2
+        click(&mut app, 13 + 8 * 10, 2 + 6 * 5 + 2);
+        assert!(!app.game.ui.is_cell_selected());
+
+        click(&mut app, 13 + 8 * 10 - 1, 2 + 6 * 5 + 2);
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(6, 7));
+    }
+
+    #[test]
+    fn compact_cells_show_a_symbol_per_piece() {
+        let (_, terminal) = rendered_app(true);
+        let symbols: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert_eq!(symbols.matches('♟').count(), 16);
+        assert_eq!(symbols.matches('♚').count(), 2);
+    }
+}
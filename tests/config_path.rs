@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{config_path, home_dir, CONFIG_ENV_VAR};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use toml::Value;
+
+    // A configuration file of the temporary folder, removed by the test
+    fn sample_config(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-config-path-{}-{name}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn flag_then_variable_then_home() {
+        // The only test of this file touching the variable, they run in parallel
+        std::env::remove_var(CONFIG_ENV_VAR);
+        let default_path = home_dir().unwrap().join(".config/chess-tui/config.toml");
+        assert_eq!(config_path(None).unwrap(), default_path);
+
+        std::env::set_var(CONFIG_ENV_VAR, "/tmp/from-variable.toml");
+        assert_eq!(
+            config_path(None).unwrap(),
+            PathBuf::from("/tmp/from-variable.toml")
+        );
+        assert_eq!(
+            config_path(Some(Path::new("/tmp/from-flag.toml"))).unwrap(),
+            PathBuf::from("/tmp/from-flag.toml")
+        );
+
+        // An empty variable is ignored
+        std::env::set_var(CONFIG_ENV_VAR, "");
+        assert_eq!(config_path(None).unwrap(), default_path);
+        std::env::remove_var(CONFIG_ENV_VAR);
+    }
+
+    #[test]
+    fn settings_are_read_from_the_override() {
+        let path = sample_config("read", "confirm_resign = false\ntheme = \"Ocean\"");
+        let mut app = App::default();
+
+        app.load_config(path.clone());
+
+        assert!(!app.confirm_resign);
+        assert_eq!(app.theme().name, "Ocean");
+        assert_eq!(app.config_path, Some(path.clone()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn settings_are_saved_to_the_override() {
+        let path = sample_config("write", "blunder_warning = true");
+        let mut app = App::default();
+        app.load_config(path.clone());
+
+        app.cycle_theme();
+        app.update_config();
+
+        let config: Value = fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(config["theme"].as_str(), Some(app.theme().name));
+        // The other settings of the file are kept
+        assert_eq!(config["blunder_warning"].as_bool(), Some(true));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn missing_override_keeps_the_defaults() {
+        let path = std::env::temp_dir().join("chess-tui-config-path-missing.toml");
+        let mut app = App::default();
+
+        app.load_config(path.clone());
+
+        assert!(app.confirm_resign);
+        assert_eq!(app.config_path, Some(path));
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::diagram::{crc32, encode_png, position_svg};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::gif::{board_frames, white_view_positions, CELL_PIXELS};
+
+    const PALETTE: [[u8; 3]; 8] = [
+        [240, 217, 181],
+        [181, 136, 99],
+        [255, 255, 255],
+        [0, 0, 0],
+        [20, 20, 20],
+        [205, 210, 106],
+        [170, 162, 58],
+        [0, 0, 0],
+    ];
+
+    #[test]
+    fn svg_draws_every_piece_of_the_board() {
+        let positions = white_view_positions(&Game::default());
+        let (board, last_move) = &positions[0];
+
+        let svg = position_svg(board, *last_move, &PALETTE);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("viewBox=\"0 0 96 96\""));
+        assert_eq!(svg.matches("<use ").count(), 32);
+        assert_eq!(svg.matches("<rect ").count(), 64);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn svg_marks_the_latest_move() {
+        let mut game = Game::default();
+        game.play_uci_move("e2e4").unwrap();
+        let positions = white_view_positions(&game);
+        let (board, last_move) = positions.last().unwrap();
+        assert_eq!(*last_move, Some((Coord::new(6, 4), Coord::new(4, 4))));
+
+        let svg = position_svg(board, *last_move, &PALETTE);
+
+        // e2 and e4 are both light cells
+        assert_eq!(svg.matches("fill=\"#cdd26a\"").count(), 2);
+        assert_eq!(svg.matches("fill=\"#aaa23a\"").count(), 0);
+    }
+
+    #[test]
+    fn png_holds_the_whole_frame() {
+        let frame = &board_frames(&Game::default())[0];
+
+        let png = encode_png(frame, &PALETTE);
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(&png[12..16], b"IHDR");
+        let size = (8 * CELL_PIXELS) as u32;
+        assert_eq!(png[16..20], size.to_be_bytes());
+        assert_eq!(png[20..24], size.to_be_bytes());
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]));
+        // Stored blocks keep every pixel and the filter byte of every line
+        assert!(png.len() > frame.pixels.len() + frame.height);
+    }
+
+    #[test]
+    fn crc_matches_the_png_specification() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(crc32(b""), 0);
+    }
+}
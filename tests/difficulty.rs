@@ -0,0 +1,176 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::uci::{go_command, parse_spin_range};
+    use chess_tui::engine::{Difficulty, DEFAULT_ELO, MAX_ELO, MIN_ELO};
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+    use toml::Value;
+
+    fn bot_app() -> App {
+        App {
+            seed: Some(7),
+            menu_cursor: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn difficulty_is_chosen_after_the_color() {
+        let mut app = bot_app();
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, Some(Popups::ColorSelection));
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.selected_color, Some(PieceColor::White));
+        assert_eq!(app.current_popup, Some(Popups::DifficultySelection));
+        assert!(app.bot.is_none());
+        // The cursor starts on the current difficulty
+        assert_eq!(app.menu_cursor, 2);
+
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.difficulty, Difficulty::Intermediate);
+        assert!(app.bot.is_some());
+    }
+
+    #[test]
+    fn escape_leaves_the_difficulty_selection_for_the_menu() {
+        let mut app = bot_app();
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.selected_color, None);
+        assert!(app.bot.is_none());
+    }
+
+    #[test]
+    fn rematch_keeps_the_difficulty() {
+        let mut app = bot_app();
+        app.menu_select();
+        app.menu_cursor = 0;
+        app.color_selection();
+        app.menu_cursor = 0;
+        app.difficulty_selection();
+
+        app.rematch();
+
+        assert_eq!(app.game.difficulty, Difficulty::Beginner);
+        assert!(app.bot.is_some());
+    }
+
+    #[test]
+    fn difficulties_map_to_the_search_of_the_engine() {
+        assert_eq!(
+            go_command(Difficulty::Beginner.search_constraints()),
+            "go depth 2"
+        );
+        assert_eq!(
+            go_command(Difficulty::Intermediate.search_constraints()),
+            "go depth 8"
+        );
+        assert_eq!(
+            go_command(Difficulty::Full.search_constraints()),
+            "go movetime 1000"
+        );
+        assert_eq!(Difficulty::Beginner.elo(), Some(1400));
+        assert_eq!(Difficulty::Full.elo(), None);
+        assert_eq!(Difficulty::default(), Difficulty::Full);
+        assert_eq!(
+            go_command(Difficulty::Elo(1200).search_constraints()),
+            "go movetime 1000"
+        );
+        assert_eq!(Difficulty::Elo(1200).elo(), Some(1200));
+    }
+
+    #[test]
+    fn elo_is_picked_with_the_arrows() {
+        let mut app = bot_app();
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.menu_cursor, 3);
+
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.game.difficulty, Difficulty::Elo(1300));
+        assert_eq!(app.engine_elo, 1300);
+    }
+
+    #[test]
+    fn arrows_only_change_the_elo_on_its_choice() {
+        let mut app = bot_app();
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+
+        press(&mut app, KeyCode::Up);
+
+        assert_eq!(app.engine_elo, DEFAULT_ELO);
+        assert_eq!(app.menu_cursor, 2);
+    }
+
+    #[test]
+    fn elo_stays_within_the_picker() {
+        let mut app = App {
+            menu_cursor: 3,
+            engine_elo: 3150,
+            ..Default::default()
+        };
+
+        app.change_engine_elo(1);
+        assert_eq!(app.engine_elo, MAX_ELO);
+        app.change_engine_elo(-100);
+        assert_eq!(app.engine_elo, MIN_ELO);
+    }
+
+    #[test]
+    fn config_can_limit_the_strength_of_the_bot() {
+        let mut app = App::default();
+        let config: Value =
+            toml::from_str("engine_limit_strength = true\nengine_elo = 1500").unwrap();
+
+        app.apply_config(&config);
+
+        assert_eq!(app.engine_elo, 1500);
+        assert_eq!(app.game.difficulty, Difficulty::Elo(1500));
+        // The cursor of the popup starts on the Elo
+        app.current_page = Pages::Bot;
+        app.menu_cursor = 0;
+        app.color_selection();
+        assert_eq!(app.menu_cursor, 3);
+    }
+
+    #[test]
+    fn elo_range_is_read_from_the_options_of_the_engine() {
+        assert_eq!(
+            parse_spin_range(
+                "option name UCI_Elo type spin default 1320 min 1320 max 3190",
+                "UCI_Elo"
+            ),
+            Some((1320, 3190))
+        );
+        assert_eq!(
+            parse_spin_range(
+                "option name UCI_LimitStrength type check default false",
+                "UCI_Elo"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_spin_range("option name UCI_Elo type check default false", "UCI_Elo"),
+            None
+        );
+    }
+}
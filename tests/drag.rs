@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::handler::handle_mouse_events;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use ratatui::crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    // Every cell of the board is a single terminal cell starting at the top left corner
+    fn solo_app() -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.game.ui.width = 1;
+        app.game.ui.height = 1;
+        app
+    }
+
+    fn mouse(app: &mut App, kind: MouseEventKind, coord: Coord) {
+        let mouse_event = MouseEvent {
+            kind,
+            column: coord.col as u16,
+            row: coord.row as u16,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, app).unwrap();
+    }
+
+    #[test]
+    fn drag_targets_match_selection() {
+        let mut selected_game = Game::default();
+        selected_game.ui.cursor_coordinates = Coord::new(7, 6);
+        selected_game.handle_cell_click();
+        let selection_targets = selected_game.game_board.get_authorized_positions(
+            selected_game.player_turn,
+            selected_game.ui.selected_coordinates,
+        );
+
+        let mut dragged_game = Game::default();
+        assert!(dragged_game.start_drag(Coord::new(7, 6)));
+
+        assert_eq!(dragged_game.drag_targets(), selection_targets);
+        assert_eq!(
+            dragged_game.ui.selected_coordinates,
+            selected_game.ui.selected_coordinates
+        );
+    }
+
+    #[test]
+    fn drag_only_grabs_pieces_of_the_player_to_move() {
+        let mut game = Game::default();
+
+        assert!(!game.start_drag(Coord::new(4, 4)));
+        assert!(!game.start_drag(Coord::new(1, 4)));
+        assert!(game.drag_targets().is_empty());
+    }
+
+    #[test]
+    fn dropping_on_a_target_plays_the_move() {
+        let mut app = solo_app();
+
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            Coord::new(6, 4),
+        );
+        assert_eq!(app.game.drag_targets().len(), 2);
+        mouse(
+            &mut app,
+            MouseEventKind::Drag(MouseButton::Left),
+            Coord::new(5, 4),
+        );
+        assert_eq!(app.game.ui.drag_hover, Some(Coord::new(5, 4)));
+        mouse(
+            &mut app,
+            MouseEventKind::Up(MouseButton::Left),
+            Coord::new(4, 4),
+        );
+
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.game.ui.dragged_from, None);
+        assert_eq!(app.game.ui.drag_hover, None);
+        assert!(app.game.drag_targets().is_empty());
+        // e4 seen from black
+        assert_eq!(
+            app.game.game_board.board[3][3],
+            Some((PieceType::Pawn, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn dropping_elsewhere_cancels_the_drag() {
+        let mut app = solo_app();
+
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            Coord::new(6, 4),
+        );
+        mouse(
+            &mut app,
+            MouseEventKind::Up(MouseButton::Left),
+            Coord::new(3, 4),
+        );
+
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(app.game.ui.dragged_from, None);
+        assert_eq!(app.game.ui.selected_coordinates, Coord::undefined());
+    }
+
+    #[test]
+    fn releasing_on_the_same_cell_keeps_the_selection() {
+        let mut app = solo_app();
+
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            Coord::new(6, 4),
+        );
+        mouse(
+            &mut app,
+            MouseEventKind::Up(MouseButton::Left),
+            Coord::new(6, 4),
+        );
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(6, 4));
+        assert_eq!(app.game.ui.dragged_from, None);
+
+        // A second click on a target still plays the move
+        mouse(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            Coord::new(4, 4),
+        );
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+    }
+}
@@ -1,9 +1,13 @@
 #[cfg(test)]
 mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::random_bot::RandomBot;
     use chess_tui::game_logic::coord::Coord;
-    use chess_tui::game_logic::game::Game;
+    use chess_tui::game_logic::game::{Game, GameState};
     use chess_tui::game_logic::game_board::GameBoard;
     use chess_tui::pieces::{PieceColor, PieceMove, PieceType};
+    use chess_tui::rng::Rng;
     #[test]
     fn is_draw_true() {
         let custom_board = [
@@ -227,4 +231,100 @@ mod tests {
         game.execute_move(&Coord::new(0, 2), &Coord::new(0, 1));
         assert!(game.game_board.is_draw(game.player_turn));
     }
+
+    fn hot_seat_app() -> App {
+        App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn draw_offer_accepted() {
+        let mut app = hot_seat_app();
+
+        app.offer_draw();
+        assert_eq!(app.current_popup, Some(Popups::DrawOffer));
+
+        app.accept_draw();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_state, GameState::Draw);
+        assert_eq!(app.game.result(), Some("1/2-1/2"));
+    }
+
+    #[test]
+    fn draw_offer_declined() {
+        let mut app = hot_seat_app();
+
+        app.offer_draw();
+        assert_eq!(app.current_popup, Some(Popups::DrawOffer));
+
+        app.decline_draw();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_state, GameState::Playing);
+        assert_eq!(app.game.result(), None);
+    }
+
+    #[test]
+    fn draw_offer_not_available_during_promotion() {
+        let mut app = hot_seat_app();
+        app.game.game_state = GameState::Promotion;
+
+        app.offer_draw();
+        assert_eq!(app.current_popup, None);
+    }
+
+    #[test]
+    fn draw_offer_not_available_after_game_end() {
+        let mut app = hot_seat_app();
+        app.game.game_state = GameState::Checkmate;
+
+        app.offer_draw();
+        assert_eq!(app.current_popup, None);
+
+        // Accepting without an offer does nothing
+        app.accept_draw();
+        assert_eq!(app.game.game_state, GameState::Checkmate);
+    }
+
+    // A game against the bot playing black from `fen`
+    fn bot_app(fen: &str) -> App {
+        App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(RandomBot::new(Rng::from_seed(1)))),
+            game: Game::from_fen(fen).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bot_declines_a_draw_when_ahead() {
+        // The bot is a queen up
+        let mut app = bot_app("3qk3/8/8/8/8/8/8/4K3 w - - 0 1");
+        assert_eq!(app.game.game_board.evaluate(PieceColor::Black), 9);
+
+        app.offer_draw();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_state, GameState::Playing);
+    }
+
+    #[test]
+    fn bot_accepts_a_draw_in_an_equal_position() {
+        let mut app = bot_app("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        assert_eq!(app.game.game_board.evaluate(PieceColor::Black), 0);
+
+        app.offer_draw();
+        assert_eq!(app.game.game_state, GameState::Draw);
+        assert_eq!(app.game.result(), Some("1/2-1/2"));
+    }
+
+    #[test]
+    fn bot_draw_threshold_is_configurable() {
+        let mut app = bot_app("4k3/pp6/8/8/8/8/8/4K3 w - - 0 1");
+        assert!(!app.bot_accepts_draw());
+
+        app.apply_config(&"bot_draw_threshold = 2".parse().unwrap());
+        assert!(app.bot_accepts_draw());
+    }
 }
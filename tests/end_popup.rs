@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{EndGameChoice, Pages, Popups};
+    use chess_tui::game_logic::board::init_board;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+
+    // A game on `page` where white played e4 and then resigned
+    fn finished_app(page: Pages) -> App {
+        let mut app = App {
+            current_page: page,
+            selected_color: Some(PieceColor::White),
+            seed: Some(3),
+            ..Default::default()
+        };
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.game.game_state = GameState::Resignation(PieceColor::White);
+        app
+    }
+
+    #[test]
+    fn arrows_move_through_the_options_and_wrap_around() {
+        let mut app = finished_app(Pages::Solo);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::Rematch);
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::NewGame);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::Review);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::Analyze);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::SavePgn);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::MainMenu);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::Rematch);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::MainMenu);
+    }
+
+    #[test]
+    fn rematch_starts_a_new_game_on_the_same_page() {
+        let mut app = finished_app(Pages::Solo);
+
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.game.game_state, GameState::Playing);
+        assert_eq!(app.game.game_board.board, init_board());
+        assert_eq!(app.end_popup_cursor, 0);
+    }
+
+    #[test]
+    fn new_game_against_the_bot_asks_for_the_color_again() {
+        let mut app = finished_app(Pages::Bot);
+        app.color_selection();
+        app.difficulty_selection();
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_page, Pages::Bot);
+        assert_eq!(app.current_popup, Some(Popups::ColorSelection));
+        assert_eq!(app.selected_color, None);
+        assert!(app.bot.is_none());
+        assert_eq!(app.game.game_state, GameState::Playing);
+        assert_eq!(app.game.game_board.board, init_board());
+    }
+
+    #[test]
+    fn main_menu_goes_back_home() {
+        let mut app = finished_app(Pages::Solo);
+
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Char(' '));
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert_eq!(app.selected_color, None);
+        assert_eq!(app.game.game_state, GameState::Playing);
+    }
+
+    #[test]
+    fn finished_game_can_be_reviewed() {
+        let mut app = finished_app(Pages::Solo);
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.review_index, Some(1));
+
+        // The review steps through the game, then goes back to the end popup
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.review_index, Some(0));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.review_index, None);
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::White)
+        );
+
+        // `v` opens the review right away
+        press(&mut app, KeyCode::Char('v'));
+        assert_eq!(app.review_index, Some(1));
+    }
+
+    #[test]
+    fn finished_game_can_be_saved() {
+        let home = std::env::temp_dir().join(format!("chess-tui-end-popup-{}", std::process::id()));
+        std::env::set_var("HOME", &home);
+        let mut app = finished_app(Pages::Solo);
+
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.end_popup_choice(), EndGameChoice::SavePgn);
+        press(&mut app, KeyCode::Enter);
+        // The headers are edited before the game is written
+        assert_eq!(app.current_popup, Some(Popups::PgnTags));
+        press(&mut app, KeyCode::Enter);
+
+        let games: Vec<_> = std::fs::read_dir(home.join(".config/chess-tui/games"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(games.len(), 1);
+        let pgn = std::fs::read_to_string(&games[0]).unwrap();
+        assert!(pgn.contains("1. e4"));
+        // The game stays over, with its popup
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::White)
+        );
+        assert_eq!(app.current_page, Pages::Solo);
+        std::fs::remove_dir_all(home).unwrap();
+    }
+}
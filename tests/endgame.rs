@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::endgame::EndgameVerdict;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::PieceColor;
+
+    fn hint(fen: &str) -> Option<EndgameVerdict> {
+        Game::from_fen(fen).unwrap().endgame_hint()
+    }
+
+    #[test]
+    fn queen_against_bare_king_wins() {
+        assert_eq!(
+            hint("4k3/8/8/8/8/8/8/Q3K3 w - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::White))
+        );
+        // Whoever is to move
+        assert_eq!(
+            hint("4k3/8/8/8/8/8/8/Q3K3 b - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::White))
+        );
+        assert_eq!(
+            hint("4k3/8/8/8/8/8/8/q3K3 w - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::Black))
+        );
+    }
+
+    #[test]
+    fn rook_against_bare_king_wins() {
+        assert_eq!(
+            hint("4k3/8/8/8/8/8/8/4K2r w - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::Black))
+        );
+    }
+
+    #[test]
+    fn bare_kings_are_a_draw() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(game.endgame_hint(), Some(EndgameVerdict::Draw));
+        assert_eq!(EndgameVerdict::Draw.to_string(), "Draw");
+    }
+
+    #[test]
+    fn hanging_piece_gives_no_hint() {
+        // Black takes the undefended queen next to its king
+        assert_eq!(hint("4k3/4Q3/8/8/8/8/8/4K3 b - - 0 1"), None);
+        // Defended by the white king, it can't be taken
+        assert_eq!(
+            hint("4k3/3Q4/2K5/8/8/8/8/8 b - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::White))
+        );
+        // The rook next to the king is lost with black to move, white to move saves it
+        assert_eq!(hint("4k3/3R4/8/8/8/8/8/4K3 b - - 0 1"), None);
+        assert_eq!(
+            hint("4k3/3R4/8/8/8/8/8/4K3 w - - 0 1"),
+            Some(EndgameVerdict::Winning(PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn other_material_gives_no_hint() {
+        assert_eq!(hint("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"), None);
+        assert_eq!(hint("4k3/8/8/8/8/8/8/QR2K3 w - - 0 1"), None);
+        assert_eq!(hint("4k3/8/8/8/8/8/8/B3K3 w - - 0 1"), None);
+        assert_eq!(hint("3qk3/8/8/8/8/8/8/Q3K3 w - - 0 1"), None);
+        assert_eq!(Game::default().endgame_hint(), None);
+    }
+
+    #[test]
+    fn verdicts_read_in_the_status_bar() {
+        assert_eq!(
+            EndgameVerdict::Winning(PieceColor::White).to_string(),
+            "White is winning"
+        );
+        assert_eq!(
+            EndgameVerdict::Winning(PieceColor::Black).to_string(),
+            "Black is winning"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::engine::uci::{
+        best_line_so_far, parse_bestmove, parse_info, parse_search_report, stream_engine_output,
+        EngineInfo, Score, SearchReport,
+    };
+    use chess_tui::event::Event;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::PieceColor;
+    use std::io::Cursor;
+    use std::sync::mpsc;
+
+    const SEARCH: [&str; 5] = [
+        "info depth 1 seldepth 1 score cp 20 nodes 20 pv e2e4",
+        "info depth 2 seldepth 2 score cp 35 nodes 120 pv d2d4 d7d5",
+        "info string NNUE evaluation using nn.nnue enabled",
+        "info depth 3 currmove g1f3 currmovenumber 2",
+        "bestmove d2d4 ponder d7d5",
+    ];
+
+    #[test]
+    fn first_pv_move_of_the_latest_info_line() {
+        assert_eq!(
+            best_line_so_far(SEARCH),
+            Some(EngineInfo {
+                best_move: "d2d4".to_string(),
+                score: Some(Score::Centipawns(35)),
+                depth: Some(2),
+            })
+        );
+        assert_eq!(
+            best_line_so_far(SEARCH[..1].iter().copied())
+                .unwrap()
+                .best_move,
+            "e2e4"
+        );
+        assert_eq!(best_line_so_far(SEARCH[2..4].iter().copied()), None);
+    }
+
+    #[test]
+    fn info_line_parsing() {
+        let info = parse_info("info depth 12 score mate -3 pv h7h8q g8h8").unwrap();
+        assert_eq!(info.best_move, "h7h8q");
+        assert_eq!(info.score, Some(Score::Mate(-3)));
+        assert_eq!(info.to_string(), "h7h8q (#-3) depth 12");
+
+        let info = parse_info("info score cp -150 pv e7e5").unwrap();
+        assert_eq!(info.to_string(), "e7e5 (-1.50)");
+
+        assert_eq!(parse_info("info string pv e2e4"), None);
+        assert_eq!(parse_info("bestmove e2e4"), None);
+    }
+
+    #[test]
+    fn score_and_depth_of_the_final_info_line() {
+        let output = [
+            "info depth 17 seldepth 24 multipv 1 score cp 41 nodes 901212 nps 1201616 pv e2e4 e7e5",
+            "info depth 18 seldepth 26 multipv 1 score cp 30 upperbound nodes 1402130 pv e2e4",
+            "info depth 18 currmove d2d4 currmovenumber 2",
+            "info string NNUE evaluation using nn.nnue enabled",
+            "bestmove e2e4 ponder e7e5",
+            "info depth 1 score cp 900 pv a2a3",
+        ];
+
+        let report = parse_search_report(output).unwrap();
+        assert_eq!(
+            report,
+            SearchReport {
+                score: Score::Centipawns(30),
+                depth: Some(18),
+            }
+        );
+        assert_eq!(report.to_string(), "eval +0.30 d18");
+
+        let mate = parse_search_report(["info depth 9 score mate 2 pv d1h5", "bestmove d1h5"]);
+        assert_eq!(mate.unwrap().to_string(), "eval #2 d9");
+
+        // Engines may play without scoring the position
+        assert_eq!(parse_search_report(SEARCH[2..].iter().copied()), None);
+    }
+
+    #[test]
+    fn bestmove_line_parsing() {
+        assert_eq!(parse_bestmove("bestmove e7e8q"), Some("e7e8q".to_string()));
+        assert_eq!(parse_bestmove("bestmove (none)"), None);
+        assert_eq!(parse_bestmove("info depth 1 pv e2e4"), None);
+    }
+
+    #[test]
+    fn engine_output_is_streamed_to_the_main_loop() {
+        let (sender, receiver) = mpsc::channel();
+        let output = SEARCH.join("\n") + "\nreadyok\n";
+
+        stream_engine_output(Cursor::new(output), &sender);
+        drop(sender);
+
+        let mut app = App::default();
+        let events: Vec<Event> = receiver.iter().collect();
+        // Two lines, the expected answer and the move
+        assert_eq!(events.len(), 4);
+        assert!(matches!(&events[2], Event::EnginePonder(ponder_move) if ponder_move == "d7d5"));
+
+        for event in events {
+            let is_best_move = matches!(event, Event::EngineBestMove(_));
+            if is_best_move {
+                assert_eq!(
+                    app.status_message(),
+                    "Engine thinking: d2d4 (+0.35) depth 2"
+                );
+            }
+            handle_event(event, &mut app).unwrap();
+        }
+
+        assert_eq!(app.engine_info, None);
+        assert_eq!(
+            app.engine_report.map(|report| report.to_string()),
+            Some("eval +0.35 d2".to_string())
+        );
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["d2d4"]);
+    }
+}
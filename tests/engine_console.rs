@@ -0,0 +1,129 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::Popups;
+    use chess_tui::engine::console::{EngineConsole, Recorded, ENGINE_CONSOLE_LINES};
+    use ratatui::crossterm::event::KeyCode;
+    use std::io::{BufRead, BufReader, Cursor, Write};
+
+    #[test]
+    fn lines_going_through_the_pipes_are_recorded() {
+        let console = EngineConsole::default();
+        let mut input = Recorded::new(vec![], console.clone(), "> ");
+        write!(input, "uci\nposition star").unwrap();
+        writeln!(input, "tpos").unwrap();
+
+        let output = Recorded::new(Cursor::new("uciok\r\nreadyok\n"), console.clone(), "< ");
+        let read: Vec<String> = BufReader::new(output).lines().map(Result::unwrap).collect();
+
+        assert_eq!(read, ["uciok", "readyok"]);
+        assert_eq!(
+            console.lines(),
+            ["> uci", "> position startpos", "< uciok", "< readyok"]
+        );
+    }
+
+    #[test]
+    fn console_keeps_the_latest_lines() {
+        let console = EngineConsole::default();
+        for index in 0..ENGINE_CONSOLE_LINES + 2 {
+            console.record(index.to_string());
+        }
+
+        let lines = console.lines();
+        assert_eq!(lines.len(), ENGINE_CONSOLE_LINES);
+        assert_eq!(lines[0], "2");
+    }
+
+    #[test]
+    fn console_explains_when_no_engine_runs() {
+        let mut app = App::default();
+
+        press(&mut app, KeyCode::Char('E'));
+        assert_eq!(app.current_popup, Some(Popups::EngineConsole));
+        assert_eq!(app.engine_console_lines.len(), 1);
+        assert!(app.engine_console_lines[0].starts_with("No engine is running"));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_popup, None);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod engine_tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::uci::UciEngine;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    const TALKING_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Talker"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "info depth 1 score cp 5 pv e7e5"; echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    #[test]
+    fn console_shows_the_traffic_of_the_bot() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-talking-engine-{}.sh",
+            std::process::id()
+        ));
+        fs::write(&path, TALKING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(path.to_str().unwrap()).unwrap())),
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+        app.play_bot_move();
+
+        press(&mut app, KeyCode::Char('E'));
+        assert_eq!(app.current_popup, Some(Popups::EngineConsole));
+        let lines = &app.engine_console_lines;
+        assert_eq!(
+            lines[..4],
+            ["> uci", "< id name Talker", "< uciok", "> isready"]
+        );
+        assert_eq!(
+            lines[lines.len() - 3..],
+            [
+                "> go movetime 1000",
+                "< info depth 1 score cp 5 pv e7e5",
+                "< bestmove e7e5"
+            ]
+        );
+
+        // Scrolling stops at the oldest line
+        for _ in 0..lines.len() + 5 {
+            press(&mut app, KeyCode::Up);
+        }
+        assert_eq!(
+            app.engine_console_scroll,
+            app.engine_console_lines.len() - 1
+        );
+        press(&mut app, KeyCode::Down);
+        assert_eq!(
+            app.engine_console_scroll,
+            app.engine_console_lines.len() - 2
+        );
+
+        press(&mut app, KeyCode::Char('E'));
+        assert_eq!(app.current_popup, None);
+        drop(app);
+        fs::remove_file(&path).unwrap();
+    }
+}
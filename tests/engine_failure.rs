@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::{press, write_fake_engine};
+    use chess_tui::app::{App, ENGINE_TIMEOUT};
+    use chess_tui::constants::{Pages, Popups, TICK_RATE_MS};
+    use chess_tui::engine::uci::{UciEngine, DEFAULT_MOVETIME};
+    use chess_tui::event::Event;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    // An engine dying as soon as it is asked to think
+    const CRASHING_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Crasher"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) exit 1 ;;
+  esac
+done
+"#;
+
+    // An engine thinking forever, even when told to stop
+    const SILENT_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Sleeper"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    const WORKING_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Worker"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn crashing_engine() -> &'static str {
+        write_fake_engine("crashing", CRASHING_ENGINE)
+    }
+
+    fn silent_engine() -> &'static str {
+        write_fake_engine("silent", SILENT_ENGINE)
+    }
+
+    fn working_engine() -> &'static str {
+        write_fake_engine("working", WORKING_ENGINE)
+    }
+
+    // A game against `engine` where the player opened with e2e4 and the engine thinks
+    fn game_against(engine: &str) -> (App, Receiver<Event>) {
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(engine).unwrap())),
+            engine_path: Some(working_engine().to_string()),
+            event_sender: Some(sender),
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+        app.tick();
+        assert!(app.engine_search.is_some());
+        (app, receiver)
+    }
+
+    #[test]
+    fn crashed_engine_is_restarted_and_the_game_goes_on() {
+        let (mut app, receiver) = game_against(crashing_engine());
+
+        let event = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert!(matches!(event, Event::EngineFailure(_)));
+        handle_event(event, &mut app).unwrap();
+        assert_eq!(app.current_popup, Some(Popups::EngineFailure));
+        assert!(app.engine_failure.is_some());
+        assert!(app.engine_search.is_none());
+        // The game waits for the player
+        app.tick();
+        assert!(app.engine_search.is_none());
+
+        press(&mut app, KeyCode::Char('r'));
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.engine_failure, None);
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+
+        app.tick();
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+    }
+
+    #[test]
+    fn silent_engine_is_given_up_for_the_random_bot() {
+        let (mut app, receiver) = game_against(silent_engine());
+        let timeout_ticks = (DEFAULT_MOVETIME + ENGINE_TIMEOUT).as_millis() as u64 / TICK_RATE_MS;
+
+        for _ in 0..timeout_ticks {
+            app.tick();
+        }
+        assert_eq!(app.current_popup, None);
+        app.tick();
+        assert_eq!(app.current_popup, Some(Popups::EngineFailure));
+        assert!(app.engine_search.is_none());
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_popup, None);
+        app.tick();
+        assert_eq!(app.game.game_board.uci_moves().len(), 2);
+
+        // The engine dies once replaced, its failure comes after the game moved on
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+        assert_eq!(app.current_popup, None);
+        assert!(!app.discard_engine_move);
+    }
+
+    #[test]
+    fn restart_failing_keeps_the_popup_open() {
+        let (mut app, receiver) = game_against(crashing_engine());
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+        app.engine_path = Some("/nonexistent/engine".to_string());
+
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_popup, Some(Popups::EngineFailure));
+        assert!(app
+            .engine_failure
+            .as_deref()
+            .is_some_and(|failure| failure.contains("/nonexistent/engine")));
+    }
+}
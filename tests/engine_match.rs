@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::engine::engine_match::{run_match, MatchGame, MatchScore};
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::engine::{Engine, SearchConstraints};
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::PieceMove;
+    use chess_tui::rng::Rng;
+
+    // Plays the moves of the fool's mate for its color
+    struct FoolsMate;
+
+    impl Engine for FoolsMate {
+        fn best_move(&mut self, fen: &str, _: SearchConstraints) -> Result<PieceMove, ChessError> {
+            let uci = match (fen.split(' ').nth(1), fen.ends_with(" 1")) {
+                (Some("w"), true) => "f2f3",
+                (_, true) => "e7e5",
+                (Some("w"), false) => "g2g4",
+                _ => "d8h4",
+            };
+            Game::from_fen(fen)?.piece_move_from_uci(uci)
+        }
+
+        fn name(&self) -> String {
+            "Fool".to_string()
+        }
+    }
+
+    struct Broken;
+
+    impl Engine for Broken {
+        fn best_move(&mut self, _: &str, _: SearchConstraints) -> Result<PieceMove, ChessError> {
+            Err(ChessError::EngineFailure("crashed".to_string()))
+        }
+    }
+
+    fn match_game(white: usize, result: &'static str) -> MatchGame {
+        MatchGame {
+            number: 1,
+            white,
+            result,
+            reason: String::new(),
+            uci_moves: vec![],
+        }
+    }
+
+    #[test]
+    fn score_counts_the_results_from_the_side_of_each_player() {
+        let mut score = MatchScore::default();
+        score.record(&match_game(0, "1-0"));
+        score.record(&match_game(1, "1-0"));
+        score.record(&match_game(1, "0-1"));
+        score.record(&match_game(0, "1/2-1/2"));
+
+        assert_eq!(score.wins, [2, 1]);
+        assert_eq!(score.draws, 1);
+        assert_eq!(score.games(), 4);
+        assert_eq!(score.points(0), 2.5);
+        assert_eq!(score.points(1), 1.5);
+        assert_eq!(
+            score.table(&["Stockfish".to_string(), "Random bot".to_string()]),
+            "Engine       Wins  Draws  Losses  Score\n\
+             Stockfish       2      1       1    2.5\n\
+             Random bot      1      1       2    1.5\n"
+        );
+    }
+
+    #[test]
+    fn players_take_turns_playing_white() {
+        let players: [Box<dyn Engine>; 2] = [Box::new(FoolsMate), Box::new(FoolsMate)];
+        let mut games = vec![];
+
+        let score = run_match(players, 3, SearchConstraints::default(), |game, _| {
+            games.push(game.clone())
+        });
+
+        assert_eq!(
+            games.iter().map(|game| game.white).collect::<Vec<_>>(),
+            [0, 1, 0]
+        );
+        assert!(games.iter().all(|game| game.result == "0-1"));
+        assert_eq!(games[0].uci_moves, ["f2f3", "e7e5", "g2g4", "d8h4"]);
+        // Black won every game
+        assert_eq!(score.wins, [1, 2]);
+    }
+
+    #[test]
+    fn player_failing_to_move_forfeits_the_game() {
+        let players: [Box<dyn Engine>; 2] = [
+            Box::new(Broken),
+            Box::new(RandomBot::new(Rng::from_seed(3))),
+        ];
+        let mut results = vec![];
+
+        let score = run_match(players, 2, SearchConstraints::default(), |game, score| {
+            results.push((game.result, *score))
+        });
+
+        assert_eq!(results[0].0, "0-1");
+        assert_eq!(results[1].0, "1-0");
+        assert_eq!(score.wins, [0, 2]);
+    }
+
+    #[test]
+    fn match_needs_an_engine() {
+        let app = App::default();
+
+        assert!(app.match_players().is_err());
+    }
+
+    #[test]
+    fn engine_failing_to_start_stops_the_match() {
+        let app = App {
+            engine_profiles: vec![],
+            engine_path: Some("/nonexistent/chess-tui-engine".to_string()),
+            ..Default::default()
+        };
+
+        assert!(app.match_players().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::profile::{parse_engine_profiles, EngineProfile};
+    use chess_tui::engine::Protocol;
+    use ratatui::crossterm::event::KeyCode;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use toml::Value;
+
+    // Writes the commands it receives next to itself
+    const RECORDING_ENGINE: &str = r#"#!/bin/sh
+log="$0.log"
+while read -r command; do
+  echo "$command" >> "$log"
+  case "$command" in
+    uci) echo "id name Recorder"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn recording_engine(name: &str) -> (PathBuf, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("chess-tui-{name}-engine-{}.sh", std::process::id()));
+        fs::write(&path, RECORDING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+        (path, log)
+    }
+
+    fn config_with_engines(paths: &[&PathBuf]) -> Value {
+        let engines: String = paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                format!(
+                    "[[engines]]\nname = \"Engine {index}\"\npath = \"{}\"\noptions = {{ Threads = 2, Style = \"Risky\" }}\n",
+                    path.display()
+                )
+            })
+            .collect();
+        toml::from_str(&engines).unwrap()
+    }
+
+    #[test]
+    fn engines_tables_give_the_profiles() {
+        let config: Value = toml::from_str(
+            r#"
+[[engines]]
+name = "Stockfish"
+path = "/usr/bin/stockfish"
+options = { Threads = 4, Ponder = false, Hash = 256 }
+
+[[engines]]
+name = "Nowhere"
+
+[[engines]]
+path = "/opt/engines/lc0"
+
+[[engines]]
+name = "GNU Chess"
+path = "/usr/games/gnuchess"
+protocol = "xboard"
+
+[[engines]]
+name = "Unknown"
+path = "/usr/games/unknown"
+protocol = "morse"
+"#,
+        )
+        .unwrap();
+
+        let profiles = parse_engine_profiles(config.get("engines").unwrap().as_array().unwrap());
+
+        assert_eq!(
+            profiles,
+            [
+                EngineProfile {
+                    name: "Stockfish".to_string(),
+                    path: "/usr/bin/stockfish".to_string(),
+                    protocol: Protocol::Uci,
+                    options: vec![
+                        ("Hash".to_string(), "256".to_string()),
+                        ("Ponder".to_string(), "false".to_string()),
+                        ("Threads".to_string(), "4".to_string()),
+                    ],
+                },
+                // Named after its file
+                EngineProfile {
+                    name: "lc0".to_string(),
+                    path: "/opt/engines/lc0".to_string(),
+                    protocol: Protocol::Uci,
+                    options: vec![],
+                },
+                EngineProfile {
+                    name: "GNU Chess".to_string(),
+                    path: "/usr/games/gnuchess".to_string(),
+                    protocol: Protocol::Xboard,
+                    options: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn engine_is_chosen_after_the_difficulty() {
+        let (first, first_log) = recording_engine("first-profile");
+        let (second, second_log) = recording_engine("second-profile");
+        let mut app = App {
+            menu_cursor: 1,
+            ..Default::default()
+        };
+        app.apply_config(&config_with_engines(&[&first, &second]));
+
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, Some(Popups::EngineSelection));
+        assert!(app.bot.is_none());
+        assert_eq!(app.menu_cursor, 0);
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.engine_profile, Some(1));
+        assert_eq!(app.bot.as_ref().unwrap().name(), "Recorder");
+
+        let commands = fs::read_to_string(&second_log).unwrap();
+        assert_eq!(
+            commands.lines().take(4).collect::<Vec<_>>(),
+            [
+                "uci",
+                "isready",
+                "setoption name Style value Risky",
+                "setoption name Threads value 2",
+            ]
+        );
+        assert!(!first_log.exists());
+
+        drop(app);
+        for path in [first, second, first_log, second_log] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn escape_leaves_the_engine_selection_for_the_menu() {
+        let mut app = App {
+            menu_cursor: 1,
+            ..Default::default()
+        };
+        app.apply_config(&config_with_engines(&[&PathBuf::from("/nonexistent")]));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert_eq!(app.current_popup, None);
+        assert!(app.bot.is_none());
+    }
+}
@@ -0,0 +1,303 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::write_fake_engine;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::uci::UciEngine;
+    use chess_tui::engine::{Difficulty, Engine, SearchConstraints};
+    use chess_tui::event::Event;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::PieceColor;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use toml::Value;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    // An engine thinking until it is told to stop, then playing the move of its latest line
+    const SLOW_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Slowfish"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "info depth 7 score cp 15 pv e7e5 g1f3" ;;
+    stop) echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn slow_engine() -> &'static str {
+        write_fake_engine("slow", SLOW_ENGINE)
+    }
+
+    #[test]
+    fn stop_makes_the_engine_play_its_latest_best_move() {
+        let mut engine = UciEngine::new(slow_engine()).unwrap();
+        let (sender, receiver) = mpsc::channel();
+
+        let search = engine
+            .start_search(
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                SearchConstraints {
+                    depth: None,
+                    movetime: Some(Duration::from_secs(3600)),
+                },
+                sender,
+            )
+            .unwrap()
+            .unwrap();
+
+        let Event::EngineInfo(info) = receiver.recv_timeout(TIMEOUT).unwrap() else {
+            panic!("the engine should report its line first");
+        };
+        assert_eq!(info.best_move, "e7e5");
+        // Nothing more comes while the engine thinks
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+        search.stop().unwrap();
+        assert!(matches!(
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            Event::EngineBestMove(best_move) if best_move == "e7e5"
+        ));
+    }
+
+    #[test]
+    fn app_plays_the_move_of_the_stopped_search() {
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(slow_engine()).unwrap())),
+            event_sender: Some(sender),
+            ..Default::default()
+        };
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+
+        app.tick();
+        assert!(app.engine_search.is_some());
+        // The next ticks do not start another search
+        app.tick();
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+        assert_eq!(
+            app.status_message(),
+            "Engine thinking: e7e5 (+0.15) depth 7 - `s` to play now"
+        );
+
+        app.stop_engine_search();
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+
+        assert!(app.engine_search.is_none());
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        assert_eq!(app.game.player_turn, PieceColor::White);
+    }
+
+    #[test]
+    fn move_of_an_abandoned_search_is_not_played() {
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(slow_engine()).unwrap())),
+            event_sender: Some(sender),
+            ..Default::default()
+        };
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.tick();
+
+        app.restart();
+        for event in [
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+            receiver.recv_timeout(TIMEOUT).unwrap(),
+        ] {
+            handle_event(event, &mut app).unwrap();
+        }
+
+        assert!(app.game.game_board.move_history.is_empty());
+    }
+
+    // An engine writing every command it receives to a log file next to it
+    const RECORDING_ENGINE: &str = r#"#!/bin/sh
+log="$0.log"
+while read -r command; do
+  echo "$command" >> "$log"
+  case "$command" in
+    uci) echo "id name Recorder"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    #[test]
+    fn bot_games_start_with_the_uci_handshake() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-recording-engine-{}.sh",
+            std::process::id()
+        ));
+        fs::write(&path, RECORDING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+
+        let mut app = App {
+            current_page: Pages::Bot,
+            engine_path: Some(path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        app.menu_cursor = 0;
+        app.color_selection();
+        app.difficulty_selection();
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.play_bot_move();
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+
+        app.rematch();
+        let commands = fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            commands.lines().collect::<Vec<_>>(),
+            [
+                "uci",
+                "isready",
+                "ucinewgame",
+                "isready",
+                "position fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                "go movetime 1000",
+                "ucinewgame",
+                "isready",
+            ]
+        );
+        drop(app);
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+
+    #[test]
+    fn weaker_difficulties_limit_the_strength_of_the_engine() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-limited-engine-{}.sh",
+            std::process::id()
+        ));
+        fs::write(&path, RECORDING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+
+        let mut app = App {
+            current_page: Pages::Bot,
+            engine_path: Some(path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        app.menu_cursor = 0;
+        app.color_selection();
+        app.menu_cursor = 0;
+        app.difficulty_selection();
+        assert_eq!(app.game.difficulty, Difficulty::Beginner);
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.play_bot_move();
+
+        let commands = fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            commands.lines().collect::<Vec<_>>(),
+            [
+                "uci",
+                "isready",
+                "ucinewgame",
+                "isready",
+                "setoption name UCI_LimitStrength value true",
+                "setoption name UCI_Elo value 1400",
+                "isready",
+                "position fen rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                "go depth 2",
+            ]
+        );
+        drop(app);
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+
+    // Like the recorder, with a range of ratings like the one of Stockfish
+    const RATED_ENGINE: &str = r#"#!/bin/sh
+log="$0.log"
+while read -r command; do
+  echo "$command" >> "$log"
+  case "$command" in
+    uci) echo "id name Rated"; echo "option name UCI_LimitStrength type check default false"; echo "option name UCI_Elo type spin default 1320 min 1320 max 3190"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    #[test]
+    fn elo_is_brought_within_the_range_of_the_engine() {
+        let path =
+            std::env::temp_dir().join(format!("chess-tui-rated-engine-{}.sh", std::process::id()));
+        fs::write(&path, RATED_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+
+        let mut engine = UciEngine::new(path.to_str().unwrap()).unwrap();
+        engine.set_difficulty(Difficulty::Elo(1200)).unwrap();
+        engine.set_difficulty(Difficulty::Elo(1800)).unwrap();
+
+        let commands = fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            commands.lines().collect::<Vec<_>>(),
+            [
+                "uci",
+                "isready",
+                "setoption name UCI_LimitStrength value true",
+                "setoption name UCI_Elo value 1320",
+                "isready",
+                "setoption name UCI_LimitStrength value true",
+                "setoption name UCI_Elo value 1800",
+                "isready",
+            ]
+        );
+        drop(engine);
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+
+    #[test]
+    fn config_caps_the_thinking_of_the_engine() {
+        let path =
+            std::env::temp_dir().join(format!("chess-tui-capped-engine-{}.sh", std::process::id()));
+        fs::write(&path, RECORDING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let log = PathBuf::from(format!("{}.log", path.display()));
+        let _ = fs::remove_file(&log);
+
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(path.to_str().unwrap()).unwrap())),
+            ..Default::default()
+        };
+        let config: Value = toml::from_str("engine_movetime_ms = 300\nengine_depth = 6").unwrap();
+        app.apply_config(&config);
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        app.play_bot_move();
+
+        let commands = fs::read_to_string(&log).unwrap();
+        assert_eq!(commands.lines().last(), Some("go depth 6 movetime 300"));
+
+        // 0 lifts the limits
+        let config: Value = toml::from_str("engine_movetime_ms = 0\nengine_depth = 0").unwrap();
+        app.apply_config(&config);
+        assert_eq!(app.engine_limits, SearchConstraints::default());
+        drop(app);
+        fs::remove_file(&path).unwrap();
+        let _ = fs::remove_file(&log);
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::engine::uci::{go_command, Score, SearchReport};
+    use chess_tui::engine::{Engine, SearchConstraints};
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::{PieceColor, PieceMove};
+    use chess_tui::rng::Rng;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    // Plays the same move in UCI notation and remembers the positions it was given
+    struct StubEngine {
+        uci_move: &'static str,
+        positions: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Engine for StubEngine {
+        fn best_move(
+            &mut self,
+            fen: &str,
+            _constraints: SearchConstraints,
+        ) -> Result<PieceMove, ChessError> {
+            self.positions.lock().unwrap().push(fen.to_string());
+            Game::from_fen(fen)?.piece_move_from_uci(self.uci_move)
+        }
+    }
+
+    // Answers e7e5 and reports an even position
+    struct ScoringEngine;
+
+    impl Engine for ScoringEngine {
+        fn best_move(&mut self, fen: &str, _: SearchConstraints) -> Result<PieceMove, ChessError> {
+            Game::from_fen(fen)?.piece_move_from_uci("e7e5")
+        }
+
+        fn last_report(&self) -> Option<SearchReport> {
+            Some(SearchReport {
+                score: Score::Centipawns(-12),
+                depth: Some(18),
+            })
+        }
+    }
+
+    struct BrokenEngine;
+
+    impl Engine for BrokenEngine {
+        fn best_move(&mut self, _: &str, _: SearchConstraints) -> Result<PieceMove, ChessError> {
+            Err(ChessError::EngineFailure("crashed".to_string()))
+        }
+    }
+
+    fn bot_app(bot: Box<dyn Engine>) -> App {
+        App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(bot),
+            seed: Some(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stub_engine_plays_the_move_of_the_bot() {
+        let positions = Arc::new(Mutex::new(vec![]));
+        let mut app = bot_app(Box::new(StubEngine {
+            uci_move: "e7e5",
+            positions: positions.clone(),
+        }));
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+
+        app.tick();
+
+        assert_eq!(
+            positions.lock().unwrap().as_slice(),
+            ["rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"]
+        );
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        assert_eq!(app.game.player_turn, PieceColor::White);
+    }
+
+    #[test]
+    fn engine_report_follows_the_bot_move() {
+        let mut app = bot_app(Box::new(ScoringEngine));
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+
+        app.tick();
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        assert_eq!(
+            app.engine_report.map(|report| report.to_string()),
+            Some("eval -0.12 d18".to_string())
+        );
+
+        app.restart();
+        assert_eq!(app.engine_report, None);
+    }
+
+    #[test]
+    fn failing_engine_hands_over_to_the_random_bot() {
+        let mut app = bot_app(Box::new(BrokenEngine));
+        assert!(app.game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+
+        app.tick();
+        // The player is asked how the game goes on
+        assert_eq!(app.current_popup, Some(Popups::EngineFailure));
+        app.replace_engine_with_random_bot();
+        app.tick();
+
+        assert_eq!(app.game.game_board.move_history.len(), 2);
+    }
+
+    #[test]
+    fn random_bot_is_an_engine() {
+        let mut bot = RandomBot::new(Rng::from_seed(5));
+        let game = Game::default();
+
+        let piece_move = bot
+            .best_move(&game.to_fen(), SearchConstraints::default())
+            .unwrap();
+
+        assert!(game
+            .game_board
+            .legal_moves(PieceColor::White)
+            .contains(&piece_move));
+    }
+
+    #[test]
+    fn to_fen_round_trips() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 3 1",
+            "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+        ];
+        for fen in fens {
+            assert_eq!(Game::from_fen(fen).unwrap().to_fen(), fen);
+        }
+
+        // Castles are lost once the king moved, even after it came back
+        let mut game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        for uci in ["e1f1", "e8d8", "f1e1"] {
+            game.play_uci_move(uci).unwrap();
+        }
+        assert_eq!(game.to_fen(), "r2k3r/8/8/8/8/8/8/R3K2R b - - 3 2");
+    }
+
+    #[test]
+    fn go_command_follows_the_constraints() {
+        assert_eq!(go_command(SearchConstraints::default()), "go movetime 1000");
+        assert_eq!(
+            go_command(SearchConstraints {
+                depth: Some(12),
+                movetime: None,
+            }),
+            "go depth 12"
+        );
+        assert_eq!(
+            go_command(SearchConstraints {
+                depth: Some(8),
+                movetime: Some(Duration::from_millis(250)),
+            }),
+            "go depth 8 movetime 250"
+        );
+    }
+
+    #[test]
+    fn tighter_limit_wins() {
+        let limits = SearchConstraints {
+            depth: Some(10),
+            movetime: Some(Duration::from_millis(300)),
+        };
+        assert_eq!(SearchConstraints::default().within(limits), limits);
+        assert_eq!(
+            SearchConstraints {
+                depth: Some(2),
+                movetime: Some(Duration::from_secs(5)),
+            }
+            .within(limits),
+            SearchConstraints {
+                depth: Some(2),
+                movetime: Some(Duration::from_millis(300)),
+            }
+        );
+        assert_eq!(limits.within(SearchConstraints::default()), limits);
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::uci::Score;
+
+    #[test]
+    fn evaluations_are_seen_from_white() {
+        let mut app = App::default();
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.play_uci_move("e7e5").unwrap();
+        app.game.set_evaluation(0, Score::Centipawns(20));
+        app.game.set_evaluation(1, Score::Centipawns(-35));
+        app.game.set_evaluation(2, Score::Mate(-3));
+
+        assert_eq!(
+            app.game.white_evaluations(),
+            [Some(20), Some(35), Some(-1000)]
+        );
+    }
+
+    #[test]
+    fn positions_not_scored_are_left_out() {
+        let mut app = App::default();
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.play_uci_move("e7e5").unwrap();
+        app.game.set_evaluation(2, Score::Centipawns(15));
+
+        assert_eq!(app.game.white_evaluations(), [None, None, Some(15)]);
+    }
+
+    #[test]
+    fn taking_a_move_back_forgets_the_evaluations_after_it() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.game.free_play = true;
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.play_uci_move("e7e5").unwrap();
+        for index in 0..3 {
+            app.game.set_evaluation(index, Score::Centipawns(10));
+        }
+
+        app.take_back();
+
+        assert_eq!(app.game.white_evaluations(), [Some(10), Some(-10)]);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod engine_tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::uci::{Score, UciEngine};
+    use chess_tui::pieces::PieceColor;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    const SCORING_ENGINE: &str = r#"#!/bin/sh
+while read -r command; do
+  case "$command" in
+    uci) echo "uciok" ;;
+    isready) echo "readyok" ;;
+    go*) echo "info depth 10 score cp 45 pv e7e5"; echo "bestmove e7e5" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    #[test]
+    fn score_of_the_bot_goes_to_the_position_it_moved_in() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-scoring-engine-{}.sh",
+            std::process::id()
+        ));
+        fs::write(&path, SCORING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(path.to_str().unwrap()).unwrap())),
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+
+        app.play_bot_move();
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        assert_eq!(app.game.evaluations, [None, Some(Score::Centipawns(45))]);
+        // Black thinks it is better
+        assert_eq!(app.game.white_evaluations(), [None, Some(-45)]);
+        drop(app);
+        fs::remove_file(&path).unwrap();
+    }
+}
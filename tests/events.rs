@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::event::Event;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::{PieceColor, PieceMove, PieceType};
+    use std::sync::mpsc;
+    use std::thread;
+
+    fn pawn_move(piece_color: PieceColor, from: Coord, to: Coord) -> PieceMove {
+        PieceMove {
+            piece_type: PieceType::Pawn,
+            piece_color,
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn engine_move_event_is_applied() {
+        let mut app = App::default();
+        let (sender, receiver) = mpsc::channel();
+
+        // e2e4 computed in a background thread
+        thread::spawn(move || {
+            let piece_move = pawn_move(PieceColor::White, Coord::new(6, 4), Coord::new(4, 4));
+            sender.send(Event::EngineMove(piece_move)).unwrap();
+        });
+        handle_event(receiver.recv().unwrap(), &mut app).unwrap();
+
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["e2e4"]);
+    }
+
+    #[test]
+    fn network_move_event_is_applied() {
+        let mut app = App::default();
+        app.load_fen("r3k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+
+        // a8a1 seen from Black's side
+        let piece_move = PieceMove {
+            piece_type: PieceType::Rook,
+            piece_color: PieceColor::Black,
+            from: Coord::new(7, 7),
+            to: Coord::new(0, 7),
+        };
+        handle_event(Event::NetworkMove(piece_move), &mut app).unwrap();
+
+        assert_eq!(app.game.game_board.uci_moves(), vec!["a8a1"]);
+        assert!(app.check_alert.is_some());
+    }
+
+    #[test]
+    fn illegal_move_event_is_ignored() {
+        let mut app = App::default();
+
+        let wrong_color = pawn_move(PieceColor::Black, Coord::new(6, 4), Coord::new(4, 4));
+        handle_event(Event::EngineMove(wrong_color), &mut app).unwrap();
+        let too_far = pawn_move(PieceColor::White, Coord::new(6, 4), Coord::new(3, 4));
+        handle_event(Event::NetworkMove(too_far), &mut app).unwrap();
+
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert!(app.game.game_board.move_history.is_empty());
+    }
+
+    #[test]
+    fn chat_event_shows_a_toast() {
+        let mut app = App::default();
+
+        handle_event(Event::NetworkChat("good luck".to_string()), &mut app).unwrap();
+
+        assert_eq!(
+            app.toast.map(|toast| toast.message),
+            Some("good luck".to_string())
+        );
+    }
+}
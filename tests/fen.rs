@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::board::init_board;
     use chess_tui::game_logic::coord::Coord;
-    use chess_tui::game_logic::game::Game;
-    use chess_tui::game_logic::game_board::GameBoard;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::game_logic::game_board::{CastlingRights, GameBoard};
     use chess_tui::pieces::{PieceColor, PieceMove, PieceType};
 
     #[test]
@@ -167,4 +171,233 @@ mod tests {
             "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"
         );
     }
+
+    #[test]
+    fn from_fen_starting_position() {
+        let game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+
+        assert_eq!(game.game_board.board, init_board());
+        assert_eq!(game.player_turn, PieceColor::White);
+        assert_eq!(game.game_state, GameState::Playing);
+    }
+
+    #[test]
+    fn from_fen_black_to_move_is_flipped() {
+        let game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+
+        assert_eq!(game.player_turn, PieceColor::Black);
+        // Black pieces are at the bottom of the board for the player to move
+        assert_eq!(
+            game.game_board.board[7][3],
+            Some((PieceType::King, PieceColor::Black))
+        );
+        // The white pawn on e4 seen from black
+        assert_eq!(
+            game.game_board.board[3][3],
+            Some((PieceType::Pawn, PieceColor::White))
+        );
+        assert_eq!(game.game_board.en_passant_target, Some(Coord::new(2, 3)));
+    }
+
+    #[test]
+    fn from_fen_en_passant_capture() {
+        let game = Game::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+
+        let authorized_positions = game
+            .game_board
+            .get_authorized_positions(PieceColor::White, Coord::new(3, 4));
+        assert!(authorized_positions.contains(&Coord::new(2, 3)));
+    }
+
+    #[test]
+    fn from_fen_without_castling_rights() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+
+        let authorized_positions = game
+            .game_board
+            .get_authorized_positions(PieceColor::White, Coord::new(7, 4));
+        assert!(!authorized_positions.contains(&Coord::new(7, 7)));
+        assert!(!authorized_positions.contains(&Coord::new(7, 0)));
+
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K2R w K - 0 1").unwrap();
+        let authorized_positions = game
+            .game_board
+            .get_authorized_positions(PieceColor::White, Coord::new(7, 4));
+        assert!(authorized_positions.contains(&Coord::new(7, 7)));
+        assert!(!authorized_positions.contains(&Coord::new(7, 0)));
+    }
+
+    #[test]
+    fn from_fen_checkmate_position() {
+        let game = Game::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+
+        assert_eq!(game.game_state, GameState::Checkmate);
+    }
+
+    #[test]
+    fn from_fen_invalid() {
+        let invalid_fens = [
+            "",
+            "not a fen",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1",
+            "rnbq1bnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e4 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - -1 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0",
+            "Pnbqkbnr/pppppppp/8/8/8/8/1PPPPPPP/RNBQKBNR w KQkq - 0 1",
+            // The side not to move is in check
+            "4k3/8/8/8/8/8/8/4R2K w - - 0 1",
+        ];
+
+        for fen in invalid_fens {
+            assert!(
+                matches!(Game::from_fen(fen), Err(ChessError::InvalidFen(_))),
+                "{fen} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn from_fen_tolerates_real_world_fens() {
+        let starting_fens = [
+            // Surrounding and repeated whitespace
+            "  rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR   w KQkq -  0 1\n",
+            // Missing counters
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            // Only the move number after the en passant square
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 1",
+            // Trailing fields
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 extra",
+            // Copied from a lichess editor URL
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR_w_KQkq_-_0_1",
+            // No castling and en passant sections
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w",
+        ];
+
+        for fen in starting_fens {
+            let game = Game::from_fen(fen).unwrap_or_else(|e| panic!("{fen:?}: {e}"));
+            assert_eq!(game.game_board.board, init_board(), "{fen:?}");
+            assert_eq!(game.player_turn, PieceColor::White, "{fen:?}");
+            assert_eq!(game.game_board.en_passant_target, None, "{fen:?}");
+            assert_eq!(
+                game.game_board.get_consecutive_non_pawn_or_capture(),
+                0,
+                "{fen:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_fen_missing_castling_is_guessed_from_the_board() {
+        // The king side rook already moved
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/R3K1R1 w").unwrap();
+
+        let castling_rights = game.game_board.castling_rights;
+        assert!(castling_rights.white_queen_side);
+        assert!(!castling_rights.white_king_side);
+        assert!(!castling_rights.black_king_side);
+        assert!(!castling_rights.black_queen_side);
+    }
+
+    #[test]
+    fn from_fen_placement_only_uses_the_defaults() {
+        let game = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R").unwrap();
+
+        assert_eq!(game.player_turn, PieceColor::White);
+        assert_eq!(
+            game.game_board.castling_rights,
+            CastlingRights::from_fen("-").unwrap()
+        );
+        assert_eq!(game.game_board.en_passant_target, None);
+        assert_eq!(game.game_board.get_consecutive_non_pawn_or_capture(), 0);
+        assert_eq!(
+            game.starting_fen.as_deref(),
+            Some("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1")
+        );
+    }
+
+    #[test]
+    fn from_fen_placement_only_differs_from_a_full_fen() {
+        // The kings and rooks are on their original cells but castling stays forbidden
+        let placement_only = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R").unwrap();
+        let with_side_to_move = Game::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w").unwrap();
+
+        assert_eq!(
+            placement_only.game_board.board,
+            with_side_to_move.game_board.board
+        );
+        assert!(!placement_only.game_board.castling_rights.white_king_side);
+        assert!(with_side_to_move.game_board.castling_rights.white_king_side);
+    }
+
+    #[test]
+    fn from_fen_tolerant_still_rejects_invalid_placement() {
+        let invalid_fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN w KQkq - 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0",
+        ];
+
+        for fen in invalid_fens {
+            assert!(
+                matches!(Game::from_fen(fen), Err(ChessError::InvalidFen(_))),
+                "{fen} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn app_load_fen_valid() {
+        let mut app = App::default();
+
+        assert!(app.load_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").is_ok());
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(
+            app.game.game_board.board[7][7],
+            Some((PieceType::Rook, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn loaded_position_is_played_against_the_bot() {
+        let mut app = App {
+            seed: Some(5),
+            ..Default::default()
+        };
+        app.load_fen("4k3/8/8/8/8/8/8/4K2R b K - 3 40").unwrap();
+
+        app.open_bot_setup();
+        assert_eq!(app.current_page, Pages::Bot);
+        app.color_selection();
+        app.difficulty_selection();
+
+        // Black is to move, the bot plays it from the loaded position
+        assert!(app.is_bot_turn());
+        app.play_bot_move();
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(
+            app.game.game_board.board[7][7],
+            Some((PieceType::Rook, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn app_load_fen_invalid_keeps_game() {
+        let mut app = App::default();
+        app.game.ui.cursor_coordinates = Coord::new(6, 4);
+        app.game.handle_cell_click();
+
+        assert!(app.load_fen("invalid fen").is_err());
+        assert_eq!(app.current_page, Pages::Home);
+        assert_eq!(app.game.game_board.board, init_board());
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(6, 4));
+    }
 }
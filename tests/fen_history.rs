@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::save::STARTING_FEN;
+
+    fn game_after(fen: Option<&str>, moves: &[&str]) -> Game {
+        let mut game = match fen {
+            Some(fen) => Game::from_fen(fen).unwrap(),
+            None => Game::default(),
+        };
+        for uci_move in moves {
+            game.play_uci_move(uci_move).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn one_fen_per_position() {
+        let game = game_after(None, &["e2e4", "e7e5", "g1f3"]);
+        let fens = game.fen_history();
+
+        assert_eq!(fens.len(), game.game_board.board_history.len());
+        assert_eq!(fens.len(), 4);
+        assert_eq!(fens[0], STARTING_FEN);
+        assert_eq!(fens.last(), Some(&game.to_fen()));
+    }
+
+    #[test]
+    fn each_fen_is_the_position_after_the_moves_played() {
+        let game = game_after(None, &["e2e4", "e7e5", "g1f3", "b8c6", "f3e5"]);
+
+        assert_eq!(
+            game.fen_history(),
+            [
+                STARTING_FEN,
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+                "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+                "r1bqkbnr/pppp1ppp/2n5/4N3/4P3/8/PPPP1PPP/RNBQKB1R b KQkq - 0 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn history_of_a_game_set_up_from_a_fen() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 b Q - 7 12";
+        let game = game_after(Some(fen), &["e8d7", "e1c1"]);
+        let fens = game.fen_history();
+
+        assert_eq!(fens.len(), 3);
+        assert_eq!(fens[0], fen);
+        assert_eq!(fens[1], "8/3k4/8/8/8/8/8/R3K3 w Q - 8 13");
+        assert_eq!(fens[2], "8/3k4/8/8/8/8/8/2KR4 b - - 9 13");
+        assert_eq!(game.fullmove_number(), 13);
+        // Every entry sets up the same position again, move number included
+        for fen in &fens {
+            assert_eq!(&Game::from_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::{App, PendingFlip};
+    use chess_tui::constants::{FlipPolicy, Pages};
+    use chess_tui::pieces::PieceColor;
+    use toml::Value;
+
+    fn hot_seat_app(flip_delay_ticks: u16) -> App {
+        App {
+            current_page: Pages::Solo,
+            flip_delay_ticks,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn board_flips_after_the_configured_ticks() {
+        let mut app = hot_seat_app(3);
+        app.play_algebraic_move("e4").unwrap();
+
+        assert_eq!(
+            app.pending_flip,
+            Some(PendingFlip {
+                bottom_color: PieceColor::White,
+                remaining_ticks: 3
+            })
+        );
+        // White stays at the bottom while the board is stored from black's side
+        assert!(app.is_board_flipped());
+
+        app.tick();
+        app.tick();
+        assert!(app.pending_flip.is_some());
+        assert!(app.is_board_flipped());
+
+        app.tick();
+        assert_eq!(app.pending_flip, None);
+        assert!(!app.is_board_flipped());
+    }
+
+    #[test]
+    fn next_move_restarts_the_delay() {
+        let mut app = hot_seat_app(3);
+        app.play_algebraic_move("e4").unwrap();
+        app.tick();
+
+        app.play_algebraic_move("e5").unwrap();
+
+        assert_eq!(
+            app.pending_flip,
+            Some(PendingFlip {
+                bottom_color: PieceColor::Black,
+                remaining_ticks: 3
+            })
+        );
+    }
+
+    #[test]
+    fn board_flips_right_away_without_delay() {
+        let mut app = hot_seat_app(0);
+        app.play_algebraic_move("e4").unwrap();
+
+        assert_eq!(app.pending_flip, None);
+        assert!(!app.is_board_flipped());
+    }
+
+    #[test]
+    fn no_delay_when_the_board_does_not_flip() {
+        let mut app = App {
+            flip_policy: FlipPolicy::NeverFlip,
+            ..hot_seat_app(3)
+        };
+        app.play_algebraic_move("e4").unwrap();
+        assert_eq!(app.pending_flip, None);
+
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            ..hot_seat_app(3)
+        };
+        app.play_algebraic_move("e4").unwrap();
+        assert_eq!(app.pending_flip, None);
+    }
+
+    #[test]
+    fn delay_is_configured_in_milliseconds() {
+        let mut app = App::default();
+        app.apply_config(&"flip_delay_ms = 600".parse::<Value>().unwrap());
+        assert_eq!(app.flip_delay_ticks, 3);
+
+        app.apply_config(&"flip_delay_ms = -5".parse::<Value>().unwrap());
+        assert_eq!(app.flip_delay_ticks, 0);
+    }
+}
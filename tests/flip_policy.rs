@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{FlipPolicy, Pages};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::handler::{handle_key_events, handle_mouse_events};
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use ratatui::crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use PieceColor::{Black as B, White as W};
+
+    const MOVES: [&str; 4] = ["e2e4", "e7e5", "g1f3", "b8c6"];
+
+    // The color at the bottom of the screen before the game and after each move
+    fn orientation_sequence(flip_policy: FlipPolicy, own_color: PieceColor) -> Vec<PieceColor> {
+        let mut game = Game::default();
+        let mut sequence = vec![game.bottom_color(flip_policy, own_color)];
+        for uci_move in MOVES {
+            game.play_uci_move(uci_move).unwrap();
+            sequence.push(game.bottom_color(flip_policy, own_color));
+        }
+        sequence
+    }
+
+    #[test]
+    fn always_flip() {
+        assert_eq!(
+            orientation_sequence(FlipPolicy::AlwaysFlip, W),
+            vec![W, B, W, B, W]
+        );
+    }
+
+    #[test]
+    fn never_flip() {
+        assert_eq!(
+            orientation_sequence(FlipPolicy::NeverFlip, W),
+            vec![W, W, W, W, W]
+        );
+    }
+
+    #[test]
+    fn flip_on_own_move() {
+        assert_eq!(
+            orientation_sequence(FlipPolicy::FlipOnOwnMove, W),
+            vec![W, B, B, W, W]
+        );
+        assert_eq!(
+            orientation_sequence(FlipPolicy::FlipOnOwnMove, B),
+            vec![W, W, B, B, W]
+        );
+    }
+
+    #[test]
+    fn board_keeps_the_mover_orientation_during_a_promotion() {
+        let mut game = Game::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        game.play_move(Coord::new(1, 0), Coord::new(0, 0));
+
+        assert_eq!(game.game_state, GameState::Promotion);
+        assert_eq!(game.board_orientation(), PieceColor::White);
+        assert_eq!(game.bottom_color(FlipPolicy::AlwaysFlip, W), W);
+
+        game.promote_piece();
+        assert_eq!(game.board_orientation(), PieceColor::Black);
+    }
+
+    #[test]
+    fn clicks_follow_the_displayed_orientation() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            flip_policy: FlipPolicy::NeverFlip,
+            ..Default::default()
+        };
+        app.game.ui.width = 1;
+        app.game.ui.height = 1;
+        app.game.play_uci_move("e2e4").unwrap();
+        assert!(app.is_board_flipped());
+
+        // e7 is still drawn on the second line of the screen
+        let mouse_event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 4,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, &mut app).unwrap();
+
+        let selected = app.game.ui.selected_coordinates;
+        assert_eq!(
+            app.game.game_board.board[&selected],
+            Some((PieceType::Pawn, PieceColor::Black))
+        );
+        assert_eq!(selected, Coord::new(6, 3));
+    }
+
+    #[test]
+    fn arrow_keys_follow_the_displayed_orientation() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            flip_policy: FlipPolicy::NeverFlip,
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+        assert!(app.is_board_flipped());
+        let press = |app: &mut App, code| {
+            handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+        };
+
+        // The cursor on e4 goes to e5 then f5 on the screen, seen from white
+        app.game.ui.cursor_coordinates = Coord::new(3, 3);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(4, 3));
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(4, 2));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(3, 2));
+    }
+}
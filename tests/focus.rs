@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Focus, Pages};
+    use chess_tui::game_logic::coord::Coord;
+
+    #[test]
+    fn focus_cycles_through_all_panels() {
+        let available = [
+            Focus::Board,
+            Focus::MoveHistory,
+            Focus::BlackMaterial,
+            Focus::WhiteMaterial,
+        ];
+
+        assert_eq!(Focus::Board.next(&available), Focus::MoveHistory);
+        assert_eq!(Focus::MoveHistory.next(&available), Focus::BlackMaterial);
+        assert_eq!(Focus::BlackMaterial.next(&available), Focus::WhiteMaterial);
+        assert_eq!(Focus::WhiteMaterial.next(&available), Focus::Board);
+    }
+
+    #[test]
+    fn focus_cycles_backward() {
+        let available = [Focus::Board, Focus::MoveHistory, Focus::BlackMaterial];
+
+        assert_eq!(Focus::Board.previous(&available), Focus::BlackMaterial);
+        assert_eq!(
+            Focus::BlackMaterial.previous(&available),
+            Focus::MoveHistory
+        );
+        assert_eq!(Focus::MoveHistory.previous(&available), Focus::Board);
+    }
+
+    #[test]
+    fn focus_skips_disabled_panels() {
+        let available = [Focus::Board, Focus::BlackMaterial];
+
+        assert_eq!(Focus::Board.next(&available), Focus::BlackMaterial);
+        assert_eq!(Focus::BlackMaterial.next(&available), Focus::Board);
+        // A panel that got disabled gives the focus back to the board
+        assert_eq!(Focus::MoveHistory.next(&available), Focus::Board);
+        assert_eq!(Focus::MoveHistory.next(&[Focus::Board]), Focus::Board);
+    }
+
+    #[test]
+    fn app_focus_stays_on_board_outside_of_a_game() {
+        let mut app = App::default();
+
+        assert_eq!(app.focus, Focus::Board);
+        app.cycle_focus();
+        assert_eq!(app.focus, Focus::Board);
+    }
+
+    #[test]
+    fn app_focus_scrolls_history() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        // Play e4 e5 Nf3 so that the history has two lines
+        for (from, to) in [((6, 4), (4, 4)), ((6, 3), (4, 3)), ((7, 6), (5, 5))] {
+            app.game.ui.cursor_coordinates = Coord::new(from.0, from.1);
+            app.handle_cell_click();
+            app.game.ui.cursor_coordinates = Coord::new(to.0, to.1);
+            app.handle_cell_click();
+        }
+
+        app.cycle_focus();
+        assert_eq!(app.focus, Focus::MoveHistory);
+
+        app.scroll_focused_panel_down();
+        app.scroll_focused_panel_down();
+        assert_eq!(app.game.ui.history_scroll, 1);
+        app.scroll_focused_panel_up();
+        app.scroll_focused_panel_up();
+        assert_eq!(app.game.ui.history_scroll, 0);
+    }
+}
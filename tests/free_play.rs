@@ -0,0 +1,116 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+
+    fn free_game(moves: &[&str]) -> Game {
+        let mut game = Game {
+            free_play: true,
+            ..Default::default()
+        };
+        for uci_move in moves {
+            game.play_uci_move(uci_move).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn white_can_move_twice_in_a_row() {
+        let game = free_game(&["e2e4", "d2d4"]);
+
+        assert_eq!(game.game_board.uci_moves(), ["e2e4", "d2d4"]);
+        assert_eq!(game.player_turn, PieceColor::Black);
+        assert!(game
+            .to_fen()
+            .starts_with("rnbqkbnr/pppppppp/8/8/3PP3/8/PPP2PPP/RNBQKBNR b KQkq d3"));
+    }
+
+    #[test]
+    fn illegal_moves_are_still_refused() {
+        let mut game = free_game(&["e2e4"]);
+
+        // Neither white out of turn nor black in turn can break the rules
+        assert!(game.play_uci_move("e4e6").is_err());
+        assert!(game.play_uci_move("e1e3").is_err());
+        assert!(game.play_uci_move("e7e4").is_err());
+        assert_eq!(game.game_board.uci_moves(), ["e2e4"]);
+
+        // Out of free play, white waits for black
+        let mut game = Game::default();
+        game.play_uci_move("e2e4").unwrap();
+        assert!(game.play_uci_move("d2d4").is_err());
+    }
+
+    #[test]
+    fn moves_are_taken_back_one_by_one() {
+        let mut game = free_game(&["e2e4", "d7d5", "e4d5", "d8d5"]);
+        assert_eq!(game.game_board.black_taken_pieces.len(), 1);
+
+        assert!(game.take_back());
+        assert!(game.take_back());
+
+        assert_eq!(game.game_board.uci_moves(), ["e2e4", "d7d5"]);
+        assert_eq!(game.player_turn, PieceColor::White);
+        assert!(game.game_board.black_taken_pieces.is_empty());
+        assert_eq!(game.to_fen(), free_game(&["e2e4", "d7d5"]).to_fen());
+
+        assert!(game.take_back());
+        assert!(game.take_back());
+        assert!(!game.take_back());
+        assert_eq!(game.to_fen(), Game::default().to_fen());
+    }
+
+    #[test]
+    fn checkmate_does_not_lock_the_board() {
+        let mut game = free_game(&["f2f3", "e7e5", "g2g4", "d8h4"]);
+        assert_eq!(game.game_state, GameState::Checkmate);
+        assert!(!game.is_locked());
+
+        // Black keeps exploring while white stays mated
+        game.play_uci_move("b8c6").unwrap();
+        assert_eq!(game.game_state, GameState::Checkmate);
+
+        // Without the mating move the game goes on
+        game.take_back();
+        game.take_back();
+        assert_eq!(game.game_state, GameState::Playing);
+        game.play_uci_move("e2e4").unwrap();
+        assert_eq!(
+            game.game_board.uci_moves(),
+            ["f2f3", "e7e5", "g2g4", "e2e4"]
+        );
+    }
+
+    #[test]
+    fn free_play_is_a_local_game_setting() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        press(&mut app, KeyCode::Char('f'));
+        assert!(app.game.free_play);
+
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.play_uci_move("g1f3").unwrap();
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+
+        app.rematch();
+        assert!(app.game.free_play);
+        press(&mut app, KeyCode::Char('f'));
+        assert!(!app.game.free_play);
+
+        let mut app = App {
+            current_page: Pages::Bot,
+            ..Default::default()
+        };
+        press(&mut app, KeyCode::Char('f'));
+        assert!(!app.game.free_play);
+    }
+}
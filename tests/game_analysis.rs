@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::game_analysis::{capped_centipawns, move_qualities, MoveQuality};
+    use chess_tui::engine::uci::Score;
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::pieces::PieceColor;
+
+    #[test]
+    fn moves_are_marked_by_the_centipawns_they_lose() {
+        assert_eq!(MoveQuality::classify(-40), None);
+        assert_eq!(MoveQuality::classify(49), None);
+        assert_eq!(MoveQuality::classify(50), Some(MoveQuality::Inaccuracy));
+        assert_eq!(MoveQuality::classify(100), Some(MoveQuality::Mistake));
+        assert_eq!(MoveQuality::classify(299), Some(MoveQuality::Mistake));
+        assert_eq!(MoveQuality::classify(300), Some(MoveQuality::Blunder));
+    }
+
+    #[test]
+    fn mates_and_decided_games_are_capped() {
+        assert_eq!(capped_centipawns(Score::Centipawns(35)), 35);
+        assert_eq!(capped_centipawns(Score::Centipawns(-4000)), -1000);
+        assert_eq!(capped_centipawns(Score::Mate(3)), 1000);
+        assert_eq!(capped_centipawns(Score::Mate(-2)), -1000);
+        assert_eq!(capped_centipawns(Score::Mate(0)), -1000);
+    }
+
+    #[test]
+    fn scores_are_compared_from_the_side_of_the_player_who_moved() {
+        let evaluations = [
+            Some(Score::Centipawns(30)),
+            // White gave away a pawn
+            Some(Score::Centipawns(70)),
+            // Black kept it
+            Some(Score::Centipawns(-60)),
+            // White walked into a mate
+            Some(Score::Mate(2)),
+            // Black missed it, the game is still won
+            Some(Score::Centipawns(-1500)),
+        ];
+
+        assert_eq!(
+            move_qualities(&evaluations),
+            [
+                Some(MoveQuality::Mistake),
+                None,
+                Some(MoveQuality::Blunder),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn moves_next_to_a_position_without_evaluation_are_not_marked() {
+        let evaluations = [
+            Some(Score::Centipawns(30)),
+            None,
+            Some(Score::Centipawns(-900)),
+        ];
+
+        assert_eq!(move_qualities(&evaluations), [None, None]);
+    }
+
+    #[test]
+    fn moves_not_evaluated_have_no_mark() {
+        let mut app = App::default();
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.play_uci_move("e7e5").unwrap();
+        app.game.evaluations = vec![Some(Score::Centipawns(20)), Some(Score::Centipawns(400))];
+
+        assert_eq!(
+            app.game.move_qualities(),
+            [Some(MoveQuality::Blunder), None]
+        );
+    }
+
+    #[test]
+    fn analysis_needs_an_engine() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.game_state = GameState::Resignation(PieceColor::Black);
+
+        app.analyze_game();
+
+        assert!(app.pending_evaluations.is_empty());
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn evaluations_of_another_game_are_dropped() {
+        let mut app = App::default();
+        app.pending_evaluations = app.game.fen_history();
+
+        app.apply_evaluation("8/8/8/8/8/8/8/K6k w - - 0 1", Ok(Score::Centipawns(0)));
+
+        assert!(app.game.evaluations.is_empty());
+        assert_eq!(app.pending_evaluations.len(), 1);
+    }
+
+    #[test]
+    fn engine_failing_to_start_stops_the_analysis() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            engine_path: Some("/nonexistent/chess-tui-engine".to_string()),
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+        app.game.game_state = GameState::Resignation(PieceColor::Black);
+
+        app.analyze_game();
+
+        assert!(app.game.evaluations.is_empty());
+        assert!(app.pending_evaluations.is_empty());
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod engine_tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{EndGameChoice, Pages};
+    use chess_tui::engine::game_analysis::MoveQuality;
+    use chess_tui::engine::uci::Score;
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::handler::handle_key_events;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    // Goes over the fool's mate, scoring each position in turn
+    const REVIEWING_ENGINE: &str = r#"#!/bin/sh
+searches=0
+while read -r command; do
+  case "$command" in
+    uci) echo "uciok" ;;
+    isready) echo "readyok" ;;
+    "go depth 12")
+      searches=$((searches + 1))
+      case $searches in
+        1) echo "info depth 12 score cp 20 pv e2e4"; echo "bestmove e2e4" ;;
+        2) echo "info depth 12 score cp 40 pv e7e5"; echo "bestmove e7e5" ;;
+        3) echo "info depth 12 score cp -50 pv g2g4"; echo "bestmove g2g4" ;;
+        *) echo "info depth 12 score mate 1 pv d8h4"; echo "bestmove d8h4" ;;
+      esac ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    #[test]
+    fn finished_game_gets_its_bad_moves_marked() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-reviewing-engine-{}.sh",
+            std::process::id()
+        ));
+        fs::write(&path, REVIEWING_ENGINE).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut app = App {
+            current_page: Pages::Solo,
+            engine_path: Some(path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            app.game.play_uci_move(uci).unwrap();
+        }
+        assert_eq!(app.game.game_state, GameState::Checkmate);
+
+        while app.end_popup_choice() != EndGameChoice::Analyze {
+            app.end_popup_cursor_down();
+        }
+        handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app).unwrap();
+
+        // The mated position is scored without the engine
+        assert_eq!(app.game.evaluations.len(), 5);
+        assert_eq!(app.game.evaluations[4], Some(Score::Mate(0)));
+        assert_eq!(
+            app.game.move_qualities(),
+            [
+                Some(MoveQuality::Inaccuracy),
+                None,
+                Some(MoveQuality::Blunder),
+                None
+            ]
+        );
+        assert!(app.pending_evaluations.is_empty());
+        assert_eq!(app.review_index, Some(4));
+        assert!(!app.toast.as_ref().unwrap().is_error);
+        fs::remove_file(&path).unwrap();
+    }
+}
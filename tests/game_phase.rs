@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::{Game, GamePhase};
+
+    #[test]
+    fn fullmove_number_goes_up_after_black_played() {
+        let mut game = Game::default();
+        assert_eq!(game.fullmove_number(), 1);
+
+        assert!(game.play_move(Coord::new(6, 4), Coord::new(4, 4)));
+        assert_eq!(game.fullmove_number(), 1);
+
+        // Black plays e5, the board is stored from their point of view
+        assert!(game.play_move(Coord::new(6, 3), Coord::new(4, 3)));
+        assert_eq!(game.fullmove_number(), 2);
+    }
+
+    #[test]
+    fn fullmove_number_when_black_moves_first() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+
+        assert!(game.play_move(Coord::new(7, 3), Coord::new(6, 3)));
+
+        assert_eq!(game.fullmove_number(), 2);
+    }
+
+    #[test]
+    fn phase_classification_on_material_counts() {
+        // Full armies early on
+        assert_eq!(GamePhase::classify(62, 1), GamePhase::Opening);
+        // A pair of minor pieces traded is still the opening
+        assert_eq!(GamePhase::classify(56, 8), GamePhase::Opening);
+        // Same material later in the game
+        assert_eq!(GamePhase::classify(62, 15), GamePhase::Middlegame);
+        // Queens traded early
+        assert_eq!(GamePhase::classify(44, 9), GamePhase::Middlegame);
+        // A rook and a minor piece each
+        assert_eq!(GamePhase::classify(16, 30), GamePhase::Endgame);
+        assert_eq!(GamePhase::classify(26, 5), GamePhase::Endgame);
+    }
+
+    #[test]
+    fn phase_of_positions() {
+        assert_eq!(Game::default().phase(), GamePhase::Opening);
+
+        let rook_endgame = Game::from_fen("4k3/pp3r2/8/8/8/8/PP3R2/4K3 w - - 0 40").unwrap();
+        assert_eq!(rook_endgame.game_board.non_pawn_material(), 10);
+        assert_eq!(rook_endgame.phase(), GamePhase::Endgame);
+    }
+}
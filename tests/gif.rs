@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::gif::{board_frames, sprite, CELL_PIXELS};
+    use chess_tui::pieces::PieceType;
+
+    fn played(moves: &[&str]) -> Game {
+        let mut game = Game::default();
+        for uci_move in moves {
+            game.play_uci_move(uci_move).unwrap();
+        }
+        game
+    }
+
+    // The palette index of the center of a cell seen from white
+    fn cell_center(game: &Game, frame: usize, row: usize, col: usize) -> u8 {
+        let frames = board_frames(game);
+        let frame = &frames[frame];
+        let y = row * CELL_PIXELS + CELL_PIXELS / 2;
+        let x = col * CELL_PIXELS + CELL_PIXELS / 2;
+        frame.pixels[y * frame.width + x]
+    }
+
+    #[test]
+    fn one_frame_per_position() {
+        let game = played(&["e2e4", "e7e5", "g1f3"]);
+
+        let frames = board_frames(&game);
+
+        assert_eq!(frames.len(), 4);
+        for frame in &frames {
+            assert_eq!(
+                (frame.width, frame.height),
+                (8 * CELL_PIXELS, 8 * CELL_PIXELS)
+            );
+            assert_eq!(frame.pixels.len(), frame.width * frame.height);
+        }
+        assert_eq!(board_frames(&Game::default()).len(), 1);
+    }
+
+    #[test]
+    fn positions_are_drawn_from_white() {
+        let game = played(&["e2e4", "e7e5"]);
+
+        // The e4 pawn appears on its light cell once white moved it, white fill in its middle
+        assert_eq!(cell_center(&game, 0, 4, 4), 0);
+        assert_eq!(cell_center(&game, 1, 4, 4), 2);
+        // Black's answer is drawn on e5 and not mirrored on d4
+        assert_eq!(cell_center(&game, 2, 3, 4), 3);
+        assert_eq!(cell_center(&game, 2, 4, 3), 1);
+    }
+
+    #[test]
+    fn piece_drawings_are_square() {
+        for piece_type in [
+            PieceType::Pawn,
+            PieceType::Rook,
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Queen,
+            PieceType::King,
+        ] {
+            let sprite = sprite(piece_type);
+            assert!(sprite.iter().all(|line| line.len() == sprite.len()));
+        }
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn gif_holds_every_frame() {
+        use chess_tui::gif::{encode_gif, palette};
+        use chess_tui::ui::theme::THEMES;
+
+        let game = played(&["e2e4", "e7e5"]);
+        let gif = encode_gif(&board_frames(&game), &palette(&THEMES[0]), 500);
+
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3B));
+        // One graphic control block of 50 hundredths of a second per frame
+        let delays = gif
+            .windows(6)
+            .filter(|window| window[..4] == [0x21, 0xF9, 0x04, 0x00] && window[4..] == [50, 0])
+            .count();
+        assert_eq!(delays, 3);
+    }
+}
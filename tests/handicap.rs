@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::board::{init_board, Board};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game_setup::{GameSetup, Handicap, MaterialOdds, TimeControl};
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    fn bot_app(handicap: Handicap, time_control: Option<TimeControl>) -> App {
+        App {
+            current_page: Pages::Bot,
+            setup: GameSetup {
+                handicap,
+                time_control,
+                ..Default::default()
+            },
+            seed: Some(7),
+            ..Default::default()
+        }
+    }
+
+    // Every cell but the removed one keeps its starting piece
+    fn assert_only_missing(board: &Board, cell: Coord) {
+        let start = init_board();
+        for row in 0..8u8 {
+            for col in 0..8u8 {
+                let coord = Coord::new(row, col);
+                if coord == cell {
+                    assert_eq!(board[&coord], None);
+                } else {
+                    assert_eq!(board[&coord], start[&coord]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn knight_odds_removes_the_queen_side_knight_of_the_bot() {
+        let mut app = bot_app(
+            Handicap {
+                material_odds: Some(MaterialOdds::Knight),
+                ..Default::default()
+            },
+            None,
+        );
+        app.menu_cursor = 0;
+
+        app.color_selection();
+        app.difficulty_selection();
+
+        // The bot plays black, its knight on b8 is gone
+        assert_only_missing(&app.game.game_board.board, Coord::new(0, 1));
+        assert_eq!(
+            app.game.game_board.board_history,
+            vec![app.game.game_board.board]
+        );
+    }
+
+    #[test]
+    fn knight_odds_given_by_a_white_bot() {
+        let mut board = init_board();
+
+        MaterialOdds::Knight
+            .remove_from(&mut board, PieceColor::White)
+            .unwrap();
+
+        assert_only_missing(&board, Coord::new(7, 1));
+    }
+
+    #[test]
+    fn material_odds_keep_the_board_when_the_piece_is_missing() {
+        let mut board = init_board();
+        board[7][3] = None;
+
+        let result = MaterialOdds::Queen.remove_from(&mut board, PieceColor::White);
+
+        assert!(matches!(result, Err(ChessError::InvalidHandicap(_))));
+        assert_eq!(board[7][0], Some((PieceType::Rook, PieceColor::White)));
+        assert!("bishop".parse::<MaterialOdds>().is_err());
+        assert_eq!("Knight".parse::<MaterialOdds>(), Ok(MaterialOdds::Knight));
+    }
+
+    #[test]
+    fn time_odds_shorten_the_clock_of_the_bot() {
+        let mut app = bot_app(
+            Handicap {
+                time_odds: Duration::from_secs(120),
+                ..Default::default()
+            },
+            Some("5+0".parse().unwrap()),
+        );
+        app.restart();
+        app.menu_cursor = 1;
+
+        app.color_selection();
+        app.difficulty_selection();
+
+        let clock = app.game.clock.unwrap();
+        let now = Instant::now();
+        assert_eq!(
+            clock.remaining(PieceColor::White, now),
+            Duration::from_secs(180)
+        );
+        assert_eq!(
+            clock.remaining(PieceColor::Black, now),
+            Duration::from_secs(300)
+        );
+    }
+}
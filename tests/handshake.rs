@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::network::handshake::{Handshake, HandshakeState, SPINNER_FRAMES};
+    use chess_tui::pgn::PlayerNames;
+    use chess_tui::pieces::PieceColor;
+    use std::io::Write;
+    use std::net::{SocketAddr, TcpListener};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    // A host on a free loopback port answering the joining player with `greeting`
+    fn start_host(greeting: &'static str) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            writeln!(stream, "{greeting}").unwrap();
+            // Keep the connection open while the handshake reads the greeting
+            thread::sleep(Duration::from_millis(500));
+        });
+        address
+    }
+
+    // Poll like the main loop does until the handshake is over
+    fn wait_for(handshake: &mut Handshake) -> HandshakeState {
+        let deadline = Instant::now() + TIMEOUT * 2;
+        while Instant::now() < deadline {
+            if *handshake.poll(Instant::now()) != HandshakeState::Connecting {
+                return handshake.state.clone();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the handshake never ended");
+    }
+
+    #[test]
+    fn start_signal_connects() {
+        let mut handshake = Handshake::connect(
+            start_host("start black\nname Host"),
+            TIMEOUT,
+            PlayerNames::default(),
+        );
+
+        assert_eq!(
+            wait_for(&mut handshake),
+            HandshakeState::Connected(PieceColor::Black, "Host".to_string())
+        );
+        assert!(handshake.take_stream().is_some());
+    }
+
+    #[test]
+    fn other_greeting_fails() {
+        let mut handshake =
+            Handshake::connect(start_host("move e2e4"), TIMEOUT, PlayerNames::default());
+
+        assert!(matches!(
+            wait_for(&mut handshake),
+            HandshakeState::Failed(ChessError::ConnectionFailed(_))
+        ));
+        assert!(handshake.take_stream().is_none());
+    }
+
+    #[test]
+    fn refused_connection_fails() {
+        // Nobody listens on the port once the listener is dropped
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut handshake = Handshake::connect(address, TIMEOUT, PlayerNames::default());
+
+        assert!(matches!(
+            wait_for(&mut handshake),
+            HandshakeState::Failed(ChessError::ConnectionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn silent_host_times_out() {
+        let mut handshake = Handshake::spawn(Duration::from_secs(1), || {
+            thread::sleep(Duration::from_secs(3));
+            Err(ChessError::ConnectionFailed("too late".to_string()))
+        });
+
+        assert_eq!(*handshake.poll(Instant::now()), HandshakeState::Connecting);
+        assert_eq!(
+            *handshake.poll(Instant::now() + Duration::from_secs(1)),
+            HandshakeState::Failed(ChessError::ConnectionFailed(
+                "no answer after 1 seconds".to_string()
+            ))
+        );
+        // The outcome does not change once it is known
+        thread::sleep(Duration::from_millis(10));
+        assert!(matches!(
+            handshake.poll(Instant::now()),
+            HandshakeState::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn spinner_turns_on_every_poll() {
+        let mut handshake = Handshake::spawn(TIMEOUT, || {
+            thread::sleep(Duration::from_secs(1));
+            Err(ChessError::ConnectionFailed("cancelled".to_string()))
+        });
+
+        let first = handshake.spinner();
+        handshake.poll(Instant::now());
+        assert_ne!(handshake.spinner(), first);
+        for _ in 1..SPINNER_FRAMES.len() {
+            handshake.poll(Instant::now());
+        }
+        assert_eq!(handshake.spinner(), first);
+    }
+
+    #[test]
+    fn app_keeps_the_stream_and_the_color() {
+        let mut app = App::default();
+        app.join_game(start_host("start white\nname Host"));
+
+        let deadline = Instant::now() + TIMEOUT;
+        while app.handshake.is_some() && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(app.handshake.is_none());
+        assert!(app.opponent.is_some());
+        assert_eq!(app.selected_color, Some(PieceColor::White));
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.players().black.as_deref(), Some("Host"));
+    }
+
+    #[test]
+    fn moves_are_refused_on_the_turn_of_the_opponent() {
+        let mut app = App::default();
+        app.join_game(start_host("start black\nname Host"));
+        let deadline = Instant::now() + TIMEOUT;
+        while app.opponent.is_none() && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(app.is_opponent_turn());
+
+        // Typed, clicked and dragged moves of the white pieces of the host
+        assert!(app.play_algebraic_move("e4").is_err());
+        app.click_board_cell(Coord::new(6, 4));
+        assert!(!app.game.ui.is_cell_selected());
+        assert_eq!(app.game.ui.dragged_from, None);
+        app.game.ui.selected_coordinates = Coord::new(6, 4);
+        app.game.ui.cursor_coordinates = Coord::new(4, 4);
+        app.handle_cell_click();
+
+        assert!(app.game.game_board.move_history.is_empty());
+    }
+
+    #[test]
+    fn nameless_host_fails() {
+        let mut handshake =
+            Handshake::connect(start_host("start black"), TIMEOUT, PlayerNames::default());
+
+        assert!(matches!(
+            wait_for(&mut handshake),
+            HandshakeState::Failed(ChessError::ConnectionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn app_reports_failures() {
+        let mut app = App {
+            handshake: Some(Handshake::spawn(TIMEOUT, || {
+                Err(ChessError::ConnectionFailed("refused".to_string()))
+            })),
+            ..Default::default()
+        };
+
+        let deadline = Instant::now() + TIMEOUT;
+        while app.handshake.is_some() && Instant::now() < deadline {
+            app.poll_handshake(Instant::now());
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(app.opponent.is_none());
+        let toast = app.toast.expect("the failure should be shown");
+        assert!(toast.is_error);
+        assert_eq!(toast.message, "Connection failed: refused");
+    }
+
+    #[test]
+    fn cancelling_stops_waiting() {
+        let mut app = App {
+            handshake: Some(Handshake::spawn(TIMEOUT, || {
+                thread::sleep(Duration::from_millis(200));
+                Err(ChessError::ConnectionFailed("cancelled".to_string()))
+            })),
+            ..Default::default()
+        };
+
+        app.cancel_handshake();
+        app.tick();
+
+        assert!(app.handshake.is_none());
+        assert!(app.toast.is_none());
+    }
+}
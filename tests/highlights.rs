@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use chess_tui::utils::flip_coord;
+
+    fn play_move(game: &mut Game, from: Coord, to: Coord) {
+        game.ui.cursor_coordinates = from;
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = to;
+        game.handle_cell_click();
+    }
+
+    #[test]
+    fn flip_coord_is_an_involution() {
+        let coord = Coord::new(6, 4);
+        assert_eq!(flip_coord(coord), Coord::new(1, 3));
+        assert_eq!(flip_coord(flip_coord(coord)), coord);
+        assert_eq!(flip_coord(Coord::undefined()), Coord::undefined());
+    }
+
+    #[test]
+    fn last_move_highlight_follows_the_flip() {
+        let mut game = Game::default();
+        play_move(&mut game, Coord::new(6, 4), Coord::new(4, 4));
+
+        // The board is now seen from black
+        let (from, to) = game.ui.last_move_highlight.unwrap();
+        assert_eq!(from, Coord::new(1, 3));
+        assert_eq!(to, Coord::new(3, 3));
+        assert_eq!(
+            game.game_board.board[&to],
+            Some((PieceType::Pawn, PieceColor::White))
+        );
+        assert_eq!(game.game_board.board[&from], None);
+    }
+
+    #[test]
+    fn highlights_stay_on_the_same_piece_after_manual_flips() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        // Queen d1 to e2 checks the black king
+        play_move(&mut game, Coord::new(7, 3), Coord::new(6, 4));
+
+        for _ in 0..3 {
+            let (_, to) = game.ui.last_move_highlight.unwrap();
+            assert_eq!(
+                game.game_board.board[&to],
+                Some((PieceType::Queen, PieceColor::White))
+            );
+
+            let checked_king = game.ui.check_highlight.unwrap();
+            assert_eq!(
+                game.game_board.board[&checked_king],
+                Some((PieceType::King, PieceColor::Black))
+            );
+
+            game.flip_the_board();
+        }
+    }
+
+    #[test]
+    fn check_highlight_cleared_once_resolved() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        assert_eq!(game.ui.check_highlight, None);
+
+        play_move(&mut game, Coord::new(7, 3), Coord::new(6, 4));
+        assert!(game.ui.check_highlight.is_some());
+
+        // The black king steps aside (e8 to d8, seen from black)
+        play_move(&mut game, Coord::new(7, 3), Coord::new(7, 4));
+        assert_eq!(game.ui.check_highlight, None);
+    }
+}
@@ -0,0 +1,154 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::{press, write_fake_engine};
+    use chess_tui::app::{App, HINT_TICKS};
+    use chess_tui::constants::Pages;
+    use chess_tui::event::Event;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::handler::handle_event;
+    use ratatui::crossterm::event::KeyCode;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    // An engine opening with the king pawn on both sides
+    const HINTING_ENGINE: &str = r#"#!/bin/sh
+best_move=e2e4
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Hinter"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    *" b "*) best_move=e7e5 ;;
+    position*) best_move=e2e4 ;;
+    "go depth 8") echo "bestmove $best_move" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn hinting_engine() -> String {
+        write_fake_engine("hinting", HINTING_ENGINE).to_string()
+    }
+
+    fn solo_app() -> App {
+        App {
+            current_page: Pages::Solo,
+            engine_path: Some(hinting_engine()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hint_lights_the_move_of_the_engine_for_a_while() {
+        let mut app = solo_app();
+
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(
+            app.game.ui.hint_highlight,
+            Some((Coord::new(6, 4), Coord::new(4, 4)))
+        );
+
+        for _ in 1..HINT_TICKS {
+            app.tick();
+        }
+        assert!(app.game.ui.hint_highlight.is_some());
+        app.tick();
+        assert_eq!(app.game.ui.hint_highlight, None);
+        assert_eq!(app.hint, None);
+    }
+
+    #[test]
+    fn hint_is_given_from_the_side_of_the_player_to_move() {
+        let mut app = solo_app();
+        app.game.play_uci_move("e2e4").unwrap();
+
+        press(&mut app, KeyCode::Char('H'));
+
+        // Black is at the bottom of the board while it is its turn
+        assert_eq!(
+            app.game.ui.hint_highlight,
+            Some((Coord::new(6, 3), Coord::new(4, 3)))
+        );
+    }
+
+    #[test]
+    fn playing_a_move_puts_the_hint_out() {
+        let mut app = solo_app();
+        press(&mut app, KeyCode::Char('H'));
+
+        app.game.ui.cursor_coordinates = Coord::new(6, 4);
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = Coord::new(4, 4);
+        app.handle_cell_click();
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+        assert_eq!(app.game.ui.hint_highlight, None);
+        assert_eq!(app.hint, None);
+    }
+
+    #[test]
+    fn hint_needs_an_engine() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+
+        press(&mut app, KeyCode::Char('H'));
+
+        assert_eq!(app.game.ui.hint_highlight, None);
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn no_hint_on_the_home_page() {
+        let mut app = App {
+            engine_path: Some(hinting_engine()),
+            ..Default::default()
+        };
+
+        press(&mut app, KeyCode::Char('H'));
+
+        assert_eq!(app.game.ui.hint_highlight, None);
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn hint_is_searched_away_from_the_main_loop() {
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            event_sender: Some(sender),
+            ..solo_app()
+        };
+
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(app.status_message(), "Engine looking for a hint...");
+        assert_eq!(app.game.ui.hint_highlight, None);
+
+        let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(event, Event::EngineHint(_, Ok(_))));
+        handle_event(event, &mut app).unwrap();
+        assert_eq!(
+            app.game.ui.hint_highlight,
+            Some((Coord::new(6, 4), Coord::new(4, 4)))
+        );
+        assert_ne!(app.status_message(), "Engine looking for a hint...");
+    }
+
+    #[test]
+    fn hint_arriving_after_a_move_is_dropped() {
+        let (sender, receiver) = mpsc::channel();
+        let mut app = App {
+            event_sender: Some(sender),
+            ..solo_app()
+        };
+        press(&mut app, KeyCode::Char('H'));
+
+        app.game.play_uci_move("d2d4").unwrap();
+        let event = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        handle_event(event, &mut app).unwrap();
+
+        assert_eq!(app.game.ui.hint_highlight, None);
+        assert_eq!(app.pending_hint, None);
+    }
+}
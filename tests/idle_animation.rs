@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::handler::handle_key_events;
+    use chess_tui::ui::idle_animation::{IdleTracker, ANIMATION_FRAME_TICKS};
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use toml::Value;
+
+    #[test]
+    fn idle_tracker_enters_animation_after_timeout() {
+        let mut idle = IdleTracker::new(Some(3));
+
+        idle.tick();
+        idle.tick();
+        assert!(!idle.is_animating());
+
+        idle.tick();
+        assert!(idle.is_animating());
+        assert_eq!(idle.animation_frame, Some(0));
+    }
+
+    #[test]
+    fn idle_tracker_animation_is_throttled() {
+        let mut idle = IdleTracker::new(Some(1));
+
+        idle.tick();
+        assert_eq!(idle.animation_frame, Some(0));
+        for _ in 0..ANIMATION_FRAME_TICKS {
+            idle.tick();
+        }
+        assert_eq!(idle.animation_frame, Some(1));
+    }
+
+    #[test]
+    fn idle_tracker_reset_exits_animation() {
+        let mut idle = IdleTracker::new(Some(1));
+        idle.tick();
+        assert!(idle.is_animating());
+
+        assert!(idle.reset());
+        assert!(!idle.is_animating());
+        assert_eq!(idle.idle_ticks, 0);
+        // Nothing to stop anymore
+        assert!(!idle.reset());
+    }
+
+    #[test]
+    fn idle_tracker_disabled_by_default() {
+        let mut idle = IdleTracker::default();
+        for _ in 0..1000 {
+            idle.tick();
+        }
+        assert!(!idle.is_animating());
+    }
+
+    #[test]
+    fn app_idle_animation_only_on_home_page() {
+        let mut app = App {
+            idle: IdleTracker::new(Some(2)),
+            ..Default::default()
+        };
+        app.tick();
+        app.tick();
+        assert!(app.idle.is_animating());
+
+        app.current_page = Pages::Solo;
+        app.tick();
+        app.tick();
+        assert!(!app.idle.is_animating());
+    }
+
+    #[test]
+    fn app_key_press_stops_animation() {
+        let mut app = App {
+            idle: IdleTracker::new(Some(1)),
+            ..Default::default()
+        };
+        app.tick();
+        assert!(app.idle.is_animating());
+
+        let key_event = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        handle_key_events(key_event, &mut app).unwrap();
+        assert!(!app.idle.is_animating());
+        // The key press only woke the home page up
+        assert_eq!(app.menu_cursor, 0);
+
+        handle_key_events(key_event, &mut app).unwrap();
+        assert_eq!(app.menu_cursor, 1);
+    }
+
+    #[test]
+    fn app_idle_timeout_from_config() {
+        let mut app = App::default();
+
+        let config: Value = "idle_animation_timeout = 30".parse().unwrap();
+        app.apply_config(&config);
+        assert_eq!(app.idle.timeout_ticks, Some(120));
+
+        let config: Value = "idle_animation_timeout = 0".parse().unwrap();
+        app.apply_config(&config);
+        assert_eq!(app.idle.timeout_ticks, None);
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use toml::Value;
+
+    fn configured_app(config: &str) -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.apply_config(&config.parse::<Value>().unwrap());
+        app
+    }
+
+    #[test]
+    fn cursor_starts_in_the_middle_by_default() {
+        let app = configured_app("");
+        assert_eq!(app.initial_cursor, None);
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(4, 4));
+    }
+
+    #[test]
+    fn configured_cell_is_used_at_game_start() {
+        let mut app = configured_app("initial_cursor = \"e2\"");
+        assert_eq!(app.initial_cursor, Some(Coord::new(6, 4)));
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(6, 4));
+
+        app.game.ui.cursor_coordinates = Coord::new(0, 0);
+        app.restart();
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(6, 4));
+
+        let app = configured_app("initial_cursor = \"g1\"");
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(7, 6));
+    }
+
+    #[test]
+    fn black_finds_the_cell_on_its_side() {
+        let mut app = configured_app("initial_cursor = \"e2\"");
+        // Black to move, the board is stored from its point of view
+        app.load_fen("4k3/4p3/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+
+        // e7, where black's king pawn stands
+        let cursor = app.game.ui.cursor_coordinates;
+        assert_eq!(cursor, Coord::new(6, 3));
+        assert_eq!(
+            app.game.game_board.board[&cursor],
+            Some((PieceType::Pawn, PieceColor::Black))
+        );
+    }
+
+    #[test]
+    fn bot_game_with_black_starts_on_its_side() {
+        let mut app = configured_app("initial_cursor = \"e2\"");
+        app.current_page = Pages::Bot;
+        app.menu_cursor = 1;
+        app.color_selection();
+        app.difficulty_selection();
+
+        // White moves first, the board is stored from its point of view
+        assert_eq!(
+            app.game.ui.cursor_coordinates,
+            Coord::from_algebraic("e7").unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_cell_is_reported() {
+        let app = configured_app("initial_cursor = \"e9\"");
+        assert_eq!(app.initial_cursor, None);
+        assert!(app.toast.as_ref().is_some_and(|toast| toast.is_error));
+        assert_eq!(app.game.ui.cursor_coordinates, Coord::new(4, 4));
+    }
+}
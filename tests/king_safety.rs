@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::board::Board;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::game_logic::game_board::GameBoard;
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    fn adjacent(a: Coord, b: Coord) -> bool {
+        a.row.abs_diff(b.row) <= 1 && a.col.abs_diff(b.col) <= 1
+    }
+
+    #[test]
+    fn kings_cannot_approach_each_other() {
+        // The kings face each other on e4 and e6
+        let game = Game::from_fen("8/8/4k3/8/4K3/8/8/8 w - - 0 1").unwrap();
+        let white_king = Coord::new(4, 4);
+        let black_king = Coord::new(2, 4);
+
+        let targets = game
+            .game_board
+            .get_authorized_positions(PieceColor::White, white_king);
+
+        assert_eq!(targets.len(), 5);
+        assert!(targets.iter().all(|cell| !adjacent(*cell, black_king)));
+    }
+
+    #[test]
+    fn kings_cannot_approach_each_other_diagonally() {
+        // Black to move, the board is stored from their point of view
+        let game = Game::from_fen("8/8/8/3k4/8/5K2/8/8 b - - 0 1").unwrap();
+        let black_king = Coord::new(4, 4);
+        let white_king = Coord::new(2, 2);
+        assert_eq!(
+            game.game_board.board[&black_king],
+            Some((PieceType::King, PieceColor::Black))
+        );
+        assert_eq!(
+            game.game_board.board[&white_king],
+            Some((PieceType::King, PieceColor::White))
+        );
+
+        let targets = game
+            .game_board
+            .get_authorized_positions(PieceColor::Black, black_king);
+
+        assert!(!targets.contains(&Coord::new(3, 3)));
+        assert!(targets.iter().all(|cell| !adjacent(*cell, white_king)));
+        assert_eq!(targets.len(), 7);
+    }
+
+    #[test]
+    fn opposing_king_is_never_captured() {
+        // Not reachable in a game: black is in check while white is to move
+        let mut board: Board = [[None; 8]; 8];
+        board[0][0] = Some((PieceType::King, PieceColor::Black));
+        board[7][0] = Some((PieceType::Rook, PieceColor::White));
+        board[7][7] = Some((PieceType::King, PieceColor::White));
+        board[1][1] = Some((PieceType::Queen, PieceColor::White));
+        let game_board = GameBoard::new(board, vec![], vec![board]);
+
+        let king_cell = Coord::new(0, 0);
+        for piece_move in game_board.legal_moves(PieceColor::White) {
+            assert_ne!(piece_move.to, king_cell, "{piece_move:?} takes the king");
+        }
+    }
+}
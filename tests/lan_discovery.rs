@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::network::discovery::{
+        announcement_packet, is_discovery_query, parse_announcement, query_packet, LanGame,
+    };
+    use chess_tui::network::protocol::Message;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const SENDER: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 7));
+
+    #[test]
+    fn announced_game_is_hosted_by_the_sender() {
+        let packet = announcement_packet("Alice's game", Ipv4Addr::new(192, 168, 1, 7), 2308);
+
+        assert_eq!(
+            parse_announcement(&packet, SENDER),
+            Some(LanGame {
+                name: "Alice's game".to_string(),
+                address: SocketAddr::new(SENDER, 2308),
+            })
+        );
+    }
+
+    #[test]
+    fn only_queries_for_games_are_answered() {
+        let announcement = announcement_packet("Game", Ipv4Addr::LOCALHOST, 2308);
+
+        assert!(is_discovery_query(&query_packet()));
+        assert!(!is_discovery_query(&announcement));
+        // A printer looked for on the same network
+        let printer_query = query_packet()
+            .iter()
+            .map(|&byte| if byte == b'c' { b'p' } else { byte })
+            .collect::<Vec<u8>>();
+        assert!(!is_discovery_query(&printer_query));
+    }
+
+    #[test]
+    fn names_written_once_are_understood() {
+        // The name of the game points back to the name of the service, and the service to the game
+        let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 2, 0, 0, 0, 0];
+        packet.extend(b"\x0a_chess-tui\x04_tcp\x05local\x00");
+        packet.extend([0, 12, 0, 1, 0, 0, 0, 120, 0, 6]);
+        let game_name = packet.len();
+        packet.extend(b"\x03Bob\xc0\x0c");
+        packet.extend([0xc0, game_name as u8, 0, 33, 0, 1, 0, 0, 0, 120, 0, 8]);
+        packet.extend([0, 0, 0, 0, 0x0f, 0xa0, 0xc0, 0x0c]);
+
+        assert_eq!(
+            parse_announcement(&packet, SENDER),
+            Some(LanGame {
+                name: "Bob".to_string(),
+                address: SocketAddr::new(SENDER, 4000),
+            })
+        );
+    }
+
+    #[test]
+    fn truncated_announcement_is_ignored() {
+        let packet = announcement_packet("Game", Ipv4Addr::LOCALHOST, 2308);
+
+        assert_eq!(parse_announcement(&packet[..40], SENDER), None);
+        assert_eq!(parse_announcement(&query_packet(), SENDER), None);
+    }
+
+    #[test]
+    fn joining_a_game_found_on_the_network_connects_to_its_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut app = App::default();
+
+        app.open_lan_games();
+        assert_eq!(app.current_page, Pages::LanGames);
+        app.lan_discovery.as_mut().unwrap().games.push(LanGame {
+            name: "Game".to_string(),
+            address: listener.local_addr().unwrap(),
+        });
+        app.join_lan_game();
+
+        assert_eq!(app.current_page, Pages::Solo);
+        assert!(app.handshake.is_some());
+        assert!(app.lan_discovery.is_none());
+    }
+
+    #[test]
+    fn joined_game_exchanges_moves_with_its_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            writeln!(stream, "start white\nname Host").unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            let player_move =
+                reader
+                    .lines()
+                    .find_map(|line| match Message::parse(&line.unwrap()) {
+                        Some(Message::Move(uci)) => Some(uci),
+                        _ => None,
+                    });
+            assert_eq!(player_move.as_deref(), Some("e2e4"));
+            writeln!(stream, "move e7e5").unwrap();
+            // Keep the connection open while the app plays the move
+            thread::sleep(Duration::from_millis(500));
+        });
+        let mut app = App::default();
+        app.open_lan_games();
+        app.lan_discovery.as_mut().unwrap().games.push(LanGame {
+            name: "Game".to_string(),
+            address,
+        });
+        app.join_lan_game();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.opponent.is_none() && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+        app.play_algebraic_move("e4").unwrap();
+        while app.game.game_board.uci_moves().len() < 2 && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+    }
+
+    #[test]
+    fn leaving_the_page_stops_looking_for_games() {
+        let mut app = App::default();
+
+        app.open_lan_games();
+        app.close_lan_games();
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert!(app.lan_discovery.is_none());
+    }
+}
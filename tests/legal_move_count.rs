@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::handler::handle_key_events;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn twenty_moves_in_the_starting_position() {
+        assert_eq!(Game::default().legal_move_count(), 20);
+    }
+
+    #[test]
+    fn black_has_twenty_answers_to_e4() {
+        let mut game = Game::default();
+        game.play_uci_move("e2e4").unwrap();
+
+        assert_eq!(game.player_turn, PieceColor::Black);
+        assert_eq!(game.legal_move_count(), 20);
+
+        // After 1...e5 the queen and the bishop of White can leave their squares
+        game.play_uci_move("e7e5").unwrap();
+        assert_eq!(game.legal_move_count(), 29);
+    }
+
+    #[test]
+    fn count_is_toggled_with_c() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        let c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+
+        handle_key_events(c, &mut app).unwrap();
+        assert!(app.game.ui.show_legal_move_count);
+
+        // The setting stays for the next game
+        app.rematch();
+        assert!(app.game.ui.show_legal_move_count);
+
+        handle_key_events(c, &mut app).unwrap();
+        assert!(!app.game.ui.show_legal_move_count);
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::game::Game;
+
+    #[test]
+    fn knight_destinations_from_the_start() {
+        assert_eq!(
+            Game::default().legal_destinations("b1").unwrap(),
+            vec!["a3", "c3"]
+        );
+    }
+
+    #[test]
+    fn destinations_are_seen_from_white_when_black_plays() {
+        let game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        assert_eq!(game.legal_destinations("g8").unwrap(), vec!["f6", "h6"]);
+        assert_eq!(game.legal_destinations("e7").unwrap(), vec!["e5", "e6"]);
+        // White pieces have no move while black is to play
+        assert!(game.legal_destinations("e4").unwrap().is_empty());
+    }
+
+    #[test]
+    fn castling_king_goes_to_the_g_file() {
+        let game = Game::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(
+            game.legal_destinations("e1").unwrap(),
+            vec!["d1", "d2", "e2", "f1", "f2", "g1"]
+        );
+    }
+
+    #[test]
+    fn empty_or_unknown_squares() {
+        assert!(Game::default().legal_destinations("e4").unwrap().is_empty());
+        assert_eq!(
+            Game::default().legal_destinations("z9"),
+            Err(ChessError::InvalidSquare("z9".to_string()))
+        );
+    }
+}
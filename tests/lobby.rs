@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::error::ChessError;
+    use chess_tui::network::lobby::{decode_game_code, encode_game_code};
+
+    #[test]
+    fn game_code_round_trip() {
+        let addresses = [
+            "192.168.1.2:2308",
+            "0.0.0.0:0",
+            "255.255.255.255:65535",
+            "10.0.0.42:80",
+        ];
+
+        for address in addresses {
+            let address: SocketAddr = address.parse().unwrap();
+            let code = encode_game_code(address).unwrap();
+            assert_eq!(decode_game_code(&code), Ok(address), "{code}");
+        }
+    }
+
+    #[test]
+    fn game_code_format() {
+        let code = encode_game_code("192.168.1.2:2308".parse().unwrap()).unwrap();
+
+        assert_eq!(code.len(), 13);
+        assert_eq!(code.matches('-').count(), 2);
+    }
+
+    #[test]
+    fn game_code_is_forgiving() {
+        let address: SocketAddr = "192.168.1.2:2308".parse().unwrap();
+        let code = encode_game_code(address).unwrap();
+
+        let typed_code = code.to_lowercase().replace('-', " ").replace('0', "o");
+        assert_eq!(decode_game_code(&typed_code), Ok(address));
+    }
+
+    #[test]
+    fn game_code_ipv6_is_rejected() {
+        let address: SocketAddr = "[::1]:2308".parse().unwrap();
+
+        assert!(matches!(
+            encode_game_code(address),
+            Err(ChessError::InvalidGameCode(_))
+        ));
+    }
+
+    #[test]
+    fn game_code_malformed() {
+        let code = encode_game_code("192.168.1.2:2308".parse().unwrap()).unwrap();
+        let mut typo = code.clone().into_bytes();
+        typo[0] = if typo[0] == b'1' { b'2' } else { b'1' };
+        let typo = String::from_utf8(typo).unwrap();
+
+        let malformed_codes = ["", "ABCD", "ZZZZ-ZZZZ-ZZZ", "60N0-0G42-84!", &typo];
+        for malformed_code in malformed_codes {
+            assert!(
+                matches!(
+                    decode_game_code(malformed_code),
+                    Err(ChessError::InvalidGameCode(_))
+                ),
+                "{malformed_code} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn typed_game_code_joins_its_host() {
+        let mut app = App::default();
+        let code = encode_game_code("127.0.0.1:9".parse().unwrap()).unwrap();
+
+        app.open_game_code_input();
+        app.game.ui.prompt.enter_str(&code);
+        app.submit_game_code_input();
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.current_page, Pages::Solo);
+        assert!(app.handshake.is_some());
+    }
+
+    #[test]
+    fn malformed_game_code_is_reported() {
+        let mut app = App::default();
+
+        app.open_game_code_input();
+        app.game.ui.prompt.enter_str("60N0-0G42");
+        app.submit_game_code_input();
+
+        assert_eq!(app.current_popup, Some(Popups::GameCodeInput));
+        assert!(app.handshake.is_none());
+        let toast = app.toast.expect("the malformed code should be reported");
+        assert!(toast.is_error);
+        assert!(toast.message.starts_with("Invalid game code"));
+    }
+}
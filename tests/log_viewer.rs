@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Popups;
+    use chess_tui::logging::tail_lines;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // A file of the temporary folder holding `content`, removed by the test
+    fn sample_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-log-viewer-{}-{name}.log",
+            std::process::id()
+        ));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn tail_keeps_the_last_lines() {
+        let content: String = (1..=10).map(|i| format!("entry {i}\n")).collect();
+        let path = sample_file("last", &content);
+
+        assert_eq!(
+            tail_lines(&path, 3).unwrap(),
+            vec!["entry 8", "entry 9", "entry 10"]
+        );
+        assert_eq!(tail_lines(&path, 50).unwrap().len(), 10);
+        assert!(tail_lines(&path, 0).unwrap().is_empty());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn tail_of_an_empty_or_missing_file() {
+        let path = sample_file("empty", "");
+        assert!(tail_lines(&path, 5).unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+
+        assert!(tail_lines(&path, 5).is_err());
+    }
+
+    #[test]
+    fn viewer_explains_missing_logs() {
+        let mut app = App::default();
+        app.toggle_log_viewer();
+        assert_eq!(app.current_popup, Some(Popups::LogViewer));
+        assert_eq!(app.log_lines.len(), 1);
+        assert!(app.log_lines[0].contains("log_level"));
+
+        let mut app = App {
+            log_file: Some(std::env::temp_dir().join("chess-tui-missing.log")),
+            ..Default::default()
+        };
+        app.toggle_log_viewer();
+        assert!(app.log_lines[0].starts_with("Can't read"));
+
+        let path = sample_file("viewer-empty", "");
+        let mut app = App {
+            log_file: Some(path.clone()),
+            ..Default::default()
+        };
+        app.toggle_log_viewer();
+        assert_eq!(app.log_lines, vec!["The log file is empty"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn viewer_follows_and_scrolls_the_log() {
+        let path = sample_file("viewer", "first\nsecond\n");
+        let mut app = App {
+            log_file: Some(path.clone()),
+            ..Default::default()
+        };
+        app.toggle_log_viewer();
+        assert_eq!(app.log_lines, vec!["first", "second"]);
+
+        // Scrolling stops on the oldest line
+        app.log_scroll_up();
+        app.log_scroll_up();
+        assert_eq!(app.log_scroll, 1);
+        app.log_scroll_down();
+        app.log_scroll_down();
+        assert_eq!(app.log_scroll, 0);
+
+        fs::write(&path, "first\nsecond\nthird\n").unwrap();
+        app.tick();
+        assert_eq!(app.log_lines.last().unwrap(), "third");
+
+        app.toggle_log_viewer();
+        assert_eq!(app.current_popup, None);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use ratatui::crossterm::event::KeyCode;
+    use toml::Value;
+
+    fn last_entry() -> u8 {
+        Pages::variant_count() as u8 - 1
+    }
+
+    #[test]
+    fn home_menu_wraps_by_default() {
+        let mut app = App::default();
+        assert!(app.menu_wrap);
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.menu_cursor, last_entry());
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.menu_cursor, 0);
+    }
+
+    #[test]
+    fn home_menu_stops_at_its_ends_without_wrapping() {
+        let mut app = App::default();
+        let config: Value = toml::from_str("menu_wrap = false").unwrap();
+        app.apply_config(&config);
+        assert!(!app.menu_wrap);
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.menu_cursor, 0);
+
+        app.menu_cursor = last_entry();
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.menu_cursor, last_entry());
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.menu_cursor, last_entry() - 1);
+    }
+
+    #[test]
+    fn color_selection_stops_at_its_ends_without_wrapping() {
+        let mut app = App {
+            current_page: Pages::Bot,
+            current_popup: Some(Popups::ColorSelection),
+            menu_wrap: false,
+            ..Default::default()
+        };
+
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.menu_cursor, 0);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.menu_cursor, 1);
+
+        app.menu_wrap = true;
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.menu_cursor, 0);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::handler::handle_mouse_events;
+    use ratatui::crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use toml::Value;
+
+    // Every cell of the board is a single terminal cell starting at the top left corner
+    fn solo_app(mouse_capture: bool) -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            mouse_capture,
+            ..Default::default()
+        };
+        app.game.ui.width = 1;
+        app.game.ui.height = 1;
+        app
+    }
+
+    fn click(app: &mut App, coord: Coord) {
+        let mouse_event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: coord.col as u16,
+            row: coord.row as u16,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, app).unwrap();
+    }
+
+    #[test]
+    fn mouse_events_are_ignored_without_mouse_capture() {
+        let mut app = solo_app(false);
+
+        click(&mut app, Coord::new(6, 4));
+        click(&mut app, Coord::new(4, 4));
+
+        assert!(app.game.game_board.move_history.is_empty());
+        assert_eq!(app.game.ui.selected_coordinates, Coord::undefined());
+        assert!(!app.game.ui.mouse_used);
+    }
+
+    #[test]
+    fn mouse_plays_moves_with_mouse_capture() {
+        let mut app = solo_app(true);
+
+        click(&mut app, Coord::new(6, 4));
+        click(&mut app, Coord::new(4, 4));
+
+        assert_eq!(app.game.game_board.move_history.len(), 1);
+    }
+
+    #[test]
+    fn mouse_capture_is_read_from_the_config() {
+        let mut app = App::default();
+        assert!(app.mouse_capture);
+
+        app.apply_config(&"mouse_capture = false".parse::<Value>().unwrap());
+
+        assert!(!app.mouse_capture);
+    }
+}
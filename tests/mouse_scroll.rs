@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Focus, Pages, Popups};
+    use chess_tui::handler::handle_mouse_events;
+    use ratatui::crossterm::event::{KeyModifiers, MouseEvent, MouseEventKind};
+    use ratatui::layout::Rect;
+
+    // The history panel takes the cells (20..30, 0..10), the pieces taken by black the cells
+    // (20..30, 10..12) and the pieces taken by white the cells (20..30, 12..14)
+    fn app_with_moves(moves: &[&str]) -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        for san in moves {
+            app.play_algebraic_move(san).unwrap();
+        }
+        app.game.ui.panel_areas = vec![
+            (Focus::MoveHistory, Rect::new(20, 0, 10, 10)),
+            (Focus::BlackMaterial, Rect::new(20, 10, 10, 2)),
+            (Focus::WhiteMaterial, Rect::new(20, 12, 10, 2)),
+        ];
+        app
+    }
+
+    fn wheel(app: &mut App, kind: MouseEventKind, column: u16, row: u16) {
+        let mouse_event = MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, app).unwrap();
+    }
+
+    #[test]
+    fn wheel_scrolls_the_history_under_the_mouse() {
+        let mut app = app_with_moves(&["e4", "e5", "Nf3", "Nc6", "Bc4"]);
+
+        wheel(&mut app, MouseEventKind::ScrollDown, 25, 5);
+        assert_eq!(app.game.ui.history_scroll, 1);
+        // Three lines of moves, the last one stays visible
+        wheel(&mut app, MouseEventKind::ScrollDown, 25, 5);
+        wheel(&mut app, MouseEventKind::ScrollDown, 25, 5);
+        assert_eq!(app.game.ui.history_scroll, 2);
+
+        wheel(&mut app, MouseEventKind::ScrollUp, 25, 5);
+        wheel(&mut app, MouseEventKind::ScrollUp, 25, 5);
+        wheel(&mut app, MouseEventKind::ScrollUp, 25, 5);
+        assert_eq!(app.game.ui.history_scroll, 0);
+        // The focus is left where it was
+        assert_eq!(app.focus, Focus::Board);
+    }
+
+    #[test]
+    fn wheel_scrolls_the_captured_pieces_under_the_mouse() {
+        let mut app = app_with_moves(&["e4", "d5", "exd5", "Qxd5", "Nc3", "Qxa2", "Rxa2"]);
+
+        wheel(&mut app, MouseEventKind::ScrollDown, 22, 11);
+        assert_eq!(app.game.ui.black_material_scroll, 1);
+        assert_eq!(app.game.ui.history_scroll, 0);
+        wheel(&mut app, MouseEventKind::ScrollDown, 22, 11);
+        assert_eq!(app.game.ui.black_material_scroll, 1);
+    }
+
+    #[test]
+    fn wheel_scrolls_only_the_captured_pieces_panel_under_the_mouse() {
+        let mut app = app_with_moves(&["e4", "d5", "exd5", "Qxd5", "Nc3", "Qxa2", "Rxa2"]);
+
+        wheel(&mut app, MouseEventKind::ScrollDown, 22, 13);
+        assert_eq!(app.game.ui.white_material_scroll, 1);
+        assert_eq!(app.game.ui.black_material_scroll, 0);
+    }
+
+    #[test]
+    fn wheel_outside_of_the_panels_does_nothing() {
+        let mut app = app_with_moves(&["e4", "e5", "Nf3", "Nc6"]);
+
+        wheel(&mut app, MouseEventKind::ScrollDown, 5, 5);
+        assert_eq!(app.game.ui.history_scroll, 0);
+        assert_eq!(app.game.ui.white_material_scroll, 0);
+        assert_eq!(app.game.ui.black_material_scroll, 0);
+    }
+
+    #[test]
+    fn wheel_scrolls_the_log_viewer() {
+        let mut app = app_with_moves(&["e4", "e5", "Nf3", "Nc6"]);
+        app.current_popup = Some(Popups::LogViewer);
+        app.log_lines = vec!["first".to_string(), "second".to_string()];
+
+        wheel(&mut app, MouseEventKind::ScrollUp, 25, 5);
+        wheel(&mut app, MouseEventKind::ScrollUp, 25, 5);
+        assert_eq!(app.log_scroll, 1);
+        assert_eq!(app.game.ui.history_scroll, 0);
+
+        wheel(&mut app, MouseEventKind::ScrollDown, 0, 0);
+        assert_eq!(app.log_scroll, 0);
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::MoveNotation;
+    use chess_tui::pgn::PlayerNames;
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+    use ratatui::Terminal;
+    use std::fs;
+    use toml::Value;
+
+    // The text of the move list drawn for the game of `app`, one string per row
+    fn rendered_history(app: &App, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(40, height)).unwrap();
+        terminal
+            .draw(|frame| {
+                app.game.ui.history_render(
+                    Rect::new(0, 0, 40, height),
+                    frame,
+                    &app.game,
+                    &PlayerNames::default(),
+                    app.move_notation,
+                    false,
+                );
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|row| {
+                (0..40)
+                    .map(|column| buffer[(column, row)].symbol())
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn moves_are_listed_in_algebraic_notation() {
+        let mut app = App::default();
+        for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            app.game.play_uci_move(uci).unwrap();
+        }
+
+        let rows = rendered_history(&app, 12);
+
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("1. f3") && row.contains("e5")));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("2. g4") && row.contains("Qh4#")));
+    }
+
+    #[test]
+    fn scrolled_list_starts_at_the_scrolled_move() {
+        let mut app = App::default();
+        for uci in ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6"] {
+            app.game.play_uci_move(uci).unwrap();
+        }
+        app.game.ui.history_scroll = 1;
+
+        let rows = rendered_history(&app, 12);
+
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("2. Nf3") && row.contains("Nc6")));
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("3. Bb5") && row.contains("a6")));
+        assert!(!rows.iter().any(|row| row.contains("1. e4")));
+    }
+
+    #[test]
+    fn coordinate_notation_lists_the_cells_of_the_moves() {
+        let mut app = App {
+            move_notation: MoveNotation::Uci,
+            ..Default::default()
+        };
+        for uci in ["e2e4", "e7e5", "e1e2"] {
+            app.game.play_uci_move(uci).unwrap();
+        }
+
+        let rows = rendered_history(&app, 12);
+
+        assert!(rows
+            .iter()
+            .any(|row| row.contains("1. e2e4") && row.contains("e7e5")));
+        assert!(rows.iter().any(|row| row.contains("2. e1e2")));
+        assert_eq!(app.game.notated_move(2, MoveNotation::San), "Ke2");
+    }
+
+    #[test]
+    fn notation_is_read_from_the_config_and_saved_when_switched() {
+        let path = std::env::temp_dir().join(format!(
+            "chess-tui-move-notation-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "move_notation = \"UCI\"").unwrap();
+        let mut app = App::default();
+        app.load_config(path.clone());
+        assert_eq!(app.move_notation, MoveNotation::Uci);
+
+        app.toggle_move_notation();
+
+        let config: Value = fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(app.move_notation, MoveNotation::San);
+        assert_eq!(config["move_notation"].as_str(), Some("SAN"));
+        fs::remove_file(path).unwrap();
+    }
+}
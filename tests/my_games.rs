@@ -0,0 +1,158 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::library::{scan_games, GameSource, LibraryGame};
+    use chess_tui::save::SavedGame;
+    use ratatui::crossterm::event::KeyCode;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const PGN_FILE: &str = r#"[Event "Club night"]
+[Date "2024.03.09"]
+[White "Dan"]
+[Black "Ada"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+
+[Event "Casual game"]
+[Date "????.??.??"]
+[White "Ada"]
+[Black "Bob"]
+[Result "*"]
+
+1. e4 e5 2. Nf3 *
+"#;
+
+    // A games folder of the temporary folder, unique to the test
+    fn games_folder(name: &str) -> PathBuf {
+        let folder =
+            std::env::temp_dir().join(format!("chess-tui-my-games-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("club.pgn"), PGN_FILE).unwrap();
+        fs::write(folder.join("notes.txt"), "not a game").unwrap();
+        let mut game = Game::default();
+        for uci_move in ["d2d4", "d7d5"] {
+            game.play_uci_move(uci_move).unwrap();
+        }
+        SavedGame::from_game(&game)
+            .write_to(&folder.join("unfinished.toml"), false)
+            .unwrap();
+        folder
+    }
+
+    fn find<'a>(games: &'a [LibraryGame], source: GameSource, file: &str) -> &'a LibraryGame {
+        games
+            .iter()
+            .find(|game| game.source == source && game.path.ends_with(file))
+            .unwrap()
+    }
+
+    #[test]
+    fn games_of_the_folder_are_listed_with_their_details() {
+        let folder = games_folder("listed");
+
+        let games = scan_games(&folder, Some("Ada"));
+
+        assert_eq!(games.len(), 3);
+        let club = find(&games, GameSource::Pgn(0), "club.pgn");
+        assert_eq!(
+            (
+                club.date.as_str(),
+                club.result.as_str(),
+                club.opponent.as_str()
+            ),
+            ("2024.03.09", "0-1", "Dan")
+        );
+        let casual = find(&games, GameSource::Pgn(1), "club.pgn");
+        assert!(casual.is_unfinished());
+        assert_eq!(casual.opponent, "Bob");
+        // A game without a date gets the one of its file
+        assert_eq!(casual.date.len(), "2024.03.09".len());
+        let saved = find(&games, GameSource::Save, "unfinished.toml");
+        assert!(saved.is_unfinished());
+        assert_eq!(saved.opponent, "Local game");
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn unknown_player_sees_both_names() {
+        let folder = games_folder("unknown");
+
+        let games = scan_games(&folder, None);
+
+        assert_eq!(
+            find(&games, GameSource::Pgn(0), "club.pgn").opponent,
+            "Dan vs Ada"
+        );
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn finished_game_opens_in_the_replay() {
+        let folder = games_folder("replay");
+        let mut app = App::default();
+        app.open_my_games_in(&folder);
+        assert_eq!(app.current_page, Pages::MyGames);
+        app.my_games_cursor = app
+            .my_games
+            .iter()
+            .position(|game| game.source == GameSource::Pgn(0))
+            .unwrap();
+
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.review_index, Some(0));
+        assert_eq!(app.game.game_board.uci_moves().len(), 4);
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn unfinished_games_go_on() {
+        let folder = games_folder("go-on");
+        let mut app = App::default();
+
+        for (source, moves) in [(GameSource::Pgn(1), 3), (GameSource::Save, 2)] {
+            app.open_my_games_in(&folder);
+            app.my_games_cursor = app
+                .my_games
+                .iter()
+                .position(|game| game.source == source)
+                .unwrap();
+            app.open_my_game();
+
+            assert_eq!(app.current_page, Pages::Solo);
+            assert_eq!(app.review_index, None);
+            assert_eq!(app.game.game_board.move_history.len(), moves);
+        }
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn escape_goes_back_home() {
+        let folder = games_folder("escape");
+        let mut app = App::default();
+        app.open_my_games_in(&folder);
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.my_games_cursor, 1);
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.current_page, Pages::Home);
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn missing_folder_lists_nothing() {
+        let folder = std::env::temp_dir().join("chess-tui-my-games-missing");
+
+        assert!(scan_games(&folder, None).is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pgn::{parse_games, parse_pgn, split_games, to_pgn, PgnTags, PlayerNames};
+    use chess_tui::pieces::PieceColor;
+    use chess_tui::rng::Rng;
+    use ratatui::crossterm::event::KeyCode;
+    use toml::Value;
+
+    fn play(game: &mut Game, moves: &[&str]) {
+        for uci_move in moves {
+            game.play_uci_move(uci_move).unwrap();
+        }
+    }
+
+    fn named(white: &str, black: &str) -> PlayerNames {
+        PlayerNames {
+            white: Some(white.to_string()),
+            black: Some(black.to_string()),
+        }
+    }
+
+    #[test]
+    fn headers_contain_the_configured_names() {
+        let mut app = App::default();
+        let config: Value = "white_player = \"Alice\"\nblack_player = \"Bob\""
+            .parse()
+            .unwrap();
+        app.apply_config(&config);
+
+        let pgn = to_pgn(&app.game, &PgnTags::new(&app.players(), "2024.05.01"));
+
+        assert!(pgn.contains("[White \"Alice\"]\n"));
+        assert!(pgn.contains("[Black \"Bob\"]\n"));
+        assert!(pgn.contains("[Date \"2024.05.01\"]\n"));
+    }
+
+    #[test]
+    fn unknown_players_are_question_marks() {
+        let mut app = App::default();
+        let config: Value = "white_player = \"\"".parse().unwrap();
+        app.apply_config(&config);
+
+        let pgn = to_pgn(&app.game, &PgnTags::new(&app.players(), "2024.05.01"));
+
+        assert!(pgn.contains("[White \"?\"]\n"));
+        assert!(pgn.contains("[Black \"?\"]\n"));
+    }
+
+    #[test]
+    fn moves_are_numbered_in_san() {
+        let mut game = Game::default();
+        play(
+            &mut game,
+            &[
+                "e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "f8c5", "e1g1", "g8f6",
+            ],
+        );
+
+        let pgn = to_pgn(&game, &PgnTags::new(&named("Alice", "Bob"), "2024.05.01"));
+
+        assert!(pgn.contains("[Result \"*\"]\n"));
+        assert!(pgn.ends_with("\n1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. O-O Nf6 *\n"));
+    }
+
+    #[test]
+    fn finished_game_ends_with_the_result() {
+        let mut game = Game::default();
+        play(&mut game, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+
+        let pgn = to_pgn(&game, &PgnTags::new(&PlayerNames::default(), "2024.05.01"));
+
+        assert!(pgn.contains("[Result \"0-1\"]\n"));
+        assert!(pgn.ends_with("\n1. f3 e5 2. g4 Qh4# 0-1\n"));
+    }
+
+    #[test]
+    fn position_set_up_from_a_fen_is_kept() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+        play(&mut game, &["e8d7", "e2e4"]);
+
+        let pgn = to_pgn(&game, &PgnTags::new(&PlayerNames::default(), "2024.05.01"));
+
+        assert!(pgn.contains("[SetUp \"1\"]\n"));
+        assert!(pgn.contains(&format!("[FEN \"{fen}\"]\n")));
+        assert!(pgn.ends_with("\n1... Kd7 2. e4 *\n"));
+    }
+
+    #[test]
+    fn bot_goes_by_the_engine_name() {
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(RandomBot::new(Rng::from_seed(1)))),
+            ..Default::default()
+        };
+        app.player_names.white = Some("Alice".to_string());
+
+        assert_eq!(app.players(), named("Alice", "Random bot"));
+
+        // A configured name wins over the engine name
+        app.player_names.black = Some("Stockfish".to_string());
+        assert_eq!(app.players(), named("Alice", "Stockfish"));
+    }
+
+    const TWO_GAMES: &str = r#"[Event "Casual game"]
+[Site "chess-tui"]
+[White "Alice"]
+[Black "Bob"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+
+[Event "Club championship"]
+[White "Carol"]
+[Black "Dave"]
+[Result "*"]
+
+1. e4 {the king's pawn} e5 2. Nf3 (2. f4 exf4) Nc6 $1
+3. Bb5! a6 *
+"#;
+
+    #[test]
+    fn two_games_give_two_move_lists() {
+        let games = parse_games(TWO_GAMES);
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].moves, ["f3", "e5", "g4", "Qh4#"]);
+        assert_eq!(games[1].moves, ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+        assert_eq!(games[1].header("Event"), Some("Club championship"));
+        assert_eq!(games[0].title(), "Alice vs Bob (0-1)");
+    }
+
+    #[test]
+    fn games_are_split_on_the_event_header() {
+        let games = split_games(TWO_GAMES);
+
+        assert_eq!(games.len(), 2);
+        assert!(games[0].starts_with("[Event \"Casual game\"]"));
+        assert!(games[1].starts_with("[Event \"Club championship\"]"));
+    }
+
+    #[test]
+    fn malformed_games_are_skipped() {
+        let pgn = format!(
+            "{TWO_GAMES}\n[Event \"Broken\"]\n\n1. e4 e5 2. Ke3 *\n\n[Event \"Torn\n\n1. d4 *\n"
+        );
+
+        let games = parse_games(&pgn);
+        assert_eq!(games.len(), 2);
+        assert!(parse_pgn("[Event \"Broken\"]\n\n1. e4 e5 2. Ke3 *").is_err());
+    }
+
+    #[test]
+    fn exported_game_reads_back() {
+        let mut game = Game::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+        play(&mut game, &["e8d7", "e2e4"]);
+        let pgn = to_pgn(&game, &PgnTags::new(&named("Alice", "Bob"), "2024.05.01"));
+
+        let read_back = parse_pgn(&pgn).unwrap().to_game().unwrap();
+
+        assert_eq!(read_back.game_board.uci_moves(), ["e8d7", "e2e4"]);
+        assert_eq!(read_back.to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn several_games_open_the_list() {
+        let mut app = App::default();
+
+        app.open_pgn_games(parse_games(TWO_GAMES)).unwrap();
+        assert_eq!(app.current_popup, Some(Popups::GameList));
+
+        app.game_list_down();
+        app.game_list_down();
+        app.select_pgn_game();
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.review_index, Some(0));
+        assert_eq!(app.game.game_board.move_history.len(), 6);
+        assert_eq!(app.players(), named("Carol", "Dave"));
+    }
+
+    #[test]
+    fn single_game_opens_right_away() {
+        let mut app = App::default();
+        let mut games = parse_games(TWO_GAMES);
+        games.truncate(1);
+
+        app.open_pgn_games(games).unwrap();
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(
+            app.game.game_board.uci_moves(),
+            ["f2f3", "e7e5", "g2g4", "d8h4"]
+        );
+        assert!(app.open_pgn_games(vec![]).is_err());
+    }
+
+    #[test]
+    fn comments_go_to_the_position_they_follow() {
+        let pgn = "{Opening study} 1. e4 {the king's pawn} e5 2. Nf3 (2. f4 {gambit}) {develops} {a knight} *";
+
+        let game = parse_pgn(pgn).unwrap();
+
+        assert_eq!(game.moves, ["e4", "e5", "Nf3"]);
+        assert_eq!(game.comment(0), Some("Opening study"));
+        assert_eq!(game.comment(1), Some("the king's pawn"));
+        assert_eq!(game.comment(2), None);
+        assert_eq!(game.comment(3), Some("develops a knight"));
+    }
+
+    #[test]
+    fn replayed_comment_follows_the_reviewed_position() {
+        let mut app = App::default();
+        let mut games = parse_games(TWO_GAMES);
+        games.remove(0);
+
+        app.open_pgn_games(games).unwrap();
+        assert_eq!(app.reviewed_comment(), None);
+
+        app.review_step_forward();
+        assert_eq!(app.reviewed_comment(), Some("the king's pawn"));
+        app.review_step_forward();
+        assert_eq!(app.reviewed_comment(), None);
+    }
+
+    #[test]
+    fn home_menu_asks_for_the_pgn_file() {
+        let mut app = App {
+            menu_cursor: 3,
+            ..Default::default()
+        };
+
+        app.menu_select();
+        assert_eq!(app.current_popup, Some(Popups::PgnInput));
+
+        app.game.ui.prompt.input = "/nonexistent/chess-tui-games.pgn".to_string();
+        app.submit_pgn_input();
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.current_page, Pages::Home);
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn local_player_is_named_after_the_config() {
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::Black),
+            bot: Some(Box::new(RandomBot::new(Rng::from_seed(1)))),
+            ..Default::default()
+        };
+        let config: Value = "player_name = \"Carol\"".parse().unwrap();
+        app.apply_config(&config);
+
+        press(&mut app, KeyCode::Char('p'));
+
+        assert_eq!(app.current_popup, Some(Popups::PgnTags));
+        assert_eq!(app.pgn_tags.white, "Random bot");
+        assert_eq!(app.pgn_tags.black, "Carol");
+        assert_eq!(app.pgn_tags.event, "Casual game");
+    }
+
+    #[test]
+    fn edited_headers_are_exported() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.open_pgn_tags();
+
+        for c in "Dan".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        // Black, then the event
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Tab);
+        for _ in 0.."Casual game".len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        for c in "Club night".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Backspace);
+
+        let pgn = to_pgn(&app.game, &app.pgn_tags);
+        assert!(pgn.contains("[White \"Dan\"]\n"));
+        assert!(pgn.contains("[Black \"?\"]\n"));
+        assert!(pgn.contains("[Event \"Club night\"]\n"));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.current_popup, None);
+    }
+}
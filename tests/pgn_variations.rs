@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::pgn::{parse_pgn, to_annotated_pgn, PgnTags, PlayerNames};
+    use ratatui::crossterm::event::KeyCode;
+
+    const ANNOTATED: &str = "[Event \"Study\"]\n\n\
+        1. e4 e5 ({The Sicilian} 1... c5 2. Nf3 (2. c3 {Alapin}) 2... d6) (1... e6 {French}) \
+        2. Nf3 {develops} Nc6 *";
+
+    fn replay(pgn: &str) -> App {
+        let mut app = App::default();
+        app.open_pgn_games(vec![parse_pgn(pgn).unwrap()]).unwrap();
+        app
+    }
+
+    #[test]
+    fn sidelines_go_with_the_move_they_replace() {
+        let game = parse_pgn(ANNOTATED).unwrap();
+
+        assert_eq!(game.moves, ["e4", "e5", "Nf3", "Nc6"]);
+        assert_eq!(game.comment(3), Some("develops"));
+        assert_eq!(game.variations.len(), 2);
+        let sicilian = &game.variations[0];
+        assert_eq!(sicilian.branch, 1);
+        assert_eq!(sicilian.line.moves, ["c5", "Nf3", "d6"]);
+        assert_eq!(sicilian.line.comments[0].as_deref(), Some("The Sicilian"));
+        // Nested in the Sicilian, in place of its second move
+        assert_eq!(sicilian.line.variations[0].branch, 1);
+        assert_eq!(sicilian.line.variations[0].line.moves, ["c3"]);
+        assert_eq!(
+            game.variations[1].line.comments[1].as_deref(),
+            Some("French")
+        );
+    }
+
+    #[test]
+    fn illegal_sideline_is_left_out() {
+        let game = parse_pgn("1. e4 e5 (1... Ke3) (1... d5) 2. Nf3 *").unwrap();
+
+        assert_eq!(game.variations.len(), 1);
+        assert_eq!(game.variations[0].line.moves, ["d5"]);
+        assert!(parse_pgn("1. e4 (1. e5) e5 *")
+            .unwrap()
+            .variations
+            .is_empty());
+    }
+
+    #[test]
+    fn stray_parenthesis_does_not_end_the_game() {
+        let game = parse_pgn("1. e4 e5 ) 2. Nf3 *").unwrap();
+
+        assert_eq!(game.moves, ["e4", "e5", "Nf3"]);
+    }
+
+    #[test]
+    fn sidelines_are_entered_and_left_during_the_review() {
+        let mut app = replay(ANNOTATED);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.reviewed_sidelines().len(), 2);
+
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.review_index, Some(2));
+        assert_eq!(
+            app.game.game_board.uci_moves(),
+            ["e2e4", "c7c5", "g1f3", "d7d6"]
+        );
+        assert_eq!(app.reviewed_comment(), Some("The Sicilian"));
+
+        // The next sideline of the same move
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e6"]);
+        assert_eq!(app.reviewed_comment(), Some("French"));
+        assert_eq!(app.parent_lines.len(), 1);
+
+        press(&mut app, KeyCode::Char('['));
+        assert!(app.parent_lines.is_empty());
+        assert_eq!(app.review_index, Some(2));
+        assert_eq!(app.game.game_board.uci_moves().len(), 4);
+    }
+
+    #[test]
+    fn nested_sideline_starts_from_its_own_line() {
+        let mut app = replay(ANNOTATED);
+        app.review_index = Some(2);
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.reviewed_sidelines().len(), 1);
+
+        press(&mut app, KeyCode::Char(']'));
+
+        assert_eq!(app.parent_lines.len(), 2);
+        assert_eq!(app.review_index, Some(3));
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "c7c5", "c2c3"]);
+        assert_eq!(app.reviewed_comment(), Some("Alapin"));
+    }
+
+    #[test]
+    fn leaving_the_review_goes_back_to_the_main_line() {
+        let mut app = replay(ANNOTATED);
+        app.review_index = Some(2);
+        press(&mut app, KeyCode::Char(']'));
+
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.review_index, None);
+        assert!(app.parent_lines.is_empty());
+        assert_eq!(
+            app.game.game_board.uci_moves(),
+            ["e2e4", "e7e5", "g1f3", "b8c6"]
+        );
+    }
+
+    #[test]
+    fn annotations_are_written_back() {
+        let game = parse_pgn(ANNOTATED).unwrap();
+        let tags = PgnTags::new(&PlayerNames::default(), "2024.05.01");
+
+        let pgn = to_annotated_pgn(
+            &game.to_game().unwrap(),
+            &tags,
+            &game.comments,
+            &game.variations,
+        );
+
+        // Long movetext goes on several lines
+        let movetext = pgn.split("\n\n").nth(1).unwrap().replace('\n', " ");
+        assert_eq!(
+            movetext.trim_end(),
+            "1. e4 e5 ({The Sicilian} 1... c5 2. Nf3 (2. c3 {Alapin}) 2... d6) (1... e6 {French}) \
+             2. Nf3 {develops} 2... Nc6 *"
+        );
+        let read_back = parse_pgn(&pgn).unwrap();
+        assert_eq!(read_back.moves, game.moves);
+        assert_eq!(read_back.comments, game.comments);
+        assert_eq!(read_back.variations, game.variations);
+    }
+}
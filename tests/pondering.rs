@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::common::write_fake_engine;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::uci::{parse_bestmove, parse_ponder, ponder_command, UciEngine};
+    use chess_tui::engine::SearchConstraints;
+    use chess_tui::event::Event;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::PieceColor;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    // An engine expecting 2. Nf3 after 1. e4 e5, and answering it with Nc6 once it is played
+    // Stopped while pondering, it gives the move of the position it thought about
+    const PONDERING_ENGINE: &str = r#"#!/bin/sh
+answer="bestmove e7e5 ponder g1f3"
+while read -r command; do
+  case "$command" in
+    uci) echo "id name Ponderer"; echo "uciok" ;;
+    isready) echo "readyok" ;;
+    *" d3 "*) answer="bestmove e5d4" ;;
+    position*) answer="bestmove e7e5 ponder g1f3" ;;
+    "go ponder"*) ;;
+    go*) echo "$answer" ;;
+    ponderhit) echo "info depth 5 score cp 10 pv b8c6"; echo "bestmove b8c6 ponder f1b5" ;;
+    stop) echo "bestmove b8c6" ;;
+    quit) exit 0 ;;
+  esac
+done
+"#;
+
+    fn pondering_engine() -> &'static str {
+        write_fake_engine("pondering", PONDERING_ENGINE)
+    }
+
+    fn bot_app(enable_pondering: bool) -> (App, Receiver<Event>) {
+        let (sender, receiver) = mpsc::channel();
+        let app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(UciEngine::new(pondering_engine()).unwrap())),
+            event_sender: Some(sender),
+            enable_pondering,
+            ..Default::default()
+        };
+        (app, receiver)
+    }
+
+    // Play the move of the player and handle the events until the bot answered it
+    fn play(app: &mut App, receiver: &Receiver<Event>, uci: &str) {
+        app.game.play_uci_move(uci).unwrap();
+        let move_count = app.game.game_board.uci_moves().len() + 1;
+        app.tick();
+        while app.game.game_board.uci_moves().len() < move_count {
+            handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), app).unwrap();
+        }
+    }
+
+    #[test]
+    fn bestmove_line_gives_the_expected_answer() {
+        let line = "bestmove e2e4 ponder e7e5";
+        assert_eq!(parse_bestmove(line), Some("e2e4".to_string()));
+        assert_eq!(parse_ponder(line), Some("e7e5".to_string()));
+        assert_eq!(parse_ponder("bestmove e2e4"), None);
+        assert_eq!(parse_ponder("info depth 3 pv e2e4 e7e5"), None);
+    }
+
+    #[test]
+    fn ponder_command_keeps_the_constraints() {
+        assert_eq!(
+            ponder_command(SearchConstraints::default()),
+            "go ponder movetime 1000"
+        );
+        assert_eq!(
+            ponder_command(SearchConstraints {
+                depth: Some(8),
+                movetime: None,
+            }),
+            "go ponder depth 8"
+        );
+    }
+
+    #[test]
+    fn expected_move_lets_the_engine_go_on_with_its_search() {
+        let (mut app, receiver) = bot_app(true);
+
+        play(&mut app, &receiver, "e2e4");
+        assert!(app.ponder.is_some());
+
+        play(&mut app, &receiver, "g1f3");
+        assert_eq!(
+            app.game.game_board.uci_moves(),
+            ["e2e4", "e7e5", "g1f3", "b8c6"]
+        );
+        assert!(app.engine_search.is_none());
+        // It thinks about the next expected move right away
+        assert!(app.ponder.is_some());
+    }
+
+    #[test]
+    fn move_found_for_another_position_is_never_played() {
+        let (mut app, receiver) = bot_app(true);
+        play(&mut app, &receiver, "e2e4");
+
+        // The engine gives b8c6, legal here but found for the position after g1f3
+        play(&mut app, &receiver, "d2d4");
+
+        assert_eq!(
+            app.game.game_board.uci_moves(),
+            ["e2e4", "e7e5", "d2d4", "e5d4"]
+        );
+        assert!(app.engine_search.is_none());
+        assert!(!app.discard_engine_move);
+    }
+
+    #[test]
+    fn engine_waits_for_the_player_without_pondering() {
+        let (mut app, receiver) = bot_app(false);
+
+        play(&mut app, &receiver, "e2e4");
+
+        assert!(app.ponder.is_none());
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+    }
+
+    #[test]
+    fn leaving_the_game_drops_the_search_on_the_time_of_the_player() {
+        let (mut app, receiver) = bot_app(true);
+        play(&mut app, &receiver, "e2e4");
+
+        app.restart();
+        assert!(app.ponder.is_none());
+        handle_event(receiver.recv_timeout(TIMEOUT).unwrap(), &mut app).unwrap();
+
+        assert!(app.game.game_board.uci_moves().is_empty());
+    }
+}
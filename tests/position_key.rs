@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::game_logic::game_board::PositionKey;
+    use chess_tui::pieces::PieceColor;
+
+    fn play(game: &mut Game, moves: &[&str]) {
+        for san in moves {
+            game.play_algebraic_move(san).unwrap();
+        }
+    }
+
+    fn key_after(moves: &[&str]) -> PositionKey {
+        let mut game = Game::default();
+        play(&mut game, moves);
+        game.game_board.position_key()
+    }
+
+    #[test]
+    fn move_orders_reaching_the_same_position_share_a_key() {
+        assert_eq!(
+            key_after(&["Nf3", "Nf6", "Nc3", "Nc6"]),
+            key_after(&["Nc3", "Nc6", "Nf3", "Nf6"])
+        );
+        // Back to the start, seen from white whatever the orientation of the stored board
+        assert_eq!(
+            key_after(&["Nf3", "Nf6", "Ng1", "Ng8"]),
+            Game::default().game_board.position_key()
+        );
+    }
+
+    #[test]
+    fn side_to_move_is_part_of_the_key() {
+        let key = key_after(&["Nf3"]);
+        assert_eq!(key.side_to_move, PieceColor::Black);
+        assert_ne!(key_after(&["e3", "e6", "e4"]), key_after(&["e4", "e6"]));
+        assert_eq!(
+            Game::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1")
+                .unwrap()
+                .game_board
+                .position_key()
+                .side_to_move,
+            PieceColor::Black
+        );
+    }
+
+    #[test]
+    fn lost_castles_change_the_key() {
+        let with_castles = key_after(&["e4", "e5"]);
+        let without_castles = key_after(&["e4", "e5", "Ke2", "Ke7", "Ke1", "Ke8"]);
+
+        assert_eq!(with_castles.placement, without_castles.placement);
+        assert_ne!(with_castles, without_castles);
+        assert_eq!(without_castles.castling_rights.to_fen(), "-");
+        assert_eq!(with_castles.castling_rights.to_fen(), "KQkq");
+    }
+
+    #[test]
+    fn en_passant_only_counts_when_a_pawn_can_take() {
+        assert_eq!(key_after(&["e4"]).en_passant_file, None);
+        assert_eq!(
+            key_after(&["e4", "d5", "e5", "f5"]).en_passant_file,
+            Some(5)
+        );
+
+        let game = Game::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(game.game_board.position_key().en_passant_file, Some(4));
+        let game = Game::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(game.game_board.position_key().en_passant_file, None);
+    }
+
+    #[test]
+    fn repetition_needs_the_same_castles() {
+        let mut game = Game::default();
+        play(&mut game, &["e4", "e5", "Ke2", "Ke7", "Ke1", "Ke8"]);
+        // The king walk repeats the board after 1...e5, but the castles are gone
+        play(&mut game, &["Ke2", "Ke7", "Ke1", "Ke8"]);
+        assert_eq!(game.game_state, GameState::Playing);
+
+        // The king walk itself is seen a third time
+        play(&mut game, &["Ke2", "Ke7"]);
+        assert_eq!(game.game_state, GameState::Draw);
+    }
+}
@@ -0,0 +1,68 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::event::Event;
+    use chess_tui::handler::handle_event;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+
+    const FEN: &str = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+
+    #[test]
+    fn pasted_fen_replaces_the_game() {
+        let mut app = App::default();
+        app.game.play_uci_move("d2d4").unwrap();
+
+        press(&mut app, KeyCode::Char('F'));
+        assert_eq!(app.current_popup, Some(Popups::PositionSetup));
+        handle_event(Event::Paste(FEN.to_string()), &mut app).unwrap();
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.current_page, Pages::Solo);
+        // The move number starts over with the set up position
+        assert!(app.game.to_fen().starts_with(FEN.trim_end_matches(" 3")));
+        assert!(app.game.game_board.move_history.is_empty());
+        assert!(!app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn paste_out_of_a_popup_opens_the_prompt() {
+        let mut app = App::default();
+
+        app.paste_text(&format!("{FEN}\n"));
+
+        assert_eq!(app.current_popup, Some(Popups::PositionSetup));
+        // A whole FEN fits in the prompt, the line break is left out
+        assert_eq!(app.game.ui.prompt.input, FEN);
+    }
+
+    #[test]
+    fn wrong_fen_keeps_the_prompt_open() {
+        let mut app = App::default();
+        app.open_position_setup();
+        app.paste_text("8/8/8 w - - 0 1");
+
+        app.submit_position_setup();
+
+        assert_eq!(app.current_popup, Some(Popups::PositionSetup));
+        assert_eq!(app.game.ui.prompt.input, "8/8/8 w - - 0 1");
+        assert!(app.toast.as_ref().unwrap().is_error);
+        assert_eq!(app.game.player_turn, PieceColor::White);
+    }
+
+    #[test]
+    fn paste_goes_to_the_open_prompt() {
+        let mut app = App::default();
+        app.open_pgn_input();
+
+        app.paste_text("my game.pgn");
+
+        assert_eq!(app.current_popup, Some(Popups::PgnInput));
+        assert_eq!(app.game.ui.prompt.input, "my game.pgn");
+    }
+}
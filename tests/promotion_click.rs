@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::handler::handle_mouse_events;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use ratatui::crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    // The pawn on a7 reaches a8, the black knight on f1 ends up under the bishop of the popup
+    // once the board turned around for black
+    fn promoting_app() -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            game: Game::from_fen("4k3/P7/8/8/8/8/8/4Kn2 w - - 0 1").unwrap(),
+            ..Default::default()
+        };
+        assert!(app.game.play_move(Coord::new(1, 0), Coord::new(0, 0)));
+        assert_eq!(app.game.game_state, GameState::Promotion);
+        // Every choice of the popup is a single terminal cell on the first row of the board
+        app.game.ui.top_x = 0;
+        app.game.ui.top_y = 0;
+        app.game.ui.width = 1;
+        app.game.ui.height = 1;
+        app
+    }
+
+    fn click(app: &mut App, column: u16, row: u16) {
+        let mouse_event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(mouse_event, app).unwrap();
+    }
+
+    #[test]
+    fn promotion_click_promotes_to_the_chosen_piece() {
+        let mut app = promoting_app();
+
+        click(&mut app, 2, 0);
+
+        assert_eq!(app.game.game_state, GameState::Playing);
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        // The board turned around, a8 is now in the bottom right corner
+        assert_eq!(
+            app.game.game_board.board[7][7],
+            Some((PieceType::Bishop, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn promotion_click_does_not_also_click_the_board() {
+        let mut app = promoting_app();
+        let mut expected = app.game.clone();
+        expected.ui.promotion_cursor = 2;
+        expected.promote_piece();
+
+        click(&mut app, 2, 0);
+
+        assert_eq!(app.game.game_board.board, expected.game_board.board);
+        assert_eq!(app.game.ui.selected_coordinates, Coord::undefined());
+        assert_eq!(app.game.ui.dragged_from, None);
+    }
+
+    #[test]
+    fn click_left_of_or_above_the_popup_is_ignored() {
+        let mut app = promoting_app();
+        app.game.ui.top_x = 5;
+        app.game.ui.top_y = 5;
+
+        click(&mut app, 2, 7);
+        click(&mut app, 6, 1);
+
+        assert_eq!(app.game.game_state, GameState::Promotion);
+    }
+}
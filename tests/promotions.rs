@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use chess_tui::game_logic::coord::Coord;
-    use chess_tui::game_logic::game::Game;
+    use chess_tui::game_logic::game::{Game, GameState};
     use chess_tui::game_logic::game_board::GameBoard;
     use chess_tui::pieces::{PieceColor, PieceMove, PieceType};
     #[test]
@@ -294,4 +294,86 @@ mod tests {
         game.player_turn = PieceColor::White;
         assert!(game.game_board.is_draw(game.player_turn));
     }
+
+    #[test]
+    fn promoting_color_white() {
+        let mut game = Game::from_fen("7k/4P3/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert_eq!(game.promoting_color(), None);
+
+        game.ui.cursor_coordinates = Coord::new(1, 4);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(0, 4);
+        game.handle_cell_click();
+
+        assert_eq!(game.game_state, GameState::Promotion);
+        assert_eq!(game.promoting_color(), Some(PieceColor::White));
+    }
+
+    #[test]
+    fn promoting_color_black() {
+        let mut game = Game::from_fen("7k/8/8/8/8/8/4p3/K7 b - - 0 1").unwrap();
+
+        // The e2 pawn seen from black
+        game.ui.cursor_coordinates = Coord::new(1, 3);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(0, 3);
+        game.handle_cell_click();
+
+        assert_eq!(game.game_state, GameState::Promotion);
+        assert_eq!(game.promoting_color(), Some(PieceColor::Black));
+    }
+
+    // The g8 piece covers a2, the last free cell of the black king on a1
+    const STALEMATE_TRICK: &str = "8/6P1/8/8/8/8/2K5/k7 w - - 0 1";
+
+    #[test]
+    fn promoted_queen_stalemates_the_opponent() {
+        let mut game = Game::from_fen(STALEMATE_TRICK).unwrap();
+
+        game.play_uci_move("g7g8q").unwrap();
+
+        assert_eq!(game.game_state, GameState::Draw);
+        assert_eq!(game.player_turn, PieceColor::Black);
+        assert_eq!(game.board_orientation(), PieceColor::Black);
+    }
+
+    #[test]
+    fn underpromotion_avoids_the_stalemate() {
+        for (uci_move, game_state) in [
+            ("g7g8b", GameState::Draw),
+            ("g7g8r", GameState::Playing),
+            ("g7g8n", GameState::Playing),
+        ] {
+            let mut game = Game::from_fen(STALEMATE_TRICK).unwrap();
+            game.play_uci_move(uci_move).unwrap();
+            assert_eq!(game.game_state, game_state, "{uci_move}");
+        }
+    }
+
+    #[test]
+    fn stalemate_chosen_in_the_promotion_popup() {
+        let mut game = Game::from_fen(STALEMATE_TRICK).unwrap();
+
+        game.ui.cursor_coordinates = Coord::new(1, 6);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(0, 6);
+        game.handle_cell_click();
+        assert_eq!(game.game_state, GameState::Promotion);
+
+        // The bishop
+        game.ui.promotion_cursor = 2;
+        game.handle_cell_click();
+
+        assert_eq!(game.game_state, GameState::Draw);
+    }
+
+    #[test]
+    fn promoted_piece_mates_the_opponent() {
+        let mut game = Game::from_fen("k7/6P1/1K6/8/8/8/8/8 w - - 0 1").unwrap();
+
+        game.play_uci_move("g7g8r").unwrap();
+
+        assert_eq!(game.game_state, GameState::Checkmate);
+        assert_eq!(game.result(), Some("1-0"));
+    }
 }
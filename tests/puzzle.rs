@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::puzzle::PuzzleSet;
+    use chess_tui::pieces::{PieceColor, PieceType};
+
+    // A back rank mate, then a knight fork
+    const PUZZLES: &str = "\
+# Mate in one
+6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1 a1a8
+
+4k3/8/8/8/8/2n5/8/R3K3 b - - 0 1 c3d1
+";
+
+    fn puzzle_app() -> App {
+        let mut app = App::default();
+        app.start_puzzles(PuzzleSet::parse(PUZZLES).unwrap())
+            .unwrap();
+        app
+    }
+
+    // The cells are given in the orientation the board is stored in
+    fn play(app: &mut App, from: Coord, to: Coord) {
+        app.click_board_cell(from);
+        app.click_board_cell(to);
+    }
+
+    #[test]
+    fn puzzles_are_read_with_their_best_move() {
+        let puzzles = PuzzleSet::parse(PUZZLES).unwrap();
+
+        assert_eq!(puzzles.puzzles.len(), 2);
+        assert_eq!(puzzles.puzzles[0].best_move, "a1a8");
+        assert!(puzzles.puzzles[1].fen.starts_with("4k3/"));
+        assert!(matches!(
+            PuzzleSet::parse("not a fen a1a8"),
+            Err(ChessError::InvalidPuzzle(_))
+        ));
+    }
+
+    #[test]
+    fn correct_move_moves_on_to_the_next_puzzle() {
+        let mut app = puzzle_app();
+
+        play(&mut app, Coord::new(7, 0), Coord::new(0, 0));
+
+        let puzzles = app.puzzles.as_ref().unwrap();
+        assert_eq!(puzzles.current, 1);
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert!(app.game.game_board.move_history.is_empty());
+        assert!(!app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn incorrect_move_is_rejected_and_the_position_restored() {
+        let mut app = puzzle_app();
+
+        play(&mut app, Coord::new(7, 0), Coord::new(1, 0));
+
+        assert_eq!(app.puzzles.as_ref().unwrap().current, 0);
+        assert!(app.game.game_board.move_history.is_empty());
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(
+            app.game.game_board.board[7][0],
+            Some((PieceType::Rook, PieceColor::White))
+        );
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn solving_the_last_puzzle_ends_the_puzzles() {
+        let mut app = puzzle_app();
+        play(&mut app, Coord::new(7, 0), Coord::new(0, 0));
+
+        // Black plays from the bottom, c3 is stored on the third row from the top
+        let knight = Coord::new(2, 5);
+        assert_eq!(
+            app.game.game_board.board[knight.row as usize][knight.col as usize],
+            Some((PieceType::Knight, PieceColor::Black))
+        );
+        play(&mut app, knight, Coord::new(0, 4));
+
+        assert!(app.puzzles.is_none());
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::game_logic::chess960::{chess960_back_rank, chess960_fen};
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::pieces::{PieceColor, PieceMove, PieceType};
+    use chess_tui::rng::Rng;
+
+    // Let the bot play both sides from the standard starting position
+    fn bot_moves(seed: u64, count: usize) -> Vec<PieceMove> {
+        let mut bot = RandomBot::new(Rng::from_seed(seed));
+        let mut game = Game::default();
+        let mut moves = vec![];
+        for _ in 0..count {
+            let Some(piece_move) = bot.choose_move(&game) else {
+                break;
+            };
+            assert!(game.play_move(piece_move.from, piece_move.to));
+            if game.game_state == GameState::Promotion {
+                game.promote_piece();
+            }
+            moves.push(piece_move);
+        }
+        moves
+    }
+
+    #[test]
+    fn same_seed_same_bot_moves() {
+        assert_eq!(bot_moves(42, 20), bot_moves(42, 20));
+        assert_ne!(bot_moves(42, 20), bot_moves(43, 20));
+    }
+
+    #[test]
+    fn same_seed_same_chess960_position() {
+        let fen = chess960_fen(&mut Rng::from_seed(7));
+        assert_eq!(fen, chess960_fen(&mut Rng::from_seed(7)));
+        assert!(Game::from_fen(&fen).is_ok());
+    }
+
+    #[test]
+    fn chess960_back_ranks_are_valid() {
+        let mut rng = Rng::from_seed(0);
+        for _ in 0..200 {
+            let back_rank = chess960_back_rank(&mut rng);
+            let position = |piece_type: PieceType| -> Vec<usize> {
+                (0..8).filter(|&i| back_rank[i] == piece_type).collect()
+            };
+
+            let bishops = position(PieceType::Bishop);
+            assert_eq!(bishops.len(), 2);
+            assert_ne!(bishops[0] % 2, bishops[1] % 2);
+            assert_eq!(position(PieceType::Knight).len(), 2);
+            assert_eq!(position(PieceType::Queen).len(), 1);
+
+            let (rooks, king) = (position(PieceType::Rook), position(PieceType::King));
+            assert_eq!(rooks.len(), 2);
+            assert!(rooks[0] < king[0] && king[0] < rooks[1]);
+        }
+    }
+
+    #[test]
+    fn bot_answers_the_player() {
+        let mut app = App {
+            seed: Some(42),
+            menu_cursor: 1,
+            ..Default::default()
+        };
+        app.menu_select();
+        assert_eq!(app.current_page, Pages::Bot);
+        assert_eq!(app.current_popup, Some(Popups::ColorSelection));
+
+        // Play black, the bot opens the game
+        app.menu_cursor = 1;
+        app.color_selection();
+        app.difficulty_selection();
+        assert_eq!(app.selected_color, Some(PieceColor::Black));
+        assert!(app.is_bot_turn());
+        app.tick();
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.game.game_board.move_history.len(), 1);
+
+        // The player's pieces are at the bottom
+        assert_eq!(app.game.board_orientation(), PieceColor::Black);
+        assert!(!app.is_board_flipped());
+
+        // The player cannot move during the bot's turn
+        app.game.play_uci_move("e7e5").unwrap();
+        app.game.ui.cursor_coordinates = Coord::new(6, 3);
+        app.handle_cell_click();
+        assert!(!app.game.ui.is_cell_selected());
+
+        app.tick();
+        assert_eq!(app.game.game_board.move_history.len(), 3);
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::network::bot_server::BotServer;
+    use chess_tui::network::handshake::{
+        resume_game, wait_for_game_start, DEFAULT_CONNECTION_TIMEOUT,
+    };
+    use chess_tui::network::protocol::Message;
+    use chess_tui::pgn::PlayerNames;
+    use chess_tui::pieces::PieceColor;
+    use chess_tui::rng::Rng;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{SocketAddr, TcpListener};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn receive(reader: &mut impl BufRead) -> Message {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        Message::parse(&line).unwrap()
+    }
+
+    // A bot playing black that takes the player back once after the connection dropped
+    fn start_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let bot = RandomBot::new(Rng::from_seed(1));
+            let mut server = BotServer::new(bot, PieceColor::Black);
+            server.serve(listener.accept().unwrap().0).unwrap();
+            while !server.game.game_state.is_over() {
+                let (stream, _) = listener.accept().unwrap();
+                if server.resume(stream).unwrap() {
+                    break;
+                }
+            }
+        });
+        address
+    }
+
+    // Tick the app like the main loop does until `done` holds
+    fn tick_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        let deadline = Instant::now() + TIMEOUT;
+        while !done(app) {
+            assert!(Instant::now() < deadline, "the app never got there");
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn session_messages_go_through_a_line() {
+        for message in [
+            Message::Session("1f2e".to_string()),
+            Message::Resume("1f2e".to_string()),
+            Message::History(vec!["e2e4".to_string(), "e7e5".to_string()]),
+            Message::History(vec![]),
+        ] {
+            assert_eq!(Message::parse(&message.to_string()), Some(message));
+        }
+        assert_eq!(Message::parse("resume"), None);
+    }
+
+    #[test]
+    fn bot_takes_the_player_back_with_the_moves_played() {
+        let address = start_server();
+        let (mut stream, _, _) =
+            wait_for_game_start(address, DEFAULT_CONNECTION_TIMEOUT, &PlayerNames::default())
+                .unwrap();
+        writeln!(stream, "{}", Message::Session("token".to_string())).unwrap();
+        writeln!(stream, "{}", Message::Move("e2e4".to_string())).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let Message::Move(bot_move) = receive(&mut reader) else {
+            panic!("the bot should answer with a move");
+        };
+        drop((stream, reader));
+
+        let (mut stream, moves) = resume_game(address, TIMEOUT, "token").unwrap();
+
+        assert_eq!(moves, ["e2e4".to_string(), bot_move]);
+        // The game goes on where it stopped
+        writeln!(stream, "{}", Message::Move("d2d4".to_string())).unwrap();
+        assert!(matches!(
+            receive(&mut BufReader::new(stream)),
+            Message::Move(_)
+        ));
+    }
+
+    #[test]
+    fn unknown_session_is_refused() {
+        let address = start_server();
+        let (mut stream, _, _) =
+            wait_for_game_start(address, DEFAULT_CONNECTION_TIMEOUT, &PlayerNames::default())
+                .unwrap();
+        writeln!(stream, "{}", Message::Session("token".to_string())).unwrap();
+        // The session is known once the bot answered the next line
+        writeln!(stream, "{}", Message::Move("e2e4".to_string())).unwrap();
+        receive(&mut BufReader::new(stream.try_clone().unwrap()));
+        drop(stream);
+
+        assert!(resume_game(address, TIMEOUT, "someone else").is_err());
+    }
+
+    // A host dropping the connection after the first move of the player, then sending `history`
+    // to the player coming back, the host is gone for good without it
+    fn start_flaky_host(history: Option<&'static str>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            writeln!(stream, "start white\nname Host").unwrap();
+            let mut reader = BufReader::new(stream);
+            assert!(matches!(receive(&mut reader), Message::Name(_)));
+            let Message::Session(token) = receive(&mut reader) else {
+                panic!("the player should give its session token");
+            };
+            assert_eq!(receive(&mut reader), Message::Move("e2e4".to_string()));
+            drop(reader);
+
+            let Some(history) = history else {
+                return;
+            };
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            assert_eq!(receive(&mut reader), Message::Resume(token));
+            writeln!(stream, "start white\nname Host\n{history}").unwrap();
+            // Keep the connection open while the app catches up
+            thread::sleep(Duration::from_millis(500));
+        });
+        address
+    }
+
+    #[test]
+    fn app_keeps_the_game_and_catches_up_after_reconnecting() {
+        let mut app = App::default();
+        app.join_game(start_flaky_host(Some("history e2e4 e7e5")));
+        tick_until(&mut app, |app| app.opponent.is_some());
+
+        app.play_algebraic_move("e4").unwrap();
+        tick_until(&mut app, |app| app.game.game_board.uci_moves().len() == 2);
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4", "e7e5"]);
+        assert!(!app.opponent.unwrap().is_reconnecting());
+        assert_eq!(app.toast.unwrap().message, "Reconnected, the game goes on");
+    }
+
+    #[test]
+    fn giving_up_keeps_the_game() {
+        let mut app = App::default();
+        app.join_game(start_flaky_host(None));
+        tick_until(&mut app, |app| app.opponent.is_some());
+        app.play_algebraic_move("e4").unwrap();
+        tick_until(&mut app, |app| {
+            app.opponent.as_ref().is_some_and(|o| o.is_reconnecting())
+        });
+
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+
+        app.give_up_reconnecting();
+
+        assert!(app.opponent.is_none());
+        assert_eq!(app.game.game_board.uci_moves(), ["e2e4"]);
+    }
+}
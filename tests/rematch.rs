@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chess_tui::app::App;
+    use chess_tui::constants::{DisplayMode, Pages};
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::board::init_board;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game_setup::{GameSetup, TimeControl};
+    use chess_tui::pieces::PieceColor;
+
+    fn blitz() -> TimeControl {
+        TimeControl {
+            initial: Duration::from_secs(300),
+            increment: Duration::from_secs(3),
+        }
+    }
+
+    #[test]
+    fn time_control_parsing() {
+        assert_eq!("5+3".parse::<TimeControl>(), Ok(blitz()));
+        assert_eq!(
+            "10".parse::<TimeControl>(),
+            Ok(TimeControl {
+                initial: Duration::from_secs(600),
+                increment: Duration::ZERO,
+            })
+        );
+        for invalid in ["", "0+2", "five+3", "5+", "5+3+1"] {
+            assert!(
+                matches!(
+                    invalid.parse::<TimeControl>(),
+                    Err(ChessError::InvalidTimeControl(_))
+                ),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rematch_preserves_the_time_control() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            setup: GameSetup {
+                time_control: Some(blitz()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.game.ui.display_mode = DisplayMode::ASCII;
+        app.game.ui.cursor_coordinates = Coord::new(6, 4);
+        app.handle_cell_click();
+        app.game.ui.cursor_coordinates = Coord::new(4, 4);
+        app.handle_cell_click();
+
+        app.rematch();
+
+        assert_eq!(app.game.game_board.board, init_board());
+        assert_eq!(app.game.player_turn, PieceColor::White);
+        assert_eq!(app.current_page, Pages::Solo);
+        assert_eq!(app.setup.time_control, Some(blitz()));
+        assert!(matches!(app.game.ui.display_mode, DisplayMode::ASCII));
+    }
+
+    #[test]
+    fn rematch_swaps_colors_when_configured() {
+        let mut app = App {
+            setup: GameSetup {
+                player_color: Some(PieceColor::White),
+                swap_colors_on_rematch: true,
+                time_control: Some(blitz()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        app.rematch();
+        assert_eq!(app.setup.player_color, Some(PieceColor::Black));
+        assert_eq!(app.selected_color, Some(PieceColor::Black));
+        assert_eq!(app.setup.time_control, Some(blitz()));
+
+        app.rematch();
+        assert_eq!(app.setup.player_color, Some(PieceColor::White));
+    }
+
+    #[test]
+    fn rematch_keeps_colors_by_default() {
+        let setup = GameSetup {
+            player_color: Some(PieceColor::White),
+            ..Default::default()
+        };
+        assert_eq!(setup.rematch(), setup);
+
+        // Nothing to swap when both players share the keyboard
+        let hot_seat = GameSetup {
+            swap_colors_on_rematch: true,
+            ..Default::default()
+        };
+        assert_eq!(hot_seat.rematch().player_color, None);
+    }
+}
@@ -0,0 +1,176 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::network::protocol::Message;
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    fn solo_app() -> App {
+        App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resign_asks_for_confirmation() {
+        let mut app = solo_app();
+
+        press(&mut app, KeyCode::Char('x'));
+
+        assert_eq!(app.current_popup, Some(Popups::ResignConfirmation));
+        assert_eq!(app.game.game_state, GameState::Playing);
+    }
+
+    #[test]
+    fn confirmed_resignation_ends_the_game() {
+        let mut app = solo_app();
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Char('y'));
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::White)
+        );
+        assert_eq!(app.game.result(), Some("0-1"));
+        assert_eq!(app.game.status_message(), "White resigned 0-1");
+    }
+
+    #[test]
+    fn cancelled_resignation_keeps_playing() {
+        for cancel_key in [KeyCode::Char('n'), KeyCode::Esc] {
+            let mut app = solo_app();
+
+            press(&mut app, KeyCode::Char('x'));
+            // Other keys are ignored while the popup is open
+            press(&mut app, KeyCode::Char('d'));
+            assert_eq!(app.current_popup, Some(Popups::ResignConfirmation));
+            press(&mut app, cancel_key);
+
+            assert_eq!(app.current_popup, None);
+            assert_eq!(app.game.game_state, GameState::Playing);
+        }
+    }
+
+    #[test]
+    fn resign_without_confirmation() {
+        let mut app = App {
+            confirm_resign: false,
+            ..solo_app()
+        };
+
+        press(&mut app, KeyCode::Char('x'));
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::White)
+        );
+    }
+
+    #[test]
+    fn player_resigns_during_the_turn_of_the_bot() {
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Char('y'));
+
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::White)
+        );
+        assert_eq!(app.game.result(), Some("0-1"));
+        assert_eq!(app.game.status_message(), "White resigned 0-1");
+    }
+
+    // Join the game of a host sending `greeting`, the lines it receives come out of the receiver
+    fn join_host(greeting: &'static str) -> (App, mpsc::Receiver<Message>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            writeln!(stream, "{greeting}").unwrap();
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else {
+                    return;
+                };
+                if let Some(message) = Message::parse(&line) {
+                    let _ = sender.send(message);
+                }
+            }
+        });
+        let mut app = App::default();
+        app.join_game(address);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.opponent.is_none() && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+        (app, receiver)
+    }
+
+    #[test]
+    fn player_resigns_during_the_turn_of_the_online_opponent() {
+        let (mut app, receiver) = join_host("start black\nname Host");
+        assert!(app.is_opponent_turn());
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Char('y'));
+
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::Black)
+        );
+        assert_eq!(app.game.result(), Some("1-0"));
+        // The host is told, after the name and the session token of the player
+        let told = receiver
+            .iter()
+            .find(|message| !matches!(message, Message::Name(_) | Message::Session(_)));
+        assert_eq!(told, Some(Message::Resign));
+    }
+
+    #[test]
+    fn resignation_of_the_online_opponent_ends_the_game() {
+        let (mut app, _receiver) = join_host("start white\nname Host\nresign");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !app.game.game_state.is_over() && Instant::now() < deadline {
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(
+            app.game.game_state,
+            GameState::Resignation(PieceColor::Black)
+        );
+        assert_eq!(app.toast.unwrap().message, "The opponent resigned");
+    }
+
+    #[test]
+    fn finished_game_cannot_be_resigned() {
+        let mut app = solo_app();
+        app.game.game_state = GameState::Draw;
+
+        press(&mut app, KeyCode::Char('x'));
+
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.game.game_state, GameState::Draw);
+    }
+}
@@ -0,0 +1,154 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::game::{halfmoves_after_move, Game, GameState};
+    use chess_tui::pieces::PieceColor;
+    use ratatui::crossterm::event::KeyCode;
+
+    // A solo game after 1. e4 e5 2. Nf3 Nc6 3. Bb5
+    fn reviewed_app() -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        for uci_move in ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5"] {
+            app.game.play_uci_move(uci_move).unwrap();
+        }
+        app
+    }
+
+    #[test]
+    fn move_numbers_map_to_halfmoves() {
+        assert_eq!(halfmoves_after_move(0, false), 0);
+        assert_eq!(halfmoves_after_move(1, false), 1);
+        assert_eq!(halfmoves_after_move(2, false), 3);
+        assert_eq!(halfmoves_after_move(15, false), 29);
+    }
+
+    #[test]
+    fn move_numbers_map_to_halfmoves_when_black_moved_first() {
+        assert_eq!(halfmoves_after_move(0, true), 0);
+        // 1... e5 has no white move before it
+        assert_eq!(halfmoves_after_move(1, true), 1);
+        assert_eq!(halfmoves_after_move(2, true), 2);
+        assert_eq!(halfmoves_after_move(15, true), 28);
+    }
+
+    #[test]
+    fn history_index_is_clamped_to_the_game() {
+        let game = reviewed_app().game;
+        assert_eq!(game.game_board.board_history.len(), 6);
+
+        assert_eq!(game.history_index_of_move(0), 0);
+        assert_eq!(game.history_index_of_move(2), 3);
+        assert_eq!(game.history_index_of_move(3), 5);
+        assert_eq!(game.history_index_of_move(15), 5);
+    }
+
+    #[test]
+    fn history_index_of_a_game_opened_by_black() {
+        let mut game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        for uci_move in ["e7e5", "g1f3", "b8c6"] {
+            game.play_uci_move(uci_move).unwrap();
+        }
+
+        assert_eq!(game.history_index_of_move(1), 1);
+        assert_eq!(game.history_index_of_move(2), 2);
+        assert_eq!(game.history_index_of_move(3), 3);
+    }
+
+    #[test]
+    fn snapshot_shows_the_game_as_it_was() {
+        let game = reviewed_app().game;
+        let snapshot = game.snapshot(1);
+
+        assert_eq!(snapshot.game_board.board, game.game_board.board_history[1]);
+        assert_eq!(snapshot.game_board.move_history.len(), 1);
+        assert_eq!(snapshot.player_turn, PieceColor::Black);
+        // After 1. e4 the board is stored from white's point of view
+        assert_eq!(snapshot.board_orientation(), PieceColor::White);
+        assert_eq!(game.snapshot(0).player_turn, PieceColor::White);
+        assert_eq!(game.game_board.move_history.len(), 5);
+    }
+
+    #[test]
+    fn typing_a_move_number_jumps_to_it() {
+        let mut app = reviewed_app();
+
+        press(&mut app, KeyCode::Char('v'));
+        assert_eq!(app.review_index, Some(5));
+
+        press(&mut app, KeyCode::Char('2'));
+        assert_eq!(app.review_input, "2");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.review_index, Some(3));
+        assert!(app.review_input.is_empty());
+
+        press(&mut app, KeyCode::Char('9'));
+        press(&mut app, KeyCode::Char('9'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.review_index, Some(5));
+
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Char('0'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.review_index, Some(0));
+    }
+
+    #[test]
+    fn arrows_step_through_the_halfmoves() {
+        let mut app = reviewed_app();
+        press(&mut app, KeyCode::Char('v'));
+
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.review_index, Some(5));
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.review_index, Some(3));
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Left);
+        }
+        assert_eq!(app.review_index, Some(0));
+    }
+
+    #[test]
+    fn leaving_the_review_resumes_the_game() {
+        let mut app = reviewed_app();
+        let board = app.game.game_board.board;
+
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.review_index, None);
+        assert_eq!(app.game.game_board.board, board);
+        assert_eq!(app.game.game_board.move_history.len(), 5);
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+    }
+
+    #[test]
+    fn finished_games_can_be_reviewed() {
+        let mut app = reviewed_app();
+        app.game.game_state = GameState::Resignation(PieceColor::White);
+
+        press(&mut app, KeyCode::Char('v'));
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.review_index, Some(1));
+    }
+
+    #[test]
+    fn no_review_outside_of_a_game() {
+        let mut app = App::default();
+        app.toggle_review();
+        assert_eq!(app.review_index, None);
+    }
+}
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::{Pages, Popups};
+    use chess_tui::error::ChessError;
+    use chess_tui::game_logic::clock::Clock;
+    use chess_tui::game_logic::game::{Game, GameState};
+    use chess_tui::game_logic::game_setup::TimeControl;
+    use chess_tui::pieces::{PieceColor, PieceType};
+    use chess_tui::save::{SavedClock, SavedGame, SAVE_FORMAT_VERSION, STARTING_FEN};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    // A file of the temporary folder holding `content`, unique to the test
+    fn existing_file(name: &str, content: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("chess-tui-{name}-{}.toml", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn v1_save_is_migrated() {
+        let v1 = "version = 1\nmoves = [\"e2e4\", \"e7e5\", \"g1f3\"]\n";
+
+        let saved = SavedGame::from_toml(v1).unwrap();
+        assert_eq!(saved.starting_fen, STARTING_FEN);
+        assert_eq!(saved.moves, vec!["e2e4", "e7e5", "g1f3"]);
+
+        let game = saved.to_game().unwrap();
+        assert_eq!(game.game_board.move_history.len(), 3);
+        assert_eq!(game.player_turn, PieceColor::Black);
+        assert_eq!(game.game_board.uci_moves(), saved.moves);
+    }
+
+    #[test]
+    fn round_trip_keeps_castling_and_promotion() {
+        let mut game = Game::from_fen("2k5/P7/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        for uci_move in ["e1g1", "c8d7", "a7a8q"] {
+            game.play_uci_move(uci_move).unwrap();
+        }
+        assert_eq!(game.game_state, GameState::Playing);
+
+        let content = SavedGame::from_game(&game).to_toml();
+        assert!(content.contains(&format!("version = {SAVE_FORMAT_VERSION}")));
+
+        let saved = SavedGame::from_toml(&content).unwrap();
+        assert_eq!(saved.moves, vec!["e1g1", "c8d7", "a7a8q"]);
+
+        let loaded = saved.to_game().unwrap();
+        assert_eq!(loaded.game_board.board, game.game_board.board);
+        assert_eq!(loaded.player_turn, game.player_turn);
+        // The promoted queen is on a8, seen from Black's side
+        assert_eq!(
+            loaded.game_board.board[7][7],
+            Some((PieceType::Queen, PieceColor::White))
+        );
+    }
+
+    #[test]
+    fn future_version_is_rejected() {
+        let content = "version = 99\nstarting_fen = \"8/8/8/8/8/8/8/8 w - - 0 1\"\nmoves = []\n";
+        assert_eq!(
+            SavedGame::from_toml(content),
+            Err(ChessError::UnsupportedSaveVersion(99))
+        );
+    }
+
+    #[test]
+    fn invalid_save_is_rejected() {
+        assert!(matches!(
+            SavedGame::from_toml("not a save"),
+            Err(ChessError::InvalidSave(_))
+        ));
+        assert!(matches!(
+            SavedGame::from_toml("moves = []"),
+            Err(ChessError::InvalidSave(_))
+        ));
+        assert_eq!(
+            SavedGame::from_toml("version = 1\nmoves = [\"e2e5\"]")
+                .unwrap()
+                .to_game()
+                .err(),
+            Some(ChessError::IllegalMove("e2e5".to_string()))
+        );
+    }
+
+    #[test]
+    fn existing_file_is_kept_without_overwrite() {
+        let path = existing_file("save-kept", "my game");
+        let saved = SavedGame::from_game(&Game::default());
+
+        assert_eq!(
+            saved.write_to(&path, false),
+            Err(ChessError::SaveFileExists(path.display().to_string()))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "my game");
+
+        saved.write_to(&path, true).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(SavedGame::from_toml(&content), Ok(saved));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn saving_over_a_file_waits_for_a_confirmation() {
+        let path = existing_file("save-confirm", "my game");
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.game.play_uci_move("e2e4").unwrap();
+
+        app.save_game_to(path.clone());
+        assert_eq!(app.current_popup, Some(Popups::OverwriteConfirmation));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "my game");
+
+        // Cancelling keeps the file
+        app.cancel_overwrite();
+        assert_eq!(app.current_popup, None);
+        assert_eq!(app.pending_save, None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "my game");
+
+        app.save_game_to(path.clone());
+        app.confirm_overwrite();
+        let saved = SavedGame::from_toml(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.moves, ["e2e4"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn new_file_is_saved_right_away() {
+        let path = existing_file("save-new", "");
+        fs::remove_file(&path).unwrap();
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+
+        app.save_game_to(path.clone());
+        assert_eq!(app.current_popup, None);
+        assert!(SavedGame::from_toml(&fs::read_to_string(&path).unwrap()).is_ok());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn v2_save_resumes_untimed() {
+        let v2 = format!("version = 2\nstarting_fen = \"{STARTING_FEN}\"\nmoves = [\"d2d4\"]\n");
+
+        let saved = SavedGame::from_toml(&v2).unwrap();
+
+        assert_eq!(saved.moves, ["d2d4"]);
+        assert_eq!(saved.clock, None);
+        assert_eq!(saved.player_color, None);
+    }
+
+    #[test]
+    fn clocks_and_color_against_the_bot_read_back() {
+        let mut game = Game {
+            clock: Some(Clock::new("5+3".parse::<TimeControl>().unwrap())),
+            ..Default::default()
+        };
+        game.play_uci_move("e2e4").unwrap();
+        let saved = SavedGame {
+            player_color: Some(PieceColor::Black),
+            ..SavedGame::from_game(&game)
+        };
+        assert_eq!(
+            saved.clock,
+            Some(SavedClock {
+                remaining: [Duration::from_secs(300); 2],
+                increment: Duration::from_secs(3),
+            })
+        );
+
+        let read_back = SavedGame::from_toml(&saved.to_toml()).unwrap();
+        assert_eq!(read_back, saved);
+
+        let resumed = read_back.to_game().unwrap();
+        let clock = resumed.clock.unwrap();
+        assert!(!clock.is_running());
+        assert_eq!(clock.increment(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn every_move_is_autosaved_until_the_game_ends() {
+        let path = existing_file("autosave", "");
+        fs::remove_file(&path).unwrap();
+        let mut app = App {
+            current_page: Pages::Solo,
+            autosave_path: Some(path.clone()),
+            ..Default::default()
+        };
+
+        app.play_algebraic_move("f3").unwrap();
+        app.play_algebraic_move("e5").unwrap();
+        let saved = SavedGame::from_toml(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.moves, ["f2f3", "e7e5"]);
+        assert_eq!(saved.player_color, None);
+
+        app.play_algebraic_move("g4").unwrap();
+        app.play_algebraic_move("Qh4#").unwrap();
+        assert_eq!(app.game.game_state, GameState::Checkmate);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn game_against_the_bot_is_resumed_against_the_bot() {
+        let path = existing_file("autosave-bot", "");
+        fs::remove_file(&path).unwrap();
+        let mut app = App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            autosave_path: Some(path.clone()),
+            ..Default::default()
+        };
+        app.play_algebraic_move("e4").unwrap();
+
+        let mut resumed = App {
+            autosave_path: Some(path.clone()),
+            menu_cursor: 4,
+            ..Default::default()
+        };
+        resumed.menu_select();
+
+        assert_eq!(resumed.current_page, Pages::Bot);
+        assert_eq!(resumed.selected_color, Some(PieceColor::White));
+        assert!(resumed.bot.is_some());
+        assert_eq!(resumed.game.game_board.uci_moves(), ["e2e4"]);
+        assert!(resumed.is_bot_turn());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn nothing_to_resume_is_reported() {
+        let mut app = App {
+            autosave_path: Some(std::env::temp_dir().join("chess-tui-no-autosave.toml")),
+            ..Default::default()
+        };
+
+        app.resume_last_game();
+
+        assert_eq!(app.current_page, Pages::Home);
+        assert!(app.toast.as_ref().unwrap().is_error);
+    }
+}
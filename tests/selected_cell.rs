@@ -0,0 +1,108 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::press;
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::ui::BoardRenderSettings;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyCode;
+    use ratatui::layout::Rect;
+    use ratatui::style::Color;
+    use ratatui::Terminal;
+    use toml::Value;
+
+    // The background of the middle of a cell once the board is drawn
+    fn cell_background(app: &mut App, cell: Coord) -> Color {
+        let mut terminal = Terminal::new(TestBackend::new(110, 45)).unwrap();
+        terminal
+            .draw(|frame| {
+                let game = app.game.clone();
+                let settings = BoardRenderSettings {
+                    flipped: false,
+                    theme: app.board_theme(),
+                    highlight_all_checks: false,
+                    compact: false,
+                };
+                app.game
+                    .ui
+                    .board_render(Rect::new(0, 0, 110, 45), frame, &game, &settings);
+            })
+            .unwrap();
+        let ui = &app.game.ui;
+        let x = ui.top_x + u16::from(cell.col) * ui.width + ui.width / 2;
+        let y = ui.top_y + u16::from(cell.row) * ui.height + ui.height / 2;
+        terminal.backend().buffer()[(x, y)].bg
+    }
+
+    fn app_with_selected_knight() -> App {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+        app.game.ui.cursor_coordinates = Coord::new(7, 6);
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(7, 6));
+        app
+    }
+
+    #[test]
+    fn origin_stays_lit_while_the_cursor_visits_the_targets() {
+        let mut app = app_with_selected_knight();
+        let selected_cell = app.theme().selected_cell;
+
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Right);
+            let cursor = app.game.ui.cursor_coordinates;
+            assert!([Coord::new(5, 5), Coord::new(5, 7)].contains(&cursor));
+
+            assert_eq!(cell_background(&mut app, Coord::new(7, 6)), selected_cell);
+            assert_eq!(cell_background(&mut app, cursor), Color::LightBlue);
+        }
+    }
+
+    #[test]
+    fn origin_color_differs_from_the_last_move() {
+        let mut app = app_with_selected_knight();
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char(' '));
+        assert!(app.game.ui.last_move_highlight.is_some());
+
+        // The black knight of g8, now at the bottom of the board
+        app.game.ui.cursor_coordinates = Coord::new(7, 1);
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Right);
+
+        assert_eq!(
+            cell_background(&mut app, Coord::new(7, 1)),
+            app.theme().selected_cell
+        );
+        assert_ne!(app.theme().selected_cell, Color::LightGreen);
+    }
+
+    #[test]
+    fn selected_cell_color_comes_from_the_config() {
+        let mut app = app_with_selected_knight();
+        let config: Value = toml::from_str("selected_cell_color = \"#ff8800\"").unwrap();
+        app.apply_config(&config);
+        press(&mut app, KeyCode::Right);
+
+        assert_eq!(
+            cell_background(&mut app, Coord::new(7, 6)),
+            Color::Rgb(255, 136, 0)
+        );
+
+        // An unknown color keeps the previous one and warns the player
+        let config: Value = toml::from_str("selected_cell_color = \"plaid\"").unwrap();
+        app.apply_config(&config);
+        assert!(app.toast.is_some());
+        assert_eq!(app.selected_cell_color, Some(Color::Rgb(255, 136, 0)));
+
+        // An empty color goes back to the one of the theme
+        let config: Value = toml::from_str("selected_cell_color = \"\"").unwrap();
+        app.apply_config(&config);
+        assert_eq!(app.board_theme(), *app.theme());
+    }
+}
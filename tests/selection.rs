@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::pieces::PieceColor;
+    use toml::Value;
+
+    // The knight on g1 is selected, d5 is an empty cell it cannot reach
+    const KNIGHT: Coord = Coord { row: 7, col: 6 };
+    const UNREACHABLE: Coord = Coord { row: 3, col: 3 };
+
+    fn app_with_selected_knight(keep_selection_on_illegal_target: bool) -> App {
+        let mut app = App {
+            keep_selection_on_illegal_target,
+            ..Default::default()
+        };
+        app.click_board_cell(KNIGHT);
+        assert_eq!(app.game.ui.selected_coordinates, KNIGHT);
+        app
+    }
+
+    #[test]
+    fn illegal_target_deselects_by_default() {
+        let mut app = app_with_selected_knight(false);
+
+        app.click_board_cell(UNREACHABLE);
+
+        assert!(!app.game.ui.is_cell_selected());
+        assert_eq!(app.game.player_turn, PieceColor::White);
+    }
+
+    #[test]
+    fn illegal_target_keeps_selection_when_configured() {
+        let mut app = app_with_selected_knight(true);
+
+        app.click_board_cell(UNREACHABLE);
+        assert_eq!(app.game.ui.selected_coordinates, KNIGHT);
+        assert_eq!(app.game.player_turn, PieceColor::White);
+
+        // The selection can still be played
+        app.click_board_cell(Coord::new(5, 5));
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert_eq!(app.game.game_board.uci_moves(), vec!["g1f3"]);
+    }
+
+    #[test]
+    fn dropping_on_illegal_target_follows_the_config() {
+        for keep_selection in [false, true] {
+            let mut app = app_with_selected_knight(keep_selection);
+
+            app.drop_dragged_piece(Some(UNREACHABLE));
+
+            assert_eq!(app.game.ui.is_cell_selected(), keep_selection);
+        }
+    }
+
+    #[test]
+    fn clicking_another_piece_selects_it() {
+        let mut app = app_with_selected_knight(true);
+
+        app.click_board_cell(Coord::new(6, 4));
+
+        assert_eq!(app.game.ui.selected_coordinates, Coord::new(6, 4));
+    }
+
+    #[test]
+    fn config_sets_the_behavior() {
+        let mut app = App::default();
+        let config: Value = "keep_selection_on_illegal_target = true".parse().unwrap();
+
+        app.apply_config(&config);
+
+        assert!(app.keep_selection_on_illegal_target);
+    }
+}
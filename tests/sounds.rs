@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::sound::{QuietHours, Sound, SoundEvent, SoundMap};
+    use chrono::NaiveTime;
+    use std::fs;
+    use std::path::PathBuf;
+    use toml::Value;
+
+    fn sound_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("chess-tui-{}-{name}", std::process::id()));
+        fs::write(&path, b"RIFF").unwrap();
+        path
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn configured_app(config: &str) -> App {
+        let mut app = App::default();
+        app.apply_config(&config.parse::<Value>().unwrap());
+        app
+    }
+
+    #[test]
+    fn events_keep_the_bundled_sounds_by_default() {
+        let sounds = SoundMap::default();
+
+        assert_eq!(sounds.get(SoundEvent::Move), Sound::Silent);
+        assert_eq!(sounds.get(SoundEvent::Capture), Sound::Silent);
+        assert_eq!(sounds.get(SoundEvent::Check), Sound::Bell);
+        assert_eq!(sounds.get(SoundEvent::GameEnd), Sound::Bell);
+    }
+
+    #[test]
+    fn configured_events_play_their_file() {
+        let capture = sound_file("capture.wav");
+        let app = configured_app(&format!(
+            "[sounds]\ncapture = {:?}\ncheck = \"\"",
+            capture.to_str().unwrap()
+        ));
+
+        assert_eq!(app.sounds.get(SoundEvent::Capture), Sound::File(capture));
+        assert_eq!(app.sounds.get(SoundEvent::Check), Sound::Silent);
+        // The other events are left alone
+        assert_eq!(app.sounds.get(SoundEvent::Move), Sound::Silent);
+        assert_eq!(app.sounds.get(SoundEvent::GameEnd), Sound::Bell);
+    }
+
+    #[test]
+    fn missing_file_keeps_the_default_sound() {
+        let app = configured_app("[sounds]\ngame_end = \"/no/such/file.wav\"");
+
+        assert_eq!(app.sounds.get(SoundEvent::GameEnd), Sound::Bell);
+    }
+
+    #[test]
+    fn moves_tell_which_sound_they_make() {
+        let mut app = App::default();
+        assert_eq!(app.latest_move_sound(), None);
+
+        for (uci_move, event) in [
+            ("e2e4", SoundEvent::Move),
+            ("d7d5", SoundEvent::Move),
+            ("e4d5", SoundEvent::Capture),
+            ("e8d7", SoundEvent::Move),
+            ("f1b5", SoundEvent::Check),
+        ] {
+            app.game.play_uci_move(uci_move).unwrap();
+            assert_eq!(app.latest_move_sound(), Some(event), "{uci_move}");
+        }
+
+        app.game = Game::default();
+        for uci_move in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            app.game.play_uci_move(uci_move).unwrap();
+        }
+        assert_eq!(app.latest_move_sound(), Some(SoundEvent::GameEnd));
+    }
+
+    #[test]
+    fn quiet_hours_go_past_midnight() {
+        let night = QuietHours {
+            start: time(22, 0),
+            end: time(7, 0),
+        };
+
+        assert!(night.contains(time(22, 0)));
+        assert!(night.contains(time(23, 59)));
+        assert!(night.contains(time(0, 0)));
+        assert!(night.contains(time(6, 59)));
+        assert!(!night.contains(time(7, 0)));
+        assert!(!night.contains(time(12, 0)));
+        assert!(!night.contains(time(21, 59)));
+    }
+
+    #[test]
+    fn quiet_hours_within_a_day() {
+        let lunch = QuietHours {
+            start: time(12, 0),
+            end: time(14, 0),
+        };
+
+        assert!(lunch.contains(time(13, 0)));
+        assert!(!lunch.contains(time(0, 0)));
+        assert!(!lunch.contains(time(14, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_come_from_the_config() {
+        let app =
+            configured_app("[sounds]\nquiet_hours_start = \"23:30\"\nquiet_hours_end = \"06:15\"");
+
+        assert!(app.sounds.is_quiet_at(time(23, 45)));
+        assert!(app.sounds.is_quiet_at(time(0, 30)));
+        assert!(!app.sounds.is_quiet_at(time(6, 15)));
+        assert!(!SoundMap::default().is_quiet_at(time(0, 30)));
+    }
+
+    #[test]
+    fn quiet_hours_need_both_ends() {
+        let app = configured_app("[sounds]\nquiet_hours_start = \"22:00\"");
+        assert_eq!(app.sounds.quiet_hours, None);
+
+        let app = configured_app(
+            "[sounds]\nquiet_hours_start = \"22:00\"\nquiet_hours_end = \"7 o'clock\"",
+        );
+        assert_eq!(app.sounds.quiet_hours, None);
+    }
+}
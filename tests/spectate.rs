@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::network::spectate::{GameSnapshot, Spectators};
+
+    const FEN_AFTER_E4: &str = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+
+    fn watched_app() -> (App, Spectators) {
+        let spectators = Spectators::default();
+        let app = App {
+            spectators: Some(spectators.clone()),
+            ..Default::default()
+        };
+        (app, spectators)
+    }
+
+    #[test]
+    fn snapshot_in_json() {
+        let mut game = Game::default();
+        game.play_uci_move("e2e4").unwrap();
+
+        assert_eq!(
+            GameSnapshot::from_game(&game).to_json(),
+            format!("{{\"fen\":\"{FEN_AFTER_E4}\",\"moves\":[\"e2e4\"]}}")
+        );
+    }
+
+    #[test]
+    fn applying_a_move_broadcasts_the_new_fen() {
+        let (mut app, spectators) = watched_app();
+        let spectator = spectators.subscribe();
+
+        app.apply_received_move("e2e4").unwrap();
+        let json = spectator.try_recv().unwrap();
+        assert!(json.contains(FEN_AFTER_E4));
+        assert!(json.contains("\"moves\":[\"e2e4\"]"));
+
+        app.apply_received_move("e7e5").unwrap();
+        assert!(spectator.try_recv().unwrap().contains("\"e2e4\",\"e7e5\""));
+        assert!(spectator.try_recv().is_err());
+    }
+
+    #[test]
+    fn late_spectators_start_from_the_latest_position() {
+        let (mut app, spectators) = watched_app();
+        app.apply_received_move("e2e4").unwrap();
+
+        let spectator = spectators.subscribe();
+        assert!(spectator.try_recv().unwrap().contains(FEN_AFTER_E4));
+    }
+
+    #[test]
+    fn spectators_who_left_are_forgotten() {
+        let (mut app, spectators) = watched_app();
+        let spectator = spectators.subscribe();
+        assert_eq!(spectators.count(), 1);
+
+        drop(spectator);
+        app.apply_received_move("e2e4").unwrap();
+        assert_eq!(spectators.count(), 0);
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn state_is_served_over_http() {
+        use chess_tui::network::spectate::start_spectator_server;
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+
+        let port = 47_000 + (std::process::id() % 1000) as u16;
+        let spectators = start_spectator_server(port).unwrap();
+        let mut game = Game::default();
+        game.play_uci_move("e2e4").unwrap();
+        spectators.broadcast(&GameSnapshot::from_game(&game));
+
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(stream, "GET /state HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(&GameSnapshot::from_game(&game).to_json()));
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::constants::Pages;
+    use chess_tui::engine::random_bot::RandomBot;
+    use chess_tui::game_logic::game::GameState;
+    use chess_tui::handler::handle_key_events;
+    use chess_tui::pieces::PieceColor;
+    use chess_tui::rng::Rng;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn bot_app() -> App {
+        App {
+            current_page: Pages::Bot,
+            selected_color: Some(PieceColor::White),
+            bot: Some(Box::new(RandomBot::new(Rng::from_seed(11)))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bot_takes_over_the_side_of_the_player() {
+        let mut app = bot_app();
+        app.game.play_uci_move("e2e4").unwrap();
+        app.play_bot_move();
+        assert_eq!(app.game.game_board.uci_moves().len(), 2);
+
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT),
+            &mut app,
+        )
+        .unwrap();
+
+        assert_eq!(app.selected_color, Some(PieceColor::Black));
+        assert_eq!(app.setup.player_color, Some(PieceColor::Black));
+        assert_eq!(app.players().white.as_deref(), Some("Random bot"));
+        // White is to move, the bot plays it from the same game
+        assert!(app.is_bot_turn());
+        app.play_bot_move();
+        let moves = app.game.game_board.uci_moves();
+        assert_eq!(moves.len(), 3);
+        assert_eq!(moves[0], "e2e4");
+        assert_eq!(app.game.player_turn, PieceColor::Black);
+        assert!(!app.is_bot_turn());
+    }
+
+    #[test]
+    fn board_turns_to_the_new_side() {
+        let mut app = bot_app();
+        assert!(!app.is_board_flipped());
+
+        app.switch_sides();
+
+        assert!(app.is_board_flipped());
+    }
+
+    #[test]
+    fn sides_stay_once_the_game_is_over() {
+        let mut app = bot_app();
+        app.game.game_state = GameState::Resignation(PieceColor::White);
+
+        app.switch_sides();
+
+        assert_eq!(app.selected_color, Some(PieceColor::White));
+    }
+
+    #[test]
+    fn local_games_have_no_side_to_switch() {
+        let mut app = App {
+            current_page: Pages::Solo,
+            ..Default::default()
+        };
+
+        app.switch_sides();
+
+        assert_eq!(app.selected_color, None);
+        assert!(app.toast.is_none());
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::app::App;
+    use chess_tui::ui::theme::{theme_index, THEMES};
+    use toml::Value;
+
+    #[test]
+    fn cycling_advances_to_the_next_theme() {
+        let mut app = App::default();
+        assert_eq!(app.theme(), &THEMES[0]);
+
+        app.cycle_theme();
+
+        assert_eq!(app.theme_index, 1);
+        assert_eq!(app.theme(), &THEMES[1]);
+    }
+
+    #[test]
+    fn cycling_past_the_last_theme_wraps_around() {
+        let mut app = App {
+            theme_index: THEMES.len() - 1,
+            ..Default::default()
+        };
+
+        app.cycle_theme();
+
+        assert_eq!(app.theme_index, 0);
+    }
+
+    #[test]
+    fn theme_is_read_from_the_config() {
+        let mut app = App::default();
+        let config: Value = "theme = \"ocean\"".parse().unwrap();
+
+        app.apply_config(&config);
+
+        assert_eq!(app.theme().name, "Ocean");
+    }
+
+    #[test]
+    fn unknown_theme_name_falls_back_to_the_default() {
+        assert_eq!(theme_index("Neon"), 0);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::game_logic::coord::Coord;
+    use chess_tui::game_logic::game::Game;
+    use chess_tui::pieces::PieceColor;
+
+    #[test]
+    fn threat_overlay_is_hidden_by_default() {
+        let game = Game::default();
+
+        assert!(game.threat_overlay().is_empty());
+    }
+
+    #[test]
+    fn threat_overlay_matches_opponent_attacks() {
+        let mut game =
+            Game::from_fen("rnbqkb1r/pppp1ppp/5n2/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+                .unwrap();
+        game.ui.toggle_threats();
+
+        assert_eq!(
+            game.threat_overlay(),
+            game.game_board.attacked_squares(PieceColor::Black)
+        );
+        // The knight on f6 attacks e4
+        assert!(game.threat_overlay().contains(&Coord::new(4, 4)));
+    }
+
+    #[test]
+    fn threat_overlay_follows_the_board() {
+        let mut game = Game::default();
+        game.ui.toggle_threats();
+
+        // e2-e4, the board is flipped for black
+        game.ui.cursor_coordinates = Coord::new(6, 4);
+        game.handle_cell_click();
+        game.ui.cursor_coordinates = Coord::new(4, 4);
+        game.handle_cell_click();
+
+        assert_eq!(game.player_turn, PieceColor::Black);
+        assert_eq!(
+            game.threat_overlay(),
+            game.game_board.attacked_squares(PieceColor::White)
+        );
+        // The bishop on f1 now controls a6, seen from black
+        assert!(game.threat_overlay().contains(&Coord::new(5, 7)));
+
+        game.ui.toggle_threats();
+        assert!(game.threat_overlay().is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use chess_tui::engine::uci::position_command;
+    use chess_tui::game_logic::game::Game;
+
+    fn play(game: &mut Game, uci_moves: &[&str]) {
+        for uci_move in uci_moves {
+            game.play_uci_move(uci_move).unwrap();
+        }
+    }
+
+    #[test]
+    fn castling_is_written_with_the_king_destination() {
+        let mut game = Game::default();
+        let moves = [
+            "e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "d7d6", "e1g1", "c8g4", "d2d3", "d8d7", "b1c3",
+            "e8c8",
+        ];
+        play(&mut game, &moves);
+
+        assert_eq!(game.game_board.uci_moves(), moves);
+    }
+
+    #[test]
+    fn promotions_are_suffixed_with_the_new_piece() {
+        let mut game = Game::from_fen("8/P6p/4k3/8/8/8/6p1/4K3 w - - 0 1").unwrap();
+        play(&mut game, &["a7a8q", "g2g1n", "a8b7", "h7h5"]);
+
+        assert_eq!(
+            game.game_board.uci_moves(),
+            vec!["a7a8q", "g2g1n", "a8b7", "h7h5"]
+        );
+    }
+
+    #[test]
+    fn position_command_for_the_engine() {
+        let mut game = Game::default();
+        assert_eq!(
+            position_command(None, &game.game_board.uci_moves()),
+            "position startpos"
+        );
+
+        play(&mut game, &["e2e4", "c7c5"]);
+        assert_eq!(
+            position_command(None, &game.game_board.uci_moves()),
+            "position startpos moves e2e4 c7c5"
+        );
+
+        let fen = "4k3/8/8/8/8/8/8/4K2R w K - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+        play(&mut game, &["e1g1"]);
+        assert_eq!(
+            position_command(game.starting_fen.as_deref(), &game.game_board.uci_moves()),
+            format!("position fen {fen} moves e1g1")
+        );
+    }
+}